@@ -0,0 +1,563 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Caseless matching, as defined in section 3.13 of the Unicode Standard.
+
+use crate::internals::CaseMapLocale;
+use crate::provider::{CaseMap, CaseMapV1};
+use crate::{CaseMapper, CaseMapperBorrowed};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::Hasher;
+use core::str::Chars;
+use icu_normalizer::provider::{
+    NormalizerNfcV1, NormalizerNfdDataV1, NormalizerNfdTablesV1, NormalizerNfkdDataV1,
+    NormalizerNfkdTablesV1,
+};
+use icu_normalizer::{
+    ComposingNormalizer, ComposingNormalizerBorrowed, Composition, DecomposingNormalizer,
+    DecomposingNormalizerBorrowed, Decomposition,
+};
+use icu_provider::prelude::*;
+
+/// Which kind of equivalence is ignored in addition to case when matching.
+///
+/// # Examples
+///
+/// ```rust
+/// use icu::casemap::options::{CaselessEquivalence, CaselessMatchOptions};
+/// use icu::casemap::CaselessMatcher;
+///
+/// let canonical = CaselessMatcher::new(Default::default());
+/// let mut options = CaselessMatchOptions::default();
+/// options.equivalence = Some(CaselessEquivalence::Compatibility);
+/// let compatibility = CaselessMatcher::new(options);
+///
+/// // Both ignore canonical differences:
+/// assert!(canonical.eq("Å", "a\u{30A}"));
+/// assert!(compatibility.eq("Å", "a\u{30A}"));
+///
+/// // Only compatibility matching ignores compatibility differences:
+/// assert!(!canonical.eq("㎒", "MHz"));
+/// assert!(compatibility.eq("㎒", "MHz"));
+/// ```
+#[non_exhaustive]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub enum CaselessEquivalence {
+    /// Canonical caseless matching (definition D145): strings match if
+    /// `NFD(toCasefold(NFD(X)))` is equal for both.
+    ///
+    /// This is the default
+    #[default]
+    Canonical,
+    /// Compatibility caseless matching (definition D146): strings match if
+    /// `NFKD(toCasefold(NFKD(toCasefold(NFD(X)))))` is equal for both.
+    Compatibility,
+}
+
+/// Which case folding mappings to use for dotted and dotless I.
+///
+/// # Examples
+///
+/// ```rust
+/// use icu::casemap::options::{CaselessMatchOptions, FoldMappings};
+/// use icu::casemap::CaselessMatcher;
+///
+/// let root = CaselessMatcher::new(Default::default());
+/// let mut options = CaselessMatchOptions::default();
+/// options.fold_mappings = Some(FoldMappings::Turkic);
+/// let turkic = CaselessMatcher::new(options);
+///
+/// assert!(root.eq("ISTANBUL", "istanbul"));
+/// assert!(!turkic.eq("ISTANBUL", "istanbul"));
+///
+/// assert!(!root.eq("İSTANBUL", "istanbul"));
+/// assert!(turkic.eq("İSTANBUL", "istanbul"));
+/// assert!(turkic.eq("I\u{307}STANBUL", "istanbul"));
+/// assert!(turkic.eq("ISPARTA", "ısparta"));
+/// ```
+#[non_exhaustive]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub enum FoldMappings {
+    /// The default (C + F) case folding mappings.
+    ///
+    /// This is the default
+    #[default]
+    Default,
+    /// The Turkic (T) case folding mappings, under which I folds to dotless ı
+    /// and İ folds to i.
+    ///
+    /// Since İ canonically decomposes to I followed by U+0307 COMBINING DOT ABOVE,
+    /// the text is brought into NFC rather than NFD before folding, so that both
+    /// spellings fold to i.
+    Turkic,
+}
+
+/// Various options for controlling caseless matching
+///
+/// See docs of [`CaselessMatcher`] for examples.
+#[non_exhaustive]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct CaselessMatchOptions {
+    /// Which kind of equivalence to ignore in addition to case.
+    ///
+    /// Default is [`CaselessEquivalence::Canonical`]
+    pub equivalence: Option<CaselessEquivalence>,
+    /// Which case folding mappings to use.
+    ///
+    /// Default is [`FoldMappings::Default`]
+    pub fold_mappings: Option<FoldMappings>,
+}
+
+/// A type for comparing and hashing strings under caseless matching, as defined in
+/// section 3.13 of the Unicode Standard.
+///
+/// Matching compares the strings after case folding and normalization, without
+/// materializing either: folding and normalization are interleaved as the strings are
+/// iterated, so [`CaselessMatcherBorrowed::eq()`], [`CaselessMatcherBorrowed::cmp()`]
+/// and [`CaselessMatcherBorrowed::hash()`] do not allocate.
+///
+/// Most methods for this type live on [`CaselessMatcherBorrowed`], which you can obtain via
+/// [`CaselessMatcher::new()`] or [`CaselessMatcher::as_borrowed()`].
+///
+/// ✨ *Enabled with the `normalizer` Cargo feature.*
+///
+/// # Examples
+///
+/// ```rust
+/// use icu::casemap::CaselessMatcher;
+/// use std::cmp::Ordering;
+///
+/// let matcher = CaselessMatcher::new(Default::default());
+///
+/// assert!(matcher.eq("Straße", "STRASSE"));
+/// assert!(matcher.eq("ǅ", "ǆ"));
+/// assert!(matcher.eq("ﬁ", "FI"));
+/// // Canonically equivalent strings match, too
+/// assert!(matcher.eq("\u{1FB3}", "α\u{345}"));
+/// assert!(matcher.eq("Å", "a\u{30A}"));
+///
+/// assert_eq!(matcher.cmp("HELLO", "hello"), Ordering::Equal);
+/// assert_eq!(matcher.cmp("Apple", "banana"), Ordering::Less);
+/// ```
+#[derive(Debug)]
+pub struct CaselessMatcher {
+    cm: CaseMapper,
+    nfc: ComposingNormalizer,
+    nfd: DecomposingNormalizer,
+    nfkd: DecomposingNormalizer,
+    options: CaselessMatchOptions,
+}
+
+impl CaselessMatcher {
+    /// A constructor which creates a [`CaselessMatcherBorrowed`] using compiled data
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    #[expect(clippy::new_ret_no_self)] // Intentional
+    pub const fn new(options: CaselessMatchOptions) -> CaselessMatcherBorrowed<'static> {
+        CaselessMatcherBorrowed::new(options)
+    }
+
+    icu_provider::gen_buffer_data_constructors!((options: CaselessMatchOptions) -> error: DataError,
+    functions: [
+        new: skip,
+        try_new_with_buffer_provider,
+        try_new_unstable,
+        Self,
+    ]);
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::new)]
+    pub fn try_new_unstable<P>(
+        provider: &P,
+        options: CaselessMatchOptions,
+    ) -> Result<Self, DataError>
+    where
+        P: DataProvider<CaseMapV1>
+            + DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        Ok(Self {
+            cm: CaseMapper::try_new_unstable(provider)?,
+            nfc: ComposingNormalizer::try_new_nfc_unstable(provider)?,
+            nfd: DecomposingNormalizer::try_new_nfd_unstable(provider)?,
+            nfkd: DecomposingNormalizer::try_new_nfkd_unstable(provider)?,
+            options,
+        })
+    }
+
+    /// Constructs a borrowed version of this type for more efficient querying.
+    pub fn as_borrowed(&self) -> CaselessMatcherBorrowed<'_> {
+        CaselessMatcherBorrowed {
+            cm: self.cm.as_borrowed(),
+            nfc: self.nfc.as_borrowed(),
+            nfd: self.nfd.as_borrowed(),
+            nfkd: self.nfkd.as_borrowed(),
+            options: self.options,
+        }
+    }
+}
+
+/// A borrowed [`CaselessMatcher`].
+///
+/// See methods or [`CaselessMatcher`] for examples.
+#[derive(Clone, Debug, Copy)]
+pub struct CaselessMatcherBorrowed<'a> {
+    cm: CaseMapperBorrowed<'a>,
+    nfc: ComposingNormalizerBorrowed<'a>,
+    nfd: DecomposingNormalizerBorrowed<'a>,
+    nfkd: DecomposingNormalizerBorrowed<'a>,
+    options: CaselessMatchOptions,
+}
+
+impl CaselessMatcherBorrowed<'static> {
+    /// A constructor which creates a [`CaselessMatcherBorrowed`] using compiled data
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub const fn new(options: CaselessMatchOptions) -> Self {
+        Self {
+            cm: CaseMapperBorrowed::new(),
+            nfc: ComposingNormalizerBorrowed::new_nfc(),
+            nfd: DecomposingNormalizerBorrowed::new_nfd(),
+            nfkd: DecomposingNormalizerBorrowed::new_nfkd(),
+            options,
+        }
+    }
+
+    /// Cheaply converts a [`CaselessMatcherBorrowed<'static>`] into a [`CaselessMatcher`].
+    ///
+    /// Note: Due to branching and indirection, using [`CaselessMatcher`] might inhibit some
+    /// compile-time optimizations that are possible with [`CaselessMatcherBorrowed`].
+    pub const fn static_to_owned(self) -> CaselessMatcher {
+        CaselessMatcher {
+            cm: self.cm.static_to_owned(),
+            nfc: self.nfc.static_to_owned(),
+            nfd: self.nfd.static_to_owned(),
+            nfkd: self.nfkd.static_to_owned(),
+            options: self.options,
+        }
+    }
+}
+
+#[cfg(feature = "compiled_data")]
+impl Default for CaselessMatcherBorrowed<'static> {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<'a> CaselessMatcherBorrowed<'a> {
+    /// Returns the characters that `src` is compared by, that is, the case-folded
+    /// and normalized form of `src`.
+    ///
+    /// Two strings match exactly when these iterators yield the same characters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use icu::casemap::CaselessMatcher;
+    ///
+    /// let matcher = CaselessMatcher::new(Default::default());
+    ///
+    /// assert_eq!(
+    ///     matcher.caseless_chars("Straße").collect::<String>(),
+    ///     "strasse"
+    /// );
+    /// assert_eq!(
+    ///     matcher.caseless_chars("Ǻ").collect::<String>(),
+    ///     "a\u{30A}\u{301}"
+    /// );
+    /// ```
+    pub fn caseless_chars(self, src: &'a str) -> impl Iterator<Item = char> + 'a {
+        self.iter(src)
+    }
+
+    /// Returns whether `a` and `b` match caselessly.
+    ///
+    /// See [`CaselessMatcher`] for an example.
+    pub fn eq(self, a: &str, b: &str) -> bool {
+        a == b || self.iter(a).eq(self.iter(b))
+    }
+
+    /// Compares `a` and `b` by the code points of their caseless forms.
+    ///
+    /// This is a total order that is consistent with [`Self::eq()`]; it is not meant for
+    /// presenting sorted strings to users, use a collator for that.
+    ///
+    /// See [`CaselessMatcher`] for an example.
+    pub fn cmp(self, a: &str, b: &str) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
+        }
+        self.iter(a).cmp(self.iter(b))
+    }
+
+    /// Feeds the caseless form of `src` into `state`.
+    ///
+    /// Strings that match under [`Self::eq()`] produce the same hash.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use icu::casemap::CaselessMatcher;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// let matcher = CaselessMatcher::new(Default::default());
+    /// let hash = |s| {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     matcher.hash(s, &mut hasher);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// assert_eq!(hash("Straße"), hash("STRASSE"));
+    /// ```
+    pub fn hash<H: Hasher>(self, src: &str, state: &mut H) {
+        for c in self.iter(src) {
+            state.write_u32(u32::from(c));
+        }
+        // Like `str`, terminate with a byte that cannot occur in the content, so
+        // that hashing consecutive strings does not collide.
+        state.write_u8(0xFF);
+    }
+
+    fn iter<'s>(self, src: &'s str) -> Caseless<'a, Chars<'s>> {
+        let locale = match self.options.fold_mappings.unwrap_or_default() {
+            FoldMappings::Default => CaseMapLocale::Root,
+            FoldMappings::Turkic => CaseMapLocale::Turkish,
+        };
+        let normalized = match locale {
+            CaseMapLocale::Turkish => Normalized::Composed(self.nfc.normalize_iter(src.chars())),
+            _ => Normalized::Decomposed(self.nfd.normalize_iter(src.chars())),
+        };
+        let folded = Folding::new(self.cm.data, locale, normalized);
+        match self.options.equivalence.unwrap_or_default() {
+            CaselessEquivalence::Canonical => Caseless::Canonical(self.nfd.normalize_iter(folded)),
+            CaselessEquivalence::Compatibility => {
+                let refolded = Folding::new(self.cm.data, locale, self.nfkd.normalize_iter(folded));
+                Caseless::Compatibility(self.nfkd.normalize_iter(refolded))
+            }
+        }
+    }
+}
+
+/// The case folding of a single character.
+///
+/// Full case foldings are at most three characters long.
+#[derive(Default)]
+struct FoldBuffer {
+    chars: [char; 3],
+    len: usize,
+    pos: usize,
+}
+
+impl fmt::Write for FoldBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars().try_for_each(|c| self.write_char(c))
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        // GIGO: longer mappings are truncated
+        if let Some(slot) = self.chars.get_mut(self.len) {
+            *slot = c;
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Full case folding over an iterator of characters.
+struct Folding<'data, I> {
+    data: &'data CaseMap<'data>,
+    locale: CaseMapLocale,
+    iter: I,
+    buffer: FoldBuffer,
+}
+
+impl<'data, I> Folding<'data, I> {
+    fn new(data: &'data CaseMap<'data>, locale: CaseMapLocale, iter: I) -> Self {
+        Self {
+            data,
+            locale,
+            iter,
+            buffer: FoldBuffer::default(),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Folding<'_, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if self.buffer.pos < self.buffer.len {
+                let c = self.buffer.chars.get(self.buffer.pos).copied();
+                self.buffer.pos += 1;
+                return c;
+            }
+            let c = self.iter.next()?;
+            self.buffer = FoldBuffer::default();
+            // Writing to a `FoldBuffer` cannot fail
+            let _ = self.data.full_fold_char(c, self.locale, &mut self.buffer);
+        }
+    }
+}
+
+/// The text before folding, in NFD, or in NFC for Turkic folding.
+enum Normalized<'data, I: Iterator<Item = char>> {
+    Decomposed(Decomposition<'data, I>),
+    Composed(Composition<'data, I>),
+}
+
+impl<I: Iterator<Item = char>> Iterator for Normalized<'_, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        match self {
+            Self::Decomposed(iter) => iter.next(),
+            Self::Composed(iter) => iter.next(),
+        }
+    }
+}
+
+type Folded<'data, I> = Folding<'data, Normalized<'data, I>>;
+
+/// The caseless form of a string.
+#[expect(clippy::large_enum_variant)] // Only ever lives on the stack
+enum Caseless<'data, I: Iterator<Item = char>> {
+    Canonical(Decomposition<'data, Folded<'data, I>>),
+    Compatibility(Decomposition<'data, Folding<'data, Decomposition<'data, Folded<'data, I>>>>),
+}
+
+impl<I: Iterator<Item = char>> Iterator for Caseless<'_, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        match self {
+            Self::Canonical(iter) => iter.next(),
+            Self::Compatibility(iter) => iter.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_options(
+        equivalence: CaselessEquivalence,
+        fold_mappings: FoldMappings,
+    ) -> CaselessMatcherBorrowed<'static> {
+        CaselessMatcherBorrowed::new(CaselessMatchOptions {
+            equivalence: Some(equivalence),
+            fold_mappings: Some(fold_mappings),
+        })
+    }
+
+    #[test]
+    fn test_canonical_caseless() {
+        let matcher = CaselessMatcherBorrowed::default();
+        assert!(matcher.eq("", ""));
+        assert!(matcher.eq("Hello", "hELLO"));
+        assert!(matcher.eq("ß", "SS"));
+        assert!(matcher.eq("ﬃ", "FFI"));
+        assert!(matcher.eq("\u{130}", "i\u{307}"));
+        // U+0345 is the reason for the initial NFD: its folding is not stable
+        // under canonical reordering.
+        assert!(matcher.eq("\u{1F80}", "\u{3B1}\u{313}\u{345}"));
+        assert!(matcher.eq("\u{1F88}", "\u{1F00}\u{3B9}"));
+        assert!(matcher.eq("ǰ\u{323}", "J\u{323}\u{30C}"));
+        assert!(!matcher.eq("ﬁ", "fi\u{301}"));
+        assert!(!matcher.eq("ǳ", "d"));
+        assert!(!matcher.eq("I", "ı"));
+    }
+
+    #[test]
+    fn test_compatibility_caseless() {
+        let matcher = with_options(CaselessEquivalence::Compatibility, FoldMappings::Default);
+        assert!(matcher.eq("ﬁ", "FI"));
+        assert!(matcher.eq("㎒", "MHZ"));
+        assert!(matcher.eq("Ⅻ", "xii"));
+        assert!(matcher.eq("ｈｅｌｌｏ", "HELLO"));
+        // U+037A GREEK YPOGEGRAMMENI decomposes to a space and U+0345, which needs folding again
+        assert!(matcher.eq("\u{37A}", " \u{3B9}"));
+        assert!(!matcher.eq("ﬁ", "fl"));
+    }
+
+    #[test]
+    fn test_turkic_caseless() {
+        for equivalence in [
+            CaselessEquivalence::Canonical,
+            CaselessEquivalence::Compatibility,
+        ] {
+            let matcher = with_options(equivalence, FoldMappings::Turkic);
+            assert!(matcher.eq("İ", "i"));
+            assert!(matcher.eq("I\u{307}", "i"));
+            assert!(matcher.eq("I", "ı"));
+            assert!(matcher.eq("İ\u{323}", "I\u{323}\u{307}"));
+            assert!(!matcher.eq("I", "i"));
+            assert!(!matcher.eq("ı\u{307}", "i"));
+        }
+    }
+
+    #[test]
+    fn test_cmp_and_hash_consistent_with_eq() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let strings = [
+            "",
+            "a",
+            "A",
+            "ß",
+            "ss",
+            "SS",
+            "Straße",
+            "STRASSE",
+            "straße!",
+            "\u{1F80}",
+            "\u{3B1}\u{313}\u{345}",
+            "Å",
+            "a\u{30A}",
+            "b",
+        ];
+        let matcher = CaselessMatcherBorrowed::default();
+        let hash = |s| {
+            let mut hasher = DefaultHasher::new();
+            matcher.hash(s, &mut hasher);
+            hasher.finish()
+        };
+        for a in strings {
+            for b in strings {
+                let eq = matcher.eq(a, b);
+                assert_eq!(eq, matcher.cmp(a, b) == Ordering::Equal, "{a:?} {b:?}");
+                assert_eq!(
+                    matcher.cmp(a, b),
+                    matcher.cmp(b, a).reverse(),
+                    "{a:?} {b:?}"
+                );
+                if eq {
+                    assert_eq!(hash(a), hash(b), "{a:?} {b:?}");
+                }
+            }
+        }
+        assert_eq!(matcher.cmp("a", "B"), Ordering::Less);
+        assert_eq!(matcher.cmp("ss", "ß"), Ordering::Equal);
+        assert_eq!(matcher.cmp("ß", "st"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_owned() {
+        let owned = CaselessMatcherBorrowed::default().static_to_owned();
+        assert!(owned.as_borrowed().eq("Straße", "STRASSE"));
+    }
+}
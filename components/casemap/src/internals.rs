@@ -545,6 +545,25 @@ impl<'data> CaseMap<'data> {
         }
     }
 
+    /// Writes the full case folding of a single character to `sink`.
+    ///
+    /// Case folding is context-insensitive, so no surrounding text is needed.
+    #[cfg(feature = "normalizer")]
+    pub(crate) fn full_fold_char<W: fmt::Write + ?Sized>(
+        &self,
+        c: char,
+        locale: CaseMapLocale,
+        sink: &mut W,
+    ) -> fmt::Result {
+        self.full_helper::<false, W>(
+            c,
+            ContextIterator::new("", ""),
+            locale,
+            MappingKind::Fold,
+            sink,
+        )
+    }
+
     /// Adds all simple case mappings and the full case folding for `c` to `set`.
     /// Also adds special case closure mappings.
     /// The character itself is not added.
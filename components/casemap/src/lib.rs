@@ -48,6 +48,8 @@
 
 extern crate alloc;
 
+#[cfg(feature = "normalizer")]
+mod caseless;
 mod casemapper;
 mod closer;
 pub mod provider;
@@ -59,6 +61,8 @@ pub(crate) mod titlecase;
 pub mod greek_to_me;
 mod internals;
 
+#[cfg(feature = "normalizer")]
+pub use caseless::{CaselessMatcher, CaselessMatcherBorrowed};
 pub use casemapper::{CaseMapper, CaseMapperBorrowed};
 pub use closer::{CaseMapCloser, CaseMapCloserBorrowed};
 pub use set::ClosureSink;
@@ -66,5 +70,7 @@ pub use titlecase::{TitlecaseMapper, TitlecaseMapperBorrowed};
 
 /// Options used by types in this crate
 pub mod options {
+    #[cfg(feature = "normalizer")]
+    pub use crate::caseless::{CaselessEquivalence, CaselessMatchOptions, FoldMappings};
     pub use crate::titlecase::{LeadingAdjustment, TitlecaseOptions, TrailingCase};
 }
@@ -3,14 +3,23 @@
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
 //! Titlecasing-specific
+use crate::internals::{CaseMapLocale, FullCaseWriteable, StringAndWriteable};
 use crate::provider::CaseMapV1;
 use crate::{CaseMapper, CaseMapperBorrowed};
 use alloc::borrow::Cow;
+#[cfg(feature = "segmenter")]
+use alloc::string::String;
+#[cfg(feature = "segmenter")]
+use alloc::vec::Vec;
+#[cfg(feature = "segmenter")]
+use core::fmt;
 use icu_locale_core::LanguageIdentifier;
 use icu_properties::props::{GeneralCategory, GeneralCategoryGroup};
 use icu_properties::provider::PropertyEnumGeneralCategoryV1;
 use icu_properties::{CodePointMapData, CodePointMapDataBorrowed};
 use icu_provider::prelude::*;
+#[cfg(feature = "segmenter")]
+use icu_segmenter::WordSegmenterBorrowed;
 use writeable::Writeable;
 
 /// How to handle the rest of the string once the beginning of the
@@ -348,6 +357,15 @@ impl<'a> TitlecaseMapperBorrowed<'a> {
         langid: &LanguageIdentifier,
         options: TitlecaseOptions,
     ) -> impl Writeable + 'a {
+        self.titlecase_segment_with_locale(src, CaseMapLocale::from_langid(langid), options)
+    }
+
+    fn titlecase_segment_with_locale(
+        self,
+        src: &'a str,
+        locale: CaseMapLocale,
+        options: TitlecaseOptions,
+    ) -> StringAndWriteable<'a, FullCaseWriteable<'a, 'a, true>> {
         if options.leading_adjustment.unwrap_or_default() == LeadingAdjustment::Auto {
             // letter, number, symbol, or private use code point
             const HEAD_GROUPS: GeneralCategoryGroup = GeneralCategoryGroup::Letter
@@ -355,12 +373,12 @@ impl<'a> TitlecaseMapperBorrowed<'a> {
                 .union(GeneralCategoryGroup::Symbol)
                 .union(GeneralCategoryGroup::PrivateUse);
             self.cm
-                .titlecase_segment_with_adjustment(src, langid, options, |_data, ch| {
+                .titlecase_segment_with_adjustment(src, locale, options, |_data, ch| {
                     HEAD_GROUPS.contains(self.gc.get(ch))
                 })
         } else {
             self.cm
-                .titlecase_segment_with_adjustment(src, langid, options, |data, ch| {
+                .titlecase_segment_with_adjustment(src, locale, options, |data, ch| {
                     data.is_cased(ch)
                 })
         }
@@ -483,4 +501,216 @@ impl<'a> TitlecaseMapperBorrowed<'a> {
             src.as_bytes(),
         )
     }
+
+    /// Returns the full titlecase mapping of the given string as a [`Writeable`], titlecasing
+    /// each word as determined by the given [`WordSegmenter`].
+    ///
+    /// Every segment between two word boundaries is titlecased as by [`Self::titlecase_segment()`],
+    /// with the same leading adjustment and trailing case behavior.
+    ///
+    /// This function is context and language sensitive. Callers should pass the text's language
+    /// as a `LanguageIdentifier` (usually the `id` field of the `Locale`) if available, or
+    /// `Default::default()` for the root locale.
+    ///
+    /// ✨ *Enabled with the `segmenter` Cargo feature.*
+    ///
+    /// See [`Self::titlecase_to_string()`] for the equivalent convenience function that returns a String,
+    /// as well as for an example.
+    ///
+    /// [`WordSegmenter`]: icu_segmenter::WordSegmenter
+    #[cfg(feature = "segmenter")]
+    pub fn titlecase(
+        self,
+        src: &'a str,
+        langid: &LanguageIdentifier,
+        options: TitlecaseOptions,
+        segmenter: WordSegmenterBorrowed,
+    ) -> impl Writeable + 'a {
+        WordsTitlecaseWriteable {
+            mapper: self,
+            src,
+            boundaries: segmenter.segment_str(src).collect(),
+            locale: CaseMapLocale::from_langid(langid),
+            options,
+        }
+    }
+
+    /// Returns the full titlecase mapping of the given string as a String, titlecasing
+    /// each word as determined by the given [`WordSegmenter`].
+    ///
+    /// This function is context and language sensitive. Callers should pass the text's language
+    /// as a `LanguageIdentifier` (usually the `id` field of the `Locale`) if available, or
+    /// `Default::default()` for the root locale.
+    ///
+    /// ✨ *Enabled with the `segmenter` Cargo feature.*
+    ///
+    /// See [`Self::titlecase()`] for the equivalent lower-level function that returns a [`Writeable`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use icu::casemap::options::{LeadingAdjustment, TitlecaseOptions, TrailingCase};
+    /// use icu::casemap::TitlecaseMapper;
+    /// use icu::locale::langid;
+    /// use icu::segmenter::WordSegmenter;
+    ///
+    /// let cm = TitlecaseMapper::new();
+    /// let segmenter = WordSegmenter::new_dictionary(Default::default());
+    /// let root = langid!("und");
+    ///
+    /// let default_options = Default::default();
+    ///
+    /// assert_eq!(
+    ///     cm.titlecase_to_string("the qUICK brown fox", &root, default_options, segmenter),
+    ///     "The Quick Brown Fox"
+    /// );
+    /// assert_eq!(
+    ///     cm.titlecase_to_string("«'twas the night», don't panic", &root, default_options, segmenter),
+    ///     "«'Twas The Night», Don't Panic"
+    /// );
+    ///
+    /// // Some behavior is language-sensitive
+    /// assert_eq!(
+    ///     cm.titlecase_to_string("ijsselmeer en ijmuiden", &root, default_options, segmenter),
+    ///     "Ijsselmeer En Ijmuiden"
+    /// );
+    /// assert_eq!(
+    ///     cm.titlecase_to_string("ijsselmeer en ijmuiden", &langid!("nl"), default_options, segmenter),
+    ///     "IJsselmeer En IJmuiden"
+    /// );
+    ///
+    /// // Options apply to every word
+    /// let mut preserve_case: TitlecaseOptions = Default::default();
+    /// preserve_case.trailing_case = Some(TrailingCase::Unchanged);
+    /// assert_eq!(
+    ///     cm.titlecase_to_string("iPhone and macOS", &root, preserve_case, segmenter),
+    ///     "IPhone And MacOS"
+    /// );
+    ///
+    /// let mut adjust_to_cased: TitlecaseOptions = Default::default();
+    /// adjust_to_cased.leading_adjustment = Some(LeadingAdjustment::ToCased);
+    /// assert_eq!(
+    ///     cm.titlecase_to_string("49ers fans", &root, default_options, segmenter),
+    ///     "49ers Fans"
+    /// );
+    /// assert_eq!(
+    ///     cm.titlecase_to_string("49ers fans", &root, adjust_to_cased, segmenter),
+    ///     "49Ers Fans"
+    /// );
+    /// ```
+    ///
+    /// [`WordSegmenter`]: icu_segmenter::WordSegmenter
+    #[cfg(feature = "segmenter")]
+    pub fn titlecase_to_string<'s>(
+        self,
+        src: &'s str,
+        langid: &LanguageIdentifier,
+        options: TitlecaseOptions,
+        segmenter: WordSegmenterBorrowed,
+    ) -> Cow<'s, str> {
+        writeable::to_string_or_borrow(
+            &self.titlecase(src, langid, options, segmenter),
+            src.as_bytes(),
+        )
+    }
+
+    /// Returns the full titlecase mapping of the given potentially ill-formed UTF-16 string,
+    /// titlecasing each word as determined by the given [`WordSegmenter`].
+    ///
+    /// Unpaired surrogates are replaced by U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// See [`Self::titlecase_to_string()`] for more information and examples.
+    ///
+    /// ✨ *Enabled with the `segmenter` Cargo feature.*
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use icu::casemap::TitlecaseMapper;
+    /// use icu::locale::langid;
+    /// use icu::segmenter::WordSegmenter;
+    ///
+    /// let cm = TitlecaseMapper::new();
+    /// let segmenter = WordSegmenter::new_dictionary(Default::default());
+    ///
+    /// let src: Vec<u16> = "ijsselmeer en ijmuiden".encode_utf16().collect();
+    /// let expected: Vec<u16> = "IJsselmeer En IJmuiden".encode_utf16().collect();
+    /// assert_eq!(
+    ///     cm.titlecase_utf16(&src, &langid!("nl"), Default::default(), segmenter),
+    ///     expected
+    /// );
+    /// ```
+    ///
+    /// [`WordSegmenter`]: icu_segmenter::WordSegmenter
+    #[cfg(feature = "segmenter")]
+    pub fn titlecase_utf16(
+        self,
+        src: &[u16],
+        langid: &LanguageIdentifier,
+        options: TitlecaseOptions,
+        segmenter: WordSegmenterBorrowed,
+    ) -> Vec<u16> {
+        let locale = CaseMapLocale::from_langid(langid);
+        let mut result = Vec::with_capacity(src.len());
+        let mut segment = String::new();
+        let mut boundaries = segmenter.segment_utf16(src);
+        let mut start = boundaries.next().unwrap_or_default();
+        for end in boundaries {
+            segment.clear();
+            segment.extend(
+                char::decode_utf16(src.get(start..end).unwrap_or_default().iter().copied())
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)),
+            );
+            let _infallible = self
+                .titlecase_segment_with_locale(&segment, locale, options)
+                .write_to(&mut WriteUtf16(&mut result));
+            start = end;
+        }
+        result
+    }
+}
+
+/// Titlecases every segment between the given word boundaries.
+#[cfg(feature = "segmenter")]
+struct WordsTitlecaseWriteable<'a> {
+    mapper: TitlecaseMapperBorrowed<'a>,
+    src: &'a str,
+    boundaries: Vec<usize>,
+    locale: CaseMapLocale,
+    options: TitlecaseOptions,
+}
+
+#[cfg(feature = "segmenter")]
+impl<'a> Writeable for WordsTitlecaseWriteable<'a> {
+    fn write_to<W: fmt::Write + ?Sized>(&self, sink: &mut W) -> fmt::Result {
+        for segment in self.boundaries.windows(2) {
+            if let [start, end] = *segment {
+                let src = self.src.get(start..end).unwrap_or_default();
+                self.mapper
+                    .titlecase_segment_with_locale(src, self.locale, self.options)
+                    .write_to(sink)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn writeable_length_hint(&self) -> writeable::LengthHint {
+        writeable::LengthHint::at_least(self.src.len())
+    }
+
+    fn write_to_string(&self) -> Cow<'a, str> {
+        writeable::to_string_or_borrow(self, self.src.as_bytes())
+    }
+}
+
+/// Writes UTF-8 as UTF-16 to a vector.
+#[cfg(feature = "segmenter")]
+struct WriteUtf16<'a>(&'a mut Vec<u16>);
+
+#[cfg(feature = "segmenter")]
+impl fmt::Write for WriteUtf16<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend(s.encode_utf16());
+        Ok(())
+    }
 }
@@ -407,3 +407,96 @@ fn test_greek_upper() {
     // Handle breathing marks on rho
     assert_greek_uppercase("ῥήματα ῤήματα", "ΡΗΜΑΤΑ ΡΗΜΑΤΑ");
 }
+
+#[cfg(feature = "segmenter")]
+#[test]
+fn test_titlecase_words() {
+    use icu_casemap::options::{LeadingAdjustment, TitlecaseOptions, TrailingCase};
+    use icu_casemap::TitlecaseMapper;
+    use icu_segmenter::WordSegmenter;
+
+    let cm = TitlecaseMapper::new();
+    let segmenter = WordSegmenter::new_dictionary(Default::default());
+    let root = langid!("und");
+    let default_options = Default::default();
+
+    for (src, expected) in [
+        ("", ""),
+        ("   ", "   "),
+        ("hello", "Hello"),
+        ("HELLO WORLD", "Hello World"),
+        ("hello, world!", "Hello, World!"),
+        ("«bonjour»  le monde", "«Bonjour»  Le Monde"),
+        ("don't stop", "Don't Stop"),
+        ("e-mail address", "E-Mail Address"),
+        ("ǆungla ǳ", "ǅungla ǲ"),
+        ("ΟΔΟΣ ΣΟΦΟΣ", "Οδος Σοφος"),
+        ("ﬁre ﬂy", "Fire Fly"),
+        ("привет мир", "Привет Мир"),
+        ("日本語 text", "日本語 Text"),
+    ] {
+        assert_eq!(
+            cm.titlecase_to_string(src, &root, default_options, segmenter),
+            expected,
+            "{src:?}"
+        );
+        let src16: Vec<u16> = src.encode_utf16().collect();
+        let expected16: Vec<u16> = expected.encode_utf16().collect();
+        assert_eq!(
+            cm.titlecase_utf16(&src16, &root, default_options, segmenter),
+            expected16,
+            "{src:?}"
+        );
+    }
+
+    let nl = langid!("nl");
+    assert_eq!(
+        cm.titlecase_to_string(
+            "ijsselmeer, ijmuiden en igloo",
+            &nl,
+            default_options,
+            segmenter
+        ),
+        "IJsselmeer, IJmuiden En Igloo"
+    );
+    let tr = langid!("tr");
+    assert_eq!(
+        cm.titlecase_to_string("istanbul ile izmir", &tr, default_options, segmenter),
+        "İstanbul İle İzmir"
+    );
+
+    let mut preserve_case: TitlecaseOptions = Default::default();
+    preserve_case.trailing_case = Some(TrailingCase::Unchanged);
+    assert_eq!(
+        cm.titlecase_to_string("the iPhone from McDonald", &root, preserve_case, segmenter),
+        "The IPhone From McDonald"
+    );
+
+    let mut no_adjust: TitlecaseOptions = Default::default();
+    no_adjust.leading_adjustment = Some(LeadingAdjustment::None);
+    let mut adjust_to_cased: TitlecaseOptions = Default::default();
+    adjust_to_cased.leading_adjustment = Some(LeadingAdjustment::ToCased);
+    assert_eq!(
+        cm.titlecase_to_string("3rd place", &root, default_options, segmenter),
+        "3rd Place"
+    );
+    assert_eq!(
+        cm.titlecase_to_string("3rd place", &root, no_adjust, segmenter),
+        "3rd Place"
+    );
+    assert_eq!(
+        cm.titlecase_to_string("3rd place", &root, adjust_to_cased, segmenter),
+        "3Rd Place"
+    );
+
+    // Unpaired surrogates are replaced
+    assert_eq!(
+        cm.titlecase_utf16(
+            &[0x61, 0xD800, 0x20, 0x62],
+            &root,
+            default_options,
+            segmenter
+        ),
+        "A\u{FFFD} B".encode_utf16().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,1372 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+// The tailoring algorithm in this file is adapted from ICU4C's
+// `collationbuilder.cpp` and `collationdatabuilder.cpp` and, therefore,
+// the code is subject to the ICU license as described in LICENSE.
+
+//! This module holds the builder that turns parsed collation rules into
+//! `CollationData` in the same format as the tailorings produced by datagen.
+//!
+//! Tailored characters are placed into lists of nodes anchored at the
+//! primary weights of the base data. Once all rules have been processed,
+//! each list gets weights allocated between its anchor and the next base
+//! weight, and the resulting mappings are encoded as CE32s, expansions,
+//! and prefix/contraction tries layered over the base data.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Bound;
+use core::ops::RangeInclusive;
+use icu_collections::char16trie::{Char16Trie, Char16TrieBuilder};
+use icu_collections::codepointtrie::{CodePointTrie, CodePointTrieHeader, TrieType};
+use icu_normalizer::provider::{DecompositionData, DecompositionTables};
+use icu_normalizer::Decomposition;
+use icu_properties::props::Script;
+use icu_properties::{CodePointMapDataBorrowed, PropertyParserBorrowed};
+use zerovec::ule::AsULE;
+use zerovec::{ZeroSlice, ZeroVec};
+
+use crate::elements::{
+    ccc_from_trie_value, CollationElement32, CollationElements, Tag, CASE_MASK,
+    CONTRACT_HAS_STARTER, CONTRACT_NEXT_CCC, CONTRACT_TRAILING_CCC, FALLBACK_CE32, JAMO_COUNT,
+    NO_CE_PRIMARY, OPTIMIZED_DIACRITICS_MAX_COUNT, SPECIAL_CE32_LOW_BYTE, TERTIARY_MASK,
+};
+use crate::provider::{
+    CollationData, CollationDiacritics, CollationReordering, CollationSpecialPrimariesValidated,
+};
+use crate::rules::{CollationRulesError, Item, Level};
+
+/// The common secondary and tertiary weight.
+const COMMON_WEIGHT16: u16 = 0x0500;
+/// The last secondary weight of the range used for common-weight compression
+/// in sort keys.
+const SECONDARY_COMPRESSION_LIMIT: u32 = 0x4500;
+/// Limit of the tertiary weight (the case bits are separate).
+const TERTIARY_LIMIT: u32 = 0x4000;
+/// Maximum number of CEs in an expansion.
+const MAX_EXPANSION_LENGTH: usize = 31;
+/// Expansions and contexts are addressed using 19 bits.
+const MAX_INDEX: usize = 1 << 19;
+/// First code point of the Combining Diacritical Marks block.
+const COMBINING_DIACRITICS_BASE: u32 = 0x0300;
+
+/// Collation elements keyed by (prefix, string after the first character).
+type ContextMappings = BTreeMap<(Vec<char>, Vec<char>), Vec<u64>>;
+
+/// A collation element of a tailored mapping before weights have been allocated.
+#[derive(Debug, Clone, Copy)]
+enum BuildCe {
+    /// A collation element of the base data.
+    Base(u64),
+    /// A collation element whose weights come from a tailored node.
+    Node(usize),
+}
+
+/// A tailored position in the collation order.
+#[derive(Debug)]
+struct Node {
+    /// The base primary weight whose list this node belongs to.
+    primary: u32,
+    /// The strength of the difference from the preceding node.
+    level: Level,
+    /// For nodes that stand for a secondary or tertiary weight of the base
+    /// data (anchors of resets), that weight.
+    fixed: Option<u16>,
+    /// Byte offset of the rule that created the node.
+    offset: usize,
+    /// The collation element allocated for the node.
+    ce: u64,
+}
+
+/// Where the next relation inserts its node.
+#[derive(Debug, Clone, Copy)]
+enum Position {
+    /// Right after the base primary weight, before any tailored node.
+    Start(u32),
+    /// After all nodes tailored after the base primary weight.
+    End(u32),
+    /// Right after a node.
+    Node(usize),
+}
+
+/// Weights used by the base data. Tailored weights are allocated in the
+/// gaps between these.
+#[derive(Debug, Default)]
+struct BaseWeights {
+    primaries: BTreeSet<u32>,
+    secondaries: BTreeSet<u16>,
+    tertiaries: BTreeSet<u16>,
+}
+
+impl BaseWeights {
+    fn add_ce(&mut self, ce: u64) {
+        let p = (ce >> 32) as u32;
+        if p != 0 {
+            self.primaries.insert(p);
+        }
+        let s = (ce >> 16) as u16;
+        if s != 0 {
+            self.secondaries.insert(s);
+        }
+        let t = (ce as u16) & TERTIARY_MASK;
+        if t != 0 {
+            self.tertiaries.insert(t);
+        }
+    }
+
+    fn add_data(&mut self, data: &CollationData) {
+        let mut visited = BTreeSet::new();
+        for range in data.trie.iter_ranges() {
+            self.add_ce32(data, range.value, &range.range, &mut visited);
+        }
+    }
+
+    fn add_ce32(
+        &mut self,
+        data: &CollationData,
+        ce32: u32,
+        range: &RangeInclusive<u32>,
+        visited: &mut BTreeSet<usize>,
+    ) {
+        let ce32 = CollationElement32::new(ce32);
+        if let Some(ce) = ce32.to_ce_self_contained() {
+            self.add_ce(ce.bits());
+            return;
+        }
+        match ce32.tag() {
+            Tag::Expansion32 => {
+                for ce32 in data.get_ce32s(ce32.index(), ce32.len()).iter() {
+                    self.add_ce32(data, ce32, range, visited);
+                }
+            }
+            Tag::Expansion => {
+                for ce in data.get_ces(ce32.index(), ce32.len()).iter() {
+                    self.add_ce(ce);
+                }
+            }
+            Tag::Digit => {
+                self.add_ce32(data, data.get_ce32(ce32.index()).bits(), range, visited);
+            }
+            Tag::Prefix | Tag::Contraction => {
+                if visited.insert(ce32.index()) {
+                    let (default, trie) = data.get_default_and_trie_data(ce32.index());
+                    self.add_ce32(data, default.bits(), range, visited);
+                    for (_, value) in Char16Trie::new(trie.as_zerovec()).iter_entries() {
+                        self.add_ce32(data, value as u32, range, visited);
+                    }
+                }
+            }
+            Tag::Offset => {
+                // The primaries in between are reached by incrementing the
+                // first one, so only the ends matter.
+                for c in [range.start(), range.end()] {
+                    if let Some(c) = char::from_u32(*c) {
+                        self.add_ce(data.ce_from_offset_ce32(c, ce32).bits());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The encoded data arrays of a tailoring.
+#[derive(Debug, Default)]
+struct DataWriter {
+    ces: Vec<u64>,
+    ce32s: Vec<u32>,
+    contexts: Vec<u16>,
+    expansions: BTreeMap<Vec<u64>, usize>,
+}
+
+/// Returns the CE32 that represents `ce` without an expansion, if any.
+fn self_contained_ce32(ce: u64) -> Option<u32> {
+    let p = (ce >> 32) as u32;
+    let lower = ce as u32;
+    if p & 0xFFFF == 0 && lower & 0x00FF_00FF == 0 {
+        // Simple CE32: two-byte primary, one-byte secondary, tertiary with case bits.
+        Some(p | ((lower >> 16) & 0xFF00) | ((lower >> 8) & 0xFF))
+    } else if lower == 0x0500_0500 && p & 0xFF == 0 {
+        Some(p | u32::from(SPECIAL_CE32_LOW_BYTE) | Tag::LongPrimary as u32)
+    } else if p == 0 && lower & 0xFF == 0 {
+        Some(lower | u32::from(SPECIAL_CE32_LOW_BYTE) | Tag::LongSecondary as u32)
+    } else {
+        None
+    }
+}
+
+impl DataWriter {
+    fn encode_ces(&mut self, ces: &[u64], offset: usize) -> Result<u32, CollationRulesError> {
+        if let [ce] = ces {
+            if let Some(ce32) = self_contained_ce32(*ce) {
+                return Ok(ce32);
+            }
+        }
+        if ces.is_empty() {
+            // A completely ignorable mapping.
+            return Ok(0);
+        }
+        let ces = ces.to_vec();
+        if ces.len() > MAX_EXPANSION_LENGTH {
+            return Err(CollationRulesError::Unsupported {
+                offset,
+                construct: "expansions to more than 31 collation elements",
+            });
+        }
+        let len = ces.len() as u32;
+        let index = if let Some(&index) = self.expansions.get(&ces) {
+            index
+        } else {
+            let index = self.ces.len();
+            self.ces.extend_from_slice(&ces);
+            self.expansions.insert(ces, index);
+            index
+        };
+        if index >= MAX_INDEX {
+            return Err(too_large());
+        }
+        Ok(((index as u32) << 13)
+            | (len << 8)
+            | u32::from(SPECIAL_CE32_LOW_BYTE)
+            | Tag::Expansion as u32)
+    }
+
+    fn write_context(
+        &mut self,
+        default: u32,
+        trie: Char16Trie<'static>,
+    ) -> Result<u32, CollationRulesError> {
+        let index = self.contexts.len();
+        if index >= MAX_INDEX {
+            return Err(too_large());
+        }
+        self.contexts.push((default >> 16) as u16);
+        self.contexts.push(default as u16);
+        self.contexts.extend(trie.data.iter());
+        Ok((index as u32) << 13)
+    }
+}
+
+fn too_large() -> CollationRulesError {
+    CollationRulesError::Unsupported {
+        offset: 0,
+        construct: "tailorings this large",
+    }
+}
+
+/// The kind of weight being allocated. Secondary and tertiary weights are
+/// handled left-aligned in 32 bits like primary weights.
+#[derive(Debug, Clone, Copy)]
+enum WeightKind<'a> {
+    Primary(&'a CollationSpecialPrimariesValidated<'a>),
+    Secondary,
+    Tertiary,
+}
+
+impl WeightKind<'_> {
+    fn max_length(self) -> u32 {
+        match self {
+            WeightKind::Primary(_) => 4,
+            WeightKind::Secondary | WeightKind::Tertiary => 2,
+        }
+    }
+
+    /// The inclusive range of valid values for byte `i` (0-based) of a weight
+    /// that starts like `w`.
+    fn byte_limits(self, w: u32, i: u32) -> (u32, u32) {
+        match (self, i) {
+            // Bytes 03 and FF are reserved for primary compression.
+            (WeightKind::Primary(special_primaries), 1)
+                if special_primaries.is_compressible((w >> 24) as u8) =>
+            {
+                (4, 0xFE)
+            }
+            (WeightKind::Primary(_) | WeightKind::Secondary, _) => (2, 0xFF),
+            (WeightKind::Tertiary, _) => (2, 0x3F),
+        }
+    }
+
+    /// Sets bytes `from..to` of `w` to their minimum values.
+    fn pad(self, mut w: u32, from: u32, to: u32) -> u32 {
+        for i in from..to {
+            let shift = 8 * (3 - i);
+            w = (w & !(0xFF << shift)) | (self.byte_limits(w, i).0 << shift);
+        }
+        w
+    }
+}
+
+/// The number of bytes of a left-aligned weight.
+fn weight_length(w: u32) -> u32 {
+    4 - w.trailing_zeros().min(32) / 8
+}
+
+/// The first `length` bytes of `w`.
+fn truncate_weight(w: u32, length: u32) -> u32 {
+    match length {
+        0 => 0,
+        1..=3 => w & (u32::MAX << (8 * (4 - length))),
+        _ => w,
+    }
+}
+
+/// Increments `w` at byte `length - 1`, carrying over into the earlier bytes.
+fn increment_weight(kind: WeightKind, w: u32, length: u32) -> Option<u32> {
+    let mut i = length.checked_sub(1)?;
+    loop {
+        let (min, max) = kind.byte_limits(w, i);
+        let shift = 8 * (3 - i);
+        let b = (w >> shift) & 0xFF;
+        if b < max {
+            let w = (w & !(0xFF << shift)) | ((b + 1).max(min) << shift);
+            return Some(kind.pad(w, i + 1, length));
+        }
+        i = i.checked_sub(1)?;
+    }
+}
+
+/// Returns `count` weights of equal length between `lower` and `upper`
+/// (both exclusive), as short as possible.
+///
+/// The weights never extend `lower` and are never a prefix of `upper`, since
+/// sort keys concatenate weights of different lengths: a weight followed by
+/// the next weight must still compare less than any longer weight after it.
+fn allocate_weights(kind: WeightKind, lower: u32, upper: u32, count: usize) -> Option<Vec<u32>> {
+    let lower_length = weight_length(lower);
+    for length in 1..=kind.max_length() {
+        let mut next = if lower_length > length {
+            increment_weight(kind, truncate_weight(lower, length), length)
+        } else {
+            increment_weight(kind, lower, lower_length).map(|w| kind.pad(w, lower_length, length))
+        };
+        let mut weights = Vec::with_capacity(count);
+        while let Some(w) = next.filter(|_| weights.len() < count) {
+            if w >= upper || truncate_weight(upper, length) == w {
+                break;
+            }
+            weights.push(w);
+            next = increment_weight(kind, w, length);
+        }
+        if weights.len() == count {
+            return Some(weights);
+        }
+    }
+    None
+}
+
+/// Combines the case bits of the collation elements that a tailored
+/// collation element replaces, in the manner of ICU4C.
+fn combined_case(cases: &[u16]) -> u16 {
+    if cases.iter().all(|&c| c == 0) {
+        0
+    } else if cases.iter().all(|&c| c == 0x8000) {
+        0x8000
+    } else {
+        // Mixed case.
+        0x4000
+    }
+}
+
+/// A fast-type `CodePointTrie` from a base trie and a set of overrides.
+fn build_trie(
+    base: Option<&CodePointTrie<u32>>,
+    overrides: &BTreeMap<u32, u32>,
+) -> Result<CodePointTrie<'static, u32>, CollationRulesError> {
+    let fallback = FALLBACK_CE32.bits();
+    let base_value = |c: u32| base.map_or(fallback, |b| b.get32(c));
+    let value = |c: u32| overrides.get(&c).copied().unwrap_or_else(|| base_value(c));
+
+    let high_value = base_value(0x10FFFF);
+    let mut high_start = 0x10000;
+    if let Some(base) = base {
+        for range in base.iter_ranges() {
+            if range.value != high_value {
+                high_start = high_start.max(range.range.end() + 1);
+            }
+        }
+    }
+    if let Some((&last, _)) = overrides.last_key_value() {
+        high_start = high_start.max(last + 1);
+    }
+    // Round up to a whole index-1 entry.
+    let high_start = ((high_start + 0x3FFF) & !0x3FFF).min(0x110000);
+
+    let mut data: Vec<u32> = Vec::new();
+    let mut blocks: BTreeMap<Vec<u32>, usize> = BTreeMap::new();
+    let mut block_offset = |values: Vec<u32>, data: &mut Vec<u32>| -> usize {
+        *blocks.entry(values).or_insert_with_key(|values| {
+            let offset = data.len();
+            data.extend_from_slice(values);
+            offset
+        })
+    };
+
+    let mut index: Vec<usize> = Vec::new();
+    for block in 0..0x400 {
+        let values = (0..64).map(|i| value((block << 6) | i)).collect();
+        index.push(block_offset(values, &mut data));
+    }
+
+    let mut index2_blocks: Vec<Vec<usize>> = Vec::new();
+    let mut index3_blocks: Vec<Vec<usize>> = Vec::new();
+    let mut index1: Vec<usize> = Vec::new();
+    for i1 in 4..(high_start >> 14) {
+        let mut index2 = Vec::new();
+        for i2 in 0..32 {
+            let mut index3 = Vec::new();
+            for i3 in 0..32 {
+                let start = (i1 << 14) | (i2 << 9) | (i3 << 4);
+                let values = (0..16).map(|i| value(start | i)).collect();
+                index3.push(block_offset(values, &mut data));
+            }
+            let position = index3_blocks
+                .iter()
+                .position(|b| *b == index3)
+                .unwrap_or_else(|| {
+                    index3_blocks.push(index3);
+                    index3_blocks.len() - 1
+                });
+            index2.push(position);
+        }
+        let position = index2_blocks
+            .iter()
+            .position(|b| *b == index2)
+            .unwrap_or_else(|| {
+                index2_blocks.push(index2);
+                index2_blocks.len() - 1
+            });
+        index1.push(position);
+    }
+
+    let index2_start = index.len() + index1.len();
+    let index3_start = index2_start + 32 * index2_blocks.len();
+    if index3_start + 32 * index3_blocks.len() > 0x8000 || data.len() > 0x10000 {
+        return Err(too_large());
+    }
+    index.extend(index1.iter().map(|i| index2_start + 32 * i));
+    for block in &index2_blocks {
+        index.extend(block.iter().map(|i| index3_start + 32 * i));
+    }
+    for block in index3_blocks {
+        index.extend(block);
+    }
+    data.push(high_value);
+    data.push(fallback);
+
+    let index: Vec<u16> = index.into_iter().map(|i| i as u16).collect();
+    CodePointTrie::try_new(
+        CodePointTrieHeader {
+            high_start,
+            shifted12_high_start: (high_start >> 12) as u16,
+            // No dedicated null blocks.
+            index3_null_offset: 0x7FFF,
+            data_null_offset: 0xFFFFF,
+            null_value: fallback,
+            trie_type: TrieType::Fast,
+        },
+        ZeroVec::alloc_from_slice(&index),
+        ZeroVec::alloc_from_slice(&data),
+    )
+    .map_err(|_| too_large())
+}
+
+/// The tailoring data built from rules.
+#[derive(Debug)]
+pub(crate) struct Tailoring {
+    pub data: CollationData<'static>,
+    pub diacritics: CollationDiacritics<'static>,
+}
+
+/// The nodes and mappings accumulated while processing the rules.
+#[derive(Debug, Default)]
+struct RuleState {
+    nodes: Vec<Node>,
+    /// Node ids in collation order, keyed by the base primary they follow.
+    lists: BTreeMap<u32, Vec<usize>>,
+    /// Tailored mappings keyed by (prefix, string), both in NFD.
+    mappings: BTreeMap<(String, String), (Vec<BuildCe>, usize)>,
+}
+
+impl RuleState {
+    fn new_node(&mut self, primary: u32, level: Level, fixed: Option<u16>, offset: usize) -> usize {
+        self.nodes.push(Node {
+            primary,
+            level,
+            fixed,
+            offset,
+            ce: 0,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Returns the position of a base collation element, creating anchor
+    /// nodes for its secondary and tertiary weights as needed.
+    fn anchor(&mut self, ce: u64, offset: usize) -> Result<Position, CollationRulesError> {
+        let p = (ce >> 32) as u32;
+        let s = (ce >> 16) as u16;
+        let t = (ce as u16) & TERTIARY_MASK;
+        if s == 0 {
+            return Err(CollationRulesError::Unsupported {
+                offset,
+                construct: "resetting to a tertiary-ignorable character",
+            });
+        }
+        let mut position = Position::Start(p);
+        let mut index = 0;
+        for (level, weight) in [(Level::Secondary, s), (Level::Tertiary, t)] {
+            if weight == COMMON_WEIGHT16 {
+                continue;
+            }
+            let list = self.lists.entry(p).or_default();
+            let found = loop {
+                match list.get(index).and_then(|&id| self.nodes.get(id)) {
+                    Some(node) if node.level > level => index += 1,
+                    Some(node) if node.level == level && node.fixed == Some(weight) => break true,
+                    Some(node) if node.level == level && node.fixed.is_none_or(|f| f < weight) => {
+                        index += 1
+                    }
+                    _ => break false,
+                }
+            };
+            let id = if found {
+                list.get(index).copied().unwrap_or_default()
+            } else {
+                let id = self.nodes.len();
+                self.lists.entry(p).or_default().insert(index, id);
+                self.new_node(p, level, Some(weight), offset)
+            };
+            position = Position::Node(id);
+            index += 1;
+        }
+        Ok(position)
+    }
+
+    /// Inserts a node with difference `level` at `position`, skipping any
+    /// following nodes that differ at a weaker level.
+    fn insert(&mut self, position: Position, level: Level, offset: usize) -> usize {
+        let (p, mut index, at_end) = match position {
+            Position::Start(p) => (p, 0, false),
+            Position::End(p) => (p, usize::MAX, true),
+            Position::Node(id) => {
+                let p = self.nodes.get(id).map(|n| n.primary).unwrap_or_default();
+                let index = self
+                    .lists
+                    .get(&p)
+                    .and_then(|l| l.iter().position(|&n| n == id))
+                    .map_or(0, |i| i + 1);
+                (p, index, false)
+            }
+        };
+        let id = self.nodes.len();
+        let list = self.lists.entry(p).or_default();
+        if at_end {
+            index = list.len();
+        } else {
+            while list
+                .get(index)
+                .and_then(|&n| self.nodes.get(n))
+                .is_some_and(|n| n.level > level)
+            {
+                index += 1;
+            }
+        }
+        list.insert(index, id);
+        self.new_node(p, level, None, offset)
+    }
+
+    /// Allocates the collation elements of all nodes.
+    fn allocate(
+        &mut self,
+        weights: &BaseWeights,
+        special_primaries: &CollationSpecialPrimariesValidated,
+    ) -> Result<(), CollationRulesError> {
+        let common = u32::from(COMMON_WEIGHT16);
+        let mut allocated: BTreeMap<usize, u32> = BTreeMap::new();
+        for (&anchor, list) in &self.lists {
+            let primary = WeightKind::Primary(special_primaries);
+            // Runs of tailored nodes per level that share their bounds,
+            // as (lower bound, nodes).
+            let mut runs: [(u32, Vec<usize>); 3] = [
+                (anchor, Vec::new()),
+                (common, Vec::new()),
+                (common, Vec::new()),
+            ];
+            for &id in list {
+                let Some(node) = self.nodes.get(id) else {
+                    continue;
+                };
+                let fixed = node.fixed.map(u32::from);
+                let (level_index, kind) = match node.level {
+                    Level::Primary => (0, primary),
+                    Level::Secondary => (1, WeightKind::Secondary),
+                    Level::Tertiary => (2, WeightKind::Tertiary),
+                    Level::Quaternary | Level::Identical => continue,
+                };
+                // A node ends the runs of the weaker levels.
+                for (weaker, kind) in [(1, WeightKind::Secondary), (2, WeightKind::Tertiary)] {
+                    if weaker > level_index {
+                        if let Some(run) = runs.get_mut(weaker) {
+                            self.close_run(kind, run, None, weights, &mut allocated)?;
+                            *run = (common, Vec::new());
+                        }
+                    }
+                }
+                let Some(run) = runs.get_mut(level_index) else {
+                    continue;
+                };
+                if let Some(fixed) = fixed {
+                    self.close_run(kind, run, Some(fixed), weights, &mut allocated)?;
+                    *run = (fixed, Vec::new());
+                } else {
+                    run.1.push(id);
+                }
+            }
+            let limit = weights
+                .primaries
+                .range((Bound::Excluded(anchor), Bound::Unbounded))
+                .next()
+                .copied()
+                .unwrap_or(u32::MAX);
+            let limit = if weights.primaries.contains(&anchor) {
+                limit
+            } else {
+                // Not a weight of the base data, for example an implicit weight.
+                limit.min(
+                    increment_weight(primary, anchor, weight_length(anchor)).unwrap_or(u32::MAX),
+                )
+            };
+            let [p, s, t] = &mut runs;
+            self.close_run(primary, p, Some(limit), weights, &mut allocated)?;
+            self.close_run(WeightKind::Secondary, s, None, weights, &mut allocated)?;
+            self.close_run(WeightKind::Tertiary, t, None, weights, &mut allocated)?;
+        }
+
+        for (&anchor, list) in &self.lists {
+            let (mut p, mut s, mut t, mut q) = (anchor, common, common, 0);
+            for &id in list {
+                let Some(node) = self.nodes.get_mut(id) else {
+                    continue;
+                };
+                let weight = node
+                    .fixed
+                    .map(u32::from)
+                    .or_else(|| allocated.get(&id).copied());
+                match (node.level, weight) {
+                    (Level::Primary, Some(w)) => (p, s, t, q) = (w, common, common, 0),
+                    (Level::Secondary, Some(w)) => (s, t, q) = (w, common, 0),
+                    (Level::Tertiary, Some(w)) => (t, q) = (w, 0),
+                    (Level::Quaternary | Level::Identical, _) if q < 3 => q += 1,
+                    _ => {
+                        return Err(CollationRulesError::WeightOverflow {
+                            offset: node.offset,
+                        })
+                    }
+                }
+                node.ce = (u64::from(p) << 32) | (u64::from(s) << 16) | u64::from(t | (q << 6));
+            }
+        }
+        Ok(())
+    }
+
+    /// Allocates weights for the nodes of a run between its lower bound and
+    /// `upper`, which defaults to the next weight of the base data.
+    fn close_run(
+        &self,
+        kind: WeightKind,
+        (lower, nodes): &mut (u32, Vec<usize>),
+        upper: Option<u32>,
+        weights: &BaseWeights,
+        allocated: &mut BTreeMap<usize, u32>,
+    ) -> Result<(), CollationRulesError> {
+        let Some(&first) = nodes.first() else {
+            return Ok(());
+        };
+        let overflow = CollationRulesError::WeightOverflow {
+            offset: self.nodes.get(first).map(|n| n.offset).unwrap_or_default(),
+        };
+        let next_base = |set: &BTreeSet<u16>, limit: u32| {
+            set.range((Bound::Excluded(*lower as u16), Bound::Unbounded))
+                .next()
+                .map_or(limit, |&w| u32::from(w))
+        };
+        let common = u32::from(COMMON_WEIGHT16);
+        let (lower32, upper32) = match kind {
+            WeightKind::Primary(_) => (*lower, upper.unwrap_or(u32::MAX)),
+            WeightKind::Secondary => (
+                // The weights right above the common weight are used for compression.
+                if *lower == common {
+                    SECONDARY_COMPRESSION_LIMIT
+                } else {
+                    *lower
+                } << 16,
+                upper
+                    .unwrap_or_else(|| next_base(&weights.secondaries, 0x10000))
+                    .checked_shl(16)
+                    .filter(|&u| u != 0)
+                    .unwrap_or(u32::MAX),
+            ),
+            WeightKind::Tertiary => (
+                *lower << 16,
+                upper.unwrap_or_else(|| next_base(&weights.tertiaries, TERTIARY_LIMIT)) << 16,
+            ),
+        };
+        let allocated_weights =
+            allocate_weights(kind, lower32, upper32, nodes.len()).ok_or(overflow)?;
+        for (&id, w) in nodes.iter().zip(allocated_weights) {
+            allocated.insert(
+                id,
+                match kind {
+                    WeightKind::Primary(_) => w,
+                    WeightKind::Secondary | WeightKind::Tertiary => w >> 16,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, ces: &[BuildCe]) -> Vec<u64> {
+        ces.iter()
+            .map(|ce| match *ce {
+                BuildCe::Base(ce) => ce,
+                BuildCe::Node(id) => self.nodes.get(id).map(|n| n.ce).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Builds tailoring data on top of the root (and optionally an imported
+/// tailoring).
+#[derive(Debug)]
+pub(crate) struct TailoringBuilder<'a> {
+    pub root: &'a CollationData<'a>,
+    /// An imported tailoring that the rules build upon.
+    pub base: Option<&'a CollationData<'a>>,
+    pub jamo: &'a [<u32 as AsULE>::ULE; JAMO_COUNT],
+    pub diacritics: &'a ZeroSlice<u16>,
+    pub decompositions: &'a DecompositionData<'a>,
+    pub tables: &'a DecompositionTables<'a>,
+    pub special_primaries: &'a CollationSpecialPrimariesValidated<'a>,
+    pub lithuanian_dot_above: bool,
+}
+
+impl TailoringBuilder<'_> {
+    fn nfd(&self, s: &str) -> String {
+        Decomposition::new(s.chars(), self.decompositions, self.tables).collect()
+    }
+
+    /// The collation elements of `s` according to the base data.
+    fn base_ces(&self, s: &str) -> Vec<u64> {
+        let mut iter = CollationElements::new(
+            s.chars(),
+            self.root,
+            self.base.unwrap_or(self.root),
+            self.jamo,
+            self.diacritics,
+            self.decompositions,
+            self.tables,
+            None,
+            self.lithuanian_dot_above,
+        );
+        iter.init();
+        let mut ces = Vec::new();
+        loop {
+            let ce = iter.next();
+            if ce.primary() == NO_CE_PRIMARY {
+                return ces;
+            }
+            if ce.bits() != 0 {
+                ces.push(ce.bits());
+            }
+        }
+    }
+
+    /// The collation elements of the NFD string `s` using the tailored
+    /// mappings so far, matching the longest tailored string first.
+    fn ces_with_tailoring(&self, state: &RuleState, s: &str) -> Vec<BuildCe> {
+        let mut ces = Vec::new();
+        let mut segment_start = 0;
+        let mut pos = 0;
+        while let Some(rest) = s.get(pos..).filter(|r| !r.is_empty()) {
+            let longest = state
+                .mappings
+                .iter()
+                .filter(|((prefix, text), _)| prefix.is_empty() && rest.starts_with(text.as_str()))
+                .max_by_key(|((_, text), _)| text.len());
+            if let Some(((_, text), (mapping, _))) = longest {
+                if let Some(segment) = s.get(segment_start..pos).filter(|s| !s.is_empty()) {
+                    ces.extend(self.base_ces(segment).into_iter().map(BuildCe::Base));
+                }
+                ces.extend_from_slice(mapping);
+                pos += text.len();
+                segment_start = pos;
+            } else {
+                pos += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        if let Some(segment) = s.get(segment_start..).filter(|s| !s.is_empty()) {
+            ces.extend(self.base_ces(segment).into_iter().map(BuildCe::Base));
+        }
+        ces
+    }
+
+    /// Sets the case bits of the tailored collation elements of `s` from the
+    /// case bits of its base collation elements.
+    fn set_case_bits(&self, s: &str, ces: &mut [u64]) {
+        let cases: Vec<u16> = self
+            .base_ces(s)
+            .into_iter()
+            .filter(|ce| ce >> 32 != 0)
+            .map(|ce| (ce as u16) & CASE_MASK)
+            .collect();
+        let count = ces.iter().filter(|ce| **ce >> 32 != 0).count();
+        let mut i = 0;
+        for ce in ces.iter_mut().filter(|ce| **ce >> 32 != 0) {
+            i += 1;
+            let case = if i == count {
+                // The last tailored primary stands for all remaining base ones.
+                combined_case(cases.get(i - 1..).unwrap_or_default())
+            } else {
+                cases.get(i - 1).copied().unwrap_or_default()
+            };
+            *ce = (*ce & !u64::from(CASE_MASK)) | u64::from(case);
+        }
+    }
+
+    /// Turns the rule items into tailoring data.
+    pub(crate) fn build(&self, items: &[Item]) -> Result<Tailoring, CollationRulesError> {
+        let mut weights = BaseWeights::default();
+        weights.add_data(self.root);
+        if let Some(base) = self.base {
+            weights.add_data(base);
+        }
+        let mut visited = BTreeSet::new();
+        for &ce32 in self.jamo.iter() {
+            weights.add_ce32(self.root, u32::from_unaligned(ce32), &(0..=0), &mut visited);
+        }
+        weights.secondaries.extend(self.diacritics.iter());
+
+        let mut state = RuleState::default();
+        let mut reset: Vec<BuildCe> = Vec::new();
+        let mut position = None;
+        for item in items {
+            match item {
+                Item::Reset {
+                    before,
+                    text,
+                    offset,
+                } => {
+                    let offset = *offset;
+                    reset = self.ces_with_tailoring(&state, &self.nfd(text));
+                    let Some(last) = reset.last_mut() else {
+                        return Err(CollationRulesError::Unsupported {
+                            offset,
+                            construct: "resetting to an ignorable string",
+                        });
+                    };
+                    position = Some(match (before, *last) {
+                        (None, BuildCe::Node(id)) => Position::Node(id),
+                        (None, BuildCe::Base(ce)) => state.anchor(ce, offset)?,
+                        (Some(Level::Primary), BuildCe::Base(ce)) => {
+                            let prev = weights
+                                .primaries
+                                .range(1..(ce >> 32) as u32)
+                                .next_back()
+                                .copied()
+                                .ok_or(CollationRulesError::Unsupported {
+                                    offset,
+                                    construct: "[before 1] without a preceding primary weight",
+                                })?;
+                            *last = BuildCe::Base((u64::from(prev) << 32) | 0x0500_0500);
+                            Position::End(prev)
+                        }
+                        (Some(_), _) => {
+                            return Err(CollationRulesError::Unsupported {
+                                offset,
+                                construct: "[before 2], [before 3], or [before] a tailored string",
+                            })
+                        }
+                    });
+                }
+                Item::Relation {
+                    level,
+                    prefix,
+                    text,
+                    extension,
+                    offset,
+                } => {
+                    let offset = *offset;
+                    let Some(current) = position else {
+                        return Err(CollationRulesError::Syntax {
+                            offset,
+                            reason: "relation without a reset",
+                        });
+                    };
+                    let prefix = self.nfd(prefix);
+                    let text = self.nfd(text);
+                    if prefix
+                        .chars()
+                        .chain(text.chars())
+                        .any(|c| ('\u{1100}'..='\u{11FF}').contains(&c))
+                    {
+                        return Err(CollationRulesError::Unsupported {
+                            offset,
+                            construct: "tailoring Hangul jamo",
+                        });
+                    }
+                    if text.is_empty() {
+                        return Err(CollationRulesError::Syntax {
+                            offset,
+                            reason: "empty string",
+                        });
+                    }
+                    let mut ces = reset.clone();
+                    if *level != Level::Identical {
+                        let primary = match current {
+                            Position::Start(p) | Position::End(p) => p,
+                            Position::Node(id) => {
+                                state.nodes.get(id).map(|n| n.primary).unwrap_or_default()
+                            }
+                        };
+                        if primary == 0 && *level == Level::Primary {
+                            return Err(CollationRulesError::Unsupported {
+                                offset,
+                                construct: "primary relations after an ignorable character",
+                            });
+                        }
+                        let id = state.insert(current, *level, offset);
+                        ces.pop();
+                        ces.push(BuildCe::Node(id));
+                        position = Some(Position::Node(id));
+                        reset.clone_from(&ces);
+                    }
+                    if !extension.is_empty() {
+                        ces.extend(self.ces_with_tailoring(&state, &self.nfd(extension)));
+                    }
+                    state.mappings.insert((prefix, text), (ces, offset));
+                }
+            }
+        }
+        state.allocate(&weights, self.special_primaries)?;
+
+        // Group the mappings by their first character.
+        let mut per_char: BTreeMap<char, ContextMappings> = BTreeMap::new();
+        for ((prefix, text), (ces, _)) in &state.mappings {
+            let mut chars = text.chars();
+            let Some(first) = chars.next() else {
+                continue;
+            };
+            let mut ces = state.resolve(ces);
+            self.set_case_bits(text, &mut ces);
+            per_char
+                .entry(first)
+                .or_default()
+                .insert((prefix.chars().collect(), chars.collect()), ces);
+        }
+
+        let mut writer = DataWriter::default();
+        if let Some(base) = self.base {
+            writer.ces.extend(base.ces.iter());
+            writer.ce32s.extend(base.ce32s.iter());
+            writer.contexts.extend(base.contexts.iter());
+        }
+
+        // The diacritics table bypasses the trie, so it has to stop before the
+        // first tailored diacritic.
+        let mut diacritics: Vec<u16> = self.diacritics.iter().collect();
+        if let Some(&first) = per_char.keys().find(|&&c| {
+            (u32::from(c).wrapping_sub(COMBINING_DIACRITICS_BASE) as usize)
+                < OPTIMIZED_DIACRITICS_MAX_COUNT
+        }) {
+            diacritics.truncate((u32::from(first) - COMBINING_DIACRITICS_BASE) as usize);
+        }
+
+        let mut overrides: BTreeMap<u32, u32> = BTreeMap::new();
+        for (i, secondary) in self.diacritics.iter().enumerate().skip(diacritics.len()) {
+            let c = COMBINING_DIACRITICS_BASE + i as u32;
+            if !per_char.keys().any(|&k| u32::from(k) == c) {
+                let ce = (u64::from(secondary) << 16) | u64::from(COMMON_WEIGHT16);
+                overrides.insert(c, writer.encode_ces(&[ce], 0)?);
+            }
+        }
+
+        for (c, tailored) in per_char {
+            let offset = state
+                .mappings
+                .iter()
+                .find(|((_, text), _)| text.starts_with(c))
+                .map(|(_, (_, offset))| *offset)
+                .unwrap_or_default();
+            let mut entries: ContextMappings = BTreeMap::new();
+            for (prefix, suffix) in self.base_contexts(c) {
+                if let Some(ces) = self.base_context_ces(c, &prefix, &suffix) {
+                    entries.insert((prefix, suffix), ces);
+                }
+            }
+            entries.insert((vec![], vec![]), self.base_ces(c.encode_utf8(&mut [0; 4])));
+            entries.extend(tailored);
+            let ce32 = self.encode_char(&mut writer, entries, offset)?;
+            overrides.insert(u32::from(c), ce32);
+        }
+
+        Ok(Tailoring {
+            data: CollationData {
+                trie: build_trie(self.base.map(|b| &b.trie), &overrides)?,
+                ces: ZeroVec::alloc_from_slice(&writer.ces),
+                ce32s: ZeroVec::alloc_from_slice(&writer.ce32s),
+                contexts: ZeroVec::alloc_from_slice(&writer.contexts),
+            },
+            diacritics: CollationDiacritics {
+                secondaries: ZeroVec::alloc_from_slice(&diacritics),
+            },
+        })
+    }
+
+    /// The (prefix, suffix) pairs for which the base data has context-sensitive
+    /// mappings starting with `c`.
+    fn base_contexts(&self, c: char) -> Vec<(Vec<char>, Vec<char>)> {
+        let mut data = self.base.unwrap_or(self.root);
+        let mut ce32 = data.ce32_for_char(c);
+        if ce32 == FALLBACK_CE32 {
+            data = self.root;
+            ce32 = data.ce32_for_char(c);
+        }
+        let mut out = Vec::new();
+        Self::collect_contexts(data, ce32, &[], &mut out);
+        out
+    }
+
+    fn collect_contexts(
+        data: &CollationData,
+        ce32: CollationElement32,
+        prefix: &[char],
+        out: &mut Vec<(Vec<char>, Vec<char>)>,
+    ) {
+        if !prefix.is_empty() {
+            out.push((prefix.to_vec(), vec![]));
+        }
+        let decode = |units: Vec<u16>| -> Vec<char> {
+            char::decode_utf16(units).filter_map(Result::ok).collect()
+        };
+        match ce32.tag_checked() {
+            Some(Tag::Prefix) => {
+                let (default, trie) = data.get_default_and_trie_data(ce32.index());
+                Self::collect_contexts(data, default, prefix, out);
+                for (units, value) in Char16Trie::new(trie.as_zerovec()).iter_entries() {
+                    // Prefixes are stored backwards.
+                    let mut p = decode(units);
+                    p.reverse();
+                    Self::collect_contexts(data, CollationElement32::new(value as u32), &p, out);
+                }
+            }
+            Some(Tag::Contraction) => {
+                let (_, trie) = data.get_default_and_trie_data(ce32.index());
+                for (units, _) in Char16Trie::new(trie.as_zerovec()).iter_entries() {
+                    out.push((prefix.to_vec(), decode(units)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The base collation elements of `c` followed by `suffix` when preceded by `prefix`.
+    fn base_context_ces(&self, c: char, prefix: &[char], suffix: &[char]) -> Option<Vec<u64>> {
+        let prefix: String = prefix.iter().collect();
+        let mut s = prefix.clone();
+        s.push(c);
+        s.extend(suffix);
+        let ces = self.base_ces(&s);
+        let prefix_ces = self.base_ces(&prefix);
+        ces.strip_prefix(prefix_ces.as_slice()).map(<[u64]>::to_vec)
+    }
+
+    /// Encodes the mappings for strings starting with one character.
+    fn encode_char(
+        &self,
+        writer: &mut DataWriter,
+        entries: ContextMappings,
+        offset: usize,
+    ) -> Result<u32, CollationRulesError> {
+        let mut groups: BTreeMap<Vec<char>, BTreeMap<Vec<char>, Vec<u64>>> = BTreeMap::new();
+        for ((prefix, suffix), ces) in entries {
+            groups.entry(prefix).or_default().insert(suffix, ces);
+        }
+        let no_prefix = groups.remove(&Vec::new()).unwrap_or_default();
+        let default = self.encode_suffixes(writer, &no_prefix, offset)?;
+        if groups.is_empty() {
+            return Ok(default);
+        }
+        let mut trie = Char16TrieBuilder::new();
+        for (prefix, mut group) in groups {
+            if prefix.len() > 2 {
+                return Err(CollationRulesError::Unsupported {
+                    offset,
+                    construct: "prefixes longer than two characters",
+                });
+            }
+            for (suffix, ces) in &no_prefix {
+                group.entry(suffix.clone()).or_insert_with(|| ces.clone());
+            }
+            let ce32 = self.encode_suffixes(writer, &group, offset)?;
+            let reversed: String = prefix.iter().rev().collect();
+            trie.insert(&reversed, ce32 as i32);
+        }
+        Ok(writer.write_context(default, trie.build())?
+            | u32::from(SPECIAL_CE32_LOW_BYTE)
+            | Tag::Prefix as u32)
+    }
+
+    /// Encodes the mappings for one prefix, keyed by the suffix after the first character.
+    fn encode_suffixes(
+        &self,
+        writer: &mut DataWriter,
+        group: &BTreeMap<Vec<char>, Vec<u64>>,
+        offset: usize,
+    ) -> Result<u32, CollationRulesError> {
+        let default = writer.encode_ces(
+            group
+                .get(&Vec::new())
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+            offset,
+        )?;
+        if group.len() == 1 {
+            return Ok(default);
+        }
+        let ccc = |c: &char| ccc_from_trie_value(self.decompositions.trie.get(*c)).to_icu4c_value();
+        let mut flags = CONTRACT_NEXT_CCC;
+        let mut trie = Char16TrieBuilder::new();
+        for (suffix, ces) in group.iter().filter(|(suffix, _)| !suffix.is_empty()) {
+            if suffix.first().is_some_and(|c| ccc(c) == 0) {
+                flags &= !CONTRACT_NEXT_CCC;
+            }
+            if suffix.last().is_some_and(|c| ccc(c) != 0) {
+                flags |= CONTRACT_TRAILING_CCC;
+            }
+            if suffix.iter().any(|c| ccc(c) == 0) {
+                flags |= CONTRACT_HAS_STARTER;
+            }
+            let suffix: String = suffix.iter().collect();
+            trie.insert(&suffix, writer.encode_ces(ces, offset)? as i32);
+        }
+        Ok(writer.write_context(default, trie.build())?
+            | flags
+            | u32::from(SPECIAL_CE32_LOW_BYTE)
+            | Tag::Contraction as u32)
+    }
+
+    /// Builds a script reordering that moves the lead bytes of the listed
+    /// scripts to the front, or to the back for scripts after `others`.
+    ///
+    /// Scripts that share a primary lead byte with another script move together.
+    pub(crate) fn build_reordering(
+        &self,
+        codes: &[String],
+        offset: usize,
+        scripts: CodePointMapDataBorrowed<Script>,
+        script_names: PropertyParserBorrowed<Script>,
+    ) -> Result<CollationReordering<'static>, CollationRulesError> {
+        let first = self.special_primaries.numeric_primary;
+        let mut claimed = BTreeSet::new();
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        let mut after_others = false;
+        for code in codes {
+            if code == "others" || code.eq_ignore_ascii_case("Zzzz") {
+                after_others = true;
+                continue;
+            }
+            if matches!(
+                code.as_str(),
+                "space" | "punct" | "symbol" | "currency" | "digit"
+            ) {
+                return Err(CollationRulesError::Unsupported {
+                    offset,
+                    construct: "reordering special groups",
+                });
+            }
+            let script = script_names
+                .get_strict(code)
+                .ok_or(CollationRulesError::Syntax {
+                    offset,
+                    reason: "unknown script code",
+                })?;
+            let mut lead_bytes = BTreeSet::new();
+            for range in scripts.iter_ranges_for_value(script) {
+                for c in range.filter_map(char::from_u32) {
+                    let lead = self
+                        .base_ces(c.encode_utf8(&mut [0; 4]))
+                        .into_iter()
+                        .find(|ce| ce >> 32 != 0)
+                        .map(|ce| (ce >> 56) as u8);
+                    if let Some(lead) = lead.filter(|&b| b > first && b < 0xFE) {
+                        lead_bytes.insert(lead);
+                    }
+                }
+            }
+            for b in lead_bytes {
+                if claimed.insert(b) {
+                    if after_others {
+                        back.push(b);
+                    } else {
+                        front.push(b);
+                    }
+                }
+            }
+        }
+        let middle = (first.saturating_add(1)..0xFE).filter(|b| !claimed.contains(b));
+        let order: Vec<u8> = front.into_iter().chain(middle).chain(back).collect();
+        let mut table: Vec<u8> = (0..=255).collect();
+        for (new, old) in (first.saturating_add(1)..0xFE).zip(order) {
+            if let Some(entry) = table.get_mut(usize::from(old)) {
+                *entry = new;
+            }
+        }
+        Ok(CollationReordering {
+            min_high_no_reorder: 0,
+            reorder_table: ZeroVec::alloc_from_slice(&table),
+            reorder_ranges: ZeroVec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{CollatorOptions, Strength};
+    use crate::{CollationRulesError, Collator, CollatorBorrowed};
+    use alloc::vec::Vec;
+    use core::cmp::Ordering;
+
+    fn from_rules(rules: &str) -> Collator {
+        Collator::try_new_from_rules(rules, CollatorOptions::default()).unwrap()
+    }
+
+    fn sort_key(collator: &CollatorBorrowed, s: &str) -> Vec<u8> {
+        let mut key = Vec::new();
+        let Ok(()) = collator.write_sort_key_to(s, &mut key);
+        key
+    }
+
+    /// Checks that `strings` are in strictly ascending order both by
+    /// comparison and by sort key.
+    fn check_order(rules: &str, strings: &[&str]) {
+        let collator = from_rules(rules);
+        let collator = collator.as_borrowed();
+        for pair in strings.windows(2) {
+            let [a, b] = pair else { unreachable!() };
+            assert_eq!(collator.compare(a, b), Ordering::Less, "{rules}: {a} < {b}");
+            assert_eq!(
+                collator.compare(b, a),
+                Ordering::Greater,
+                "{rules}: {b} > {a}"
+            );
+            assert!(
+                sort_key(&collator, a) < sort_key(&collator, b),
+                "{rules}: key({a}) < key({b})"
+            );
+        }
+    }
+
+    #[test]
+    fn primary_and_tertiary() {
+        check_order("&a < b <<< B", &["a", "aa", "b", "B", "ba", "c"]);
+        check_order("&z < a", &["b", "z", "a", "aa"]);
+    }
+
+    #[test]
+    fn expansion_by_equality() {
+        let collator = from_rules("&Mac = Mc");
+        let collator = collator.as_borrowed();
+        assert_eq!(collator.compare("McDonald", "MacDonald"), Ordering::Equal);
+        assert_eq!(collator.compare("McDonald", "Mack"), Ordering::Less);
+        assert_eq!(collator.compare("Mcz", "Mad"), Ordering::Less);
+    }
+
+    #[test]
+    fn phonebook() {
+        check_order(
+            "&AE << ä <<< Ä",
+            &["ad", "ae", "ä", "Ä", "af", "Müller", "Mz"],
+        );
+        let collator = from_rules("&AE << ä <<< Ä");
+        let collator = collator.as_borrowed();
+        let mut options = CollatorOptions::default();
+        options.strength = Some(Strength::Primary);
+        let primary = Collator::try_new_from_rules("&AE << ä <<< Ä", options).unwrap();
+        assert_eq!(
+            primary.as_borrowed().compare("Märchen", "Maerchen"),
+            Ordering::Equal
+        );
+        assert_eq!(collator.compare("Märchen", "Maerchen"), Ordering::Greater);
+    }
+
+    #[test]
+    fn contraction() {
+        check_order("&h < ch", &["cz", "h", "hz", "ch", "chz", "i"]);
+    }
+
+    #[test]
+    fn prefix() {
+        check_order("&x < a|b", &["ax", "ab", "ay"]);
+        check_order("&x < a|b", &["cb", "cx"]);
+    }
+
+    #[test]
+    fn settings() {
+        let collator = from_rules("[strength 1]");
+        assert_eq!(collator.as_borrowed().compare("a", "A"), Ordering::Equal);
+        let collator = from_rules("[numericOrdering on]");
+        assert_eq!(
+            collator.as_borrowed().compare("a10", "a2"),
+            Ordering::Greater
+        );
+        // Explicit options win over settings in the rules.
+        let mut options = CollatorOptions::default();
+        options.strength = Some(Strength::Tertiary);
+        let collator = Collator::try_new_from_rules("[strength 1]", options).unwrap();
+        assert_eq!(collator.as_borrowed().compare("a", "A"), Ordering::Less);
+    }
+
+    #[test]
+    fn import() {
+        let mut options = CollatorOptions::default();
+        options.strength = Some(Strength::Primary);
+        let collator = Collator::try_new_from_rules("[import de-u-co-phonebk]", options).unwrap();
+        assert_eq!(
+            collator.as_borrowed().compare("Müller", "Mueller"),
+            Ordering::Equal
+        );
+        check_order("[import sv]&z < ß", &["z", "ß", "å", "ä", "ö"]);
+    }
+
+    #[test]
+    fn reorder() {
+        check_order("[reorder Grek]", &["α", "ω", "a", "z"]);
+        check_order("[reorder others Latn]", &["α", "ω", "a", "z"]);
+    }
+
+    #[test]
+    fn errors() {
+        let error =
+            |rules| Collator::try_new_from_rules(rules, CollatorOptions::default()).unwrap_err();
+        assert!(matches!(
+            error("&[first variable] < x"),
+            CollationRulesError::Unsupported { offset: 1, .. }
+        ));
+        assert!(matches!(
+            error("&a < \u{1100}"),
+            CollationRulesError::Unsupported { offset: 3, .. }
+        ));
+        assert_eq!(
+            error("&a <<<< b <<<< c <<<< d <<<< e"),
+            CollationRulesError::WeightOverflow { offset: 24 }
+        );
+        assert!(matches!(
+            error("[reorder Xyzw]"),
+            CollationRulesError::Syntax { .. }
+        ));
+    }
+}
@@ -12,6 +12,7 @@
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 
+use crate::builder::TailoringBuilder;
 use crate::elements::CharacterAndClassAndTrieValue;
 use crate::elements::CollationElement32;
 use crate::elements::Tag;
@@ -20,14 +21,15 @@ use crate::elements::CE_BUFFER_SIZE;
 use crate::elements::FALLBACK_CE32;
 use crate::elements::NON_ROUND_TRIP_MARKER;
 use crate::elements::{
-    char_from_u32, CollationElement, CollationElements, NonPrimary, FFFD_CE32,
-    HANGUL_SYLLABLE_MARKER, HIGH_ZEROS_MASK, JAMO_COUNT, LOW_ZEROS_MASK, NO_CE, NO_CE_PRIMARY,
-    NO_CE_QUATERNARY, NO_CE_SECONDARY, NO_CE_TERTIARY, OPTIMIZED_DIACRITICS_MAX_COUNT,
-    QUATERNARY_MASK,
+    char_from_u32, decomposition_starts_with_non_starter, CollationElement, CollationElements,
+    NonPrimary, FFFD_CE32, HANGUL_SYLLABLE_MARKER, HIGH_ZEROS_MASK, JAMO_COUNT, LOW_ZEROS_MASK,
+    NO_CE, NO_CE_PRIMARY, NO_CE_QUATERNARY, NO_CE_SECONDARY, NO_CE_TERTIARY,
+    OPTIMIZED_DIACRITICS_MAX_COUNT, QUATERNARY_MASK,
 };
 use crate::options::CollatorOptionsBitField;
 use crate::options::{
-    AlternateHandling, CollatorOptions, MaxVariable, ResolvedCollatorOptions, Strength,
+    AlternateHandling, CollatorOptions, MaxVariable, ResolvedCollatorOptions, SortKeyBound,
+    Strength,
 };
 use crate::preferences::{CollationCaseFirst, CollationNumericOrdering, CollationType};
 use crate::provider::CollationData;
@@ -42,15 +44,22 @@ use crate::provider::CollationRootV1;
 use crate::provider::CollationSpecialPrimariesV1;
 use crate::provider::CollationSpecialPrimariesValidated;
 use crate::provider::CollationTailoringV1;
+use crate::rules::CollationRulesError;
 use core::array;
 use core::cmp::Ordering;
 use core::convert::{Infallible, TryFrom};
+use icu_locale_core::Locale;
 use icu_normalizer::provider::DecompositionData;
 use icu_normalizer::provider::DecompositionTables;
 use icu_normalizer::provider::NormalizerNfdDataV1;
 use icu_normalizer::provider::NormalizerNfdTablesV1;
 use icu_normalizer::DecomposingNormalizerBorrowed;
 use icu_normalizer::Decomposition;
+use icu_properties::props::Script;
+use icu_properties::provider::{PropertyEnumScriptV1, PropertyNameParseScriptV1};
+use icu_properties::{
+    CodePointMapData, CodePointMapDataBorrowed, PropertyParser, PropertyParserBorrowed,
+};
 use icu_provider::marker::ErasedMarker;
 use icu_provider::prelude::*;
 use smallvec::SmallVec;
@@ -657,40 +666,7 @@ impl Collator {
             return Err(DataError::custom("invalid").with_marker(CollationJamoV1::INFO));
         }
 
-        // `variant_count` isn't stable yet:
-        // https://github.com/rust-lang/rust/issues/73662
-        if special_primaries.get().last_primaries.len() <= (MaxVariable::Currency as usize) {
-            return Err(DataError::custom("invalid").with_marker(CollationSpecialPrimariesV1::INFO));
-        }
-        let special_primaries = special_primaries.map_project(|csp, _| {
-            if csp.last_primaries.len()
-                == (MaxVariable::Currency as usize)
-                    + core::mem::size_of_val(
-                        &CollationSpecialPrimariesValidated::HARDCODED_FALLBACK.compressible_bytes,
-                    ) / core::mem::size_of::<u16>()
-            {
-                CollationSpecialPrimariesValidated {
-                    compressible_bytes: array::from_fn(|i| {
-                        #[expect(clippy::unwrap_used)] // protected by the if
-                        {
-                            csp.last_primaries
-                                .get((MaxVariable::Currency as usize) + i)
-                                .unwrap()
-                        }
-                    }),
-                    last_primaries: csp.last_primaries.truncated(MaxVariable::Currency as usize),
-                    numeric_primary: csp.numeric_primary,
-                }
-            } else {
-                // Data without compressible bytes, add hardcoded data
-                CollationSpecialPrimariesValidated {
-                    last_primaries: csp.last_primaries,
-                    compressible_bytes: CollationSpecialPrimariesValidated::HARDCODED_FALLBACK
-                        .compressible_bytes,
-                    numeric_primary: csp.numeric_primary,
-                }
-            }
-        });
+        let special_primaries = validate_special_primaries(special_primaries)?;
 
         Ok(Collator {
             special_primaries,
@@ -705,6 +681,238 @@ impl Collator {
             lithuanian_dot_above: locale_dependent.lithuanian_dot_above,
         })
     }
+
+    /// Creates a collator from rules in the [ICU collation rule syntax] using compiled data.
+    ///
+    /// The rules tailor the root collation or, if they start with an `[import]`
+    /// setting, the collation of the imported locale. Settings in the rules
+    /// (such as `[strength 2]`) take effect unless `options` sets them explicitly.
+    ///
+    /// Not all of the syntax is supported: special reset positions like
+    /// `[first variable]`, `[before 2]`, `[before 3]`, prefixes of more than two
+    /// characters, `[suppressContractions]`, `[hiraganaQ on]`, and tailoring
+    /// Hangul jamo result in [`CollationRulesError::Unsupported`].
+    /// `[reorder]` moves scripts by whole primary lead bytes, so scripts
+    /// that share a lead byte with a listed script move along with it.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    ///
+    /// [ICU collation rule syntax]: https://unicode-org.github.io/icu/userguide/collation/customization/
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use icu::collator::options::CollatorOptions;
+    /// use icu::collator::Collator;
+    ///
+    /// // Sort "Mc" as if it were spelled "Mac".
+    /// let collator =
+    ///     Collator::try_new_from_rules("&Mac = Mc", CollatorOptions::default())
+    ///         .unwrap();
+    /// let collator = collator.as_borrowed();
+    ///
+    /// assert_eq!(collator.compare("McDonald", "MacDonald"), Ordering::Equal);
+    /// assert_eq!(collator.compare("McDonald", "Mack"), Ordering::Less);
+    /// ```
+    #[cfg(feature = "compiled_data")]
+    pub fn try_new_from_rules(
+        rules: &str,
+        options: CollatorOptions,
+    ) -> Result<Self, CollationRulesError> {
+        Self::try_new_from_rules_internal(
+            &crate::provider::Baked,
+            DataPayload::from_static_ref(crate::provider::Baked::SINGLETON_COLLATION_ROOT_V1),
+            DataPayload::from_static_ref(
+                icu_normalizer::provider::Baked::SINGLETON_NORMALIZER_NFD_DATA_V1,
+            ),
+            DataPayload::from_static_ref(
+                icu_normalizer::provider::Baked::SINGLETON_NORMALIZER_NFD_TABLES_V1,
+            ),
+            DataPayload::from_static_ref(crate::provider::Baked::SINGLETON_COLLATION_JAMO_V1),
+            DataPayload::from_static_ref(
+                crate::provider::Baked::SINGLETON_COLLATION_SPECIAL_PRIMARIES_V1,
+            ),
+            CodePointMapData::<Script>::new(),
+            PropertyParser::<Script>::new(),
+            rules,
+            options,
+        )
+    }
+
+    icu_provider::gen_buffer_data_constructors!(
+        (rules: &str, options: CollatorOptions) -> error: CollationRulesError,
+        functions: [
+            try_new_from_rules: skip,
+            try_new_from_rules_with_buffer_provider,
+            try_new_from_rules_unstable,
+            Self
+        ]
+    );
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::try_new_from_rules)]
+    pub fn try_new_from_rules_unstable<D>(
+        provider: &D,
+        rules: &str,
+        options: CollatorOptions,
+    ) -> Result<Self, CollationRulesError>
+    where
+        D: DataProvider<CollationSpecialPrimariesV1>
+            + DataProvider<CollationRootV1>
+            + DataProvider<CollationTailoringV1>
+            + DataProvider<CollationDiacriticsV1>
+            + DataProvider<CollationJamoV1>
+            + DataProvider<CollationMetadataV1>
+            + DataProvider<CollationReorderingV1>
+            + DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<PropertyEnumScriptV1>
+            + DataProvider<PropertyNameParseScriptV1>
+            + ?Sized,
+    {
+        let scripts = CodePointMapData::<Script>::try_new_unstable(provider)?;
+        let script_names = PropertyParser::<Script>::try_new_unstable(provider)?;
+        Self::try_new_from_rules_internal(
+            provider,
+            provider.load(Default::default())?.payload,
+            provider.load(Default::default())?.payload,
+            provider.load(Default::default())?.payload,
+            provider.load(Default::default())?.payload,
+            provider.load(Default::default())?.payload,
+            scripts.as_borrowed(),
+            script_names.as_borrowed(),
+            rules,
+            options,
+        )
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    fn try_new_from_rules_internal<D>(
+        provider: &D,
+        root: DataPayload<CollationRootV1>,
+        decompositions: DataPayload<NormalizerNfdDataV1>,
+        tables: DataPayload<NormalizerNfdTablesV1>,
+        jamo: DataPayload<CollationJamoV1>,
+        special_primaries: DataPayload<CollationSpecialPrimariesV1>,
+        scripts: CodePointMapDataBorrowed<Script>,
+        script_names: PropertyParserBorrowed<Script>,
+        rules: &str,
+        options: CollatorOptions,
+    ) -> Result<Self, CollationRulesError>
+    where
+        D: DataProvider<CollationTailoringV1>
+            + DataProvider<CollationDiacriticsV1>
+            + DataProvider<CollationMetadataV1>
+            + DataProvider<CollationReorderingV1>
+            + ?Sized,
+    {
+        let parsed = crate::rules::parse(rules)?;
+
+        let prefs = match &parsed.import {
+            Some((locale, offset)) => {
+                let locale =
+                    Locale::try_from_str(locale).map_err(|_| CollationRulesError::Syntax {
+                        offset: *offset,
+                        reason: "invalid locale in [import]",
+                    })?;
+                CollatorPreferences::from(&locale)
+            }
+            None => CollatorPreferences::default(),
+        };
+        let base = LocaleSpecificDataHolder::try_new_unstable_internal(
+            provider,
+            prefs,
+            CollatorOptions::default(),
+        )?;
+
+        // TODO: redesign Korean search collation handling
+        let jamo_table =
+            <&[<u32 as AsULE>::ULE; JAMO_COUNT]>::try_from(jamo.get().ce32s.as_ule_slice())
+                .map_err(|_| DataError::custom("invalid").with_marker(CollationJamoV1::INFO))?;
+        let special_primaries = validate_special_primaries(special_primaries)?;
+
+        let builder = TailoringBuilder {
+            root: root.get(),
+            base: base.tailoring.as_ref().map(|t| t.get()),
+            jamo: jamo_table,
+            diacritics: &base.diacritics.get().secondaries,
+            decompositions: decompositions.get(),
+            tables: tables.get(),
+            special_primaries: special_primaries.get(),
+            lithuanian_dot_above: base.lithuanian_dot_above,
+        };
+        let tailoring = builder.build(&parsed.items)?;
+        let reordering = match &parsed.reorder {
+            Some((codes, _)) if codes.is_empty() => None,
+            Some((codes, offset)) => Some(DataPayload::from_owned(builder.build_reordering(
+                codes,
+                *offset,
+                scripts,
+                script_names,
+            )?)),
+            None => base.reordering,
+        };
+
+        let mut merged_options = CollatorOptionsBitField::from(options);
+        merged_options.set_defaults(parsed.settings);
+        merged_options.set_defaults(base.merged_options);
+
+        Ok(Collator {
+            special_primaries,
+            root,
+            tailoring: Some(DataPayload::from_owned(tailoring.data)),
+            jamo,
+            diacritics: DataPayload::from_owned(tailoring.diacritics),
+            options: merged_options,
+            reordering,
+            decompositions,
+            tables,
+            lithuanian_dot_above: base.lithuanian_dot_above,
+        })
+    }
+}
+
+/// Checks the special primaries and fills in the compressible bytes if the
+/// data does not have them.
+fn validate_special_primaries(
+    special_primaries: DataPayload<CollationSpecialPrimariesV1>,
+) -> Result<DataPayload<ErasedMarker<CollationSpecialPrimariesValidated<'static>>>, DataError> {
+    // `variant_count` isn't stable yet:
+    // https://github.com/rust-lang/rust/issues/73662
+    if special_primaries.get().last_primaries.len() <= (MaxVariable::Currency as usize) {
+        return Err(DataError::custom("invalid").with_marker(CollationSpecialPrimariesV1::INFO));
+    }
+    Ok(special_primaries.map_project(|csp, _| {
+        if csp.last_primaries.len()
+            == (MaxVariable::Currency as usize)
+                + core::mem::size_of_val(
+                    &CollationSpecialPrimariesValidated::HARDCODED_FALLBACK.compressible_bytes,
+                ) / core::mem::size_of::<u16>()
+        {
+            CollationSpecialPrimariesValidated {
+                compressible_bytes: array::from_fn(|i| {
+                    #[expect(clippy::unwrap_used)] // protected by the if
+                    {
+                        csp.last_primaries
+                            .get((MaxVariable::Currency as usize) + i)
+                            .unwrap()
+                    }
+                }),
+                last_primaries: csp.last_primaries.truncated(MaxVariable::Currency as usize),
+                numeric_primary: csp.numeric_primary,
+            }
+        } else {
+            // Data without compressible bytes, add hardcoded data
+            CollationSpecialPrimariesValidated {
+                last_primaries: csp.last_primaries,
+                compressible_bytes: CollationSpecialPrimariesValidated::HARDCODED_FALLBACK
+                    .compressible_bytes,
+                numeric_primary: csp.numeric_primary,
+            }
+        }
+    }))
 }
 
 macro_rules! compare {
@@ -735,7 +943,7 @@ macro_rules! compare {
 
 /// Compares strings according to culturally-relevant ordering,
 /// borrowed version.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct CollatorBorrowed<'a> {
     special_primaries: &'a CollationSpecialPrimariesValidated<'a>,
     root: &'a CollationData<'a>,
@@ -972,7 +1180,7 @@ impl CollatorBorrowed<'_> {
     }
 
     #[inline(always)]
-    fn variable_top(&self) -> u32 {
+    pub(crate) fn variable_top(&self) -> u32 {
         if self.options.alternate_handling() == AlternateHandling::NonIgnorable {
             0
         } else {
@@ -983,6 +1191,36 @@ impl CollatorBorrowed<'_> {
         }
     }
 
+    /// Returns an initialized iterator over the collation elements of `chars`
+    /// using the data and the numeric option of this collator.
+    pub(crate) fn raw_collation_elements<I: Iterator<Item = char>>(
+        &self,
+        chars: I,
+    ) -> CollationElements<'_, I> {
+        let mut iter = collation_elements!(
+            self,
+            chars,
+            self.tailoring_or_root(),
+            self.numeric_primary()
+        );
+        iter.init();
+        iter
+    }
+
+    /// Applies the script reordering of this collator to a primary weight.
+    pub(crate) fn reorder_primary(&self, primary: u32) -> u32 {
+        match self.reordering {
+            Some(reordering) => reordering.reorder(primary),
+            None => primary,
+        }
+    }
+
+    /// Whether the canonical decomposition of `c` starts with a starter, i.e.
+    /// whether collation elements can be computed starting from `c`.
+    pub(crate) fn decomposition_starts_with_starter(&self, c: char) -> bool {
+        !decomposition_starts_with_non_starter(self.decompositions.trie.get(c))
+    }
+
     /// The implementation of the comparison operation.
     ///
     /// `head_chars` is an iterator _backward_ over the identical
@@ -1859,7 +2097,7 @@ impl CollatorBorrowed<'_> {
         };
 
         let mut state = S::State::default();
-        self.write_sort_key_up_to_quaternary(iter, sink, &mut state)?;
+        self.write_sort_key_up_to_quaternary(iter, self.sort_key_levels(), sink, &mut state)?;
 
         if let Some(iter) = identical {
             let nfd =
@@ -1873,13 +2111,153 @@ impl CollatorBorrowed<'_> {
         sink.finish(state)
     }
 
-    /// Write the sort key bytes up to the collator's strength.
+    /// Given valid UTF-8, write a bound for range queries over sort keys: the sort key of `s`
+    /// reduced to its first `levels` levels, followed by bytes according to `bound`.
+    ///
+    /// The levels are those of the sort key written by [`Self::write_sort_key_to`]: primary,
+    /// secondary, the case level if enabled, tertiary, and so on up to the collator's strength.
+    /// If the sort key has fewer than `levels` levels, all of them are kept.
+    ///
+    /// A lower bound and an upper bound of the same string at the same number of levels
+    /// delimit the sort keys of all strings that are equal to it at those levels, which
+    /// makes it possible to find these strings with a range query over stored sort keys.
+    /// This is the equivalent of ICU4C's `ucol_getBound`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use icu::collator::options::{CollatorOptions, SortKeyBound};
+    /// use icu::collator::Collator;
+    ///
+    /// let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+    /// let key = |s| {
+    ///     let mut key = Vec::new();
+    ///     let Ok(()) = collator.write_sort_key_to(s, &mut key);
+    ///     key
+    /// };
+    /// let bound = |s, bound| {
+    ///     let mut key = Vec::new();
+    ///     let Ok(()) = collator.write_sort_key_bound_to(s, bound, 1, &mut key);
+    ///     key
+    /// };
+    ///
+    /// let lower = bound("resume", SortKeyBound::Lower);
+    /// let upper = bound("resume", SortKeyBound::Upper);
+    /// assert!(lower <= key("Résumé") && key("Résumé") < upper);
+    /// assert!(upper < key("resumes"));
+    ///
+    /// let upper_long = bound("resume", SortKeyBound::UpperLong);
+    /// assert!(key("resumes") < upper_long);
+    /// assert!(upper_long < key("resun"));
+    /// ```
+    pub fn write_sort_key_bound_to<S>(
+        &self,
+        s: &str,
+        bound: SortKeyBound,
+        levels: usize,
+        sink: &mut S,
+    ) -> Result<S::Output, S::Error>
+    where
+        S: CollationKeySink + ?Sized,
+        S::State: Default,
+    {
+        self.write_sort_key_bound_impl(s.chars(), bound, levels, sink)
+    }
+
+    /// Given potentially invalid UTF-8, write a bound for range queries over sort keys.
+    ///
+    /// For further details, see [`Self::write_sort_key_bound_to`].
+    pub fn write_sort_key_bound_utf8_to<S>(
+        &self,
+        s: &[u8],
+        bound: SortKeyBound,
+        levels: usize,
+        sink: &mut S,
+    ) -> Result<S::Output, S::Error>
+    where
+        S: CollationKeySink + ?Sized,
+        S::State: Default,
+    {
+        self.write_sort_key_bound_impl(s.chars(), bound, levels, sink)
+    }
+
+    /// Given potentially invalid UTF-16, write a bound for range queries over sort keys.
+    ///
+    /// For further details, see [`Self::write_sort_key_bound_to`].
+    pub fn write_sort_key_bound_utf16_to<S>(
+        &self,
+        s: &[u16],
+        bound: SortKeyBound,
+        levels: usize,
+        sink: &mut S,
+    ) -> Result<S::Output, S::Error>
+    where
+        S: CollationKeySink + ?Sized,
+        S::State: Default,
+    {
+        self.write_sort_key_bound_impl(s.chars(), bound, levels, sink)
+    }
+
+    fn write_sort_key_bound_impl<I, S>(
+        &self,
+        iter: I,
+        bound: SortKeyBound,
+        levels: usize,
+        sink: &mut S,
+    ) -> Result<S::Output, S::Error>
+    where
+        I: Iterator<Item = char> + Clone,
+        S: CollationKeySink + ?Sized,
+        S::State: Default,
+    {
+        // A sort key with fewer levels is a prefix of the full sort key.
+        let all_levels = self.sort_key_levels();
+        let mut kept = 0;
+        let mut remaining = levels;
+        for flag in [
+            PRIMARY_LEVEL_FLAG,
+            SECONDARY_LEVEL_FLAG,
+            CASE_LEVEL_FLAG,
+            TERTIARY_LEVEL_FLAG,
+            QUATERNARY_LEVEL_FLAG,
+        ] {
+            if remaining > 0 && all_levels & flag != 0 {
+                kept |= flag;
+                remaining -= 1;
+            }
+        }
+        let identical = if remaining > 0 && self.options.strength() == Strength::Identical {
+            Some(iter.clone())
+        } else {
+            None
+        };
+
+        let mut state = S::State::default();
+        if kept != 0 {
+            self.write_sort_key_up_to_quaternary(iter, kept, sink, &mut state)?;
+        }
+        if let Some(iter) = identical {
+            let nfd =
+                DecomposingNormalizerBorrowed::new_with_data(self.decompositions, self.tables);
+            sink.write_byte(&mut state, LEVEL_SEPARATOR_BYTE)?;
+            write_identical_level(nfd.normalize_iter(iter), sink, &mut state)?;
+        }
+        match bound {
+            SortKeyBound::Lower => {}
+            SortKeyBound::Upper => sink.write_byte(&mut state, 2)?,
+            SortKeyBound::UpperLong => sink.write(&mut state, &[0xFF, 0xFF])?,
+        }
+        sink.finish(state)
+    }
+
+    /// Write the sort key bytes of the given `levels` (a set of level flags).
     ///
     /// Optionally write the case level.  Separate levels with the `LEVEL_SEPARATOR_BYTE`, but
     /// do not write a terminating zero as with a C string.
     fn write_sort_key_up_to_quaternary<I, S>(
         &self,
         iter: I,
+        levels: u8,
         sink: &mut S,
         state: &mut S::State,
     ) -> Result<(), S::Error>
@@ -1888,7 +2266,6 @@ impl CollatorBorrowed<'_> {
         S: CollationKeySink + ?Sized,
     {
         // This algorithm comes from `CollationKeys::writeSortKeyUpToQuaternary` in ICU4C.
-        let levels = self.sort_key_levels();
 
         let mut iter =
             collation_elements!(self, iter, self.tailoring_or_root(), self.numeric_primary());
@@ -2668,6 +3045,44 @@ mod test {
         assert_eq!(k8, k16);
     }
 
+    #[test]
+    fn sort_key_bounds() {
+        let collator = collator_en(Strength::Tertiary);
+        let key = |s: &str| {
+            let mut k = Vec::new();
+            let Ok(()) = collator.write_sort_key_to(s, &mut k);
+            k
+        };
+        let bound = |s: &str, bound, levels| {
+            let mut k = Vec::new();
+            let Ok(()) = collator.write_sort_key_bound_to(s, bound, levels, &mut k);
+            k
+        };
+
+        let full = key("abc");
+        let primary = bound("abc", SortKeyBound::Lower, 1);
+        assert!(!primary.is_empty() && full.starts_with(&primary));
+        assert_eq!(full.get(primary.len()), Some(&LEVEL_SEPARATOR_BYTE));
+        assert!(bound("abc", SortKeyBound::Lower, 0).is_empty());
+        assert_eq!(bound("abc", SortKeyBound::Lower, 3), full);
+        assert_eq!(bound("abc", SortKeyBound::Lower, 9), full);
+
+        // Two levels: accents matter, case does not.
+        let lower = bound("abc", SortKeyBound::Lower, 2);
+        let upper = bound("abc", SortKeyBound::Upper, 2);
+        assert!(lower < key("ABC") && key("ABC") < upper);
+        assert!(key("ábc") > upper);
+
+        let upper_long = bound("abc", SortKeyBound::UpperLong, 1);
+        assert!(key("abcd") < upper_long && upper_long < key("abd"));
+
+        let mut k16 = Vec::new();
+        let utf16 = "abc".encode_utf16().collect::<Vec<_>>();
+        let Ok(()) =
+            collator.write_sort_key_bound_utf16_to(&utf16, SortKeyBound::Upper, 2, &mut k16);
+        assert_eq!(k16, upper);
+    }
+
     #[test]
     fn sort_keys_invalid() {
         let collator = collator_en(Strength::Identical);
@@ -137,14 +137,14 @@ fn trie_value_indicates_special_non_starter_decomposition(trie_value: u32) -> bo
 /// starts with a non-starter.
 ///
 /// See components/normalizer/trie-value-format.md
-fn decomposition_starts_with_non_starter(trie_value: u32) -> bool {
+pub(crate) fn decomposition_starts_with_non_starter(trie_value: u32) -> bool {
     trie_value_has_ccc(trie_value)
 }
 
 /// Extracts a canonical combining class (possibly zero) from a trie value.
 ///
 /// See components/normalizer/trie-value-format.md
-fn ccc_from_trie_value(trie_value: u32) -> CanonicalCombiningClass {
+pub(crate) fn ccc_from_trie_value(trie_value: u32) -> CanonicalCombiningClass {
     if trie_value_has_ccc(trie_value) {
         CanonicalCombiningClass::from_icu4c_value(trie_value as u8)
     } else {
@@ -175,7 +175,7 @@ pub(crate) const QUATERNARY_MASK: u16 = 0xC0;
 // A CE32 is special if its low byte is this or greater.
 // Impossible case bits 11 mark special CE32s.
 // This value itself is used to indicate a fallback to the root collation.
-const SPECIAL_CE32_LOW_BYTE: u8 = 0xC0;
+pub(crate) const SPECIAL_CE32_LOW_BYTE: u8 = 0xC0;
 pub(crate) const FALLBACK_CE32: CollationElement32 =
     CollationElement32(SPECIAL_CE32_LOW_BYTE as u32);
 const LONG_PRIMARY_CE32_LOW_BYTE: u8 = 0xC1; // SPECIAL_CE32_LOW_BYTE | LONG_PRIMARY_TAG
@@ -193,11 +193,11 @@ const UNASSIGNED_IMPLICIT_BYTE: u8 = 0xFE;
 // const CONTRACT_SINGLE_CP_NO_MATCH: u32 = 0x100;
 
 /// Set if the first character of every contraction suffix has lccc!=0.
-const CONTRACT_NEXT_CCC: u32 = 0x200;
+pub(crate) const CONTRACT_NEXT_CCC: u32 = 0x200;
 /// Set if any contraction suffix ends with lccc!=0.
-const CONTRACT_TRAILING_CCC: u32 = 0x400;
+pub(crate) const CONTRACT_TRAILING_CCC: u32 = 0x400;
 /// Set if at least one contraction suffix contains a starter
-const CONTRACT_HAS_STARTER: u32 = 0x800;
+pub(crate) const CONTRACT_HAS_STARTER: u32 = 0x800;
 
 // const NO_CE32: CollationElement32 = CollationElement32::default();
 // constants named NO_CE* : End of input. Only used in runtime code, not stored in data.
@@ -393,6 +393,11 @@ impl CollationElement32 {
         CollationElement32(u32::from_unaligned(ule))
     }
 
+    #[inline(always)]
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
     #[inline(always)]
     fn low_byte(self) -> u8 {
         self.0 as u8
@@ -541,6 +546,11 @@ impl CollationElement {
         CollationElement(bits)
     }
 
+    #[inline(always)]
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
     #[inline(always)]
     pub fn new_from_primary(primary: u32) -> Self {
         CollationElement((u64::from(primary) << 32) | COMMON_SEC_AND_TER_CE)
@@ -310,6 +310,7 @@
 
 extern crate alloc;
 
+mod builder;
 mod comparison;
 #[cfg(doc)]
 pub mod docs;
@@ -322,10 +323,16 @@ mod elements;
 
 pub mod options;
 pub mod provider;
+mod rules;
+mod search;
+mod weights;
 
 pub use comparison::Collator;
 pub use comparison::CollatorBorrowed;
 pub use comparison::CollatorPreferences;
+pub use rules::CollationRulesError;
+pub use search::CollationSearcher;
+pub use weights::CollationWeights;
 
 /// Locale preferences used by this crate
 pub mod preferences {
@@ -225,6 +225,25 @@ pub enum CaseLevel {
     On = 1,
 }
 
+/// Which bound of a range query to produce from a sort key with
+/// [`CollatorBorrowed::write_sort_key_bound_to`](crate::CollatorBorrowed::write_sort_key_bound_to).
+///
+/// A bound keeps only the first levels of a sort key, so that it compares equal to the
+/// keys of all strings that are equal to the original string at those levels.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+#[non_exhaustive]
+pub enum SortKeyBound {
+    /// A lower bound: less than or equal to the sort keys of all strings that are equal
+    /// to the original string at the kept levels.
+    Lower,
+    /// An upper bound: greater than the sort keys of all strings that are equal to the
+    /// original string at the kept levels.
+    Upper,
+    /// An upper bound: greater than the sort keys of all strings that are equal to the
+    /// original string at the kept levels, and of those that start with such a string.
+    UpperLong,
+}
+
 /// Options settable by the user of the API.
 ///
 /// With the exception of reordering (BCP47 `kr`), options that can by implied by locale are
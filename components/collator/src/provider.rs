@@ -240,7 +240,7 @@ impl<'data> CollationData<'data> {
         debug_assert!(false);
         SINGLE_U64
     }
-    fn get_default_and_trie_impl(
+    pub(crate) fn get_default_and_trie_data(
         &'data self,
         index: usize,
     ) -> (CollationElement32, &'data ZeroSlice<u16>) {
@@ -265,11 +265,11 @@ impl<'data> CollationData<'data> {
         &'data self,
         index: usize,
     ) -> (CollationElement32, Char16TrieIterator<'data>) {
-        let (ce32, trie) = self.get_default_and_trie_impl(index);
+        let (ce32, trie) = self.get_default_and_trie_data(index);
         (ce32, Char16TrieIterator::new(trie))
     }
     pub(crate) fn get_default(&'data self, index: usize) -> CollationElement32 {
-        let (ce32, _) = self.get_default_and_trie_impl(index);
+        let (ce32, _) = self.get_default_and_trie_data(index);
         ce32
     }
     pub(crate) fn ce_from_offset_ce32(
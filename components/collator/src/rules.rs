@@ -0,0 +1,729 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+// The rule syntax handled in this file follows ICU4C's
+// `collationruleparser.cpp` and, therefore, the code is subject
+// to the ICU license as described in LICENSE.
+
+//! This module holds the parser for the ICU collation rule syntax
+//! (`&a < b <<< B`) used by `Collator::try_new_from_rules`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use icu_provider::DataError;
+
+use crate::options::{AlternateHandling, CollatorOptionsBitField, MaxVariable, Strength};
+use crate::preferences::CollationCaseFirst;
+
+/// An error returned when collation rules cannot be turned into a collator.
+#[derive(Debug, Clone, PartialEq, displaydoc::Display)]
+#[non_exhaustive]
+pub enum CollationRulesError {
+    /// Syntax error at byte offset {offset}: {reason}
+    Syntax {
+        /// Byte offset into the rule string
+        offset: usize,
+        /// What was wrong
+        reason: &'static str,
+    },
+    /// Unsupported rule construct at byte offset {offset}: {construct}
+    Unsupported {
+        /// Byte offset into the rule string
+        offset: usize,
+        /// The construct that is not supported
+        construct: &'static str,
+    },
+    /// Too many tailored weights between two adjacent base weights at byte offset {offset}
+    WeightOverflow {
+        /// Byte offset into the rule string of the rule chain that ran out of weights
+        offset: usize,
+    },
+    /// Data error: {0}
+    Data(DataError),
+}
+
+impl core::error::Error for CollationRulesError {}
+
+impl From<DataError> for CollationRulesError {
+    fn from(e: DataError) -> Self {
+        CollationRulesError::Data(e)
+    }
+}
+
+/// The strength of a relation. The numeric values match the number of
+/// `<` characters of the relation operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Level {
+    Primary = 1,
+    Secondary = 2,
+    Tertiary = 3,
+    Quaternary = 4,
+    Identical = 5,
+}
+
+impl Level {
+    fn from_count(count: usize) -> Option<Level> {
+        match count {
+            1 => Some(Level::Primary),
+            2 => Some(Level::Secondary),
+            3 => Some(Level::Tertiary),
+            4 => Some(Level::Quaternary),
+            _ => None,
+        }
+    }
+}
+
+/// A single reset or relation, with strings in the form written in the rules
+/// (not yet normalized).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Item {
+    /// `&[before n] text`
+    Reset {
+        before: Option<Level>,
+        text: String,
+        offset: usize,
+    },
+    /// `< prefix | text / extension`
+    Relation {
+        level: Level,
+        prefix: String,
+        text: String,
+        extension: String,
+        offset: usize,
+    },
+}
+
+/// The result of parsing a rule string.
+#[derive(Debug)]
+pub(crate) struct ParsedRules {
+    /// Resets and relations in rule order.
+    pub items: Vec<Item>,
+    /// Settings from the rules. Only settings that the rules mention are
+    /// marked as explicitly set.
+    pub settings: CollatorOptionsBitField,
+    /// The locale from an `[import]` setting.
+    pub import: Option<(String, usize)>,
+    /// The reorder codes from a `[reorder]` setting.
+    pub reorder: Option<(Vec<String>, usize)>,
+}
+
+/// Whether `c` is an ASCII character that has to be quoted in order to
+/// be used as part of a string.
+fn is_syntax_char(c: char) -> bool {
+    matches!(c, '\u{21}'..='\u{2F}' | '\u{3A}'..='\u{40}' | '\u{5B}'..='\u{60}' | '\u{7B}'..='\u{7E}')
+}
+
+/// `Pattern_White_Space`
+fn is_white_space(c: char) -> bool {
+    matches!(
+        c,
+        '\u{09}'..='\u{0D}' | ' ' | '\u{85}' | '\u{200E}' | '\u{200F}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+struct Parser<'a> {
+    rules: &'a str,
+    pos: usize,
+    items: Vec<Item>,
+    settings: CollatorOptionsBitField,
+    import: Option<(String, usize)>,
+    reorder: Option<(Vec<String>, usize)>,
+}
+
+/// Parses ICU collation rule syntax.
+pub(crate) fn parse(rules: &str) -> Result<ParsedRules, CollationRulesError> {
+    let mut parser = Parser {
+        rules,
+        pos: 0,
+        items: Vec::new(),
+        settings: CollatorOptionsBitField::default(),
+        import: None,
+        reorder: None,
+    };
+    parser.parse()?;
+    Ok(ParsedRules {
+        items: parser.items,
+        settings: parser.settings,
+        import: parser.import,
+        reorder: parser.reorder,
+    })
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.rules.get(self.pos..).and_then(|s| s.chars().next())
+    }
+
+    fn rest(&self) -> &str {
+        self.rules.get(self.pos..).unwrap_or_default()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn syntax(&self, reason: &'static str) -> CollationRulesError {
+        CollationRulesError::Syntax {
+            offset: self.pos,
+            reason,
+        }
+    }
+
+    fn unsupported(offset: usize, construct: &'static str) -> CollationRulesError {
+        CollationRulesError::Unsupported { offset, construct }
+    }
+
+    /// Skips white space and `#` comments.
+    fn skip_white_space(&mut self) {
+        while let Some(c) = self.peek() {
+            if is_white_space(c) {
+                self.bump();
+            } else if c == '#' {
+                while let Some(c) = self.bump() {
+                    if c == '\n' || c == '\r' || c == '\u{2028}' || c == '\u{2029}' {
+                        break;
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn parse(&mut self) -> Result<(), CollationRulesError> {
+        loop {
+            self.skip_white_space();
+            match self.peek() {
+                None => return Ok(()),
+                Some('&') => {
+                    self.bump();
+                    self.parse_rule_chain()?;
+                }
+                Some('[') => self.parse_setting()?,
+                Some('@') => {
+                    // Legacy syntax for `[backwards 2]`
+                    self.bump();
+                    self.settings.set_backward_second_level(Some(true));
+                }
+                Some('!') => {
+                    return Err(Self::unsupported(self.pos, "Thai/Lao prevowel reordering"));
+                }
+                Some(_) => return Err(self.syntax("expected a reset or setting or comment")),
+            }
+        }
+    }
+
+    /// Parses a reset and the relations that follow it. The `&` has
+    /// already been consumed.
+    fn parse_rule_chain(&mut self) -> Result<(), CollationRulesError> {
+        let offset = self.pos;
+        self.skip_white_space();
+        let mut before = None;
+        if self.rest().starts_with("[before") {
+            let (content, start) = self.parse_bracketed()?;
+            let level = match content.get("before".len()..).map(str::trim) {
+                Some("1") => Level::Primary,
+                Some("2") => Level::Secondary,
+                Some("3") => Level::Tertiary,
+                _ => {
+                    return Err(CollationRulesError::Syntax {
+                        offset: start,
+                        reason: "expected [before 1], [before 2], or [before 3]",
+                    })
+                }
+            };
+            before = Some(level);
+            self.skip_white_space();
+        }
+        if self.peek() == Some('[') {
+            return Err(Self::unsupported(
+                self.pos,
+                "special reset positions such as [first regular]",
+            ));
+        }
+        let text = self.parse_string()?;
+        if text.is_empty() {
+            return Err(self.syntax("missing reset string"));
+        }
+        self.items.push(Item::Reset {
+            before,
+            text,
+            offset,
+        });
+        let mut relations = 0;
+        loop {
+            self.skip_white_space();
+            let offset = self.pos;
+            let Some((level, star)) = self.parse_relation_operator() else {
+                break;
+            };
+            if star {
+                self.parse_star_relation(level, offset)?;
+            } else {
+                self.parse_relation(level, offset)?;
+            }
+            relations += 1;
+        }
+        if relations == 0 {
+            return Err(self.syntax("reset not followed by a relation"));
+        }
+        Ok(())
+    }
+
+    /// Consumes a relation operator if there is one. Returns the level and
+    /// whether the operator was a star operator (`<*`).
+    fn parse_relation_operator(&mut self) -> Option<(Level, bool)> {
+        let level = match self.peek()? {
+            '<' => {
+                let count = self.rest().chars().take_while(|&c| c == '<').count();
+                let level = Level::from_count(count.min(4))?;
+                self.pos += level as usize;
+                level
+            }
+            '=' => {
+                self.bump();
+                Level::Identical
+            }
+            // Legacy operators
+            ';' => {
+                self.bump();
+                Level::Secondary
+            }
+            ',' => {
+                self.bump();
+                Level::Tertiary
+            }
+            _ => return None,
+        };
+        let star = self.peek() == Some('*');
+        if star {
+            self.bump();
+        }
+        Some((level, star))
+    }
+
+    fn parse_relation(&mut self, level: Level, offset: usize) -> Result<(), CollationRulesError> {
+        self.skip_white_space();
+        let mut prefix = String::new();
+        let mut text = self.parse_string()?;
+        self.skip_white_space();
+        if self.peek() == Some('|') {
+            self.bump();
+            self.skip_white_space();
+            prefix = text;
+            text = self.parse_string()?;
+            self.skip_white_space();
+        }
+        let mut extension = String::new();
+        if self.peek() == Some('/') {
+            self.bump();
+            self.skip_white_space();
+            extension = self.parse_string()?;
+            if extension.is_empty() {
+                return Err(self.syntax("missing extension string after /"));
+            }
+        }
+        if text.is_empty() {
+            return Err(self.syntax("missing relation string"));
+        }
+        if prefix.chars().count() > 2 {
+            return Err(Self::unsupported(
+                offset,
+                "prefixes longer than two characters",
+            ));
+        }
+        self.items.push(Item::Relation {
+            level,
+            prefix,
+            text,
+            extension,
+            offset,
+        });
+        Ok(())
+    }
+
+    /// Parses the operand of a star relation, such as `<*a-cx`, which is
+    /// equivalent to `<a<b<c<x`.
+    fn parse_star_relation(
+        &mut self,
+        level: Level,
+        offset: usize,
+    ) -> Result<(), CollationRulesError> {
+        self.skip_white_space();
+        let text = self.parse_string_with_ranges()?;
+        if text.is_empty() {
+            return Err(self.syntax("missing star relation string"));
+        }
+        for c in text {
+            self.items.push(Item::Relation {
+                level,
+                prefix: String::new(),
+                text: c.into(),
+                extension: String::new(),
+                offset,
+            });
+        }
+        Ok(())
+    }
+
+    /// Parses a possibly-quoted string and returns the characters with `a-c`
+    /// ranges expanded.
+    fn parse_string_with_ranges(&mut self) -> Result<Vec<char>, CollationRulesError> {
+        let mut out: Vec<char> = Vec::new();
+        loop {
+            let s = self.parse_string()?;
+            out.extend(s.chars());
+            if self.peek() != Some('-') {
+                return Ok(out);
+            }
+            let start_offset = self.pos;
+            self.bump();
+            let Some(start) = out.pop() else {
+                return Err(CollationRulesError::Syntax {
+                    offset: start_offset,
+                    reason: "range without a start character",
+                });
+            };
+            let end_str = self.parse_string()?;
+            let mut end_chars = end_str.chars();
+            let Some(end) = end_chars.next() else {
+                return Err(self.syntax("range without an end character"));
+            };
+            if end < start {
+                return Err(CollationRulesError::Syntax {
+                    offset: start_offset,
+                    reason: "range start is after range end",
+                });
+            }
+            out.extend(start..=end);
+            out.extend(end_chars);
+        }
+    }
+
+    /// Parses a string that ends at unquoted white space or an unquoted
+    /// syntax character.
+    fn parse_string(&mut self) -> Result<String, CollationRulesError> {
+        let mut s = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\'' {
+                self.bump();
+                if self.peek() == Some('\'') {
+                    // `''` is an apostrophe
+                    self.bump();
+                    s.push('\'');
+                    continue;
+                }
+                loop {
+                    match self.bump() {
+                        None => return Err(self.syntax("unterminated quote")),
+                        Some('\'') => {
+                            if self.peek() == Some('\'') {
+                                self.bump();
+                                s.push('\'');
+                            } else {
+                                break;
+                            }
+                        }
+                        Some(c) => s.push(c),
+                    }
+                }
+            } else if c == '\\' {
+                self.bump();
+                let c = self.parse_escape()?;
+                s.push(c);
+            } else if is_white_space(c) || is_syntax_char(c) {
+                break;
+            } else {
+                self.bump();
+                s.push(c);
+            }
+        }
+        Ok(s)
+    }
+
+    /// Parses the part after a backslash.
+    fn parse_escape(&mut self) -> Result<char, CollationRulesError> {
+        let hex = |parser: &mut Self, min: usize, max: usize| {
+            let digits = parser
+                .rest()
+                .chars()
+                .take(max)
+                .take_while(char::is_ascii_hexdigit)
+                .count();
+            if digits < min {
+                return Err(parser.syntax("invalid escape sequence"));
+            }
+            let value = parser
+                .rest()
+                .get(..digits)
+                .and_then(|d| u32::from_str_radix(d, 16).ok())
+                .and_then(char::from_u32)
+                .ok_or_else(|| parser.syntax("escape sequence is not a scalar value"))?;
+            parser.pos += digits;
+            Ok(value)
+        };
+        match self.bump() {
+            None => Err(self.syntax("backslash at the end of the rules")),
+            Some('u') => hex(self, 4, 4),
+            Some('U') => hex(self, 8, 8),
+            Some('x') => {
+                if self.peek() == Some('{') {
+                    self.bump();
+                    let c = hex(self, 1, 6)?;
+                    if self.bump() != Some('}') {
+                        return Err(self.syntax("expected } to end the escape sequence"));
+                    }
+                    Ok(c)
+                } else {
+                    hex(self, 2, 2)
+                }
+            }
+            Some('t') => Ok('\t'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some(c) => Ok(c),
+        }
+    }
+
+    /// Consumes a `[...]` (possibly with nested brackets) and returns its
+    /// trimmed content and the offset of the opening bracket.
+    fn parse_bracketed(&mut self) -> Result<(&'a str, usize), CollationRulesError> {
+        let start = self.pos;
+        self.bump();
+        let content_start = self.pos;
+        let mut depth = 1;
+        loop {
+            match self.bump() {
+                None => {
+                    return Err(CollationRulesError::Syntax {
+                        offset: start,
+                        reason: "unterminated [",
+                    })
+                }
+                Some('[') => depth += 1,
+                Some(']') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                Some('\\') => {
+                    self.bump();
+                }
+                Some(_) => {}
+            }
+        }
+        let content = self
+            .rules
+            .get(content_start..self.pos - 1)
+            .unwrap_or_default();
+        Ok((content.trim(), start))
+    }
+
+    fn parse_setting(&mut self) -> Result<(), CollationRulesError> {
+        let (content, offset) = self.parse_bracketed()?;
+        let mut words = content.split(is_white_space).filter(|w| !w.is_empty());
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        let invalid = || CollationRulesError::Syntax {
+            offset,
+            reason: "invalid value for a setting",
+        };
+        let single = || match args.as_slice() {
+            [value] => Ok(*value),
+            _ => Err(invalid()),
+        };
+        let on_off = || match single()? {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            _ => Err(invalid()),
+        };
+        match name {
+            "strength" => self.settings.set_strength(Some(match single()? {
+                "1" => Strength::Primary,
+                "2" => Strength::Secondary,
+                "3" => Strength::Tertiary,
+                "4" => Strength::Quaternary,
+                "I" => Strength::Identical,
+                _ => return Err(invalid()),
+            })),
+            "alternate" => self.settings.set_alternate_handling(Some(match single()? {
+                "shifted" => AlternateHandling::Shifted,
+                "non-ignorable" => AlternateHandling::NonIgnorable,
+                _ => return Err(invalid()),
+            })),
+            "backwards" => match single()? {
+                "2" => self.settings.set_backward_second_level(Some(true)),
+                _ => return Err(invalid()),
+            },
+            "caseLevel" => self.settings.set_case_level(Some(on_off()?)),
+            "caseFirst" => self.settings.set_case_first(Some(match single()? {
+                "upper" => CollationCaseFirst::Upper,
+                "lower" => CollationCaseFirst::Lower,
+                "off" => CollationCaseFirst::False,
+                _ => return Err(invalid()),
+            })),
+            "numericOrdering" => self.settings.set_numeric(Some(on_off()?)),
+            "maxVariable" => self.settings.set_max_variable(Some(match single()? {
+                "space" => MaxVariable::Space,
+                "punct" => MaxVariable::Punctuation,
+                "symbol" => MaxVariable::Symbol,
+                "currency" => MaxVariable::Currency,
+                _ => return Err(invalid()),
+            })),
+            // Normalization is always on.
+            "normalization" => {
+                on_off()?;
+            }
+            "hiraganaQ" => {
+                if on_off()? {
+                    return Err(Self::unsupported(offset, "[hiraganaQ on]"));
+                }
+            }
+            "reorder" => {
+                if args.is_empty() {
+                    return Err(invalid());
+                }
+                self.reorder = Some((args.into_iter().map(String::from).collect(), offset));
+            }
+            "import" => {
+                if self.import.is_some() {
+                    return Err(Self::unsupported(offset, "more than one [import]"));
+                }
+                if !self.items.is_empty() {
+                    return Err(CollationRulesError::Syntax {
+                        offset,
+                        reason: "[import] must precede the rules",
+                    });
+                }
+                self.import = Some((String::from(single()?), offset));
+            }
+            // A performance hint that does not affect the order.
+            "optimize" => {}
+            "suppressContractions" => {
+                return Err(Self::unsupported(offset, "[suppressContractions]"))
+            }
+            "before" => {
+                return Err(CollationRulesError::Syntax {
+                    offset,
+                    reason: "[before] must follow &",
+                })
+            }
+            _ => {
+                return Err(CollationRulesError::Syntax {
+                    offset,
+                    reason: "unknown setting",
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn relation(level: Level, text: &str, offset: usize) -> Item {
+        Item::Relation {
+            level,
+            prefix: String::new(),
+            text: text.to_string(),
+            extension: String::new(),
+            offset,
+        }
+    }
+
+    #[test]
+    fn chains() {
+        let parsed = parse("&a < b <<< B # comment\n &'-' = \\u002B").unwrap();
+        assert_eq!(
+            parsed.items,
+            [
+                Item::Reset {
+                    before: None,
+                    text: "a".to_string(),
+                    offset: 1,
+                },
+                relation(Level::Primary, "b", 3),
+                relation(Level::Tertiary, "B", 7),
+                Item::Reset {
+                    before: None,
+                    text: "-".to_string(),
+                    offset: 25,
+                },
+                relation(Level::Identical, "+", 29),
+            ]
+        );
+    }
+
+    #[test]
+    fn star_prefix_and_extension() {
+        let parsed = parse("&[before 1]b <*a-c &x < k|a / 'e'").unwrap();
+        assert_eq!(parsed.items.len(), 6);
+        assert_eq!(
+            parsed.items.first(),
+            Some(&Item::Reset {
+                before: Some(Level::Primary),
+                text: "b".to_string(),
+                offset: 1,
+            })
+        );
+        assert_eq!(
+            parsed.items.get(3),
+            Some(&relation(Level::Primary, "c", 13))
+        );
+        assert_eq!(
+            parsed.items.get(5),
+            Some(&Item::Relation {
+                level: Level::Primary,
+                prefix: "k".to_string(),
+                text: "a".to_string(),
+                extension: "e".to_string(),
+                offset: 22,
+            })
+        );
+    }
+
+    #[test]
+    fn settings() {
+        let parsed =
+            parse("[import de-u-co-phonebk][strength 2][reorder Grek others][caseFirst upper]")
+                .unwrap();
+        assert!(parsed.items.is_empty());
+        assert_eq!(parsed.import, Some(("de-u-co-phonebk".to_string(), 0)));
+        assert_eq!(
+            parsed.reorder,
+            Some((vec!["Grek".to_string(), "others".to_string()], 36))
+        );
+        assert_eq!(parsed.settings.strength(), Strength::Secondary);
+        assert!(parsed.settings.upper_first());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse("&a < -").unwrap_err(),
+            CollationRulesError::Syntax {
+                offset: 5,
+                reason: "missing relation string",
+            }
+        );
+        assert!(matches!(
+            parse("&a").unwrap_err(),
+            CollationRulesError::Syntax { .. }
+        ));
+        assert!(matches!(
+            parse("&[first regular] < a").unwrap_err(),
+            CollationRulesError::Unsupported { .. }
+        ));
+        assert!(matches!(
+            parse("[strength 7]").unwrap_err(),
+            CollationRulesError::Syntax { .. }
+        ));
+    }
+}
@@ -0,0 +1,541 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Collation-based string search.
+//!
+//! The approach is that of ICU4C's `usearch.cpp`: a match is a range of the
+//! text whose collation elements, reduced to the search strength, equal
+//! those of the pattern. Match boundaries never split a combining sequence.
+
+use crate::comparison::CollatorBorrowed;
+use crate::elements::{CollationElement, CASE_MASK, NO_CE_PRIMARY, TERTIARY_MASK};
+use crate::options::{AlternateHandling, CaseLevel, Strength};
+use alloc::vec::Vec;
+use core::ops::Range;
+use utf16_iter::Utf16CharsEx;
+use utf8_iter::Utf8CharsEx;
+
+#[cfg(feature = "segmenter")]
+use icu_segmenter::WordSegmenterBorrowed;
+
+/// Finds the ranges of a text that are equal to a pattern according to a
+/// collator at a given strength.
+///
+/// Created by [`CollatorBorrowed::searcher`].
+///
+/// Strengths above [`Strength::Tertiary`] search at tertiary strength.
+/// Numeric ordering, the case level and the handling of variable characters
+/// are taken from the collator: with [`AlternateHandling::Shifted`], spaces and
+/// punctuation are ignored.
+///
+/// Matches never start or end inside a combining character sequence, so
+/// searching for `"e"` does not find the first part of `"e\u{301}"`, and
+/// searching for `"resume"` at primary strength finds the whole `"re\u{301}sume\u{301}"`.
+///
+/// # Examples
+///
+/// ```
+/// use icu::collator::options::{CollatorOptions, Strength};
+/// use icu::collator::Collator;
+///
+/// let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+///
+/// let text = "Résumé: resume the RESUME";
+/// let primary = collator.searcher("resume", Strength::Primary);
+/// assert_eq!(
+///     primary.find_iter(text).collect::<Vec<_>>(),
+///     [0..8, 10..16, 21..27]
+/// );
+///
+/// let secondary = collator.searcher("resume", Strength::Secondary);
+/// assert_eq!(
+///     secondary.find_iter(text).collect::<Vec<_>>(),
+///     [10..16, 21..27]
+/// );
+///
+/// let tertiary = collator.searcher("resume", Strength::Tertiary);
+/// assert_eq!(tertiary.find_iter(text).collect::<Vec<_>>(), [10..16]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CollationSearcher<'a> {
+    collator: CollatorBorrowed<'a>,
+    strength: Strength,
+    /// The collation elements of the pattern, reduced to `strength`.
+    pattern: Vec<u64>,
+}
+
+/// The collation elements of a text, reduced to the search strength.
+struct Elements {
+    keys: Vec<u64>,
+    /// The offsets at which matches can start and end, with the index in
+    /// `keys` of the first collation element after each of them.
+    boundaries: Vec<(usize, usize)>,
+}
+
+impl<'a> CollatorBorrowed<'a> {
+    /// Creates a searcher for `pattern` that matches at `strength`.
+    ///
+    /// See [`CollationSearcher`] for details.
+    pub fn searcher(&self, pattern: &str, strength: Strength) -> CollationSearcher<'a> {
+        let mut searcher = CollationSearcher {
+            collator: *self,
+            strength,
+            pattern: Vec::new(),
+        };
+        searcher.pattern = searcher.keys(pattern.chars());
+        searcher
+    }
+
+    /// Whether well-formed UTF-8 `s` starts with `prefix` according to this
+    /// collator.
+    ///
+    /// The comparison uses the collator's strength, up to [`Strength::Tertiary`],
+    /// and does not split combining character sequences: at tertiary strength,
+    /// `"e\u{301}"` does not start with `"e"`. For repeated tests with the same
+    /// prefix, create a [`CollationSearcher`] and use [`CollationSearcher::prefix_len`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collator::options::{CollatorOptions, Strength};
+    /// use icu::collator::Collator;
+    ///
+    /// let mut options = CollatorOptions::default();
+    /// options.strength = Some(Strength::Primary);
+    /// let collator = Collator::try_new(Default::default(), options).unwrap();
+    ///
+    /// assert!(collator.starts_with("Éclair", "ecl"));
+    /// assert!(!collator.starts_with("Éclair", "lair"));
+    /// ```
+    pub fn starts_with(&self, s: &str, prefix: &str) -> bool {
+        self.searcher(prefix, self.resolved_options().strength)
+            .prefix_len(s)
+            .is_some()
+    }
+}
+
+impl CollationSearcher<'_> {
+    /// Returns the non-overlapping matches in well-formed UTF-8 `text`, as byte ranges.
+    ///
+    /// A pattern that is ignorable at the search strength matches nothing.
+    pub fn find_iter<'t>(&'t self, text: &'t str) -> impl Iterator<Item = Range<usize>> + 't {
+        self.matches(text.char_indices().collect(), text.len(), None)
+    }
+
+    /// Returns the non-overlapping matches in potentially ill-formed UTF-8
+    /// `text`, as byte ranges.
+    ///
+    /// Ill-formed input is treated as if errors had been replaced with
+    /// REPLACEMENT CHARACTERs according to the WHATWG Encoding Standard.
+    pub fn find_iter_utf8<'t>(&'t self, text: &'t [u8]) -> impl Iterator<Item = Range<usize>> + 't {
+        self.matches(text.char_indices().collect(), text.len(), None)
+    }
+
+    /// Returns the non-overlapping matches in potentially ill-formed UTF-16
+    /// `text`, as ranges of code units.
+    ///
+    /// Unpaired surrogates are treated as REPLACEMENT CHARACTERs.
+    pub fn find_iter_utf16<'t>(
+        &'t self,
+        text: &'t [u16],
+    ) -> impl Iterator<Item = Range<usize>> + 't {
+        self.matches(text.char_indices().collect(), text.len(), None)
+    }
+
+    /// Returns the non-overlapping matches in well-formed UTF-8 `text` that
+    /// start and end at word boundaries, as byte ranges.
+    ///
+    /// ✨ *Enabled with the `segmenter` Cargo feature.*
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collator::options::{CollatorOptions, Strength};
+    /// use icu::collator::Collator;
+    /// use icu::segmenter::options::WordBreakInvariantOptions;
+    /// use icu::segmenter::WordSegmenter;
+    ///
+    /// let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+    /// let segmenter = WordSegmenter::new_dictionary(WordBreakInvariantOptions::default());
+    ///
+    /// let searcher = collator.searcher("cafe", Strength::Primary);
+    /// let text = "Café, cafeteria";
+    /// assert_eq!(searcher.find_iter(text).count(), 2);
+    /// assert_eq!(
+    ///     searcher.find_words_iter(text, segmenter).collect::<Vec<_>>(),
+    ///     [0..5]
+    /// );
+    /// ```
+    #[cfg(feature = "segmenter")]
+    pub fn find_words_iter<'t>(
+        &'t self,
+        text: &'t str,
+        segmenter: WordSegmenterBorrowed,
+    ) -> impl Iterator<Item = Range<usize>> + 't {
+        let words = segmenter.segment_str(text).collect();
+        self.matches(text.char_indices().collect(), text.len(), Some(words))
+    }
+
+    /// Returns the non-overlapping matches in potentially ill-formed UTF-8
+    /// `text` that start and end at word boundaries, as byte ranges.
+    ///
+    /// ✨ *Enabled with the `segmenter` Cargo feature.*
+    #[cfg(feature = "segmenter")]
+    pub fn find_words_iter_utf8<'t>(
+        &'t self,
+        text: &'t [u8],
+        segmenter: WordSegmenterBorrowed,
+    ) -> impl Iterator<Item = Range<usize>> + 't {
+        let words = segmenter.segment_utf8(text).collect();
+        self.matches(text.char_indices().collect(), text.len(), Some(words))
+    }
+
+    /// Returns the non-overlapping matches in potentially ill-formed UTF-16
+    /// `text` that start and end at word boundaries, as ranges of code units.
+    ///
+    /// ✨ *Enabled with the `segmenter` Cargo feature.*
+    #[cfg(feature = "segmenter")]
+    pub fn find_words_iter_utf16<'t>(
+        &'t self,
+        text: &'t [u16],
+        segmenter: WordSegmenterBorrowed,
+    ) -> impl Iterator<Item = Range<usize>> + 't {
+        let words = segmenter.segment_utf16(text).collect();
+        self.matches(text.char_indices().collect(), text.len(), Some(words))
+    }
+
+    /// Returns the length of the prefix of well-formed UTF-8 `text` that
+    /// matches the pattern, in bytes, or `None` if `text` does not start with
+    /// the pattern.
+    ///
+    /// As with [`Self::find_iter`], the prefix does not end inside a combining
+    /// character sequence. An ignorable pattern is a prefix of every text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collator::options::{CollatorOptions, Strength};
+    /// use icu::collator::Collator;
+    ///
+    /// let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+    ///
+    /// let searcher = collator.searcher("resu", Strength::Primary);
+    /// assert_eq!(searcher.prefix_len("Résumé"), Some(5));
+    /// assert_eq!(searcher.prefix_len("presume"), None);
+    /// ```
+    pub fn prefix_len(&self, text: &str) -> Option<usize> {
+        self.prefix_len_impl(text.char_indices().collect(), text.len())
+    }
+
+    /// Returns the length of the prefix of potentially ill-formed UTF-8 `text`
+    /// that matches the pattern, in bytes.
+    ///
+    /// For further details, see [`Self::prefix_len`].
+    pub fn prefix_len_utf8(&self, text: &[u8]) -> Option<usize> {
+        self.prefix_len_impl(text.char_indices().collect(), text.len())
+    }
+
+    /// Returns the length of the prefix of potentially ill-formed UTF-16
+    /// `text` that matches the pattern, in code units.
+    ///
+    /// For further details, see [`Self::prefix_len`].
+    pub fn prefix_len_utf16(&self, text: &[u16]) -> Option<usize> {
+        self.prefix_len_impl(text.char_indices().collect(), text.len())
+    }
+
+    fn prefix_len_impl(&self, chars: Vec<(usize, char)>, len: usize) -> Option<usize> {
+        if self.pattern.is_empty() {
+            return Some(0);
+        }
+        let m = self.pattern.len();
+        let elements = self.elements(&chars, len, Some(m));
+        if elements.keys.get(..m)? != self.pattern.as_slice() {
+            return None;
+        }
+        elements
+            .boundaries
+            .iter()
+            .find(|&&(_, key)| key == m)
+            .map(|&(offset, _)| offset)
+    }
+
+    /// Computes the collation elements of a text of length `len` (in code
+    /// units), given as its characters with their offsets.
+    ///
+    /// The text is split into groups that start with a starter and whose
+    /// collation elements do not depend on neighboring groups, so that the
+    /// collation elements of a range of groups are a range of those of the
+    /// whole text. Groups are normally single combining character sequences,
+    /// but contractions and prefix mappings can join several of them.
+    ///
+    /// If `limit` is set, stops once more than `limit` collation elements
+    /// have been computed.
+    fn elements(&self, chars: &[(usize, char)], len: usize, limit: Option<usize>) -> Elements {
+        let keys_of = |range: Range<usize>| {
+            self.keys(chars.get(range).unwrap_or_default().iter().map(|&(_, c)| c))
+        };
+        let starters = chars
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|&(_, &(_, c))| self.collator.decomposition_starts_with_starter(c))
+            .map(|(i, _)| i)
+            .chain(core::iter::once(chars.len()));
+
+        let mut elements = Elements {
+            keys: Vec::new(),
+            boundaries: Vec::new(),
+        };
+        let flush = |elements: &mut Elements, group: Range<usize>, keys: Vec<u64>| {
+            if let Some(&(offset, c)) = chars.get(group.start) {
+                if self.collator.decomposition_starts_with_starter(c) {
+                    elements.boundaries.push((offset, elements.keys.len()));
+                }
+            }
+            elements.keys.extend(keys);
+        };
+
+        let mut group = 0..0;
+        let mut group_keys = Vec::new();
+        for end in starters {
+            let segment = group.end..end;
+            let segment_keys = keys_of(segment.clone());
+            if group.is_empty() {
+                (group, group_keys) = (segment, segment_keys);
+                continue;
+            }
+            let joined_keys = keys_of(group.start..end);
+            if joined_keys.len() == group_keys.len() + segment_keys.len()
+                && joined_keys.starts_with(&group_keys)
+                && joined_keys.ends_with(&segment_keys)
+            {
+                flush(&mut elements, group, group_keys);
+                if limit.is_some_and(|limit| elements.keys.len() > limit) {
+                    return elements;
+                }
+                (group, group_keys) = (segment, segment_keys);
+            } else {
+                (group, group_keys) = (group.start..end, joined_keys);
+            }
+        }
+        flush(&mut elements, group, group_keys);
+        elements.boundaries.push((len, elements.keys.len()));
+        elements
+    }
+
+    /// Searches the characters of a text of length `len` (in code units)
+    /// given with their offsets. If `words` is set, matches must start and end
+    /// at one of these offsets.
+    fn matches(
+        &self,
+        chars: Vec<(usize, char)>,
+        len: usize,
+        words: Option<Vec<usize>>,
+    ) -> impl Iterator<Item = Range<usize>> + '_ {
+        let mut elements = self.elements(&chars, len, None);
+        if let Some(words) = words {
+            elements
+                .boundaries
+                .retain(|(offset, _)| words.binary_search(offset).is_ok());
+        }
+        let m = self.pattern.len();
+        let mut next = 0;
+        core::iter::from_fn(move || {
+            if m == 0 {
+                return None;
+            }
+            loop {
+                let (&(start_offset, start), ends) =
+                    elements.boundaries.get(next..)?.split_first()?;
+                next += 1;
+                if elements.keys.get(start..start + m) != Some(self.pattern.as_slice()) {
+                    continue;
+                }
+                // Matches must not start with a character that is ignorable at
+                // the search strength.
+                let first_char = chars
+                    .binary_search_by_key(&start_offset, |&(offset, _)| offset)
+                    .ok()
+                    .and_then(|i| chars.get(i))
+                    .map(|&(_, c)| c);
+                if self.keys(first_char.into_iter()).is_empty() {
+                    continue;
+                }
+                // The shortest match, ending at the first boundary after the
+                // pattern's collation elements.
+                let i = ends.partition_point(|&(_, key)| key < start + m);
+                if let Some(&(end_offset, key)) = ends.get(i) {
+                    if key == start + m {
+                        next += i + 1;
+                        return Some(start_offset..end_offset);
+                    }
+                }
+            }
+        })
+    }
+
+    /// The collation elements of `chars`, reduced to the search strength.
+    fn keys(&self, chars: impl Iterator<Item = char>) -> Vec<u64> {
+        let mut keys = Vec::new();
+        self.for_each_key(chars, |key| {
+            keys.push(key);
+            true
+        });
+        keys
+    }
+
+    /// Calls `f` with the collation elements of `chars`, reduced to the search
+    /// strength, until it returns `false`. Ignorable collation elements are skipped.
+    fn for_each_key(&self, chars: impl Iterator<Item = char>, mut f: impl FnMut(u64) -> bool) {
+        let options = self.collator.resolved_options();
+        let variable_top = if options.alternate_handling == AlternateHandling::Shifted {
+            self.collator.variable_top()
+        } else {
+            0
+        };
+        let case_mask = if options.case_level == CaseLevel::On {
+            u64::from(CASE_MASK)
+        } else {
+            0
+        };
+        let lower_mask = match self.strength {
+            Strength::Primary => case_mask,
+            Strength::Secondary => 0xFFFF_0000 | case_mask,
+            _ => 0xFFFF_0000 | u64::from(TERTIARY_MASK) | case_mask,
+        };
+        let mut iter = self.collator.raw_collation_elements(chars);
+        let mut after_variable = false;
+        loop {
+            let ce: CollationElement = iter.next();
+            let p = ce.primary();
+            if p == NO_CE_PRIMARY {
+                return;
+            }
+            if p == 0 && after_variable {
+                // Ignorables following a shifted variable are ignored as well.
+                continue;
+            }
+            after_variable = p != 0 && p < variable_top;
+            if after_variable {
+                continue;
+            }
+            let key = (u64::from(p) << 32) | (ce.bits() & lower_mask);
+            let ignorable = match self.strength {
+                Strength::Primary => p == 0,
+                _ => key == 0,
+            };
+            if !ignorable && !f(key) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{AlternateHandling, CollatorOptions, Strength};
+    use crate::CollationSearcher;
+    use crate::Collator;
+    use alloc::vec::Vec;
+
+    fn find(searcher: &CollationSearcher, text: &str) -> Vec<(usize, usize)> {
+        searcher.find_iter(text).map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn combining_sequences() {
+        let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+        let text = "cafe\u{301} cafe";
+        let searcher = collator.searcher("cafe", Strength::Primary);
+        assert_eq!(find(&searcher, text), [(0, 6), (7, 11)]);
+        let searcher = collator.searcher("cafe", Strength::Secondary);
+        assert_eq!(find(&searcher, text), [(7, 11)]);
+        // A match does not end in the middle of a combining sequence.
+        let searcher = collator.searcher("e", Strength::Secondary);
+        assert_eq!(find(&searcher, text), [(10, 11)]);
+        // Canonically equivalent text matches.
+        let searcher = collator.searcher("café", Strength::Tertiary);
+        assert_eq!(find(&searcher, text), [(0, 6)]);
+    }
+
+    #[test]
+    fn encodings() {
+        let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+        let searcher = collator.searcher("resume", Strength::Primary);
+        let utf16 = "Résumé résumé".encode_utf16().collect::<Vec<_>>();
+        assert_eq!(
+            searcher.find_iter_utf16(&utf16).collect::<Vec<_>>(),
+            [0..6, 7..13]
+        );
+        assert_eq!(
+            searcher
+                .find_iter_utf8(b"R\xC3\xA9sum\xC3\xA9 \xFFresume")
+                .collect::<Vec<_>>(),
+            [0..8, 10..16]
+        );
+    }
+
+    #[test]
+    fn ignorables() {
+        let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+        let searcher = collator.searcher("\u{301}", Strength::Primary);
+        assert_eq!(searcher.find_iter("e\u{301}").count(), 0);
+        let searcher = collator.searcher("email", Strength::Primary);
+        assert_eq!(searcher.find_iter("e-mail").count(), 0);
+
+        let mut options = CollatorOptions::default();
+        options.alternate_handling = Some(AlternateHandling::Shifted);
+        let collator = Collator::try_new(Default::default(), options).unwrap();
+        let searcher = collator.searcher("email", Strength::Primary);
+        assert_eq!(find(&searcher, "e-mail, -email"), [(0, 6), (9, 14)]);
+    }
+
+    #[test]
+    fn contractions() {
+        let collator = Collator::try_new(
+            icu_locale_core::locale!("sk").into(),
+            CollatorOptions::default(),
+        )
+        .unwrap();
+        // "ch" is a contraction in Slovak, so its "h" is not a match.
+        let searcher = collator.searcher("h", Strength::Primary);
+        assert_eq!(find(&searcher, "chata hrad"), [(6, 7)]);
+        let searcher = collator.searcher("ch", Strength::Primary);
+        assert_eq!(find(&searcher, "chata hrad, CH"), [(0, 2), (12, 14)]);
+        assert_eq!(searcher.prefix_len("chata"), Some(2));
+        assert_eq!(
+            collator
+                .searcher("c", Strength::Primary)
+                .prefix_len("chata"),
+            None
+        );
+    }
+
+    #[test]
+    fn prefixes() {
+        let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+        let searcher = collator.searcher("cafe", Strength::Secondary);
+        assert_eq!(searcher.prefix_len("Cafeteria"), Some(4));
+        assert_eq!(searcher.prefix_len("café"), None);
+        assert_eq!(searcher.prefix_len("caf"), None);
+        assert_eq!(searcher.prefix_len(""), None);
+        // Ignorables at the start of the text are skipped.
+        assert_eq!(searcher.prefix_len("\u{AD}cafe"), Some(6));
+        let utf16 = "CAFE\u{301}".encode_utf16().collect::<Vec<_>>();
+        assert_eq!(
+            collator
+                .searcher("cafe", Strength::Primary)
+                .prefix_len_utf16(&utf16),
+            Some(5)
+        );
+        assert_eq!(
+            collator.searcher("", Strength::Primary).prefix_len("cafe"),
+            Some(0)
+        );
+
+        assert!(collator.starts_with("cafe", "cafe"));
+        assert!(!collator.starts_with("Cafe", "cafe"));
+        assert!(!collator.starts_with("e\u{301}", "e"));
+    }
+}
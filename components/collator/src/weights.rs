@@ -0,0 +1,142 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Public access to the collation elements of strings.
+
+use crate::comparison::CollatorBorrowed;
+use crate::elements::{CollationElement, CASE_MASK, NO_CE_PRIMARY, TERTIARY_MASK};
+use utf16_iter::Utf16CharsEx;
+use utf8_iter::Utf8CharsEx;
+
+/// The weights of a collation element, as produced by
+/// [`CollatorBorrowed::collation_elements`].
+///
+/// Strings compare like the sequences of their collation elements, compared level by
+/// level: first the nonzero primary weights, then the nonzero secondary weights, and so on.
+/// A weight of zero means that the collation element is ignorable at that level.
+///
+/// The weights are those of the collator's locale and script reordering. They are not
+/// stable across versions of the collation data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollationWeights {
+    /// The primary weight, already reordered.
+    primary: u32,
+    /// The lower 32 bits of the collation element.
+    lower: u32,
+}
+
+impl CollationWeights {
+    /// The primary weight, for base letters. Left-aligned: the most significant byte is
+    /// the lead byte, and unused trailing bytes are zero.
+    pub fn primary(self) -> u32 {
+        self.primary
+    }
+
+    /// The secondary weight, for accents.
+    pub fn secondary(self) -> u16 {
+        (self.lower >> 16) as u16
+    }
+
+    /// The tertiary weight, for case and variants, without the case bits.
+    pub fn tertiary(self) -> u16 {
+        (self.lower as u16) & TERTIARY_MASK
+    }
+
+    /// The case bits: 0 for lowercase or uncased, 1 for mixed case (in collation
+    /// elements of expansions), and 2 for uppercase.
+    pub fn case(self) -> u8 {
+        ((self.lower as u16 & CASE_MASK) >> 14) as u8
+    }
+
+    /// Whether the primary weight is zero, as for combining accents.
+    pub fn is_primary_ignorable(self) -> bool {
+        self.primary == 0
+    }
+}
+
+impl CollatorBorrowed<'_> {
+    /// Returns the collation elements of well-formed UTF-8 `s`.
+    ///
+    /// Completely ignorable collation elements (all weights zero) are skipped. Numeric
+    /// ordering is taken into account, but variable handling (see
+    /// [`AlternateHandling`](crate::options::AlternateHandling)) is not: with
+    /// `AlternateHandling::Shifted`, collation elements whose primary weight is at most
+    /// the variable top are ignored by comparison up to tertiary strength.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collator::options::CollatorOptions;
+    /// use icu::collator::Collator;
+    ///
+    /// let collator = Collator::try_new(Default::default(), CollatorOptions::default()).unwrap();
+    ///
+    /// let primaries = |s| {
+    ///     collator
+    ///         .collation_elements(s)
+    ///         .map(|ce| ce.primary())
+    ///         .filter(|&p| p != 0)
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// // Accents and case do not change primary weights.
+    /// assert_eq!(primaries("resume"), primaries("Résumé"));
+    /// assert_ne!(primaries("resume"), primaries("resumes"));
+    ///
+    /// let accent = collator.collation_elements("\u{301}").next().unwrap();
+    /// assert!(accent.is_primary_ignorable());
+    /// assert_ne!(accent.secondary(), 0);
+    /// ```
+    pub fn collation_elements<'s>(
+        &'s self,
+        s: &'s str,
+    ) -> impl Iterator<Item = CollationWeights> + 's {
+        self.weights(s.chars())
+    }
+
+    /// Returns the collation elements of potentially ill-formed UTF-8 `s`.
+    ///
+    /// Ill-formed input is treated as if errors had been replaced with REPLACEMENT
+    /// CHARACTERs according to the WHATWG Encoding Standard.
+    ///
+    /// For further details, see [`Self::collation_elements`].
+    pub fn collation_elements_utf8<'s>(
+        &'s self,
+        s: &'s [u8],
+    ) -> impl Iterator<Item = CollationWeights> + 's {
+        self.weights(s.chars())
+    }
+
+    /// Returns the collation elements of potentially ill-formed UTF-16 `s`.
+    ///
+    /// Unpaired surrogates are treated as REPLACEMENT CHARACTERs.
+    ///
+    /// For further details, see [`Self::collation_elements`].
+    pub fn collation_elements_utf16<'s>(
+        &'s self,
+        s: &'s [u16],
+    ) -> impl Iterator<Item = CollationWeights> + 's {
+        self.weights(s.chars())
+    }
+
+    fn weights<'s, I: Iterator<Item = char> + 's>(
+        &'s self,
+        chars: I,
+    ) -> impl Iterator<Item = CollationWeights> + 's {
+        let mut iter = self.raw_collation_elements(chars);
+        core::iter::from_fn(move || loop {
+            let ce: CollationElement = iter.next();
+            let primary = ce.primary();
+            if primary == NO_CE_PRIMARY {
+                return None;
+            }
+            let lower = ce.bits() as u32;
+            if primary != 0 || lower != 0 {
+                return Some(CollationWeights {
+                    primary: self.reorder_primary(primary),
+                    lower,
+                });
+            }
+        })
+    }
+}
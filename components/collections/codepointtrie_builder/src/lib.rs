@@ -8,16 +8,17 @@
 //! Because it is normally expected for [`CodePointTrie`] data to be pre-compiled, this crate is not
 //! optimized for speed; it should be used during a build phase.
 //!
-//! Under the hood, this crate uses the CodePointTrie builder code from ICU4C, [`UMutableCPTrie`].
+//! Under the hood, this crate uses [`MutableCodePointTrie`], a Rust port of the CodePointTrie
+//! builder code from ICU4C, [`UMutableCPTrie`]. Its output is identical to that of ICU4C.
 //! For more context, see <https://github.com/unicode-org/icu4x/issues/1837>.
 //!
-//! Unlike most of ICU4X, due in large part to the native dependency, this crate is not guaranteed
-//! to be panic-free.
-//!
 //! # Build configuration
 //!
-//! This crate has two primary modes it can be used in: `"wasm"` and `"icu4c"`, exposed as
-//! Cargo features. If both are enabled, the code will internally use the wasm codepath.
+//! By default, this crate has no native or Wasm dependencies. For cross-checking against
+//! ICU4C, the ICU4C builder code can be used instead via one of two Cargo features,
+//! `"wasm"` and `"icu4c"`. If both are enabled, the code will internally use the wasm codepath.
+//! Unlike most of ICU4X, due in large part to the native dependency, these modes are not
+//! guaranteed to be panic-free.
 //!
 //! The `"wasm"` mode uses a Wasm module packaged into this Rust crate that contains
 //! pre-compiled ICU4C CodePointTrie builder code. It evaluates the Wasm module using
@@ -69,6 +70,7 @@
 //!
 //! [`ICU4X`]: ../icu/index.html
 //! [`CodePointTrie`]: icu_collections::codepointtrie::CodePointTrie
+//! [`MutableCodePointTrie`]: icu_collections::codepointtrie::MutableCodePointTrie
 //! [`UMutableCPTrie`]: (https://unicode-org.github.io/icu-docs/apidoc/dev/icu4c/umutablecptrie_8h.html#ad8945cf34ca9d40596a66a1395baa19b)
 
 #![cfg_attr(
@@ -134,13 +136,16 @@ where
 {
     /// Build the [`CodePointTrie`].
     ///
-    /// Under the hood, this function runs ICU4C code compiled into WASM,
-    /// or links natively to ICU4C as specified by the `ICU4C_LIB_PATH` env var
+    /// Under the hood, this function uses [`MutableCodePointTrie`]. If the `wasm` or the
+    /// `icu4c` Cargo feature is enabled, it instead runs ICU4C code compiled into WASM,
+    /// or links natively to ICU4C as specified by the `ICU4C_LIB_PATH` env var.
+    ///
+    /// # Panics
     ///
-    /// ✨ *Enabled with either the `wasm` or the `icu4c` Cargo feature.*
+    /// Panics if the values cannot be represented by a [`CodePointTrie`].
     ///
     /// [`CodePointTrie`]: icu_collections::codepointtrie::CodePointTrie
-    #[cfg(any(feature = "wasm", feature = "icu4c"))]
+    /// [`MutableCodePointTrie`]: icu_collections::codepointtrie::MutableCodePointTrie
     pub fn build(self) -> icu_collections::codepointtrie::CodePointTrie<'static, T> {
         #[cfg(feature = "wasm")]
         {
@@ -151,11 +156,31 @@ where
         {
             native::run_native(&self)
         }
+
+        #[cfg(not(any(feature = "wasm", feature = "icu4c")))]
+        {
+            let mut builder = icu_collections::codepointtrie::MutableCodePointTrie::new(
+                self.default_value,
+                self.error_value,
+            );
+            match self.data {
+                CodePointTrieBuilderData::ValuesByCodePoint(values) => {
+                    for (cp, value) in (0..).zip(values) {
+                        if *value != self.default_value {
+                            builder.set(cp, *value);
+                        }
+                    }
+                }
+            }
+            #[allow(clippy::expect_used)] // documented
+            builder
+                .build(self.trie_type)
+                .expect("values should fit into a CodePointTrie")
+        }
     }
 }
 
 #[test]
-#[cfg(any(feature = "wasm", feature = "icu4c"))]
 fn test_cpt_builder() {
     // Buckets of ten characters for 0 to 100, and then some default values, and then heterogenous "last hex digit" for 0x100 to 0x200
     let values: Vec<u32> = (0..100)
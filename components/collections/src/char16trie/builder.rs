@@ -0,0 +1,360 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use super::trie::*;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use zerovec::ZeroVec;
+
+/// A builder for [`Char16Trie`] data.
+///
+/// The produced data uses the same serialization as ICU4C's `UCharsTriesBuilder`
+/// (in its "small" build option), so it can be read by [`Char16Trie`] as well as by ICU4C.
+///
+/// # Examples
+///
+/// ```
+/// use icu::collections::char16trie::{Char16TrieBuilder, TrieResult};
+///
+/// let mut builder = Char16TrieBuilder::new();
+/// builder.insert("東京", 10);
+/// builder.insert("東京都", 20);
+/// builder.insert("京都", 30);
+/// let trie = builder.build();
+///
+/// let mut iter = trie.iter();
+/// assert_eq!(iter.next('東'), TrieResult::NoValue);
+/// assert_eq!(iter.next('京'), TrieResult::Intermediate(10));
+/// assert_eq!(iter.next('都'), TrieResult::FinalValue(20));
+///
+/// let mut iter = trie.iter();
+/// assert_eq!(iter.next('都'), TrieResult::NoMatch);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Char16TrieBuilder {
+    entries: BTreeMap<Vec<u16>, i32>,
+}
+
+impl Char16TrieBuilder {
+    /// Returns an empty [`Char16TrieBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a string with its value, replacing the value if the string was already added.
+    ///
+    /// The string is stored as a sequence of UTF-16 code units. Negative values are stored
+    /// in the three-unit form, so every `i32` bit pattern round-trips. The empty string is
+    /// ignored, since a [`Char16Trie`] cannot report a value for it.
+    pub fn insert(&mut self, s: &str, value: i32) {
+        self.insert16(&s.encode_utf16().collect::<Vec<_>>(), value)
+    }
+
+    /// Adds a sequence of 16-bit units with its value, replacing the value if the sequence
+    /// was already added.
+    ///
+    /// See [`Self::insert`].
+    pub fn insert16(&mut self, units: &[u16], value: i32) {
+        if !units.is_empty() {
+            self.entries.insert(units.to_vec(), value);
+        }
+    }
+
+    /// Whether no strings have been added to the builder.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serializes the added strings into a [`Char16Trie`].
+    pub fn build(self) -> Char16Trie<'static> {
+        let entries: Vec<Entry> = self
+            .entries
+            .iter()
+            .map(|(units, &value)| (units.as_slice(), value))
+            .collect();
+        let data = if entries.is_empty() {
+            // A lone final value: every lookup fails.
+            write_value(0, true)
+        } else {
+            write_node(&entries)
+        };
+        Char16Trie::new(ZeroVec::alloc_from_slice(&data))
+    }
+}
+
+/// Serializes a value, either as a final-value node or as the target of a jump.
+fn write_value(value: i32, is_final: bool) -> Vec<u16> {
+    let final_bit = if is_final { VALUE_IS_FINAL } else { 0 };
+    let v = value as u32;
+    if v <= MAX_ONE_UNIT_VALUE as u32 {
+        vec![v as u16 | final_bit]
+    } else if v < ((THREE_UNIT_VALUE_LEAD - MIN_TWO_UNIT_VALUE_LEAD) as u32) << 16 {
+        vec![
+            (MIN_TWO_UNIT_VALUE_LEAD + (v >> 16) as u16) | final_bit,
+            v as u16,
+        ]
+    } else {
+        vec![
+            THREE_UNIT_VALUE_LEAD | final_bit,
+            (v >> 16) as u16,
+            v as u16,
+        ]
+    }
+}
+
+/// Serializes a jump delta of a binary-search branch node.
+fn write_delta(delta: usize) -> Vec<u16> {
+    let d = delta as u32;
+    if d <= MAX_ONE_UNIT_DELTA as u32 {
+        vec![d as u16]
+    } else if d < ((THREE_UNIT_DELTA_LEAD - MIN_TWO_UNIT_DELTA_LEAD) as u32) << 16 {
+        vec![MIN_TWO_UNIT_DELTA_LEAD + (d >> 16) as u16, d as u16]
+    } else {
+        vec![THREE_UNIT_DELTA_LEAD, (d >> 16) as u16, d as u16]
+    }
+}
+
+/// Adds an intermediate value to the lead unit of a branch or linear-match node.
+fn add_node_value(node: &mut Vec<u16>, value: i32) {
+    let v = value as u32;
+    let (lead_bits, extra) = if v <= MAX_ONE_UNIT_NODE_VALUE as u32 {
+        (((v + 1) << 6) as u16, Vec::new())
+    } else if v < (((THREE_UNIT_NODE_VALUE_LEAD - MIN_TWO_UNIT_NODE_VALUE_LEAD) >> 6) as u32) << 16
+    {
+        (
+            MIN_TWO_UNIT_NODE_VALUE_LEAD + (((v >> 10) as u16) & THREE_UNIT_NODE_VALUE_LEAD),
+            vec![v as u16],
+        )
+    } else {
+        (THREE_UNIT_NODE_VALUE_LEAD, vec![(v >> 16) as u16, v as u16])
+    };
+    if let Some(lead) = node.first_mut() {
+        *lead |= lead_bits;
+    }
+    // The extra value units precede the rest of the node.
+    node.splice(1..1, extra);
+}
+
+/// An entry of the builder, with the units that have already been matched removed.
+type Entry<'a> = (&'a [u16], i32);
+
+/// Serializes the node reached after matching a common prefix of the given sorted,
+/// non-empty entries.
+fn write_node(entries: &[Entry]) -> Vec<u16> {
+    let (value, rest) = match entries {
+        [(&[], value), rest @ ..] => (Some(*value), rest),
+        _ => (None, entries),
+    };
+    let (Some((first, _)), Some((last, _))) = (rest.first(), rest.last()) else {
+        return write_value(value.unwrap_or_default(), true);
+    };
+    let mut node = if first.first() == last.first() {
+        // All entries continue with the same units: a linear-match node. Since the entries
+        // are sorted, the common prefix of the first and last entry is shared by all of them.
+        let length = first
+            .iter()
+            .zip(last.iter())
+            .take(MAX_LINEAR_MATCH_LENGTH as usize)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut node = vec![MIN_LINEAR_MATCH + length as u16 - 1];
+        node.extend(first.iter().take(length));
+        node.extend(write_node(
+            &rest
+                .iter()
+                .map(|&(units, value)| (units.get(length..).unwrap_or_default(), value))
+                .collect::<Vec<_>>(),
+        ));
+        node
+    } else {
+        let mut groups: Vec<(u16, Vec<Entry>)> = Vec::new();
+        for &(units, value) in rest {
+            let Some((&unit, units)) = units.split_first() else {
+                continue;
+            };
+            match groups.last_mut() {
+                Some((u, group)) if *u == unit => group.push((units, value)),
+                _ => groups.push((unit, vec![(units, value)])),
+            }
+        }
+        let length = groups.len() - 1;
+        let mut node = if length < MIN_LINEAR_MATCH as usize {
+            vec![length as u16]
+        } else {
+            vec![0, length as u16]
+        };
+        node.extend(write_branch(&groups));
+        node
+    };
+    if let Some(value) = value {
+        add_node_value(&mut node, value);
+    }
+    node
+}
+
+/// Serializes the body of a branch node (or sub-node) over groups of entries,
+/// each of which continues with a distinct unit.
+fn write_branch(groups: &[(u16, Vec<Entry>)]) -> Vec<u16> {
+    if groups.len() > MAX_BRANCH_LINEAR_SUB_NODE_LENGTH {
+        // Binary search: units below the split unit are in the lower half, which is
+        // jumped to; the upper half follows directly.
+        let (lower, upper) = groups.split_at(groups.len() / 2);
+        let split = upper.first().map(|&(unit, _)| unit).unwrap_or_default();
+        let lower = write_branch(lower);
+        let upper = write_branch(upper);
+        let mut node = vec![split];
+        node.extend(write_delta(upper.len()));
+        node.extend(upper);
+        node.extend(lower);
+        return node;
+    }
+
+    // Linear list: every entry but the last has either a final value or a jump delta to
+    // its node. The last entry's node follows directly.
+    let Some(((last_unit, last_entries), others)) = groups.split_last() else {
+        return Vec::new();
+    };
+    let mut tail = write_node(last_entries);
+    let mut targets = Vec::with_capacity(others.len());
+    for (_, entries) in others {
+        match entries.as_slice() {
+            [(&[], value)] => targets.push(Err(*value)),
+            _ => {
+                targets.push(Ok(tail.len()));
+                tail.extend(write_node(entries));
+            }
+        }
+    }
+    // Jump deltas are relative to the end of the entry, so lay out the entries backwards.
+    let mut head = vec![*last_unit];
+    for ((unit, _), target) in others.iter().zip(targets).rev() {
+        let mut entry = vec![*unit];
+        match target {
+            Err(value) => entry.extend(write_value(value, true)),
+            Ok(offset) => entry.extend(write_value((head.len() + offset) as i32, false)),
+        }
+        head.splice(0..0, entry);
+    }
+    head.extend(tail);
+    head
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::char16trie::TrieResult;
+
+    fn check(entries: &[(&str, i32)]) {
+        let mut builder = Char16TrieBuilder::new();
+        for &(s, value) in entries {
+            builder.insert(s, value);
+        }
+        let trie = builder.build();
+        let mut expected: Vec<(Vec<u16>, i32)> = entries
+            .iter()
+            .map(|&(s, value)| (s.encode_utf16().collect(), value))
+            .collect();
+        expected.sort();
+        assert_eq!(trie.iter_entries().collect::<Vec<_>>(), expected);
+        for &(s, value) in entries {
+            let mut iter = trie.iter();
+            let mut chars = s.chars().peekable();
+            while let Some(c) = chars.next() {
+                let result = iter.next(c);
+                if chars.peek().is_some() {
+                    assert!(
+                        matches!(result, TrieResult::NoValue | TrieResult::Intermediate(_)),
+                        "{s}: {result:?}"
+                    );
+                } else {
+                    assert!(
+                        matches!(result, TrieResult::Intermediate(v) | TrieResult::FinalValue(v) if v == value),
+                        "{s}: {result:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_icu4c() {
+        let mut builder = Char16TrieBuilder::new();
+        builder.insert("a", 1);
+        builder.insert("ab", 100);
+        assert_eq!(builder.build().data.to_vec(), [48, 97, 176, 98, 32868]);
+    }
+
+    #[test]
+    fn empty() {
+        let trie = Char16TrieBuilder::new().build();
+        assert_eq!(trie.iter().next('a'), TrieResult::NoMatch);
+    }
+
+    #[test]
+    fn values() {
+        check(&[
+            ("a", 0),
+            ("b", 0x3fff),
+            ("c", 0x4000),
+            ("d", 0x3ffeffff),
+            ("e", 0x3fff0000),
+            ("f", i32::MAX),
+            ("g", -1),
+            ("h", i32::MIN),
+            ("hh", 5),
+            ("aa", 0xff),
+            ("aaa", 0x100),
+            ("aaaa", 0xfdffff),
+            ("aaaaa", 0xfe0000),
+            ("aaaaaa", 7),
+        ]);
+    }
+
+    #[test]
+    fn negative_values() {
+        // Collation contraction tries store CE32 bit patterns, which can have the top bit set.
+        check(&[
+            ("a", -1),
+            ("ab", i32::MIN),
+            ("b", 0x8000_0500_u32 as i32),
+            ("c", 0xffff_ff00_u32 as i32),
+            ("cd", -0x4000),
+        ]);
+        let mut builder = Char16TrieBuilder::new();
+        builder.insert("x", -2);
+        assert_eq!(builder.build().iter().next('x'), TrieResult::FinalValue(-2));
+    }
+
+    #[test]
+    fn branches() {
+        // Long linear matches, wide branches with binary search, and supplementary code points.
+        let words: Vec<String> = (0..2000u32)
+            .map(|i| {
+                let mut s = String::new();
+                let mut n = i;
+                loop {
+                    s.push(char::from_u32(0x4e00 + n % 37).unwrap());
+                    n /= 37;
+                    if n == 0 {
+                        break;
+                    }
+                }
+                if i % 7 == 0 {
+                    s.push_str("abcdefghijklmnopqrstuvwxyz");
+                }
+                if i % 11 == 0 {
+                    s.push('𠀋');
+                }
+                s
+            })
+            .collect();
+        let entries: Vec<(&str, i32)> = words
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.as_str(), (i as i32) * 40_000))
+            .collect();
+        check(&entries);
+    }
+}
@@ -12,6 +12,8 @@
 //! ## Architecture
 //!
 //! ICU4X [`Char16Trie`] is designed to provide a read-only view of `UCharsTrie` data that is exported from ICU4C.
+//! With the `alloc` Cargo feature, tries in the same format can also be built at runtime
+//! using [`Char16TrieBuilder`].
 //!
 //! ## Examples
 //!
@@ -35,10 +37,34 @@
 //! assert_eq!(res, TrieResult::NoMatch);
 //! ```
 //!
+//!
+//! ### Building a `Char16Trie`
+//!
+//! ```rust
+//! use icu::collections::char16trie::{Char16TrieBuilder, TrieResult};
+//!
+//! let mut builder = Char16TrieBuilder::new();
+//! builder.insert("a", 1);
+//! builder.insert("ab", 100);
+//! let trie = builder.build();
+//!
+//! // The built trie is identical to the one exported from ICU4C.
+//! assert_eq!(trie.data.to_vec(), [48, 97, 176, 98, 32868]);
+//!
+//! let mut iter = trie.iter();
+//! assert_eq!(iter.next('a'), TrieResult::Intermediate(1));
+//! assert_eq!(iter.next('b'), TrieResult::FinalValue(100));
+//! ```
+//!
 //! [`ICU4X`]: ../icu/index.html
 
+#[cfg(feature = "alloc")]
+mod builder;
 mod trie;
 
+#[cfg(feature = "alloc")]
+pub use builder::Char16TrieBuilder;
+
 pub use trie::Char16Trie;
 pub use trie::Char16TrieIterator;
 pub use trie::TrieResult;
@@ -2,6 +2,8 @@
 // called LICENSE at the top level of the ICU4X source tree
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use zerofrom::ZeroFrom;
 use zerovec::{ZeroSlice, ZeroVec};
 
@@ -12,38 +14,39 @@ use zerovec::{ZeroSlice, ZeroVec};
 
 // For a branch sub-node with at most this many entries, we drop down
 // to a linear search.
-const MAX_BRANCH_LINEAR_SUB_NODE_LENGTH: usize = 5;
+pub(super) const MAX_BRANCH_LINEAR_SUB_NODE_LENGTH: usize = 5;
 
 // 0030..003f: Linear-match node, match 1..16 units and continue reading the next node.
-const MIN_LINEAR_MATCH: u16 = 0x30;
-const MAX_LINEAR_MATCH_LENGTH: u16 = 0x10;
+pub(super) const MIN_LINEAR_MATCH: u16 = 0x30;
+pub(super) const MAX_LINEAR_MATCH_LENGTH: u16 = 0x10;
 
 // Match-node lead unit bits 14..6 for the optional intermediate value.
 // If these bits are 0, then there is no intermediate value.
 // Otherwise, see the *NodeValue* constants below.
-const MIN_VALUE_LEAD: u16 = MIN_LINEAR_MATCH + MAX_LINEAR_MATCH_LENGTH; // 0x40
+pub(super) const MIN_VALUE_LEAD: u16 = MIN_LINEAR_MATCH + MAX_LINEAR_MATCH_LENGTH; // 0x40
 const NODE_TYPE_MASK: u16 = MIN_VALUE_LEAD - 1; // 0x003f
 
 // A final-value node has bit 15 set.
-const VALUE_IS_FINAL: u16 = 0x8000;
+pub(super) const VALUE_IS_FINAL: u16 = 0x8000;
 
 // Compact value: After testing bit 0, shift right by 15 and then use the following thresholds.
-const MAX_ONE_UNIT_VALUE: u16 = 0x3fff;
+pub(super) const MAX_ONE_UNIT_VALUE: u16 = 0x3fff;
 
-const MIN_TWO_UNIT_VALUE_LEAD: u16 = MAX_ONE_UNIT_VALUE + 1; // 0x4000
+pub(super) const MIN_TWO_UNIT_VALUE_LEAD: u16 = MAX_ONE_UNIT_VALUE + 1; // 0x4000
 
-const MAX_ONE_UNIT_NODE_VALUE: u16 = 0xff;
+pub(super) const MAX_ONE_UNIT_NODE_VALUE: u16 = 0xff;
 
-const MIN_TWO_UNIT_NODE_VALUE_LEAD: u16 = MIN_VALUE_LEAD + ((MAX_ONE_UNIT_NODE_VALUE + 1) << 6); // 0x4040
+pub(super) const MIN_TWO_UNIT_NODE_VALUE_LEAD: u16 =
+    MIN_VALUE_LEAD + ((MAX_ONE_UNIT_NODE_VALUE + 1) << 6); // 0x4040
 
-const THREE_UNIT_NODE_VALUE_LEAD: u16 = 0x7fc0;
+pub(super) const THREE_UNIT_NODE_VALUE_LEAD: u16 = 0x7fc0;
 
-const THREE_UNIT_VALUE_LEAD: u16 = 0x7fff;
+pub(super) const THREE_UNIT_VALUE_LEAD: u16 = 0x7fff;
 
 // Compact delta integers.
-const MAX_ONE_UNIT_DELTA: u16 = 0xfbff;
-const MIN_TWO_UNIT_DELTA_LEAD: u16 = MAX_ONE_UNIT_DELTA + 1; // 0xfc00
-const THREE_UNIT_DELTA_LEAD: u16 = 0xffff;
+pub(super) const MAX_ONE_UNIT_DELTA: u16 = 0xfbff;
+pub(super) const MIN_TWO_UNIT_DELTA_LEAD: u16 = MAX_ONE_UNIT_DELTA + 1; // 0xfc00
+pub(super) const THREE_UNIT_DELTA_LEAD: u16 = 0xffff;
 
 fn skip_value(pos: usize, lead: u16) -> usize {
     if lead < MIN_TWO_UNIT_VALUE_LEAD {
@@ -98,6 +101,32 @@ impl<'data> Char16Trie<'data> {
     pub fn iter(&self) -> Char16TrieIterator<'_> {
         Char16TrieIterator::new(&self.data)
     }
+
+    /// Returns all strings in the trie with their values, in code unit order.
+    ///
+    /// ✨ *Enabled with the `alloc` Cargo feature.*
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collections::char16trie::Char16Trie;
+    /// use zerovec::ZeroVec;
+    ///
+    /// // A Char16Trie containing the ASCII characters mapping 'a' to 1 and 'ab'
+    /// // to 100.
+    /// let trie_data = [48, 97, 176, 98, 32868];
+    /// let trie = Char16Trie::new(ZeroVec::from_slice_or_alloc(&trie_data));
+    ///
+    /// let entries: Vec<(Vec<u16>, i32)> = trie.iter_entries().collect();
+    /// assert_eq!(entries, [(vec![97], 1), (vec![97, 98], 100)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn iter_entries(&self) -> impl Iterator<Item = (Vec<u16>, i32)> + '_ {
+        let mut entries = Vec::new();
+        let mut units = Vec::new();
+        Char16TrieIterator::new(&self.data).collect_node(0, &mut units, &mut entries);
+        entries.into_iter()
+    }
 }
 
 /// This struct represents an iterator over a [`Char16Trie`].
@@ -406,7 +435,7 @@ impl<'a> Char16TrieIterator<'a> {
         self.pos = None;
     }
 
-    #[inline(always)] // 1 call site and we want the Option to go away
+    #[inline(always)] // we want the Option to go away
     fn jump_by_delta(&self, pos: usize) -> Option<usize> {
         let delta = self.trie.get(pos)?;
         let v = if delta < MIN_TWO_UNIT_DELTA_LEAD {
@@ -430,7 +459,7 @@ impl<'a> Char16TrieIterator<'a> {
         Some(skip_value(pos + 1, lead_unit & 0x7fff))
     }
 
-    #[inline(always)] // 1 call site and we want the Option to go away
+    #[inline(always)] // we want the Option to go away
     fn skip_delta(&self, pos: usize) -> Option<usize> {
         let delta = self.trie.get(pos)?;
         let v = if delta < MIN_TWO_UNIT_DELTA_LEAD {
@@ -466,7 +495,96 @@ impl<'a> Char16TrieIterator<'a> {
         }
     }
 
-    #[inline(always)] // 1 call site and we want the Option to go away
+    /// Appends the strings reachable from the node at `pos`, each prefixed with `units`.
+    #[cfg(feature = "alloc")]
+    fn collect_node(
+        &self,
+        pos: usize,
+        units: &mut Vec<u16>,
+        entries: &mut Vec<(Vec<u16>, i32)>,
+    ) -> Option<()> {
+        let prefix_len = units.len();
+        let mut node = self.trie.get(pos)?;
+        let mut pos = pos + 1;
+        loop {
+            if node >= MIN_VALUE_LEAD {
+                if node & VALUE_IS_FINAL != 0 {
+                    entries.push((units.clone(), self.read_value(pos, node & 0x7fff)?));
+                    break;
+                }
+                entries.push((units.clone(), self.read_node_value(pos, node)?));
+                pos = skip_node_value(pos, node);
+                node &= NODE_TYPE_MASK;
+            }
+            if node < MIN_LINEAR_MATCH {
+                self.collect_branch(pos, node as usize, units, entries)?;
+                break;
+            }
+            let length = (node - MIN_LINEAR_MATCH) as usize + 1;
+            units.extend(self.trie.get_subslice(pos..pos + length)?.iter());
+            pos += length;
+            node = self.trie.get(pos)?;
+            pos += 1;
+        }
+        units.truncate(prefix_len);
+        Some(())
+    }
+
+    /// Appends the strings reachable from the branch node with the given length at `pos`.
+    #[cfg(feature = "alloc")]
+    fn collect_branch(
+        &self,
+        pos: usize,
+        length: usize,
+        units: &mut Vec<u16>,
+        entries: &mut Vec<(Vec<u16>, i32)>,
+    ) -> Option<()> {
+        let mut pos = pos;
+        let mut length = length;
+        if length == 0 {
+            length = self.trie.get(pos)? as usize;
+            pos += 1;
+        }
+        self.collect_sub_branch(pos, length + 1, units, entries)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn collect_sub_branch(
+        &self,
+        pos: usize,
+        length: usize,
+        units: &mut Vec<u16>,
+        entries: &mut Vec<(Vec<u16>, i32)>,
+    ) -> Option<()> {
+        let mut pos = pos;
+        let mut length = length;
+        while length > MAX_BRANCH_LINEAR_SUB_NODE_LENGTH {
+            // The units less than the split unit are behind the jump.
+            self.collect_sub_branch(self.jump_by_delta(pos + 1)?, length >> 1, units, entries)?;
+            length -= length >> 1;
+            pos = self.skip_delta(pos + 1)?;
+        }
+        while length > 1 {
+            units.push(self.trie.get(pos)?);
+            let lead_unit = self.trie.get(pos + 1)?;
+            let value = self.read_value(pos + 2, lead_unit & 0x7fff)?;
+            pos = skip_value(pos + 2, lead_unit & 0x7fff);
+            if lead_unit & VALUE_IS_FINAL != 0 {
+                entries.push((units.clone(), value));
+            } else {
+                // The non-final value is the jump delta to the node.
+                self.collect_node(pos + value as usize, units, entries)?;
+            }
+            units.pop();
+            length -= 1;
+        }
+        units.push(self.trie.get(pos)?);
+        self.collect_node(pos + 1, units, entries)?;
+        units.pop();
+        Some(())
+    }
+
+    #[inline(always)] // we want the Option to go away
     fn read_value(&self, pos: usize, lead_unit: u16) -> Option<i32> {
         let v = if lead_unit < MIN_TWO_UNIT_VALUE_LEAD {
             lead_unit.into()
@@ -478,7 +596,7 @@ impl<'a> Char16TrieIterator<'a> {
         Some(v)
     }
 
-    #[inline(always)] // 1 call site and we want the Option to go away
+    #[inline(always)] // we want the Option to go away
     fn read_node_value(&self, pos: usize, lead_unit: u16) -> Option<i32> {
         let v = if lead_unit < (MIN_TWO_UNIT_NODE_VALUE_LEAD) {
             ((lead_unit >> 6) - 1).into()
@@ -447,10 +447,20 @@ impl<'data> CodePointInversionList<'data> {
     /// assert_eq!(Some(0x44..=0x44), example_iter_ranges.next());
     /// assert_eq!(Some(0x46..=char::MAX as u32), example_iter_ranges.next());
     /// assert_eq!(None, example_iter_ranges.next());
+    ///
+    /// let all = CodePointInversionList::all();
+    /// assert_eq!(None, all.iter_ranges_complemented().next());
+    /// let empty = CodePointInversionList::try_from_u32_inversion_list_slice(&[]).unwrap();
+    /// assert_eq!(
+    ///     Some(0..=char::MAX as u32),
+    ///     empty.iter_ranges_complemented().next()
+    /// );
     /// ```
     pub fn iter_ranges_complemented(&self) -> impl Iterator<Item = RangeInclusive<u32>> + '_ {
         let inv_ule = self.inv_list.as_ule_slice();
-        let middle = inv_ule.get(1..inv_ule.len() - 1).unwrap_or(&[]);
+        let middle = inv_ule
+            .get(1..inv_ule.len().saturating_sub(1))
+            .unwrap_or(&[]);
         let beginning = if let Some(first) = self.inv_list.first() {
             let first = u32::from(first);
             if first == 0 {
@@ -459,11 +469,11 @@ impl<'data> CodePointInversionList<'data> {
                 Some(0..=first - 1)
             }
         } else {
-            None
+            Some(0..=char::MAX as u32)
         };
         let end = if let Some(last) = self.inv_list.last() {
             let last = u32::from(last);
-            if last == char::MAX as u32 {
+            if last > char::MAX as u32 {
                 None
             } else {
                 Some(last..=char::MAX as u32)
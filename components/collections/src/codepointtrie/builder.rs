@@ -0,0 +1,1245 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! A port of ICU4C's `umutablecptrie.cpp`.
+//!
+//! The compaction steps follow ICU4C closely, so that the built tries are identical to the
+//! ones built by `umutablecptrie_buildImmutable`.
+
+use super::cptrie::{CodePointMapRange, CodePointTrie, CodePointTrieHeader, TrieType, TrieValue};
+use super::error::Error;
+use super::impl_const::*;
+use crate::codepointinvlist::CodePointInversionList;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::iter;
+use core::ops::RangeInclusive;
+use zerovec::ule::ULE;
+use zerovec::ZeroVec;
+
+const UNICODE_LIMIT: u32 = 0x110000;
+const BMP_LIMIT: u32 = 0x10000;
+const ASCII_LIMIT: usize = 0x80;
+
+const BMP_I_LIMIT: usize = (BMP_LIMIT >> SHIFT_3) as usize;
+const ASCII_I_LIMIT: usize = ASCII_LIMIT >> SHIFT_3;
+
+const SMALL_DATA_BLOCKS_PER_BMP_BLOCK: usize = 1 << (FAST_TYPE_SHIFT as u32 - SHIFT_3);
+const SMALL_BLOCK_LENGTH: usize = SMALL_DATA_BLOCK_LENGTH as usize;
+const FAST_BLOCK_LENGTH: usize = FAST_TYPE_DATA_BLOCK_LENGTH as usize;
+const INDEX_3_BLOCK: usize = INDEX_3_BLOCK_LENGTH as usize;
+const INDEX_3_18BIT_BLOCK_LENGTH: usize = INDEX_3_BLOCK + INDEX_3_BLOCK / 8;
+
+const NO_INDEX3_NULL_OFFSET: u32 = 0x7fff;
+const NO_DATA_NULL_OFFSET: u32 = 0xfffff;
+
+/// The largest data length whose last block offset can be stored in the index.
+const MAX_DATA_LENGTH: usize = 0x3ffff + SMALL_BLOCK_LENGTH;
+
+/// The state of a block of [`SMALL_BLOCK_LENGTH`] code points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    /// The index entry is the value of all code points in the block.
+    AllSame,
+    /// The index entry is the offset of the block's values in the data array.
+    Mixed,
+    /// While compacting: the index entry is the index of an earlier block with the
+    /// same value.
+    SameAs,
+}
+
+/// The kind of an index-3 block, while compacting the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Index3 {
+    /// All entries are the data null offset.
+    Null,
+    /// Identical to a part of the fast index.
+    Bmp,
+    /// All entries fit into 16 bits.
+    Bits16,
+    /// Some entries need 18 bits.
+    Bits18,
+}
+
+/// A mutable map from code points to values, which can be compacted into a [`CodePointTrie`].
+///
+/// This is a port of ICU4C's [`UMutableCPTrie`]. The tries built by
+/// [`MutableCodePointTrie::build`] are identical to the ones ICU4C builds from the same
+/// values.
+///
+/// ✨ *Enabled with the `alloc` Cargo feature.*
+///
+/// # Examples
+///
+/// ```
+/// use icu::collections::codepointtrie::{MutableCodePointTrie, TrieType};
+///
+/// let mut builder = MutableCodePointTrie::new(0u8, 0xff);
+/// builder.set_range('a' as u32..='z' as u32, 1);
+/// builder.set('_' as u32, 2);
+/// assert_eq!(builder.get('q' as u32), 1);
+///
+/// let trie = builder.build(TrieType::Small).unwrap();
+/// assert_eq!(trie.get('q'), 1);
+/// assert_eq!(trie.get('_'), 2);
+/// assert_eq!(trie.get('A'), 0);
+/// assert_eq!(trie.get32(0x110000), 0xff);
+/// ```
+///
+/// [`UMutableCPTrie`]: https://unicode-org.github.io/icu-docs/apidoc/released/icu4c/umutablecptrie_8h.html
+#[derive(Debug, Clone)]
+pub struct MutableCodePointTrie<T> {
+    /// For each block of 16 code points below the high start, either the value of all of
+    /// them, or the offset of their values in `data`.
+    index: Vec<u32>,
+    flags: Vec<Block>,
+    data: Vec<u32>,
+    initial_value: T,
+    error_value: T,
+}
+
+impl<T: TrieValue> MutableCodePointTrie<T> {
+    /// Creates a trie that maps all code points to `initial_value`.
+    ///
+    /// `error_value` is returned by the built trie for values that are not code points.
+    pub fn new(initial_value: T, error_value: T) -> Self {
+        Self {
+            index: Vec::new(),
+            flags: Vec::new(),
+            data: Vec::new(),
+            initial_value,
+            error_value,
+        }
+    }
+
+    /// Creates a trie with the same values as `trie`.
+    ///
+    /// As in ICU4C's `umutablecptrie_fromUCPTrie`, the initial value is the value of
+    /// U+10FFFF, so that building the result with the type and value width of `trie`
+    /// yields the same trie.
+    pub fn from_code_point_trie(trie: &CodePointTrie<T>) -> Self {
+        let mut result = Self::new(trie.get32(CODE_POINT_MAX), trie.error_value);
+        for range in trie.iter_ranges() {
+            if range.value != result.initial_value {
+                result.set_range(range.range, range.value);
+            }
+        }
+        result
+    }
+
+    /// Returns the value for a code point, or the error value for values above U+10FFFF.
+    pub fn get(&self, code_point: u32) -> T {
+        if code_point > CODE_POINT_MAX {
+            return self.error_value;
+        }
+        self.value_from_bits(self.get_bits(code_point))
+    }
+
+    /// Returns an iterator over the maximal ranges of code points that map to the same value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collections::codepointtrie::{CodePointMapRange, MutableCodePointTrie};
+    ///
+    /// let mut builder = MutableCodePointTrie::new(0u16, 0xffff);
+    /// builder.set_range(0x41..=0x5a, 1);
+    /// builder.set_range(0x5b..=0x60, 1);
+    ///
+    /// let mut ranges = builder.iter_ranges();
+    /// assert_eq!(
+    ///     ranges.next(),
+    ///     Some(CodePointMapRange { range: 0..=0x40, value: 0 })
+    /// );
+    /// assert_eq!(
+    ///     ranges.next(),
+    ///     Some(CodePointMapRange { range: 0x41..=0x60, value: 1 })
+    /// );
+    /// assert_eq!(
+    ///     ranges.next(),
+    ///     Some(CodePointMapRange { range: 0x61..=0x10ffff, value: 0 })
+    /// );
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    pub fn iter_ranges(&self) -> impl Iterator<Item = CodePointMapRange<T>> + '_ {
+        let mut next = Some(0);
+        iter::from_fn(move || {
+            let start = next?;
+            let end = self.range_end(start);
+            next = end.checked_add(1).filter(|&c| c <= CODE_POINT_MAX);
+            Some(CodePointMapRange {
+                range: start..=end,
+                value: self.value_from_bits(self.get_bits(start)),
+            })
+        })
+    }
+
+    /// Sets the value for a code point. Values above U+10FFFF are ignored.
+    pub fn set(&mut self, code_point: u32, value: T) {
+        if code_point > CODE_POINT_MAX {
+            return;
+        }
+        self.ensure_high_start(code_point);
+        let block = self.get_data_block((code_point >> SHIFT_3) as usize);
+        if let Some(bits) = self
+            .data
+            .get_mut(block + (code_point & SMALL_DATA_MASK) as usize)
+        {
+            *bits = Self::to_bits(value);
+        }
+    }
+
+    /// Sets the value for a range of code points. The part of the range above U+10FFFF
+    /// is ignored.
+    pub fn set_range(&mut self, range: RangeInclusive<u32>, value: T) {
+        let mut start = *range.start();
+        let end = (*range.end()).min(CODE_POINT_MAX);
+        if start > end {
+            return;
+        }
+        let value = Self::to_bits(value);
+        self.ensure_high_start(end);
+
+        let mut limit = end + 1;
+        if start & SMALL_DATA_MASK != 0 {
+            // Set the partial block at [start..following block boundary[.
+            let block = self.get_data_block((start >> SHIFT_3) as usize);
+            let next_start = (start + SMALL_DATA_MASK) & !SMALL_DATA_MASK;
+            let block_start = block + (start & SMALL_DATA_MASK) as usize;
+            if next_start <= limit {
+                self.fill(block_start..block + SMALL_BLOCK_LENGTH, value);
+                start = next_start;
+            } else {
+                self.fill(
+                    block_start..block + (limit & SMALL_DATA_MASK) as usize,
+                    value,
+                );
+                return;
+            }
+        }
+
+        // Number of positions in the last, partial block.
+        let rest = (limit & SMALL_DATA_MASK) as usize;
+        // Round down the limit to a block boundary.
+        limit &= !SMALL_DATA_MASK;
+
+        // Iterate over the whole blocks.
+        let blocks = (start >> SHIFT_3) as usize..(limit >> SHIFT_3) as usize;
+        if let (Some(flags), Some(index)) =
+            (self.flags.get(blocks.clone()), self.index.get_mut(blocks))
+        {
+            for (&flag, entry) in flags.iter().zip(index) {
+                if flag == Block::Mixed {
+                    let block = *entry as usize;
+                    if let Some(data) = self.data.get_mut(block..block + SMALL_BLOCK_LENGTH) {
+                        data.fill(value);
+                    }
+                } else {
+                    *entry = value;
+                }
+            }
+        }
+
+        if rest > 0 {
+            // Set the partial block at [last block boundary..limit[.
+            let block = self.get_data_block((limit >> SHIFT_3) as usize);
+            self.fill(block..block + rest, value);
+        }
+    }
+
+    /// Sets the value for all code points in `set`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collections::codepointinvlist::CodePointInversionList;
+    /// use icu::collections::codepointtrie::MutableCodePointTrie;
+    ///
+    /// let digits = CodePointInversionList::try_from_u32_inversion_list_slice(&[
+    ///     0x30, 0x3a, 0x660, 0x66a,
+    /// ])
+    /// .unwrap();
+    ///
+    /// let mut builder = MutableCodePointTrie::new(0u8, 0);
+    /// builder.set_set(&digits, 1);
+    /// assert_eq!(builder.get('7' as u32), 1);
+    /// assert_eq!(builder.get('٧' as u32), 1);
+    /// assert_eq!(builder.get('a' as u32), 0);
+    /// ```
+    pub fn set_set(&mut self, set: &CodePointInversionList, value: T) {
+        for range in set.iter_ranges() {
+            self.set_range(range, value);
+        }
+    }
+
+    /// Merges the values of `ranges` into this trie.
+    ///
+    /// For each code point in one of the `ranges`, the value is set to `merge` applied to the
+    /// current value and the value of the range. Code points outside of `ranges` keep
+    /// their values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collections::codepointtrie::{CodePointMapRange, MutableCodePointTrie};
+    ///
+    /// let mut builder = MutableCodePointTrie::new(0u32, 0);
+    /// builder.set_range(0x20..=0x7e, 1);
+    ///
+    /// builder.merge(
+    ///     [CodePointMapRange { range: 0x41..=0xff, value: 2 }],
+    ///     |current, other| current | other,
+    /// );
+    /// assert_eq!(builder.get(0x20), 1);
+    /// assert_eq!(builder.get(0x41), 3);
+    /// assert_eq!(builder.get(0xff), 2);
+    /// assert_eq!(builder.get(0x100), 0);
+    /// ```
+    pub fn merge<U: Copy>(
+        &mut self,
+        ranges: impl IntoIterator<Item = CodePointMapRange<U>>,
+        mut merge: impl FnMut(T, U) -> T,
+    ) {
+        for CodePointMapRange { range, value } in ranges {
+            let mut start = *range.start();
+            let end = (*range.end()).min(CODE_POINT_MAX);
+            while start <= end {
+                // Split the range at the ends of the runs of equal values in this trie.
+                let run_end = self.range_end(start).min(end);
+                let current = self.get(start);
+                self.set_range(start..=run_end, merge(current, value));
+                start = run_end + 1;
+            }
+        }
+    }
+
+    /// Returns a trie with `f` applied to all values, including the initial and error
+    /// values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collections::codepointtrie::{MutableCodePointTrie, TrieType};
+    ///
+    /// let mut builder = MutableCodePointTrie::new(0u32, u32::MAX);
+    /// builder.set_range(0x370..=0x3ff, 700);
+    ///
+    /// let trie = builder
+    ///     .map_values(|value| u8::try_from(value / 100).unwrap_or(0xff))
+    ///     .build(TrieType::Small)
+    ///     .unwrap();
+    /// assert_eq!(trie.get('λ'), 7);
+    /// assert_eq!(trie.get('a'), 0);
+    /// assert_eq!(trie.get32(0x110000), 0xff);
+    /// ```
+    pub fn map_values<U: TrieValue>(&self, mut f: impl FnMut(T) -> U) -> MutableCodePointTrie<U> {
+        let mut map = |bits| MutableCodePointTrie::<U>::to_bits(f(self.value_from_bits(bits)));
+        MutableCodePointTrie {
+            index: self
+                .index
+                .iter()
+                .zip(&self.flags)
+                .map(|(&entry, &flag)| {
+                    if flag == Block::Mixed {
+                        entry
+                    } else {
+                        map(entry)
+                    }
+                })
+                .collect(),
+            flags: self.flags.clone(),
+            data: self.data.iter().map(|&bits| map(bits)).collect(),
+            initial_value: f(self.initial_value),
+            error_value: f(self.error_value),
+        }
+    }
+
+    /// Compacts the values into a [`CodePointTrie`] of the given type.
+    ///
+    /// The value width of the trie is the size of `T`'s unaligned representation: 8, 16 or
+    /// 32 bits, where 24-bit values are built as 32-bit values and then narrowed.
+    ///
+    /// This returns [`CodePointTrieError::TooLarge`](super::CodePointTrieError::TooLarge) if the values
+    /// are too varied to be addressed by the trie's index.
+    pub fn build(&self, trie_type: TrieType) -> Result<CodePointTrie<'static, T>, Error> {
+        let fast_limit = match trie_type {
+            TrieType::Fast => BMP_LIMIT,
+            TrieType::Small => SMALL_LIMIT,
+        };
+
+        let mut trie = self.clone();
+        trie.ensure_high_start(CODE_POINT_MAX);
+        let Compacted {
+            index,
+            mut data,
+            high_start,
+            high_value,
+            null_value,
+            index3_null_offset,
+            data_null_offset,
+        } = trie.compact((fast_limit >> SHIFT_3) as usize)?;
+
+        let error_value = Self::to_bits(self.error_value);
+        let mut index = index.into_iter().map(|i| i as u16).collect::<Vec<_>>();
+        let ends_with_special_values = |data: &[u32]| data.ends_with(&[high_value, error_value]);
+        // Make the total length of the trie a multiple of four bytes by padding the data,
+        // and store the high and error values as the last two data values.
+        match core::mem::size_of::<T::ULE>() {
+            1 => {
+                let mut and3 = (index.len() * 2 + data.len()) & 3;
+                if and3 == 0 && ends_with_special_values(&data) {
+                    // All set.
+                } else if and3 == 3 && data.last() == Some(&high_value) {
+                    data.push(error_value);
+                } else {
+                    while and3 != 2 {
+                        data.push(high_value);
+                        and3 = (and3 + 1) & 3;
+                    }
+                    data.push(high_value);
+                    data.push(error_value);
+                }
+            }
+            2 => {
+                if (index.len() ^ data.len()) & 1 != 0 {
+                    data.push(error_value);
+                }
+                if !ends_with_special_values(&data) {
+                    data.push(high_value);
+                    data.push(error_value);
+                }
+            }
+            _ => {
+                // The index length must be even for 32-bit data.
+                if index.len() & 1 != 0 {
+                    index.push(0xffee);
+                }
+                if !ends_with_special_values(&data) {
+                    if data.last() != Some(&high_value) {
+                        data.push(high_value);
+                    }
+                    data.push(error_value);
+                }
+            }
+        }
+
+        let header = CodePointTrieHeader {
+            high_start,
+            // Rounded up to a multiple of 0x1000 for easy testing from UTF-8 lead bytes.
+            shifted12_high_start: ((high_start + 0xfff) >> 12) as u16,
+            index3_null_offset: index3_null_offset as u16,
+            data_null_offset,
+            null_value,
+            trie_type,
+        };
+
+        let width = core::mem::size_of::<T::ULE>();
+        let bytes = data
+            .iter()
+            .flat_map(|value| value.to_le_bytes().into_iter().take(width))
+            .collect::<Vec<u8>>();
+        let data = ZeroVec::<T>::parse_bytes(&bytes)
+            .map_err(|_| Error::FromDeserialized {
+                reason: "Built data contains invalid values",
+            })?
+            .into_owned();
+
+        CodePointTrie::try_new(header, ZeroVec::alloc_from_slice(&index), data)
+    }
+
+    /// The bits of a value that are stored in a trie with the width of `T`.
+    fn to_bits(value: T) -> u32 {
+        match core::mem::size_of::<T::ULE>() {
+            1 => value.to_u32() & 0xff,
+            2 => value.to_u32() & 0xffff,
+            _ => value.to_u32(),
+        }
+    }
+
+    fn from_bits(bits: u32) -> Option<T> {
+        let bytes = bits.to_le_bytes();
+        let ule = T::ULE::parse_bytes_to_slice(bytes.get(..core::mem::size_of::<T::ULE>())?)
+            .ok()?
+            .first()?;
+        Some(T::from_unaligned(*ule))
+    }
+
+    fn value_from_bits(&self, bits: u32) -> T {
+        Self::from_bits(bits).unwrap_or(self.error_value)
+    }
+
+    /// Returns the last code point of the run of code points starting at `start` that
+    /// have the same value.
+    fn range_end(&self, start: u32) -> u32 {
+        let bits = self.get_bits(start);
+        let mut code_point = start;
+        while code_point <= CODE_POINT_MAX {
+            let i = (code_point >> SHIFT_3) as usize;
+            match self.flags.get(i) {
+                // All code points above the allocated blocks have the initial value.
+                None if bits == Self::to_bits(self.initial_value) => return CODE_POINT_MAX,
+                None => return code_point - 1,
+                Some(Block::Mixed) => {
+                    let block = self.index.get(i).copied().unwrap_or_default() as usize;
+                    let values = self
+                        .data
+                        .get(
+                            block + (code_point & SMALL_DATA_MASK) as usize
+                                ..block + SMALL_BLOCK_LENGTH,
+                        )
+                        .unwrap_or_default();
+                    if let Some(k) = values.iter().position(|&v| v != bits) {
+                        return code_point + k as u32 - 1;
+                    }
+                }
+                Some(_) => {
+                    if self.index.get(i) != Some(&bits) {
+                        return code_point - 1;
+                    }
+                }
+            }
+            code_point = (code_point | SMALL_DATA_MASK) + 1;
+        }
+        CODE_POINT_MAX
+    }
+
+    fn get_bits(&self, code_point: u32) -> u32 {
+        let i = (code_point >> SHIFT_3) as usize;
+        let bits = match (self.flags.get(i), self.index.get(i)) {
+            (Some(Block::Mixed), Some(&block)) => self
+                .data
+                .get(block as usize + (code_point & SMALL_DATA_MASK) as usize)
+                .copied(),
+            (Some(_), Some(&value)) => Some(value),
+            _ => None,
+        };
+        bits.unwrap_or_else(|| Self::to_bits(self.initial_value))
+    }
+
+    /// Extends the index so that it covers `code_point`, in units of index-2 entries.
+    fn ensure_high_start(&mut self, code_point: u32) {
+        let high_start = (code_point + CP_PER_INDEX_2_ENTRY) & !(CP_PER_INDEX_2_ENTRY - 1);
+        let i_limit = (high_start >> SHIFT_3) as usize;
+        if i_limit > self.index.len() {
+            self.index
+                .resize(i_limit, Self::to_bits(self.initial_value));
+            self.flags.resize(i_limit, Block::AllSame);
+        }
+    }
+
+    /// Sets the values at `range` in the data array.
+    fn fill(&mut self, range: core::ops::Range<usize>, value: u32) {
+        if let Some(data) = self.data.get_mut(range) {
+            data.fill(value);
+        }
+    }
+
+    /// Turns the block into a mixed block if necessary, and returns its data offset.
+    ///
+    /// Blocks in the BMP are turned into mixed blocks together with the other blocks of the
+    /// same fast block, so that fast blocks are contiguous.
+    ///
+    /// The index must cover the block.
+    fn get_data_block(&mut self, i: usize) -> usize {
+        if self.flags.get(i) == Some(&Block::Mixed) {
+            return self.index.get(i).copied().unwrap_or_default() as usize;
+        }
+        let (i_start, count) = if i < BMP_I_LIMIT {
+            (
+                i & !(SMALL_DATA_BLOCKS_PER_BMP_BLOCK - 1),
+                SMALL_DATA_BLOCKS_PER_BMP_BLOCK,
+            )
+        } else {
+            (i, 1)
+        };
+        let blocks = i_start..i_start + count;
+        if let (Some(flags), Some(index)) = (
+            self.flags.get_mut(blocks.clone()),
+            self.index.get_mut(blocks),
+        ) {
+            for (flag, entry) in flags.iter_mut().zip(index) {
+                let offset = self.data.len();
+                self.data.extend(iter::repeat_n(*entry, SMALL_BLOCK_LENGTH));
+                *flag = Block::Mixed;
+                *entry = offset as u32;
+            }
+        }
+        self.index.get(i).copied().unwrap_or_default() as usize
+    }
+
+    /// Returns the start of the trailing blocks that all have the high value.
+    fn find_high_start(&self, high_value: u32) -> u32 {
+        for (i, (&flag, &entry)) in self.flags.iter().zip(&self.index).enumerate().rev() {
+            let matches = if flag == Block::Mixed {
+                let block = entry as usize;
+                self.data
+                    .get(block..block + SMALL_BLOCK_LENGTH)
+                    .unwrap_or_default()
+                    .iter()
+                    .all(|&v| v == high_value)
+            } else {
+                entry == high_value
+            };
+            if !matches {
+                return ((i + 1) as u32) << SHIFT_3;
+            }
+        }
+        0
+    }
+
+    /// Compacts the data and the index. The index must cover all code points.
+    #[expect(clippy::indexing_slicing)] // the index covers all code points
+    fn compact(mut self, fast_i_limit: usize) -> Result<Compacted, Error> {
+        let initial_value = Self::to_bits(self.initial_value);
+
+        // Find the real high start and round it up.
+        let mut high_value = self.get_bits(CODE_POINT_MAX);
+        let real_high_start = (self.find_high_start(high_value) + CP_PER_INDEX_2_ENTRY - 1)
+            & !(CP_PER_INDEX_2_ENTRY - 1);
+        if real_high_start == UNICODE_LIMIT {
+            high_value = initial_value;
+        }
+
+        // We always store indexes and data values for the fast range.
+        // Pin the high start to the top of that range while building.
+        let fast_limit = (fast_i_limit as u32) << SHIFT_3;
+        let high_start = if real_high_start < fast_limit {
+            for i in (real_high_start >> SHIFT_3) as usize..fast_i_limit {
+                self.flags[i] = Block::AllSame;
+                self.index[i] = high_value;
+            }
+            fast_limit
+        } else {
+            real_high_start
+        };
+
+        let ascii_data = (0..ASCII_LIMIT as u32)
+            .map(|c| self.get_bits(c))
+            .collect::<Vec<_>>();
+
+        // First we look for which data blocks have the same value repetition
+        // or are equivalent to the ASCII data block.
+        let mut all_same_blocks = AllSameBlocks::default();
+        self.compact_whole_data_blocks(fast_i_limit, high_start, &mut all_same_blocks);
+
+        let data_null_index = all_same_blocks.find_most_used();
+        let data = self.compact_data(fast_i_limit, high_start, ascii_data, data_null_index);
+        if data.len() > MAX_DATA_LENGTH {
+            // The offset of the last data block is too high to be stored in the index.
+            return Err(Error::TooLarge);
+        }
+
+        let (data_null_offset, null_value) = match data_null_index {
+            Some(i) => (Some(self.index[i]), data[self.index[i] as usize]),
+            None => (None, initial_value),
+        };
+
+        let (index, index3_null_offset) =
+            self.compact_index(fast_i_limit, high_start, data_null_offset)?;
+
+        Ok(Compacted {
+            index,
+            data,
+            high_start: real_high_start,
+            high_value,
+            null_value,
+            index3_null_offset,
+            data_null_offset: data_null_offset.unwrap_or(NO_DATA_NULL_OFFSET),
+        })
+    }
+
+    /// Turns mixed blocks with a single value into all-same blocks, and links all-same
+    /// blocks to the first block with the same value.
+    #[expect(clippy::indexing_slicing)] // block indexes are below the high start, data offsets of whole blocks
+    fn compact_whole_data_blocks(
+        &mut self,
+        fast_i_limit: usize,
+        high_start: u32,
+        all_same_blocks: &mut AllSameBlocks,
+    ) {
+        let i_limit = (high_start >> SHIFT_3) as usize;
+        let mut block_length = FAST_BLOCK_LENGTH;
+        let mut inc = SMALL_DATA_BLOCKS_PER_BMP_BLOCK;
+        let mut i = 0;
+        while i < i_limit {
+            if i == fast_i_limit {
+                block_length = SMALL_BLOCK_LENGTH;
+                inc = 1;
+            }
+            let mut value = self.index[i];
+            if self.flags[i] == Block::Mixed {
+                // Really mixed?
+                let block = value as usize;
+                value = self.data[block];
+                if self.data[block + 1..block + block_length]
+                    .iter()
+                    .all(|&v| v == value)
+                {
+                    self.flags[i] = Block::AllSame;
+                    self.index[i] = value;
+                } else {
+                    i += inc;
+                    continue;
+                }
+            } else if inc > 1 && self.index[i + 1..i + inc].iter().any(|&v| v != value) {
+                // Not all of the fast block's parts have the same value.
+                self.get_data_block(i);
+                i += inc;
+                continue;
+            }
+
+            // Is there another all-same block with the same value?
+            let other = match all_same_blocks.find_or_add(i, inc, value) {
+                FindOrAdd::Found(other) => Some(other),
+                FindOrAdd::NewUnique => None,
+                FindOrAdd::Overflow => {
+                    // Slow check for a duplicate block.
+                    let mut j_inc = SMALL_DATA_BLOCKS_PER_BMP_BLOCK;
+                    let mut j = 0;
+                    loop {
+                        if j == i {
+                            all_same_blocks.add(i, inc, value);
+                            break None;
+                        }
+                        if j == fast_i_limit {
+                            j_inc = 1;
+                        }
+                        if self.flags[j] == Block::AllSame && self.index[j] == value {
+                            all_same_blocks.add(j, j_inc + inc, value);
+                            break Some(j);
+                        }
+                        j += j_inc;
+                    }
+                }
+            };
+            if let Some(other) = other {
+                self.flags[i] = Block::SameAs;
+                self.index[i] = other as u32;
+            }
+            i += inc;
+        }
+    }
+
+    /// Writes the deduplicated and overlapped data blocks, and replaces the index entries
+    /// with their offsets.
+    #[expect(clippy::indexing_slicing)] // block indexes are below the high start, data offsets of whole blocks
+    fn compact_data(
+        &mut self,
+        fast_i_limit: usize,
+        high_start: u32,
+        ascii_data: Vec<u32>,
+        data_null_index: Option<usize>,
+    ) -> Vec<u32> {
+        // The linear ASCII data is written first.
+        let mut new_data = ascii_data;
+        for (k, i) in (0..ASCII_I_LIMIT)
+            .step_by(SMALL_DATA_BLOCKS_PER_BMP_BLOCK)
+            .enumerate()
+        {
+            self.index[i] = (k * FAST_BLOCK_LENGTH) as u32;
+        }
+
+        let mut block_length = FAST_BLOCK_LENGTH;
+        let mut mixed_blocks = MixedBlocks::new(block_length);
+        mixed_blocks.extend(&new_data, 0, 0);
+
+        let i_limit = (high_start >> SHIFT_3) as usize;
+        let mut inc = SMALL_DATA_BLOCKS_PER_BMP_BLOCK;
+        let mut fast_length = 0;
+        let mut i = ASCII_I_LIMIT;
+        while i < i_limit {
+            if i == fast_i_limit {
+                block_length = SMALL_BLOCK_LENGTH;
+                inc = 1;
+                fast_length = new_data.len();
+                mixed_blocks = MixedBlocks::new(block_length);
+                mixed_blocks.extend(&new_data, 0, 0);
+            }
+            match self.flags[i] {
+                Block::AllSame => {
+                    let value = self.index[i];
+                    // Find an earlier part of the data array of length block_length
+                    // that is filled with this value.
+                    let mut n = mixed_blocks.find_all_same_block(&new_data, value);
+                    // If we find a match, and the current block is the data null block,
+                    // and it is not a fast block but matches the start of a fast block,
+                    // then we need to continue looking.
+                    // This is because this small block is shorter than the fast block,
+                    // and not all of the rest of the fast block is filled with this value.
+                    // Otherwise trie.getRange() would detect that the fast block starts at
+                    // dataNullOffset and assume incorrectly that it is filled with the null value.
+                    while let Some(offset) = n {
+                        if Some(i) == data_null_index
+                            && i >= fast_i_limit
+                            && offset < fast_length
+                            && self.is_start_of_some_fast_block(offset as u32, fast_i_limit)
+                        {
+                            n = find_all_same_block(&new_data, offset + 1, value, block_length);
+                        } else {
+                            break;
+                        }
+                    }
+                    self.index[i] = match n {
+                        Some(offset) => offset as u32,
+                        None => {
+                            let n = get_all_same_overlap(&new_data, value, block_length);
+                            let offset = new_data.len() - n;
+                            let prev_length = new_data.len();
+                            new_data.extend(iter::repeat_n(value, block_length - n));
+                            mixed_blocks.extend(&new_data, 0, prev_length);
+                            offset as u32
+                        }
+                    };
+                }
+                Block::Mixed => {
+                    let start = self.index[i] as usize;
+                    let block = &self.data[start..start + block_length];
+                    self.index[i] = match mixed_blocks.find_block(&new_data, block) {
+                        Some(offset) => offset as u32,
+                        None => {
+                            let n = get_overlap(&new_data, block);
+                            let offset = new_data.len() - n;
+                            let prev_length = new_data.len();
+                            new_data.extend_from_slice(&block[n..]);
+                            mixed_blocks.extend(&new_data, 0, prev_length);
+                            offset as u32
+                        }
+                    };
+                }
+                Block::SameAs => {
+                    self.index[i] = self.index[self.index[i] as usize];
+                }
+            }
+            i += inc;
+        }
+        new_data
+    }
+
+    fn is_start_of_some_fast_block(&self, offset: u32, fast_i_limit: usize) -> bool {
+        self.index
+            .iter()
+            .take(fast_i_limit)
+            .step_by(SMALL_DATA_BLOCKS_PER_BMP_BLOCK)
+            .any(|&i3| i3 == offset)
+    }
+
+    /// Builds the index: the fast index, followed by the index-1, index-3 and index-2
+    /// tables if the trie has code points above the fast range.
+    ///
+    /// Returns the index with the index-3 null offset.
+    #[expect(clippy::indexing_slicing)] // block indexes are below the high start, index-1 entries are preallocated
+    fn compact_index(
+        &mut self,
+        fast_i_limit: usize,
+        high_start: u32,
+        data_null_offset: Option<u32>,
+    ) -> Result<(Vec<u32>, u32), Error> {
+        let fast_index_length = fast_i_limit / SMALL_DATA_BLOCKS_PER_BMP_BLOCK;
+        if (high_start >> FAST_TYPE_SHIFT) as usize <= fast_index_length {
+            // Only the linear fast index, no multi-stage index tables.
+            let index = (0..fast_i_limit)
+                .step_by(SMALL_DATA_BLOCKS_PER_BMP_BLOCK)
+                .map(|i| self.index[i] & 0xffff)
+                .collect();
+            return Ok((index, NO_INDEX3_NULL_OFFSET));
+        }
+
+        // Condense the fast index table.
+        // Also, does it contain an index-3 block with all data null offsets?
+        let mut fast_index = Vec::with_capacity(fast_index_length);
+        let mut index3_null_offset = None;
+        let mut i3_first_null = None;
+        for (j, i) in (0..fast_i_limit)
+            .step_by(SMALL_DATA_BLOCKS_PER_BMP_BLOCK)
+            .enumerate()
+        {
+            let mut i3 = self.index[i];
+            fast_index.push(i3 & 0xffff);
+            if Some(i3) == data_null_offset {
+                match i3_first_null {
+                    None => i3_first_null = Some(j),
+                    Some(first) => {
+                        if index3_null_offset.is_none() && j - first + 1 == INDEX_3_BLOCK {
+                            index3_null_offset = Some(first as u32);
+                        }
+                    }
+                }
+            } else {
+                i3_first_null = None;
+            }
+            // Set the index entries that compact_data() skipped.
+            // Needed when the multi-stage index covers the fast index range as well.
+            for k in i + 1..i + SMALL_DATA_BLOCKS_PER_BMP_BLOCK {
+                i3 += SMALL_DATA_BLOCK_LENGTH;
+                self.index[k] = i3;
+            }
+        }
+
+        let mut mixed_blocks = MixedBlocks::new(INDEX_3_BLOCK);
+        mixed_blocks.extend(&fast_index, 0, 0);
+
+        // Examine the index-3 blocks. For each determine one of:
+        // - same as the index-3 null block
+        // - same as a fast-index block
+        // - 16-bit indexes
+        // - 18-bit indexes
+        let mut has_null_block = index3_null_offset.is_some();
+        let mut has_long_i3_blocks = false;
+        // If the fast index covers the whole BMP, then the multi-stage index is only for
+        // supplementary code points. Otherwise, the multi-stage index covers all of Unicode.
+        let i_start = if fast_i_limit < BMP_I_LIMIT {
+            0
+        } else {
+            BMP_I_LIMIT
+        };
+        let i_limit = (high_start >> SHIFT_3) as usize;
+        let mut kinds = Vec::with_capacity((i_limit - i_start) / INDEX_3_BLOCK);
+        for i in (i_start..i_limit).step_by(INDEX_3_BLOCK) {
+            let block = &self.index[i..i + INDEX_3_BLOCK];
+            let ored = block.iter().fold(0, |ored, &i3| ored | i3);
+            let kind = if block.iter().all(|&i3| Some(i3) == data_null_offset) {
+                if !has_null_block {
+                    has_long_i3_blocks |= ored > 0xffff;
+                    has_null_block = true;
+                }
+                Index3::Null
+            } else if ored <= 0xffff {
+                match mixed_blocks.find_block(&fast_index, block) {
+                    Some(n) => {
+                        self.index[i] = n as u32;
+                        Index3::Bmp
+                    }
+                    None => Index3::Bits16,
+                }
+            } else {
+                has_long_i3_blocks = true;
+                Index3::Bits18
+            };
+            kinds.push(kind);
+        }
+
+        let index2_length = (i_limit - i_start) >> SHIFT_2_3;
+        // Length of the index-1 table, rounded up.
+        let index1_length = (index2_length + INDEX_2_MASK as usize) >> SHIFT_1_2;
+
+        // Index table: fast index, index-1, index-3, index-2.
+        let index3_start = fast_index_length + index1_length;
+        let mut index16 = fast_index;
+        index16.resize(index3_start, 0);
+
+        let mut mixed_blocks = MixedBlocks::new(INDEX_3_BLOCK);
+        let mut long_i3_blocks = MixedBlocks::new(INDEX_3_18BIT_BLOCK_LENGTH);
+
+        // Compact the index-3 table and write an uncompacted version of the index-2 table.
+        let mut index2 = Vec::with_capacity(index2_length);
+        let mut has_null_block = index3_null_offset.is_some();
+        for (i, mut kind) in (i_start..i_limit).step_by(INDEX_3_BLOCK).zip(kinds) {
+            if kind == Index3::Null && !has_null_block {
+                // First index-3 null block. Write & overlap it like a normal block, then
+                // remember it.
+                kind = if data_null_offset.is_some_and(|offset| offset > 0xffff) {
+                    Index3::Bits18
+                } else {
+                    Index3::Bits16
+                };
+                has_null_block = true;
+            }
+            let i3 = match kind {
+                Index3::Null => index3_null_offset.unwrap_or(NO_INDEX3_NULL_OFFSET),
+                Index3::Bmp => self.index[i],
+                Index3::Bits16 => {
+                    let block = &self.index[i..i + INDEX_3_BLOCK];
+                    match mixed_blocks.find_block(&index16, block) {
+                        Some(n) => n as u32,
+                        None => {
+                            let n = if index16.len() == index3_start {
+                                // No overlap at the boundary between the index-1 and index-3
+                                // tables.
+                                0
+                            } else {
+                                get_overlap(&index16, block)
+                            };
+                            let i3 = index16.len() - n;
+                            let prev_length = index16.len();
+                            index16.extend_from_slice(&block[n..]);
+                            mixed_blocks.extend(&index16, index3_start, prev_length);
+                            if has_long_i3_blocks {
+                                long_i3_blocks.extend(&index16, index3_start, prev_length);
+                            }
+                            i3 as u32
+                        }
+                    }
+                }
+                Index3::Bits18 => {
+                    // Encode an index-3 block that contains one or more data indexes
+                    // exceeding 16 bits: each group of eight entries is preceded by
+                    // their upper bits.
+                    let mut block = Vec::with_capacity(INDEX_3_18BIT_BLOCK_LENGTH);
+                    for group in self.index[i..i + INDEX_3_BLOCK].chunks(8) {
+                        let upper_bits = group.iter().enumerate().fold(0, |upper_bits, (k, &v)| {
+                            upper_bits | (v & 0x30000) >> (2 + 2 * k)
+                        });
+                        block.push(upper_bits);
+                        block.extend(group.iter().map(|&v| v & 0xffff));
+                    }
+                    let n = match long_i3_blocks.find_block(&index16, &block) {
+                        Some(n) => n,
+                        None => {
+                            let n = if index16.len() == index3_start {
+                                // No overlap at the boundary between the index-1 and index-3
+                                // tables.
+                                0
+                            } else {
+                                get_overlap(&index16, &block)
+                            };
+                            let i3 = index16.len() - n;
+                            let prev_length = index16.len();
+                            index16.extend_from_slice(&block[n..]);
+                            mixed_blocks.extend(&index16, index3_start, prev_length);
+                            long_i3_blocks.extend(&index16, index3_start, prev_length);
+                            i3
+                        }
+                    };
+                    n as u32 | 0x8000
+                }
+            };
+            if index3_null_offset.is_none() && has_null_block {
+                index3_null_offset = Some(i3);
+            }
+            // Set the index-2 table entry.
+            index2.push(i3);
+        }
+
+        let index3_null_offset = index3_null_offset.unwrap_or(NO_INDEX3_NULL_OFFSET);
+        if index16.len() >= (NO_INDEX3_NULL_OFFSET as usize + INDEX_3_BLOCK) {
+            // The index-3 offsets exceed 15 bits, or the last one cannot be distinguished
+            // from the no-null-block value.
+            return Err(Error::TooLarge);
+        }
+
+        // Compact the index-2 table and write the index-1 table.
+        let mut i1 = fast_index_length;
+        for block in index2.chunks(INDEX_2_BLOCK_LENGTH as usize) {
+            let n = if block.len() == INDEX_2_BLOCK_LENGTH as usize {
+                mixed_blocks.find_block(&index16, block)
+            } else {
+                // The high start is inside the last index-2 block. Shorten it.
+                find_same_block(&index16, index3_start, block)
+            };
+            let i2 = match n {
+                Some(n) => n,
+                None => {
+                    let n = if index16.len() == index3_start {
+                        // No overlap at the boundary between the index-1 and index-3/2
+                        // tables.
+                        0
+                    } else {
+                        get_overlap(&index16, block)
+                    };
+                    let i2 = index16.len() - n;
+                    let prev_length = index16.len();
+                    index16.extend_from_slice(&block[n..]);
+                    mixed_blocks.extend(&index16, index3_start, prev_length);
+                    i2
+                }
+            };
+            // Set the index-1 table entry.
+            index16[i1] = i2 as u32;
+            i1 += 1;
+        }
+
+        Ok((index16, index3_null_offset))
+    }
+}
+
+/// The result of [`MutableCodePointTrie::compact`], before the special values are
+/// appended to the data.
+struct Compacted {
+    index: Vec<u32>,
+    data: Vec<u32>,
+    high_start: u32,
+    high_value: u32,
+    null_value: u32,
+    index3_null_offset: u32,
+    data_null_offset: u32,
+}
+
+enum FindOrAdd {
+    Found(usize),
+    NewUnique,
+    Overflow,
+}
+
+/// The first all-same blocks for up to 32 values, with reference counts.
+#[derive(Default)]
+struct AllSameBlocks {
+    /// (block index, value, reference count)
+    blocks: Vec<(usize, u32, usize)>,
+    most_recent: Option<usize>,
+}
+
+impl AllSameBlocks {
+    const CAPACITY: usize = 32;
+
+    fn find_or_add(&mut self, index: usize, count: usize, value: u32) -> FindOrAdd {
+        if let Some(block) = self.most_recent.and_then(|k| self.blocks.get_mut(k)) {
+            if block.1 == value {
+                block.2 += count;
+                return FindOrAdd::Found(block.0);
+            }
+        }
+        if let Some((k, block)) = self
+            .blocks
+            .iter_mut()
+            .enumerate()
+            .find(|(_, &mut (_, v, _))| v == value)
+        {
+            self.most_recent = Some(k);
+            block.2 += count;
+            return FindOrAdd::Found(block.0);
+        }
+        if self.blocks.len() == Self::CAPACITY {
+            return FindOrAdd::Overflow;
+        }
+        self.most_recent = Some(self.blocks.len());
+        self.blocks.push((index, value, count));
+        FindOrAdd::NewUnique
+    }
+
+    /// Replaces the block which has the lowest reference count.
+    fn add(&mut self, index: usize, count: usize, value: u32) {
+        if let Some((least, block)) = self
+            .blocks
+            .iter_mut()
+            .enumerate()
+            .min_by_key(|(_, &mut (_, _, ref_count))| ref_count)
+        {
+            self.most_recent = Some(least);
+            *block = (index, value, count);
+        }
+    }
+
+    fn find_most_used(&self) -> Option<usize> {
+        let mut max = None;
+        let mut max_count = 0;
+        for &(index, _, ref_count) in &self.blocks {
+            if ref_count > max_count {
+                max = Some(index);
+                max_count = ref_count;
+            }
+        }
+        max
+    }
+}
+
+/// The offsets of blocks of a fixed length in an array that is being written, keyed by
+/// their hash codes. Only the first of several equal blocks is recorded.
+struct MixedBlocks {
+    block_length: usize,
+    blocks: BTreeMap<u32, Vec<usize>>,
+}
+
+impl MixedBlocks {
+    fn new(block_length: usize) -> Self {
+        Self {
+            block_length,
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the blocks that end between `prev_length` and the end of `data`, ignoring
+    /// blocks that start before `min_start`.
+    fn extend(&mut self, data: &[u32], min_start: usize, prev_length: usize) {
+        let start = match prev_length.checked_sub(self.block_length) {
+            // Skip the last block that was added last time.
+            Some(start) if start >= min_start => start + 1,
+            // Begin with the first full block.
+            _ => min_start,
+        };
+        for (start, block) in data.windows(self.block_length).enumerate().skip(start) {
+            let hash_code = Self::hash_code(block.iter().copied());
+            if self.find(data, hash_code, |p| p == block).is_none() {
+                self.blocks.entry(hash_code).or_default().push(start);
+            }
+        }
+    }
+
+    fn find_block(&self, data: &[u32], block: &[u32]) -> Option<usize> {
+        let hash_code = Self::hash_code(block.iter().copied());
+        self.find(data, hash_code, |p| p == block)
+    }
+
+    fn find_all_same_block(&self, data: &[u32], value: u32) -> Option<usize> {
+        let hash_code = Self::hash_code(iter::repeat_n(value, self.block_length));
+        self.find(data, hash_code, |p| p.iter().all(|&v| v == value))
+    }
+
+    fn find(&self, data: &[u32], hash_code: u32, eq: impl Fn(&[u32]) -> bool) -> Option<usize> {
+        self.blocks
+            .get(&hash_code)?
+            .iter()
+            .copied()
+            .find(|&start| data.get(start..start + self.block_length).is_some_and(&eq))
+    }
+
+    fn hash_code(mut block: impl Iterator<Item = u32>) -> u32 {
+        let first = block.next().unwrap_or_default();
+        block.fold(first, |hash_code, v| {
+            hash_code.wrapping_mul(37).wrapping_add(v)
+        })
+    }
+}
+
+/// Finds a block of `block_length` copies of `value` in `data`, starting at `start`.
+fn find_all_same_block(
+    data: &[u32],
+    start: usize,
+    value: u32,
+    block_length: usize,
+) -> Option<usize> {
+    // Ensure that we do not even partially get past the end.
+    let limit = data.len().checked_sub(block_length)?;
+    let mut block = start;
+    while block <= limit {
+        match data
+            .get(block..block + block_length)?
+            .iter()
+            .position(|&v| v != value)
+        {
+            None => return Some(block),
+            // Continue after the differing value.
+            Some(k) => block += k + 1,
+        }
+    }
+    None
+}
+
+/// Finds `block` in `data`, starting at `start`.
+fn find_same_block(data: &[u32], start: usize, block: &[u32]) -> Option<usize> {
+    // Ensure that we do not even partially get past the end.
+    let limit = data.len().checked_sub(block.len())?;
+    (start..=limit).find(|&p| data.get(p..p + block.len()) == Some(block))
+}
+
+/// Returns the maximum overlap of the beginning of `block` with the end of `data`.
+fn get_overlap(data: &[u32], block: &[u32]) -> usize {
+    let mut overlap = (block.len() - 1).min(data.len());
+    while overlap > 0
+        && !block
+            .get(..overlap)
+            .is_some_and(|prefix| data.ends_with(prefix))
+    {
+        overlap -= 1;
+    }
+    overlap
+}
+
+/// Returns the number of trailing copies of `value` in `data`, up to `block_length - 1`.
+fn get_all_same_overlap(data: &[u32], value: u32, block_length: usize) -> usize {
+    data.iter()
+        .rev()
+        .take(block_length - 1)
+        .take_while(|&&v| v == value)
+        .count()
+}
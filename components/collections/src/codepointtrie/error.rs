@@ -25,6 +25,9 @@ pub enum Error {
     /// [`CodePointTrie`](super::CodePointTrie) must be constructed from data vector long enough to accommodate fast-path access
     #[displaydoc("CodePointTrie must be constructed from data vector long enough to accommodate fast-path access")]
     DataTooShortForFastAccess,
+    /// The values of a [`MutableCodePointTrie`](super::MutableCodePointTrie) need more index or data entries than a [`CodePointTrie`](super::CodePointTrie) can address
+    #[displaydoc("CodePointTrie values need more index or data entries than can be addressed")]
+    TooLarge,
 }
 
 impl core::error::Error for Error {}
@@ -31,6 +31,8 @@
 
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod builder;
 mod cptrie;
 mod error;
 mod impl_const;
@@ -42,6 +44,8 @@ pub mod toml;
 #[cfg(feature = "serde")]
 mod serde;
 
+#[cfg(feature = "alloc")]
+pub use builder::MutableCodePointTrie;
 pub use cptrie::CodePointMapRange;
 pub use cptrie::CodePointMapRangeIterator;
 pub use cptrie::CodePointTrie;
@@ -60,6 +60,11 @@ where
                     super::CodePointTrieError::DataTooShortForFastAccess => {
                         return Err(D::Error::custom("CodePointTrie must be constructed from data vector long enough to accommodate fast-path access"));
                     }
+                    super::CodePointTrieError::TooLarge => {
+                        // Not supposed to be returned by `validate_fields`.
+                        debug_assert!(false);
+                        return Err(D::Error::custom("CodePointTrie values need more index or data entries than can be addressed"));
+                    }
                 }
             }
         };
@@ -293,6 +293,59 @@ fn months() {
     assert_eq!(res, TrieResult::NoMatch);
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn iter_entries() {
+    for data in [
+        include_str!("data/char16trie/empty.toml"),
+        include_str!("data/char16trie/months.toml"),
+        include_str!("data/char16trie/test_a.toml"),
+        include_str!("data/char16trie/test_a_ab.toml"),
+        include_str!("data/char16trie/test_branches.toml"),
+        include_str!("data/char16trie/test_compact.toml"),
+        include_str!("data/char16trie/test_long_branch.toml"),
+        include_str!("data/char16trie/test_long_sequence.toml"),
+        include_str!("data/char16trie/test_shortest_branch.toml"),
+    ] {
+        let trie_data = toml::from_str::<TestFile>(data).unwrap().ucharstrie.data;
+        let trie = Char16Trie::new(ZeroVec::from_slice_or_alloc(trie_data.as_slice()));
+
+        let entries: Vec<(Vec<u16>, i32)> = trie.iter_entries().collect();
+        assert!(entries.windows(2).all(|w| w[0].0 < w[1].0));
+        for (units, value) in entries {
+            let mut iter = trie.iter();
+            // ICU4C tries can have a value for the empty string, which cannot be looked up.
+            let Some((last, init)) = units.split_last() else {
+                continue;
+            };
+            for &unit in init {
+                assert!(matches!(
+                    iter.next16(unit),
+                    TrieResult::NoValue | TrieResult::Intermediate(_)
+                ));
+            }
+            assert!(matches!(
+                iter.next16(*last),
+                TrieResult::Intermediate(v) | TrieResult::FinalValue(v) if v == value
+            ));
+        }
+    }
+
+    let trie_data = toml::from_str::<TestFile>(include_str!("data/char16trie/months.toml"))
+        .unwrap()
+        .ucharstrie
+        .data;
+    let trie = Char16Trie::new(ZeroVec::from_slice_or_alloc(trie_data.as_slice()));
+    let entries: Vec<(String, i32)> = trie
+        .iter_entries()
+        .map(|(units, value)| (String::from_utf16(&units).unwrap(), value))
+        .collect();
+    assert!(entries.contains(&("jun".into(), 6)));
+    assert!(entries.contains(&("june".into(), 6)));
+    assert!(entries.contains(&("july".into(), 7)));
+    assert!(entries.contains(&("august".into(), 8)));
+}
+
 #[derive(serde::Deserialize)]
 pub struct TestFile {
     ucharstrie: Char16TrieVec,
@@ -55,6 +55,55 @@ fn planes_trie_deserialize_check_test() {
 
     check_trie(&act_planes_trie, &check_ranges);
     check_trie(&exp_planes_trie, &check_ranges);
+    check_builder(&act_planes_trie, TrieType::Small, &check_ranges);
+}
+
+#[test]
+fn builder_property_data_test() {
+    use icu_properties::props::*;
+    use icu_properties::{props::EnumeratedProperty, CodePointMapData};
+
+    fn check<T: TrieValue + EnumeratedProperty + core::fmt::Debug>() {
+        let data = CodePointMapData::<T>::new().static_to_owned();
+        let trie = data.as_code_point_trie().unwrap();
+        let mut builder = MutableCodePointTrie::new(trie.get32(0x10ffff), trie.get32(0x110000));
+        for range in trie.iter_ranges() {
+            builder.set_range(range.range, range.value);
+        }
+        assert_eq!(&builder.build(trie_type(trie)).unwrap(), trie);
+    }
+
+    fn trie_type<T: TrieValue>(trie: &CodePointTrie<T>) -> TrieType {
+        match trie.as_typed_ref() {
+            Typed::Fast(_) => TrieType::Fast,
+            Typed::Small(_) => TrieType::Small,
+        }
+    }
+
+    check::<BidiClass>();
+    check::<CanonicalCombiningClass>();
+    check::<EastAsianWidth>();
+    check::<GeneralCategory>();
+    check::<GraphemeClusterBreak>();
+    check::<HangulSyllableType>();
+    check::<IndicSyllabicCategory>();
+    check::<JoiningType>();
+    check::<LineBreak>();
+    check::<SentenceBreak>();
+    check::<VerticalOrientation>();
+    check::<WordBreak>();
+
+    // ICU4C builds the Script trie with 8-bit values, which are widened to `Script`.
+    let data = CodePointMapData::<Script>::new().static_to_owned();
+    let trie = data.as_code_point_trie().unwrap();
+    let builder = MutableCodePointTrie::from_code_point_trie(trie)
+        .map_values(|script| u8::try_from(script.to_icu4c_value()).unwrap());
+    let widened = builder
+        .build(trie_type(trie))
+        .unwrap()
+        .try_alloc_map_value(|value| Ok::<_, ()>(Script::from_icu4c_value(value.into())))
+        .unwrap();
+    assert_eq!(&widened, trie);
 }
 
 #[test]
@@ -375,6 +424,104 @@ pub struct EnumPropSerializedCPTStruct {
     pub null_value: u32,
 }
 
+/// Builds a trie with the values of the check ranges using [`MutableCodePointTrie`], and
+/// checks that it is identical to the given trie built by ICU4C.
+pub fn check_builder<T: TrieValue + core::fmt::Debug>(
+    trie: &CodePointTrie<T>,
+    trie_type: TrieType,
+    check_ranges: &[u32],
+) {
+    // Like `umutablecptrie_fromUCPTrie`, use the high value as the initial value.
+    let mut builder = MutableCodePointTrie::new(trie.get32(0x10ffff), trie.get32(0x110000));
+    let mut start = 0;
+    for range_tuple in check_ranges.chunks(2) {
+        let range_limit = range_tuple[0];
+        if range_limit == 0 {
+            continue;
+        }
+        let range_value = T::try_from_u32(range_tuple[1]).ok().unwrap();
+        builder.set_range(start..=range_limit - 1, range_value);
+        start = range_limit;
+    }
+    assert_eq!(&builder.build(trie_type).unwrap(), trie);
+    assert!(builder.iter_ranges().eq(trie.iter_ranges()));
+
+    let builder = MutableCodePointTrie::from_code_point_trie(trie);
+    assert_eq!(&builder.build(trie_type).unwrap(), trie);
+}
+
+#[test]
+fn mutable_trie_map_test() {
+    // Compare against a plain array of values.
+    let mut expected = vec![0u16; 0x110000];
+    let mut builder = MutableCodePointTrie::new(0u16, 0xffff);
+    let mut set_range =
+        |builder: &mut MutableCodePointTrie<u16>, range: core::ops::RangeInclusive<u32>, value| {
+            expected[*range.start() as usize..=*range.end() as usize].fill(value);
+            builder.set_range(range, value);
+        };
+    set_range(&mut builder, 0x20..=0x7e, 1);
+    set_range(&mut builder, 0x41..=0x5a, 2);
+    set_range(&mut builder, 0x3000..=0x3fff, 3);
+    set_range(&mut builder, 0x10000..=0x1ffff, 4);
+    set_range(&mut builder, 0x10fffe..=0x10ffff, 5);
+
+    let others = [
+        CodePointMapRange {
+            range: 0x30..=0x4f,
+            value: 0x10,
+        },
+        CodePointMapRange {
+            range: 0x2ff0..=0x1000f,
+            value: 0x20,
+        },
+        CodePointMapRange {
+            range: 0x10fff0..=0x110005,
+            value: 0x30,
+        },
+    ];
+    builder.merge(others.clone(), |current, other| current + other);
+    for other in others {
+        for cp in other.range.clone().filter(|&cp| cp <= 0x10ffff) {
+            expected[cp as usize] += other.value;
+        }
+    }
+
+    let builder = builder.map_values(|value| value as u32 * 3);
+    let expected = expected
+        .into_iter()
+        .map(|value| value as u32 * 3)
+        .collect::<Vec<_>>();
+
+    for (cp, &value) in (0..).zip(&expected) {
+        assert_eq!(builder.get(cp), value, "{cp:#x}");
+    }
+    assert_eq!(builder.get(0x110000), 0xffff * 3);
+
+    let mut ranges = builder.iter_ranges().peekable();
+    assert_eq!(ranges.peek().map(|r| *r.range.start()), Some(0));
+    let mut last_end = None;
+    for range in ranges {
+        if let Some(last_end) = last_end {
+            assert_eq!(*range.range.start(), last_end + 1);
+            assert_ne!(builder.get(last_end), range.value);
+        }
+        for cp in range.range.clone() {
+            assert_eq!(expected[cp as usize], range.value, "{cp:#x}");
+        }
+        last_end = Some(*range.range.end());
+    }
+    assert_eq!(last_end, Some(0x10ffff));
+
+    for trie_type in [TrieType::Fast, TrieType::Small] {
+        let trie = builder.build(trie_type).unwrap();
+        for (cp, &value) in (0..).zip(&expected) {
+            assert_eq!(trie.get32(cp), value, "{cp:#x}");
+        }
+        assert!(trie.iter_ranges().eq(builder.iter_ranges()));
+    }
+}
+
 // Given a .toml file dumped from ICU4C test data for UCPTrie, run the test
 // data file deserialization into the test file struct, convert and construct
 // the `CodePointTrie`, and test the constructed struct against the test file's
@@ -446,8 +593,11 @@ pub fn run_deserialize_test_from_test_data(test_file: &str) {
                 test_struct.value_width_enum_val,
                 ValueWidthEnum::Bits8 as u8
             );
-            run_trie_tests(
-                &trie_result.unwrap(),
+            let trie = trie_result.unwrap();
+            run_trie_tests(&trie, &test_file.code_point_trie.test_data.check_ranges);
+            check_builder(
+                &trie,
+                trie_type_enum,
                 &test_file.code_point_trie.test_data.check_ranges,
             );
         }
@@ -460,8 +610,11 @@ pub fn run_deserialize_test_from_test_data(test_file: &str) {
                 test_struct.value_width_enum_val,
                 ValueWidthEnum::Bits16 as u8
             );
-            run_trie_tests(
-                &trie_result.unwrap(),
+            let trie = trie_result.unwrap();
+            run_trie_tests(&trie, &test_file.code_point_trie.test_data.check_ranges);
+            check_builder(
+                &trie,
+                trie_type_enum,
                 &test_file.code_point_trie.test_data.check_ranges,
             );
         }
@@ -474,8 +627,11 @@ pub fn run_deserialize_test_from_test_data(test_file: &str) {
                 test_struct.value_width_enum_val,
                 ValueWidthEnum::Bits32 as u8
             );
-            run_trie_tests(
-                &trie_result.unwrap(),
+            let trie = trie_result.unwrap();
+            run_trie_tests(&trie, &test_file.code_point_trie.test_data.check_ranges);
+            check_builder(
+                &trie,
+                trie_type_enum,
                 &test_file.code_point_trie.test_data.check_ranges,
             );
         }
@@ -0,0 +1,506 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use icu_collator::options::{CollatorOptions, Strength};
+use icu_collator::preferences::CollationType;
+use icu_collator::provider::{
+    CollationDiacriticsV1, CollationJamoV1, CollationMetadataV1, CollationReorderingV1,
+    CollationRootV1, CollationSpecialPrimariesV1, CollationTailoringV1,
+};
+use icu_collator::{Collator, CollatorBorrowed, CollatorPreferences};
+use icu_collections::codepointinvliststringlist::CodePointInversionListAndStringList;
+use icu_locale::exemplar_chars::ExemplarCharacters;
+use icu_locale::provider::LocaleExemplarCharactersIndexV1;
+use icu_locale_core::preferences::{define_preferences, prefs_convert};
+use icu_normalizer::provider::{NormalizerNfdDataV1, NormalizerNfdTablesV1};
+use icu_properties::props::Script;
+use icu_properties::provider::PropertyEnumScriptV1;
+use icu_properties::{CodePointMapData, CodePointMapDataBorrowed};
+use icu_provider::prelude::*;
+
+define_preferences!(
+    /// The preferences for alphabetic indexes.
+    [Copy]
+    AlphabeticIndexPreferences,
+    {
+        /// The collation type. This corresponds to the `-u-co` BCP-47 tag.
+        collation_type: CollationType
+    }
+);
+prefs_convert!(AlphabeticIndexPreferences, CollatorPreferences, {
+    collation_type
+});
+
+/// The label of the underflow, inflow and overflow buckets, as in CLDR.
+const ELLIPSIS: &str = "…";
+
+/// A noncharacter that CLDR tailorings put in contractions with index characters,
+/// where the index characters do not sort at the start of their buckets
+/// (for example, the pinyin buckets of Chinese).
+const BUCKET_BOUNDARY_PREFIX: char = '\u{FDD0}';
+
+/// Options for [`AlphabeticIndex`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AlphabeticIndexOptions {
+    /// The maximum number of labels. If the locales have more index characters,
+    /// evenly spaced ones are dropped.
+    ///
+    /// Defaults to 99.
+    pub max_label_count: Option<usize>,
+}
+
+/// The kind of a [`Bucket`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketKind {
+    /// The bucket of strings that sort before the first label, such as numbers and symbols.
+    Underflow,
+    /// A bucket labelled with an index character.
+    Normal,
+    /// The bucket between the labels of two scripts, for strings of scripts that sort
+    /// in between.
+    Inflow,
+    /// The bucket of strings that sort after the labels and are not of the
+    /// script of the last label.
+    Overflow,
+}
+
+/// A section of an [`AlphabeticIndex`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    /// The label of the bucket: an index character for [`BucketKind::Normal`] buckets,
+    /// and "…" otherwise.
+    pub label: String,
+    /// The kind of the bucket.
+    pub kind: BucketKind,
+}
+
+/// The lower bound of a normal bucket.
+#[derive(Debug, Clone)]
+struct Boundary {
+    /// Strings sorting at or after this belong to the bucket.
+    lower: String,
+    /// The index of the bucket.
+    bucket: usize,
+    /// The script of the label, with Katakana unified with Hiragana.
+    script: Option<Script>,
+}
+
+/// Sorts strings into labelled buckets for display as the sections of a list,
+/// following the [ICU AlphabeticIndex](https://unicode-org.github.io/icu/userguide/collation/customization/indexes.html).
+///
+/// The labels are the index exemplar characters of one or more locales, in the order of
+/// the collator of the first locale. Strings are assigned to the last bucket whose label
+/// sorts before or equal to them at primary strength, so that for example "Ärger" is under
+/// "A" in German but under "Ä" in Swedish.
+///
+/// Besides the labelled buckets, the index has an underflow bucket for strings that sort
+/// before the first label, an overflow bucket for strings of other scripts that sort after
+/// the last label, and an inflow bucket between the labels of two scripts for strings of
+/// other scripts that sort in between.
+///
+/// # Examples
+///
+/// ```
+/// use icu::experimental::alphabetic_index::{
+///     AlphabeticIndex, AlphabeticIndexOptions, BucketKind,
+/// };
+/// use icu::locale::locale;
+///
+/// let index = AlphabeticIndex::try_new(
+///     locale!("sv").into(),
+///     AlphabeticIndexOptions::default(),
+/// )
+/// .unwrap();
+///
+/// let label = |s| index.bucket(s).label.as_str();
+/// assert_eq!(label("Andersson"), "A");
+/// assert_eq!(label("Ängel"), "Ä");
+/// assert_eq!(label("Östlund"), "Ö");
+/// assert_eq!(label("123"), "…");
+/// assert_eq!(index.bucket("Σωκράτης").kind, BucketKind::Overflow);
+/// ```
+///
+/// Indexes for multiple scripts:
+///
+/// ```
+/// use icu::experimental::alphabetic_index::{
+///     AlphabeticIndex, AlphabeticIndexOptions, BucketKind,
+/// };
+/// use icu::locale::locale;
+///
+/// let index = AlphabeticIndex::try_new_with_additional_locales(
+///     locale!("en").into(),
+///     &[locale!("ru").into()],
+///     AlphabeticIndexOptions::default(),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(index.bucket("Zoe").label, "Z");
+/// assert_eq!(index.bucket("Жуков").label, "Ж");
+/// assert_eq!(index.bucket("Σωκράτης").kind, BucketKind::Inflow);
+/// ```
+#[derive(Debug)]
+pub struct AlphabeticIndex {
+    collator: Collator,
+    scripts: CodePointMapData<Script>,
+    buckets: Vec<Bucket>,
+    /// The boundaries of the normal buckets, in collation order.
+    boundaries: Vec<Boundary>,
+}
+
+impl AlphabeticIndex {
+    /// Creates an alphabetic index for a locale from compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub fn try_new(
+        prefs: AlphabeticIndexPreferences,
+        options: AlphabeticIndexOptions,
+    ) -> Result<Self, DataError> {
+        Self::try_new_with_additional_locales(prefs, &[], options)
+    }
+
+    /// Creates an alphabetic index for a locale from compiled data, with the index
+    /// characters of additional locales, for lists in multiple scripts.
+    ///
+    /// The collation order comes from `prefs`.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub fn try_new_with_additional_locales(
+        prefs: AlphabeticIndexPreferences,
+        additional_locales: &[AlphabeticIndexPreferences],
+        options: AlphabeticIndexOptions,
+    ) -> Result<Self, DataError> {
+        let collator = Collator::try_new((&prefs).into(), Self::collator_options())?;
+        let exemplars = core::iter::once(&prefs)
+            .chain(additional_locales)
+            .map(|prefs| {
+                ExemplarCharacters::try_new_index(&LocaleExemplarCharactersIndexV1::make_locale(
+                    prefs.locale_preferences,
+                ))
+                .map(|exemplars| exemplars.static_to_owned())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new_internal(
+            collator.static_to_owned(),
+            CodePointMapData::<Script>::new().static_to_owned(),
+            &exemplars,
+            options,
+        ))
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc = icu_provider::gen_buffer_unstable_docs!(BUFFER, Self::try_new)]
+    pub fn try_new_with_buffer_provider(
+        provider: &(impl BufferProvider + ?Sized),
+        prefs: AlphabeticIndexPreferences,
+        options: AlphabeticIndexOptions,
+    ) -> Result<Self, DataError> {
+        Self::try_new_unstable(&provider.as_deserializing(), prefs, options)
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc = icu_provider::gen_buffer_unstable_docs!(BUFFER, Self::try_new_with_additional_locales)]
+    pub fn try_new_with_additional_locales_with_buffer_provider(
+        provider: &(impl BufferProvider + ?Sized),
+        prefs: AlphabeticIndexPreferences,
+        additional_locales: &[AlphabeticIndexPreferences],
+        options: AlphabeticIndexOptions,
+    ) -> Result<Self, DataError> {
+        Self::try_new_with_additional_locales_unstable(
+            &provider.as_deserializing(),
+            prefs,
+            additional_locales,
+            options,
+        )
+    }
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::try_new)]
+    pub fn try_new_unstable<D>(
+        provider: &D,
+        prefs: AlphabeticIndexPreferences,
+        options: AlphabeticIndexOptions,
+    ) -> Result<Self, DataError>
+    where
+        D: DataProvider<LocaleExemplarCharactersIndexV1>
+            + DataProvider<PropertyEnumScriptV1>
+            + DataProvider<CollationSpecialPrimariesV1>
+            + DataProvider<CollationRootV1>
+            + DataProvider<CollationTailoringV1>
+            + DataProvider<CollationDiacriticsV1>
+            + DataProvider<CollationJamoV1>
+            + DataProvider<CollationMetadataV1>
+            + DataProvider<CollationReorderingV1>
+            + DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + ?Sized,
+    {
+        Self::try_new_with_additional_locales_unstable(provider, prefs, &[], options)
+    }
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::try_new_with_additional_locales)]
+    pub fn try_new_with_additional_locales_unstable<D>(
+        provider: &D,
+        prefs: AlphabeticIndexPreferences,
+        additional_locales: &[AlphabeticIndexPreferences],
+        options: AlphabeticIndexOptions,
+    ) -> Result<Self, DataError>
+    where
+        D: DataProvider<LocaleExemplarCharactersIndexV1>
+            + DataProvider<PropertyEnumScriptV1>
+            + DataProvider<CollationSpecialPrimariesV1>
+            + DataProvider<CollationRootV1>
+            + DataProvider<CollationTailoringV1>
+            + DataProvider<CollationDiacriticsV1>
+            + DataProvider<CollationJamoV1>
+            + DataProvider<CollationMetadataV1>
+            + DataProvider<CollationReorderingV1>
+            + DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + ?Sized,
+    {
+        let collator =
+            Collator::try_new_unstable(provider, (&prefs).into(), Self::collator_options())?;
+        let exemplars = core::iter::once(&prefs)
+            .chain(additional_locales)
+            .map(|prefs| {
+                ExemplarCharacters::try_new_index_unstable(
+                    provider,
+                    &LocaleExemplarCharactersIndexV1::make_locale(prefs.locale_preferences),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new_internal(
+            collator,
+            CodePointMapData::<Script>::try_new_unstable(provider)?,
+            &exemplars,
+            options,
+        ))
+    }
+
+    fn collator_options() -> CollatorOptions {
+        let mut options = CollatorOptions::default();
+        options.strength = Some(Strength::Primary);
+        options
+    }
+
+    fn new_internal(
+        collator: Collator,
+        scripts: CodePointMapData<Script>,
+        exemplars: &[ExemplarCharacters],
+        options: AlphabeticIndexOptions,
+    ) -> Self {
+        let borrowed = collator.as_borrowed();
+        let script_map = scripts.as_borrowed();
+
+        let mut labels: Vec<(String, String)> = exemplars
+            .iter()
+            .flat_map(|exemplars| labels(&exemplars.as_borrowed()))
+            .map(|label| (lower_bound(borrowed, &label), label))
+            .collect();
+        labels.sort_by(|(a, _), (b, _)| borrowed.compare(a, b));
+        labels.dedup_by(|(a, _), (b, _)| borrowed.compare(a, b) == Ordering::Equal);
+
+        let max_label_count = options.max_label_count.unwrap_or(99).max(1);
+        if labels.len() > max_label_count {
+            // Keep evenly spaced labels, starting with the first one.
+            let count = labels.len();
+            labels = (0..max_label_count)
+                .filter_map(|k| labels.get(k * count / max_label_count).cloned())
+                .collect();
+        }
+
+        let mut buckets = Vec::with_capacity(labels.len() + 2);
+        let mut boundaries: Vec<Boundary> = Vec::with_capacity(labels.len());
+        buckets.push(Bucket {
+            label: ELLIPSIS.into(),
+            kind: BucketKind::Underflow,
+        });
+        for (lower, label) in labels {
+            let script = if lower.starts_with(BUCKET_BOUNDARY_PREFIX) {
+                Some(Script::Han)
+            } else {
+                first_script(script_map, &label)
+            };
+            if boundaries.last().is_some_and(|b| b.script != script) {
+                buckets.push(Bucket {
+                    label: ELLIPSIS.into(),
+                    kind: BucketKind::Inflow,
+                });
+            }
+            boundaries.push(Boundary {
+                lower,
+                bucket: buckets.len(),
+                script,
+            });
+            buckets.push(Bucket {
+                label,
+                kind: BucketKind::Normal,
+            });
+        }
+        buckets.push(Bucket {
+            label: ELLIPSIS.into(),
+            kind: BucketKind::Overflow,
+        });
+
+        Self {
+            collator,
+            scripts,
+            buckets,
+            boundaries,
+        }
+    }
+
+    /// Returns the buckets of the index, in order.
+    ///
+    /// The first bucket is the underflow bucket and the last one the overflow bucket.
+    pub fn buckets(&self) -> &[Bucket] {
+        &self.buckets
+    }
+
+    /// Returns the index into [`Self::buckets`] of the bucket of `s`.
+    pub fn bucket_index(&self, s: &str) -> usize {
+        let collator = self.collator.as_borrowed();
+        let i = self
+            .boundaries
+            .partition_point(|b| collator.compare(&b.lower, s) != Ordering::Greater);
+        let Some(boundary) = i.checked_sub(1).and_then(|i| self.boundaries.get(i)) else {
+            return 0;
+        };
+        let script = first_script(self.scripts.as_borrowed(), s);
+        let next = self.boundaries.get(i);
+        if script.is_none()
+            || script == boundary.script
+            || next.is_some_and(|next| next.script == boundary.script)
+        {
+            boundary.bucket
+        } else if next.is_some() {
+            // After the last label of the script: the inflow bucket before the next script.
+            boundary.bucket + 1
+        } else {
+            self.buckets.len() - 1
+        }
+    }
+
+    /// Returns the bucket of `s`.
+    pub fn bucket(&self, s: &str) -> &Bucket {
+        &self.buckets[self.bucket_index(s)]
+    }
+}
+
+/// The index characters of an exemplar set.
+fn labels(exemplars: &CodePointInversionListAndStringList) -> Vec<String> {
+    exemplars
+        .code_points()
+        .iter_chars()
+        .map(String::from)
+        .chain(exemplars.strings().iter().map(String::from))
+        .collect()
+}
+
+/// The string that starts the bucket of `label`.
+fn lower_bound(collator: CollatorBorrowed, label: &str) -> String {
+    let mut boundary = String::from(BUCKET_BOUNDARY_PREFIX);
+    boundary.push_str(label);
+    if collator.compare(&boundary, label) == Ordering::Less {
+        boundary
+    } else {
+        label.into()
+    }
+}
+
+/// The script of the first character of `s` that has one, with Katakana unified
+/// with Hiragana as they are in collation.
+fn first_script(scripts: CodePointMapDataBorrowed<Script>, s: &str) -> Option<Script> {
+    s.chars()
+        .map(|c| scripts.get(c))
+        .find(|&script| !matches!(script, Script::Common | Script::Inherited | Script::Unknown))
+        .map(|script| {
+            if script == Script::Katakana {
+                Script::Hiragana
+            } else {
+                script
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icu_locale_core::locale;
+
+    fn labels(index: &AlphabeticIndex, strings: &[&str]) -> Vec<String> {
+        strings
+            .iter()
+            .map(|s| index.bucket(s).label.clone())
+            .collect()
+    }
+
+    #[test]
+    fn japanese_kana_rows() {
+        let index =
+            AlphabeticIndex::try_new(locale!("ja").into(), AlphabeticIndexOptions::default())
+                .unwrap();
+        assert_eq!(
+            labels(
+                &index,
+                &["あおい", "カメラ", "ごま", "すし", "ワイン", "ん"]
+            ),
+            ["あ", "か", "か", "さ", "わ", "わ"]
+        );
+    }
+
+    #[test]
+    fn chinese_pinyin() {
+        let index =
+            AlphabeticIndex::try_new(locale!("zh").into(), AlphabeticIndexOptions::default())
+                .unwrap();
+        assert_eq!(
+            labels(&index, &["八", "北京", "中国", "爱"]),
+            ["B", "B", "Z", "A"]
+        );
+    }
+
+    #[test]
+    fn buckets() {
+        let index =
+            AlphabeticIndex::try_new(locale!("en").into(), AlphabeticIndexOptions::default())
+                .unwrap();
+        let buckets = index.buckets();
+        assert_eq!(buckets.len(), 28);
+        assert_eq!(buckets.first().unwrap().kind, BucketKind::Underflow);
+        assert_eq!(buckets.last().unwrap().kind, BucketKind::Overflow);
+        assert_eq!(index.bucket_index("42"), 0);
+        assert_eq!(index.bucket_index("apple"), 1);
+        assert_eq!(index.bucket_index("Zürich"), 26);
+        assert_eq!(index.bucket_index("東京"), 27);
+    }
+
+    #[test]
+    fn max_label_count() {
+        let options = AlphabeticIndexOptions {
+            max_label_count: Some(5),
+        };
+        let index = AlphabeticIndex::try_new(locale!("en").into(), options).unwrap();
+        let labels = index
+            .buckets()
+            .iter()
+            .filter(|b| b.kind == BucketKind::Normal)
+            .map(|b| b.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, ["A", "F", "K", "P", "U"]);
+        assert_eq!(index.bucket("Hugo").label, "F");
+    }
+}
@@ -0,0 +1,20 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Alphabetic indexes: the labelled sections ("A", "B", …) of a sorted list of strings,
+//! such as the section headers of a contact list.
+//!
+//! The labels come from the index exemplar characters of a locale, and strings are
+//! assigned to sections by a [`Collator`](icu_collator::Collator) of that locale. See
+//! [`AlphabeticIndex`].
+//!
+//! ✨ *Enabled with the `alphabetic_index` Cargo feature.*
+
+#![warn(missing_docs)]
+
+mod index;
+
+pub use index::{
+    AlphabeticIndex, AlphabeticIndexOptions, AlphabeticIndexPreferences, Bucket, BucketKind,
+};
@@ -0,0 +1,17 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Validation and scanning of identifiers, and tokenization of pattern syntax, following
+//! [UAX #31: Unicode Identifiers and Syntax](https://www.unicode.org/reports/tr31/).
+//!
+//! See [`IdentifierSyntax`].
+
+#![warn(missing_docs)]
+
+mod syntax;
+
+pub use syntax::{
+    IdentifierOptions, IdentifierProfile, IdentifierSyntax, PatternToken, PatternTokenKind,
+    PatternTokens,
+};
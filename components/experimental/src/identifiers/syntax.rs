@@ -0,0 +1,660 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use alloc::borrow::Cow;
+use icu_collections::codepointinvlist::CodePointInversionList;
+use icu_normalizer::provider::{
+    NormalizerNfcV1, NormalizerNfdTablesV1, NormalizerNfkdDataV1, NormalizerNfkdTablesV1,
+};
+use icu_normalizer::ComposingNormalizer;
+use icu_properties::props::{
+    CanonicalCombiningClass, GeneralCategory, GeneralCategoryGroup, JoiningType,
+    NoncharacterCodePoint, PatternSyntax, PatternWhiteSpace, XidContinue, XidStart,
+};
+use icu_properties::provider::{
+    PropertyBinaryNoncharacterCodePointV1, PropertyBinaryPatternSyntaxV1,
+    PropertyBinaryPatternWhiteSpaceV1, PropertyBinaryXidContinueV1, PropertyBinaryXidStartV1,
+    PropertyEnumCanonicalCombiningClassV1, PropertyEnumGeneralCategoryV1,
+    PropertyEnumJoiningTypeV1,
+};
+use icu_properties::{CodePointMapData, CodePointSetData};
+use icu_provider::prelude::*;
+
+const ZWNJ: char = '\u{200C}';
+const ZWJ: char = '\u{200D}';
+
+/// The sets of characters that identifiers are made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum IdentifierProfile {
+    /// Default identifiers, which start with an `XID_Start` character, followed by
+    /// `XID_Continue` characters.
+    ///
+    /// These properties are closed under NFKC, so the NFKC form of a default identifier is
+    /// itself a default identifier, unless characters are added with [`IdentifierOptions`].
+    #[default]
+    Default,
+    /// Immutable identifiers, which consist of any characters except `Pattern_Syntax`,
+    /// `Pattern_White_Space`, controls, surrogates, private use characters and noncharacters.
+    ///
+    /// Unassigned code points are allowed, so that identifiers remain valid when characters are
+    /// added to Unicode.
+    Immutable,
+}
+
+/// Options for [`IdentifierSyntax`].
+///
+/// The additional characters allow profiles that extend the identifier syntax of UAX #31,
+/// such as allowing `_` at the start of identifiers, or `-` between identifier characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IdentifierOptions {
+    /// The sets of characters that identifiers are made of.
+    pub profile: IdentifierProfile,
+    /// Characters that are allowed anywhere in identifiers, in addition to the start characters
+    /// of the profile.
+    pub additional_start: CodePointInversionList<'static>,
+    /// Characters that are allowed after the first character of identifiers, in addition to the
+    /// continue characters of the profile.
+    pub additional_continue: CodePointInversionList<'static>,
+    /// Medial characters, which are allowed between two characters of an identifier, but not at
+    /// its start or end, nor next to each other.
+    pub medial: CodePointInversionList<'static>,
+    /// Whether U+200C ZERO WIDTH NON-JOINER and U+200D ZERO WIDTH JOINER are only allowed in
+    /// the contexts of UAX #31, instead of wherever continue characters are allowed.
+    ///
+    /// ZWNJ is allowed where it breaks a cursive connection, `$LJ $T* ZWNJ $T* $RJ` (A1),
+    /// and where it follows a virama, `$L $M* $V $M₁* ZWNJ` (A2). ZWJ is allowed where it
+    /// follows a virama, `$L $M* $V $M₁* ZWJ` (B).
+    pub restrict_joiners: bool,
+}
+
+impl Default for IdentifierOptions {
+    fn default() -> Self {
+        Self {
+            profile: Default::default(),
+            additional_start: core::iter::empty().collect(),
+            additional_continue: core::iter::empty().collect(),
+            medial: core::iter::empty().collect(),
+            restrict_joiners: false,
+        }
+    }
+}
+
+/// The syntax of identifiers and patterns, following
+/// [UAX #31: Unicode Identifiers and Syntax](https://www.unicode.org/reports/tr31/).
+///
+/// An identifier consists of a start character, followed by continue characters, where
+/// medial characters may appear between two continue characters:
+///
+/// ```text
+/// <Identifier> := <Start> <Continue>* (<Medial> <Continue>+)*
+/// ```
+///
+/// The characters are determined by the [`IdentifierOptions`]. Identifiers can be
+/// validated with [`IdentifierSyntax::is_identifier()`] and scanned from the start of
+/// a string with [`IdentifierSyntax::scan_identifier()`].
+///
+/// Identifiers can be compared under NFKC (UAX #31 R4) with [`IdentifierSyntax::normalize_identifier()`].
+///
+/// Text following the pattern syntax of UAX #31, where `Pattern_White_Space` and
+/// `Pattern_Syntax` characters separate identifiers, can be split into tokens with
+/// [`IdentifierSyntax::tokenize()`].
+///
+/// # Examples
+///
+/// ```
+/// use icu::collections::codepointinvlist::CodePointInversionListBuilder;
+/// use icu::experimental::identifiers::{
+///     IdentifierOptions, IdentifierSyntax, PatternTokenKind,
+/// };
+///
+/// let mut options = IdentifierOptions::default();
+/// let mut builder = CodePointInversionListBuilder::new();
+/// builder.add_char('_');
+/// options.additional_start = builder.build();
+/// let mut builder = CodePointInversionListBuilder::new();
+/// builder.add_char('-');
+/// options.medial = builder.build();
+///
+/// let syntax = IdentifierSyntax::new(options);
+///
+/// assert!(syntax.is_identifier("größe"));
+/// assert!(syntax.is_identifier("_max-width"));
+/// assert!(!syntax.is_identifier("max--width"));
+/// assert!(!syntax.is_identifier("2d"));
+///
+/// assert_eq!(syntax.scan_identifier("width- = 2"), 5);
+///
+/// // U+FB01 LATIN SMALL LIGATURE FI
+/// assert_eq!(syntax.normalize_identifier("\u{FB01}le").unwrap(), "file");
+///
+/// let tokens = syntax
+///     .tokenize("x-min=-2 ")
+///     .map(|t| (t.kind, t.text))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     tokens,
+///     [
+///         (PatternTokenKind::Identifier, "x-min"),
+///         (PatternTokenKind::Syntax, "="),
+///         (PatternTokenKind::Syntax, "-"),
+///         (PatternTokenKind::Other, "2"),
+///         (PatternTokenKind::WhiteSpace, " "),
+///     ]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct IdentifierSyntax {
+    options: IdentifierOptions,
+    xid_start: CodePointSetData,
+    xid_continue: CodePointSetData,
+    pattern_syntax: CodePointSetData,
+    pattern_white_space: CodePointSetData,
+    noncharacter: CodePointSetData,
+    general_category: CodePointMapData<GeneralCategory>,
+    canonical_combining_class: CodePointMapData<CanonicalCombiningClass>,
+    joining_type: CodePointMapData<JoiningType>,
+    nfkc: ComposingNormalizer,
+}
+
+impl IdentifierSyntax {
+    /// Creates a new [`IdentifierSyntax`] with the given options.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub fn new(options: IdentifierOptions) -> Self {
+        Self {
+            options,
+            xid_start: CodePointSetData::new::<XidStart>().static_to_owned(),
+            xid_continue: CodePointSetData::new::<XidContinue>().static_to_owned(),
+            pattern_syntax: CodePointSetData::new::<PatternSyntax>().static_to_owned(),
+            pattern_white_space: CodePointSetData::new::<PatternWhiteSpace>().static_to_owned(),
+            noncharacter: CodePointSetData::new::<NoncharacterCodePoint>().static_to_owned(),
+            general_category: CodePointMapData::new().static_to_owned(),
+            canonical_combining_class: CodePointMapData::new().static_to_owned(),
+            joining_type: CodePointMapData::new().static_to_owned(),
+            nfkc: ComposingNormalizer::new_nfkc().static_to_owned(),
+        }
+    }
+
+    /// Creates a new [`IdentifierSyntax`] using a [`BufferProvider`].
+    ///
+    /// ✨ *Enabled with the `serde` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    ///
+    /// [`BufferProvider`]: icu_provider::buf::BufferProvider
+    #[cfg(feature = "serde")]
+    pub fn try_new_with_buffer_provider(
+        provider: &(impl icu_provider::buf::BufferProvider + ?Sized),
+        options: IdentifierOptions,
+    ) -> Result<Self, DataError> {
+        use icu_provider::buf::AsDeserializingBufferProvider;
+        Self::try_new_unstable(&provider.as_deserializing(), options)
+    }
+
+    /// Creates a new [`IdentifierSyntax`] using a [`DataProvider`].
+    ///
+    /// <div class="stab unstable">
+    /// ⚠️ The bounds on <tt>provider</tt> may change over time, including in SemVer minor releases.
+    /// </div>
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    pub fn try_new_unstable<D>(provider: &D, options: IdentifierOptions) -> Result<Self, DataError>
+    where
+        D: DataProvider<PropertyBinaryXidStartV1>
+            + DataProvider<PropertyBinaryXidContinueV1>
+            + DataProvider<PropertyBinaryPatternSyntaxV1>
+            + DataProvider<PropertyBinaryPatternWhiteSpaceV1>
+            + DataProvider<PropertyBinaryNoncharacterCodePointV1>
+            + DataProvider<PropertyEnumGeneralCategoryV1>
+            + DataProvider<PropertyEnumCanonicalCombiningClassV1>
+            + DataProvider<PropertyEnumJoiningTypeV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        Ok(Self {
+            options,
+            xid_start: CodePointSetData::try_new_unstable::<XidStart>(provider)?,
+            xid_continue: CodePointSetData::try_new_unstable::<XidContinue>(provider)?,
+            pattern_syntax: CodePointSetData::try_new_unstable::<PatternSyntax>(provider)?,
+            pattern_white_space: CodePointSetData::try_new_unstable::<PatternWhiteSpace>(provider)?,
+            noncharacter: CodePointSetData::try_new_unstable::<NoncharacterCodePoint>(provider)?,
+            general_category: CodePointMapData::try_new_unstable(provider)?,
+            canonical_combining_class: CodePointMapData::try_new_unstable(provider)?,
+            joining_type: CodePointMapData::try_new_unstable(provider)?,
+            nfkc: ComposingNormalizer::try_new_nfkc_unstable(provider)?,
+        })
+    }
+
+    /// Returns the options of this [`IdentifierSyntax`].
+    pub fn options(&self) -> &IdentifierOptions {
+        &self.options
+    }
+
+    /// Returns whether identifiers can start with `c`.
+    pub fn is_start(&self, c: char) -> bool {
+        self.options.additional_start.contains(c)
+            || match self.options.profile {
+                IdentifierProfile::Default => self.xid_start.as_borrowed().contains(c),
+                IdentifierProfile::Immutable => self.is_immutable_identifier_char(c),
+            }
+    }
+
+    /// Returns whether `c` can appear in identifiers after their first character.
+    ///
+    /// If [`IdentifierOptions::restrict_joiners`] is set, this is `false` for ZWJ and ZWNJ,
+    /// which are only allowed in certain contexts.
+    pub fn is_continue(&self, c: char) -> bool {
+        if self.options.restrict_joiners && matches!(c, ZWNJ | ZWJ) {
+            return false;
+        }
+        self.options.additional_start.contains(c)
+            || self.options.additional_continue.contains(c)
+            || match self.options.profile {
+                IdentifierProfile::Default => self.xid_continue.as_borrowed().contains(c),
+                IdentifierProfile::Immutable => self.is_immutable_identifier_char(c),
+            }
+    }
+
+    /// Returns whether `c` is a medial character, see [`IdentifierOptions::medial`].
+    pub fn is_medial(&self, c: char) -> bool {
+        self.options.medial.contains(c)
+    }
+
+    /// Returns whether `c` is a `Pattern_Syntax` character.
+    pub fn is_pattern_syntax(&self, c: char) -> bool {
+        self.pattern_syntax.as_borrowed().contains(c)
+    }
+
+    /// Returns whether `c` is a `Pattern_White_Space` character.
+    pub fn is_pattern_white_space(&self, c: char) -> bool {
+        self.pattern_white_space.as_borrowed().contains(c)
+    }
+
+    /// Returns whether `s` is an identifier.
+    pub fn is_identifier(&self, s: &str) -> bool {
+        !s.is_empty() && self.scan_identifier(s) == s.len()
+    }
+
+    /// Returns the length in bytes of the longest identifier at the start of `s`, or 0 if `s`
+    /// does not start with an identifier.
+    ///
+    /// Medial characters at the end of the identifier are not included.
+    pub fn scan_identifier(&self, s: &str) -> usize {
+        let mut chars = s.char_indices();
+        let mut end = match chars.next() {
+            Some((_, c)) if self.is_start(c) => c.len_utf8(),
+            _ => return 0,
+        };
+        let mut after_medial = false;
+        for (i, c) in chars {
+            if self.is_continue(c) || self.is_joiner_in_context(s, i, c) {
+                end = i + c.len_utf8();
+                after_medial = false;
+            } else if !after_medial && self.is_medial(c) {
+                after_medial = true;
+            } else {
+                break;
+            }
+        }
+        end
+    }
+
+    /// Returns whether `s` is an identifier in Normalization Form KC.
+    ///
+    /// Languages that require identifiers to be normalized (UAX #31 R4) can use this to
+    /// reject identifiers that are not.
+    pub fn is_nfkc_identifier(&self, s: &str) -> bool {
+        self.is_identifier(s) && self.nfkc.as_borrowed().is_normalized(s)
+    }
+
+    /// Returns the NFKC form of the identifier `s`, under which identifiers are compared
+    /// (UAX #31 R4).
+    ///
+    /// Returns `None` if `s` is not an identifier, or if its NFKC form is not an identifier.
+    /// The latter can only happen if characters were added with [`IdentifierOptions`], or
+    /// with the [`IdentifierProfile::Immutable`] profile.
+    pub fn normalize_identifier<'a>(&self, s: &'a str) -> Option<Cow<'a, str>> {
+        if !self.is_identifier(s) {
+            return None;
+        }
+        let normalized = self.nfkc.as_borrowed().normalize(s);
+        if self.is_identifier(&normalized) {
+            Some(normalized)
+        } else {
+            None
+        }
+    }
+
+    /// Splits `s` into pattern tokens, see [`PatternTokenKind`].
+    pub fn tokenize<'a>(&'a self, s: &'a str) -> PatternTokens<'a> {
+        PatternTokens {
+            syntax: self,
+            text: s,
+            position: 0,
+        }
+    }
+
+    fn is_immutable_identifier_char(&self, c: char) -> bool {
+        !self.pattern_syntax.as_borrowed().contains(c)
+            && !self.pattern_white_space.as_borrowed().contains(c)
+            && !self.noncharacter.as_borrowed().contains(c)
+            && !matches!(
+                self.general_category.as_borrowed().get(c),
+                GeneralCategory::Control | GeneralCategory::Surrogate | GeneralCategory::PrivateUse
+            )
+    }
+
+    /// Returns whether `c` at index `i` of `s` is a joiner in one of the contexts
+    /// of [`IdentifierOptions::restrict_joiners`].
+    fn is_joiner_in_context(&self, s: &str, i: usize, c: char) -> bool {
+        if !self.options.restrict_joiners {
+            return false;
+        }
+        let (before, after) = (&s[..i], &s[i + c.len_utf8()..]);
+        match c {
+            ZWNJ => self.breaks_cursive_connection(before, after) || self.follows_virama(before),
+            ZWJ => self.follows_virama(before),
+            _ => false,
+        }
+    }
+
+    /// `$LJ $T* ZWNJ $T* $RJ`
+    fn breaks_cursive_connection(&self, before: &str, after: &str) -> bool {
+        let joining_type = self.joining_type.as_borrowed();
+        let before = before
+            .chars()
+            .rev()
+            .map(|c| joining_type.get(c))
+            .find(|&jt| jt != JoiningType::Transparent);
+        let after = after
+            .chars()
+            .map(|c| joining_type.get(c))
+            .find(|&jt| jt != JoiningType::Transparent);
+        matches!(
+            before,
+            Some(JoiningType::LeftJoining | JoiningType::DualJoining)
+        ) && matches!(
+            after,
+            Some(JoiningType::RightJoining | JoiningType::DualJoining)
+        )
+    }
+
+    /// `$L $M* $V $M₁*`, followed by the joiner
+    fn follows_virama(&self, before: &str) -> bool {
+        let general_category = self.general_category.as_borrowed();
+        let canonical_combining_class = self.canonical_combining_class.as_borrowed();
+        let mut found_virama = false;
+        for c in before.chars().rev() {
+            let gc = general_category.get(c);
+            let ccc = canonical_combining_class.get(c);
+            if found_virama {
+                if gc != GeneralCategory::NonspacingMark {
+                    return GeneralCategoryGroup::Letter.contains(gc);
+                }
+            } else if ccc == CanonicalCombiningClass::Virama {
+                found_virama = true;
+            } else if gc != GeneralCategory::NonspacingMark
+                || ccc == CanonicalCombiningClass::NotReordered
+            {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+/// The kind of a [`PatternToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatternTokenKind {
+    /// An identifier, see [`IdentifierSyntax::scan_identifier()`].
+    Identifier,
+    /// A run of `Pattern_White_Space` characters.
+    WhiteSpace,
+    /// A single `Pattern_Syntax` character.
+    Syntax,
+    /// A run of characters that do not start any of the other tokens, such as digits.
+    Other,
+}
+
+/// A token of pattern syntax, returned by [`IdentifierSyntax::tokenize()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PatternToken<'a> {
+    /// The kind of the token.
+    pub kind: PatternTokenKind,
+    /// The byte offset of the token in the tokenized string.
+    pub start: usize,
+    /// The text of the token.
+    pub text: &'a str,
+}
+
+/// An iterator over the [`PatternToken`]s of a string, see [`IdentifierSyntax::tokenize()`].
+///
+/// Tokens are matched in the following order: identifiers, so that identifiers can start with
+/// `Pattern_Syntax` characters added with [`IdentifierOptions::additional_start`], then runs of
+/// white space, then syntax characters.
+#[derive(Debug)]
+pub struct PatternTokens<'a> {
+    syntax: &'a IdentifierSyntax,
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Iterator for PatternTokens<'a> {
+    type Item = PatternToken<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.text[self.position..];
+        let c = rest.chars().next()?;
+        let (kind, len) = match self.syntax.scan_identifier(rest) {
+            0 if self.syntax.is_pattern_white_space(c) => (
+                PatternTokenKind::WhiteSpace,
+                rest.find(|c| !self.syntax.is_pattern_white_space(c))
+                    .unwrap_or(rest.len()),
+            ),
+            0 if self.syntax.is_pattern_syntax(c) => (PatternTokenKind::Syntax, c.len_utf8()),
+            0 => (
+                PatternTokenKind::Other,
+                rest.char_indices()
+                    .skip(1)
+                    .find(|&(_, c)| {
+                        self.syntax.is_start(c)
+                            || self.syntax.is_pattern_white_space(c)
+                            || self.syntax.is_pattern_syntax(c)
+                    })
+                    .map(|(i, _)| i)
+                    .unwrap_or(rest.len()),
+            ),
+            len => (PatternTokenKind::Identifier, len),
+        };
+        let token = PatternToken {
+            kind,
+            start: self.position,
+            text: &rest[..len],
+        };
+        self.position += len;
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "compiled_data")]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use icu_collections::codepointinvlist::CodePointInversionListBuilder;
+
+    fn set(chars: &str) -> CodePointInversionList<'static> {
+        let mut builder = CodePointInversionListBuilder::new();
+        chars.chars().for_each(|c| builder.add_char(c));
+        builder.build()
+    }
+
+    #[test]
+    fn test_default_identifiers() {
+        let syntax = IdentifierSyntax::new(Default::default());
+
+        assert!(syntax.is_identifier("abc"));
+        assert!(syntax.is_identifier("a1"));
+        assert!(syntax.is_identifier("ελληνικά"));
+        assert!(syntax.is_identifier("e\u{301}"));
+        assert!(!syntax.is_identifier(""));
+        assert!(!syntax.is_identifier("1a"));
+        assert!(!syntax.is_identifier("_a"));
+        assert!(!syntax.is_identifier("a b"));
+        assert!(!syntax.is_identifier("\u{301}e"));
+        // ZWNJ and ZWJ are XID_Continue since Unicode 15.1
+        assert!(syntax.is_identifier("a\u{200C}b"));
+        assert!(syntax.is_identifier("a\u{200D}b"));
+
+        assert_eq!(syntax.scan_identifier("abc+d"), 3);
+        assert_eq!(syntax.scan_identifier("+abc"), 0);
+        assert_eq!(syntax.scan_identifier("αβ γ"), 4);
+    }
+
+    #[test]
+    fn test_additional_and_medial() {
+        let options = IdentifierOptions {
+            additional_start: set("$"),
+            additional_continue: set("'"),
+            medial: set("-."),
+            ..Default::default()
+        };
+        let syntax = IdentifierSyntax::new(options);
+
+        assert!(syntax.is_identifier("$x"));
+        assert!(syntax.is_identifier("x$"));
+        assert!(syntax.is_identifier("x'"));
+        assert!(!syntax.is_identifier("'x"));
+        assert!(syntax.is_identifier("a-b.c"));
+        assert!(!syntax.is_identifier("-a"));
+        assert!(!syntax.is_identifier("a-"));
+        assert!(!syntax.is_identifier("a--b"));
+        assert!(!syntax.is_identifier("a-.b"));
+
+        assert_eq!(syntax.scan_identifier("a-b-"), 3);
+        assert_eq!(syntax.scan_identifier("a--b"), 1);
+    }
+
+    #[test]
+    fn test_immutable_identifiers() {
+        let options = IdentifierOptions {
+            profile: IdentifierProfile::Immutable,
+            ..Default::default()
+        };
+        let syntax = IdentifierSyntax::new(options);
+
+        assert!(syntax.is_identifier("abc"));
+        assert!(syntax.is_identifier("1a"));
+        assert!(syntax.is_identifier("😀"));
+        // unassigned
+        assert!(syntax.is_identifier("a\u{E0080}"));
+        assert!(!syntax.is_identifier("a+b"));
+        assert!(!syntax.is_identifier("a b"));
+        assert!(!syntax.is_identifier("a\u{0}"));
+        assert!(!syntax.is_identifier("a\u{E000}"));
+        assert!(!syntax.is_identifier("a\u{FFFF}"));
+    }
+
+    #[test]
+    fn test_joiners() {
+        let options = IdentifierOptions {
+            restrict_joiners: true,
+            ..Default::default()
+        };
+        let syntax = IdentifierSyntax::new(options);
+
+        // A1: ZWNJ between dual-joining Arabic letters
+        assert!(syntax.is_identifier("\u{628}\u{200C}\u{628}"));
+        // A1: transparent marks around the ZWNJ
+        assert!(syntax.is_identifier("\u{628}\u{64E}\u{200C}\u{628}"));
+        // A1: ALEF is right-joining, so there is no connection to break
+        assert!(!syntax.is_identifier("\u{627}\u{200C}\u{628}"));
+        // A2: ZWNJ after DEVANAGARI SIGN VIRAMA
+        assert!(syntax.is_identifier("\u{915}\u{94D}\u{200C}\u{937}"));
+        // B: ZWJ after DEVANAGARI SIGN VIRAMA
+        assert!(syntax.is_identifier("\u{915}\u{94D}\u{200D}\u{937}"));
+        // No virama
+        assert!(!syntax.is_identifier("\u{915}\u{200D}\u{937}"));
+        assert!(!syntax.is_identifier("a\u{200C}b"));
+        assert!(!syntax.is_identifier("a\u{200D}b"));
+
+        assert_eq!(syntax.scan_identifier("a\u{200C}b"), 1);
+    }
+
+    #[test]
+    fn test_nfkc() {
+        let syntax = IdentifierSyntax::new(Default::default());
+
+        assert!(syntax.is_nfkc_identifier("file"));
+        assert!(!syntax.is_nfkc_identifier("\u{FB01}le"));
+        assert!(!syntax.is_nfkc_identifier("e\u{301}"));
+        assert!(syntax.is_nfkc_identifier("é"));
+
+        assert_eq!(
+            syntax.normalize_identifier("file"),
+            Some(Cow::Borrowed("file"))
+        );
+        assert_eq!(syntax.normalize_identifier("\u{FB01}le").unwrap(), "file");
+        // FULLWIDTH LATIN CAPITAL LETTER A
+        assert_eq!(syntax.normalize_identifier("\u{FF21}").unwrap(), "A");
+        assert_eq!(syntax.normalize_identifier("a b"), None);
+
+        // U+2460 CIRCLED DIGIT ONE is not an identifier character, but its NFKC form is
+        let options = IdentifierOptions {
+            additional_continue: set("\u{2460}"),
+            ..Default::default()
+        };
+        let syntax = IdentifierSyntax::new(options);
+        assert_eq!(syntax.normalize_identifier("a\u{2460}").unwrap(), "a1");
+        // ...and U+00A8 DIAERESIS normalizes to a space and a combining mark
+        let options = IdentifierOptions {
+            additional_continue: set("\u{A8}"),
+            ..Default::default()
+        };
+        let syntax = IdentifierSyntax::new(options);
+        assert!(syntax.is_identifier("a\u{A8}"));
+        assert_eq!(syntax.normalize_identifier("a\u{A8}"), None);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let options = IdentifierOptions {
+            additional_start: set("$"),
+            medial: set("-"),
+            ..Default::default()
+        };
+        let syntax = IdentifierSyntax::new(options);
+
+        let tokens = syntax
+            .tokenize("$a-b =\t\n-12x ,")
+            .map(|t| (t.kind, t.start, t.text))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                (PatternTokenKind::Identifier, 0, "$a-b"),
+                (PatternTokenKind::WhiteSpace, 4, " "),
+                (PatternTokenKind::Syntax, 5, "="),
+                (PatternTokenKind::WhiteSpace, 6, "\t\n"),
+                (PatternTokenKind::Syntax, 8, "-"),
+                (PatternTokenKind::Other, 9, "12"),
+                (PatternTokenKind::Identifier, 11, "x"),
+                (PatternTokenKind::WhiteSpace, 12, " "),
+                (PatternTokenKind::Syntax, 13, ","),
+            ]
+        );
+
+        assert_eq!(syntax.tokenize("").next(), None);
+    }
+}
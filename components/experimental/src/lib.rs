@@ -17,13 +17,21 @@
 
 extern crate alloc;
 
+#[cfg(feature = "alphabetic_index")]
+pub mod alphabetic_index;
 pub mod compactdecimal;
 pub mod dimension;
 pub mod displaynames;
 pub mod duration;
+pub mod identifiers;
 pub mod measure;
+#[cfg(feature = "segmenter")]
+pub mod paragraph;
 pub mod personnames;
+#[cfg(feature = "segmenter")]
+pub mod regex;
 pub mod relativetime;
+pub mod spoof;
 pub mod transliterate;
 pub mod unicodeset_parse;
 pub mod units;
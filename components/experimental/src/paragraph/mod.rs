@@ -0,0 +1,19 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Fitting paragraphs of text into lines of a maximum width.
+//!
+//! This module builds on the line break opportunities found by
+//! [`LineSegmenter`](icu_segmenter::LineSegmenter), adding a measure of text width and an
+//! algorithm choosing which opportunities to break at. See [`LineWrapper`].
+//!
+//! ✨ *Enabled with the `segmenter` Cargo feature.*
+
+#![warn(missing_docs)]
+
+mod width;
+mod wrap;
+
+pub use width::{DisplayWidth, WidthMeasure};
+pub use wrap::{FittingStrategy, LineWrapper, WrapOptions, WrappedLine};
@@ -0,0 +1,113 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use icu_properties::props::EastAsianWidth;
+use icu_properties::CodePointMapDataBorrowed;
+use icu_segmenter::GraphemeClusterSegmenterBorrowed;
+
+/// A way of measuring the width of text, used by [`LineWrapper`](super::LineWrapper).
+///
+/// Widths are assumed to be additive: the width of a string is the sum of the widths of the
+/// pieces it is split into at line break opportunities.
+///
+/// This is implemented for closures taking a `&str`, and by [`DisplayWidth`].
+pub trait WidthMeasure {
+    /// Returns the width of `text`.
+    fn width(&self, text: &str) -> usize;
+}
+
+impl<F: Fn(&str) -> usize> WidthMeasure for F {
+    fn width(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// Measures text in columns of a monospace display, such as a terminal.
+///
+/// Each grapheme cluster takes up one column, or two columns if it starts with an
+/// East Asian Wide or Fullwidth character (see [`EastAsianWidth`]) or is presented as
+/// an emoji. Control characters take up no columns. East Asian Ambiguous characters are
+/// treated as narrow.
+///
+/// # Examples
+///
+/// ```
+/// use icu::experimental::paragraph::{DisplayWidth, WidthMeasure};
+///
+/// let width = DisplayWidth::new();
+/// assert_eq!(width.width("abc"), 3);
+/// assert_eq!(width.width("日本語"), 6);
+/// assert_eq!(width.width("ｱｲｳ"), 3);
+/// // A single grapheme cluster
+/// assert_eq!(width.width("e\u{301}"), 1);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayWidth<'data> {
+    grapheme: GraphemeClusterSegmenterBorrowed<'data>,
+    east_asian_width: CodePointMapDataBorrowed<'data, EastAsianWidth>,
+}
+
+#[cfg(feature = "compiled_data")]
+impl Default for DisplayWidth<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "compiled_data")]
+impl DisplayWidth<'static> {
+    /// Creates a [`DisplayWidth`] using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    pub const fn new() -> Self {
+        Self {
+            grapheme: icu_segmenter::GraphemeClusterSegmenter::new(),
+            east_asian_width: icu_properties::CodePointMapData::<EastAsianWidth>::new(),
+        }
+    }
+}
+
+impl<'data> DisplayWidth<'data> {
+    /// Creates a [`DisplayWidth`] from a grapheme cluster segmenter and East Asian Width data,
+    /// which may have been loaded from a custom data provider.
+    pub fn new_with_data(
+        grapheme: GraphemeClusterSegmenterBorrowed<'data>,
+        east_asian_width: CodePointMapDataBorrowed<'data, EastAsianWidth>,
+    ) -> Self {
+        Self {
+            grapheme,
+            east_asian_width,
+        }
+    }
+
+    fn cluster_width(&self, cluster: &str) -> usize {
+        let Some(first) = cluster.chars().next() else {
+            return 0;
+        };
+        if first.is_control() {
+            0
+        } else if matches!(
+            self.east_asian_width.get(first),
+            EastAsianWidth::Wide | EastAsianWidth::Fullwidth
+        ) || cluster.contains('\u{FE0F}')
+        {
+            // U+FE0F VARIATION SELECTOR-16 requests emoji presentation.
+            2
+        } else {
+            1
+        }
+    }
+}
+
+impl WidthMeasure for DisplayWidth<'_> {
+    fn width(&self, text: &str) -> usize {
+        let mut width = 0;
+        let mut start = 0;
+        for end in self.grapheme.segment_str(text).skip(1) {
+            width += self.cluster_width(text.get(start..end).unwrap_or_default());
+            start = end;
+        }
+        width
+    }
+}
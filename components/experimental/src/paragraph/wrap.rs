@@ -0,0 +1,530 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use super::{DisplayWidth, WidthMeasure};
+use alloc::vec;
+use alloc::vec::Vec;
+use icu_properties::props::LineBreak;
+use icu_properties::CodePointMapDataBorrowed;
+use icu_segmenter::LineSegmenterBorrowed;
+
+/// The algorithm used to choose line breaks.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FittingStrategy {
+    /// Puts as much text as fits on each line before moving on to the next one.
+    ///
+    /// This is fast, and the usual choice for terminals and text editors.
+    #[default]
+    Greedy,
+    /// Chooses the line breaks of each paragraph with the Knuth–Plass algorithm, which
+    /// minimizes the total demerits of its lines.
+    ///
+    /// The unused width of a line is treated as glue that can stretch by a quarter of the
+    /// maximum width, from which the badness of the line follows as in TeX. Lines cannot
+    /// shrink, so a line is only wider than the maximum width if it holds a single piece of
+    /// text that does not fit, and the last line of a paragraph can stretch indefinitely.
+    /// Demerits are added for breaking after a hyphen, for consecutive lines ending in
+    /// hyphens, for a hyphen at the end of the second-to-last line, and for adjacent lines
+    /// whose fitness classes (very loose, loose or decent) are not adjacent. The parameters
+    /// are those of plain TeX.
+    ///
+    /// This produces more even lines, at the cost of quadratic time in the length of
+    /// a line.
+    Optimal,
+}
+
+/// The demerits of every line (`\linepenalty` in TeX).
+const LINE_PENALTY: u64 = 10;
+/// The penalty for breaking after a hyphen (`\hyphenpenalty` and `\exhyphenpenalty`).
+const HYPHEN_PENALTY: u64 = 50;
+/// The demerits for two consecutive lines ending in hyphens (`\doublehyphendemerits`).
+const DOUBLE_HYPHEN_DEMERITS: u64 = 10_000;
+/// The demerits for a hyphen at the end of the second-to-last line
+/// (`\finalhyphendemerits`).
+const FINAL_HYPHEN_DEMERITS: u64 = 5_000;
+/// The demerits for adjacent lines whose fitness classes are not adjacent
+/// (`\adjdemerits`).
+const ADJACENT_DEMERITS: u64 = 10_000;
+/// The badness of a line that would have to stretch indefinitely.
+const MAX_BADNESS: u64 = 10_000;
+
+/// The fitness classes of lines that stretch by more than their stretchability, by more
+/// than half of it, and by less. Lines never shrink, so there is no class for tight lines.
+const VERY_LOOSE: usize = 0;
+const LOOSE: usize = 1;
+const DECENT: usize = 2;
+
+/// The badness of a line with the given unused width, which is 100 times the cube of
+/// the ratio of the unused width to the stretchability, a quarter of the maximum width.
+fn badness(unused: usize, max_width: usize) -> u64 {
+    if unused == 0 {
+        0
+    } else if max_width == 0 {
+        MAX_BADNESS
+    } else {
+        let ratio_cubed = (6400 * (unused as u128).pow(3)) / (max_width as u128).pow(3);
+        u64::try_from(ratio_cubed).map_or(MAX_BADNESS, |b| b.min(MAX_BADNESS))
+    }
+}
+
+fn fitness(badness: u64) -> usize {
+    match badness {
+        100.. => VERY_LOOSE,
+        13.. => LOOSE,
+        _ => DECENT,
+    }
+}
+
+/// A piece of text between two break opportunities.
+#[derive(Debug, Clone, Copy)]
+struct Piece {
+    /// The end of the content of the piece, without trailing spaces.
+    end: usize,
+    /// The width of the piece.
+    width: usize,
+    /// The width of the content of the piece.
+    content_width: usize,
+    /// Whether the content ends with a hyphen, which makes a break after the piece a
+    /// flagged break in the Knuth–Plass algorithm.
+    hyphenated: bool,
+}
+
+/// Options for [`LineWrapper`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WrapOptions {
+    /// The algorithm used to choose line breaks.
+    pub strategy: FittingStrategy,
+}
+
+/// A line produced by [`LineWrapper::wrap`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrappedLine<'s> {
+    /// The text of the line, excluding trailing spaces and line terminators.
+    pub text: &'s str,
+    /// The offset of the line in the wrapped text, in bytes.
+    pub start: usize,
+    /// The width of [`Self::text`].
+    pub width: usize,
+    /// Whether the line is followed by a mandatory break, such as a line feed or the end
+    /// of the text.
+    pub mandatory_break: bool,
+}
+
+/// Wraps text into lines of a maximum width, breaking only at the line break opportunities
+/// of a [`LineSegmenter`](icu_segmenter::LineSegmenter).
+///
+/// Mandatory breaks (such as line feeds) always end a line. Spaces and line terminators at the
+/// end of a line do not count towards its width. Text between two break opportunities that is
+/// wider than the maximum width is put on a line of its own, which then exceeds the maximum
+/// width.
+///
+/// # Examples
+///
+/// ```
+/// use icu::experimental::paragraph::{
+///     FittingStrategy, LineWrapper, WrapOptions,
+/// };
+/// use icu::segmenter::LineSegmenter;
+///
+/// let segmenter = LineSegmenter::new_auto(Default::default());
+/// let wrapper = LineWrapper::new(segmenter, WrapOptions::default());
+///
+/// let text = "The quick brown fox jumps over the lazy dog.";
+/// let lines: Vec<&str> =
+///     wrapper.wrap(text, 15).iter().map(|line| line.text).collect();
+/// assert_eq!(lines, ["The quick brown", "fox jumps over", "the lazy dog."]);
+///
+/// // East Asian wide characters take up two columns.
+/// let lines: Vec<&str> = wrapper
+///     .wrap("日本語の文章を折り返す", 8)
+///     .iter()
+///     .map(|line| line.text)
+///     .collect();
+/// assert_eq!(lines, ["日本語の", "文章を折", "り返す"]);
+///
+/// // Optimal fitting avoids a very short line.
+/// let mut options = WrapOptions::default();
+/// options.strategy = FittingStrategy::Optimal;
+/// let wrapper = LineWrapper::new(segmenter, options);
+/// let lines: Vec<&str> = wrapper
+///     .wrap("aaa bb cc ddddd", 6)
+///     .iter()
+///     .map(|line| line.text)
+///     .collect();
+/// assert_eq!(lines, ["aaa", "bb cc", "ddddd"]);
+/// ```
+///
+/// A custom [`WidthMeasure`], such as a closure, can be used for proportional fonts:
+///
+/// ```
+/// use icu::experimental::paragraph::{LineWrapper, WrapOptions};
+/// use icu::segmenter::LineSegmenter;
+///
+/// let segmenter = LineSegmenter::new_auto(Default::default());
+/// let wrapper = LineWrapper::new_with_measure(
+///     segmenter,
+///     |s: &str| s.chars().map(|c| if c == 'm' { 3 } else { 2 }).sum(),
+///     WrapOptions::default(),
+/// );
+/// let lines: Vec<&str> = wrapper
+///     .wrap("lorem ipsum\ndolor sit amet", 20)
+///     .iter()
+///     .map(|line| line.text)
+///     .collect();
+/// assert_eq!(lines, ["lorem", "ipsum", "dolor sit", "amet"]);
+/// ```
+#[derive(Debug)]
+pub struct LineWrapper<'data, W = DisplayWidth<'data>> {
+    segmenter: LineSegmenterBorrowed<'data>,
+    line_break: CodePointMapDataBorrowed<'data, LineBreak>,
+    measure: W,
+    options: WrapOptions,
+}
+
+#[cfg(feature = "compiled_data")]
+impl<'data> LineWrapper<'data> {
+    /// Creates a [`LineWrapper`] that measures text with [`DisplayWidth`], using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    pub fn new(segmenter: LineSegmenterBorrowed<'data>, options: WrapOptions) -> Self {
+        Self::new_with_measure(segmenter, DisplayWidth::new(), options)
+    }
+}
+
+#[cfg(feature = "compiled_data")]
+impl<'data, W: WidthMeasure> LineWrapper<'data, W> {
+    /// Creates a [`LineWrapper`] with a custom [`WidthMeasure`], using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    pub fn new_with_measure(
+        segmenter: LineSegmenterBorrowed<'data>,
+        measure: W,
+        options: WrapOptions,
+    ) -> Self {
+        Self::new_with_data(
+            segmenter,
+            icu_properties::CodePointMapData::<LineBreak>::new(),
+            measure,
+            options,
+        )
+    }
+}
+
+impl<'data, W: WidthMeasure> LineWrapper<'data, W> {
+    /// Creates a [`LineWrapper`] with a custom [`WidthMeasure`] and Line_Break data, which may
+    /// have been loaded from a custom data provider.
+    ///
+    /// The Line_Break data is used to recognize mandatory breaks and trailing spaces.
+    pub fn new_with_data(
+        segmenter: LineSegmenterBorrowed<'data>,
+        line_break: CodePointMapDataBorrowed<'data, LineBreak>,
+        measure: W,
+        options: WrapOptions,
+    ) -> Self {
+        Self {
+            segmenter,
+            line_break,
+            measure,
+            options,
+        }
+    }
+
+    /// Wraps `text` into lines no wider than `max_width`, where possible.
+    ///
+    /// Returns no lines for the empty string.
+    pub fn wrap<'s>(&self, text: &'s str, max_width: usize) -> Vec<WrappedLine<'s>> {
+        let mut lines = Vec::new();
+        let mut breaks = self.segmenter.segment_str(text).skip(1).peekable();
+        let mut start = 0;
+        while breaks.peek().is_some() {
+            // Collect the break opportunities of one paragraph, up to and including the
+            // next mandatory break.
+            let mut paragraph = vec![start];
+            for b in breaks.by_ref() {
+                paragraph.push(b);
+                if self.is_mandatory_break(text, b) {
+                    break;
+                }
+            }
+            let end = paragraph.last().copied().unwrap_or(start);
+            self.wrap_paragraph(text, &paragraph, max_width, &mut lines);
+            if let Some(last) = lines.last_mut() {
+                last.mandatory_break = self.is_mandatory_break(text, end);
+            }
+            start = end;
+        }
+        lines
+    }
+
+    fn is_mandatory_break(&self, text: &str, offset: usize) -> bool {
+        offset == text.len()
+            || text
+                .get(..offset)
+                .and_then(|s| s.chars().next_back())
+                .is_some_and(|c| {
+                    matches!(
+                        self.line_break.get(c),
+                        LineBreak::MandatoryBreak
+                            | LineBreak::CarriageReturn
+                            | LineBreak::LineFeed
+                            | LineBreak::NextLine
+                    )
+                })
+    }
+
+    /// Whether a character at the end of a line does not count towards its width.
+    fn is_hanging(&self, c: char) -> bool {
+        matches!(
+            self.line_break.get(c),
+            LineBreak::Space
+                | LineBreak::MandatoryBreak
+                | LineBreak::CarriageReturn
+                | LineBreak::LineFeed
+                | LineBreak::NextLine
+        )
+    }
+
+    /// Wraps a paragraph, given by the offsets of its start and its break opportunities.
+    fn wrap_paragraph<'s>(
+        &self,
+        text: &'s str,
+        breaks: &[usize],
+        max_width: usize,
+        lines: &mut Vec<WrappedLine<'s>>,
+    ) {
+        let pieces: Vec<Piece> = breaks
+            .windows(2)
+            .filter_map(|w| {
+                let &[start, end] = w else {
+                    return None;
+                };
+                let piece = text.get(start..end).unwrap_or_default();
+                let content = piece.trim_end_matches(|c| self.is_hanging(c));
+                Some(Piece {
+                    end: start + content.len(),
+                    width: self.measure.width(piece),
+                    content_width: self.measure.width(content),
+                    hyphenated: content.ends_with(['-', '\u{00AD}', '\u{2010}']),
+                })
+            })
+            .collect();
+        // prefix[i] is the full width of the first i pieces.
+        let prefix: Vec<usize> = core::iter::once(0)
+            .chain(pieces.iter().scan(0, |sum, piece| {
+                *sum += piece.width;
+                Some(*sum)
+            }))
+            .collect();
+        // The width of a line made of the pieces i..j, for 0 <= i < j <= pieces.len().
+        let line_width = |i: usize, j: usize| {
+            let before_last = prefix.get(j - 1).copied().unwrap_or_default();
+            let last = pieces.get(j - 1).map(|piece| piece.content_width);
+            before_last - prefix.get(i).copied().unwrap_or_default() + last.unwrap_or_default()
+        };
+
+        let ends = match self.options.strategy {
+            FittingStrategy::Greedy => {
+                let mut ends = Vec::new();
+                let mut i = 0;
+                for j in 1..pieces.len() {
+                    // Break before piece j if it does not fit on the current line.
+                    if line_width(i, j + 1) > max_width {
+                        ends.push(j);
+                        i = j;
+                    }
+                }
+                ends.push(pieces.len());
+                ends
+            }
+            FittingStrategy::Optimal => knuth_plass(&pieces, max_width, line_width),
+        };
+
+        let mut i = 0;
+        for j in ends {
+            let start = breaks.get(i).copied().unwrap_or_default();
+            let end = pieces.get(j - 1).map(|piece| piece.end).unwrap_or(start);
+            lines.push(WrappedLine {
+                text: text.get(start..end).unwrap_or_default(),
+                start,
+                width: line_width(i, j),
+                mandatory_break: false,
+            });
+            i = j;
+        }
+    }
+}
+
+/// Returns the ends of the lines of a paragraph chosen by the Knuth–Plass algorithm, as
+/// numbers of pieces.
+///
+/// This is a dynamic program over the feasible breaks, equivalent to the active list of the
+/// original algorithm: a break stays active while the line starting at it still fits.
+fn knuth_plass(
+    pieces: &[Piece],
+    max_width: usize,
+    line_width: impl Fn(usize, usize) -> usize,
+) -> Vec<usize> {
+    let hyphenated = |j: usize| {
+        j.checked_sub(1)
+            .and_then(|j| pieces.get(j))
+            .is_some_and(|piece| piece.hyphenated)
+    };
+    // best[j][c] is the least total demerits of breaking after the first j pieces with a
+    // last line of fitness class c, and the start and fitness class of the line before.
+    let mut best: Vec<[Option<(u64, usize, usize)>; 3]> = vec![[None; 3]; pieces.len() + 1];
+    if let Some(b) = best.first_mut().and_then(|b| b.get_mut(DECENT)) {
+        *b = Some((0, 0, DECENT));
+    }
+    for j in 1..=pieces.len() {
+        let last = j == pieces.len();
+        for i in (0..j).rev() {
+            let width = line_width(i, j);
+            if width > max_width && i + 1 < j {
+                // Lines only get wider as more pieces are added.
+                break;
+            }
+            let badness = if last || width > max_width {
+                0
+            } else {
+                badness(max_width - width, max_width)
+            };
+            let class = fitness(badness);
+            let flagged = !last && hyphenated(j);
+            let mut demerits = (LINE_PENALTY + badness).pow(2);
+            if flagged {
+                demerits += HYPHEN_PENALTY.pow(2);
+                if hyphenated(i) {
+                    demerits += DOUBLE_HYPHEN_DEMERITS;
+                }
+            } else if last && hyphenated(i) {
+                demerits += FINAL_HYPHEN_DEMERITS;
+            }
+            let Some(previous) = best.get(i).copied() else {
+                continue;
+            };
+            for (previous_class, previous) in previous.into_iter().enumerate() {
+                let Some((total, _, _)) = previous else {
+                    continue;
+                };
+                let mut total = total + demerits;
+                if class.abs_diff(previous_class) > 1 {
+                    total += ADJACENT_DEMERITS;
+                }
+                if let Some(b) = best.get_mut(j).and_then(|b| b.get_mut(class)) {
+                    if b.is_none_or(|(best, _, _)| total < best) {
+                        *b = Some((total, i, previous_class));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut ends = Vec::new();
+    let mut j = pieces.len();
+    let mut class = best
+        .get(j)
+        .and_then(|b| {
+            b.iter()
+                .enumerate()
+                .filter_map(|(class, b)| Some((b.as_ref()?.0, class)))
+                .min()
+        })
+        .map(|(_, class)| class)
+        .unwrap_or(DECENT);
+    while j > 0 {
+        ends.push(j);
+        let Some(&Some((_, start, previous_class))) = best.get(j).and_then(|b| b.get(class)) else {
+            break;
+        };
+        j = start;
+        class = previous_class;
+    }
+    ends.reverse();
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icu_segmenter::LineSegmenter;
+
+    fn wrap(text: &str, max_width: usize, strategy: FittingStrategy) -> Vec<WrappedLine<'_>> {
+        let options = WrapOptions {
+            strategy,
+            ..Default::default()
+        };
+        LineWrapper::new(LineSegmenter::new_auto(Default::default()), options).wrap(text, max_width)
+    }
+
+    #[test]
+    fn mandatory_breaks() {
+        for strategy in [FittingStrategy::Greedy, FittingStrategy::Optimal] {
+            let lines = wrap("ab cd\r\n\nef   \n", 10, strategy);
+            assert_eq!(
+                lines,
+                [
+                    WrappedLine {
+                        text: "ab cd",
+                        start: 0,
+                        width: 5,
+                        mandatory_break: true,
+                    },
+                    WrappedLine {
+                        text: "",
+                        start: 7,
+                        width: 0,
+                        mandatory_break: true,
+                    },
+                    WrappedLine {
+                        text: "ef",
+                        start: 8,
+                        width: 2,
+                        mandatory_break: true,
+                    },
+                ]
+            );
+            assert!(wrap("", 10, strategy).is_empty());
+        }
+    }
+
+    #[test]
+    fn knuth_plass() {
+        fn texts(lines: Vec<WrappedLine<'_>>) -> Vec<&str> {
+            lines.iter().map(|line| line.text).collect()
+        }
+        // Breaking after a hyphen is penalized, which outweighs a more ragged line
+        assert_eq!(
+            texts(wrap("aa b-yy ccc", 5, FittingStrategy::Greedy)),
+            ["aa b-", "yy", "ccc"]
+        );
+        assert_eq!(
+            texts(wrap("aa b-yy ccc", 5, FittingStrategy::Optimal)),
+            ["aa", "b-yy", "ccc"]
+        );
+        // A very loose line is followed by a loose line rather than a decent one
+        assert_eq!(
+            texts(wrap("aa b ccccc d e", 9, FittingStrategy::Greedy)),
+            ["aa b", "ccccc d e"]
+        );
+        assert_eq!(
+            texts(wrap("aa b ccccc d e", 9, FittingStrategy::Optimal)),
+            ["aa b", "ccccc d", "e"]
+        );
+    }
+
+    #[test]
+    fn overlong() {
+        for strategy in [FittingStrategy::Greedy, FittingStrategy::Optimal] {
+            let lines = wrap("a bbbbbbbb c d", 4, strategy);
+            let texts: Vec<_> = lines.iter().map(|line| line.text).collect();
+            assert_eq!(texts, ["a", "bbbbbbbb", "c d"]);
+            assert_eq!(lines[1].width, 8);
+            assert!(!lines[1].mandatory_break);
+            assert!(lines[2].mandatory_break);
+        }
+    }
+}
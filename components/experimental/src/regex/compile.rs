@@ -0,0 +1,279 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use icu_casemap::CaseMapperBorrowed;
+
+use super::parse::{Assertion, Node};
+use super::RegexError;
+
+/// The largest number of instructions in a program.
+const MAX_INSTRUCTIONS: usize = 100_000;
+
+/// An instruction of the backtracking matcher.
+#[derive(Debug)]
+pub(crate) enum Inst {
+    /// Matches a character exactly.
+    Char(char),
+    /// Matches characters whose full case foldings concatenate to the string.
+    Folded(String),
+    /// Matches an element of a set.
+    Set(usize),
+    /// Matches any character, excluding line terminators unless `dot_all`.
+    Any {
+        dot_all: bool,
+    },
+    /// Matches an extended grapheme cluster.
+    Grapheme,
+    Assert(Assertion),
+    /// Stores the current position in a slot.
+    Save(usize),
+    /// Continues at the first target, backtracking to the second.
+    Split(usize, usize),
+    Jump(usize),
+    /// Continues at `exit` if the position is the one stored in `slot`, to stop a
+    /// loop whose body matched the empty string.
+    CheckProgress {
+        slot: usize,
+        exit: usize,
+    },
+    BackReference {
+        group: usize,
+        case_insensitive: bool,
+    },
+    /// Matches the following instructions up to [`Inst::LookEnd`] without consuming
+    /// input, continuing at `end`.
+    LookAhead {
+        negate: bool,
+        end: usize,
+    },
+    LookEnd,
+    Match,
+}
+
+/// A compiled pattern.
+#[derive(Debug)]
+pub(crate) struct Program {
+    pub(crate) insts: Vec<Inst>,
+    /// The number of slots, two per capture group followed by the loop slots.
+    pub(crate) slots: usize,
+}
+
+impl Program {
+    /// Whether the program needs grapheme cluster boundaries, for `\X` and `\b{g}`.
+    pub(crate) fn uses_graphemes(&self) -> bool {
+        self.insts.iter().any(|inst| {
+            matches!(
+                inst,
+                Inst::Grapheme | Inst::Assert(Assertion::GraphemeBoundary)
+            )
+        })
+    }
+
+    /// Whether the program needs word boundaries, for `\b{w}`.
+    pub(crate) fn uses_words(&self) -> bool {
+        self.insts
+            .iter()
+            .any(|inst| matches!(inst, Inst::Assert(Assertion::UnicodeWordBoundary)))
+    }
+}
+
+pub(crate) struct Compiler<'a> {
+    insts: Vec<Inst>,
+    slots: usize,
+    mapper: CaseMapperBorrowed<'a>,
+}
+
+impl<'a> Compiler<'a> {
+    pub(crate) fn compile(
+        node: &Node,
+        groups: usize,
+        mapper: CaseMapperBorrowed<'a>,
+    ) -> Result<Program, RegexError> {
+        let mut compiler = Self {
+            insts: Vec::new(),
+            slots: 2 * groups,
+            mapper,
+        };
+        compiler.push(Inst::Save(0))?;
+        compiler.node(node)?;
+        compiler.push(Inst::Save(1))?;
+        compiler.push(Inst::Match)?;
+        Ok(Program {
+            insts: compiler.insts,
+            slots: compiler.slots,
+        })
+    }
+
+    fn push(&mut self, inst: Inst) -> Result<usize, RegexError> {
+        if self.insts.len() >= MAX_INSTRUCTIONS {
+            return Err(RegexError::TooLarge);
+        }
+        self.insts.push(inst);
+        Ok(self.insts.len() - 1)
+    }
+
+    fn patch(&mut self, at: usize, inst: Inst) {
+        if let Some(i) = self.insts.get_mut(at) {
+            *i = inst;
+        }
+    }
+
+    fn next(&self) -> usize {
+        self.insts.len()
+    }
+
+    fn node(&mut self, node: &Node) -> Result<(), RegexError> {
+        match node {
+            Node::Empty => {}
+            Node::Char(c) => {
+                self.push(Inst::Char(*c))?;
+            }
+            Node::FoldedChar(c) => self.folded(core::slice::from_ref(c))?,
+            Node::Set(i) => {
+                self.push(Inst::Set(*i))?;
+            }
+            Node::Any { dot_all } => {
+                self.push(Inst::Any { dot_all: *dot_all })?;
+            }
+            Node::Grapheme => {
+                self.push(Inst::Grapheme)?;
+            }
+            Node::Assert(a) => {
+                self.push(Inst::Assert(*a))?;
+            }
+            Node::Group(None, node) => self.node(node)?,
+            Node::Group(Some(index), node) => {
+                self.push(Inst::Save(2 * index))?;
+                self.node(node)?;
+                self.push(Inst::Save(2 * index + 1))?;
+            }
+            Node::LookAhead { negate, node } => self.look_ahead(*negate, node)?,
+            Node::Concat(nodes) => self.concat(nodes)?,
+            Node::Alternation(branches) => self.alternation(branches)?,
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => self.repeat(node, *min, *max, *greedy)?,
+            Node::BackReference {
+                group,
+                case_insensitive,
+            } => {
+                self.push(Inst::BackReference {
+                    group: *group,
+                    case_insensitive: *case_insensitive,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    // The compound nodes are compiled in separate functions, which keeps the stack frame
+    // of the recursion through `node` small.
+
+    fn look_ahead(&mut self, negate: bool, node: &Node) -> Result<(), RegexError> {
+        let look = self.push(Inst::LookAhead { negate, end: 0 })?;
+        self.node(node)?;
+        self.push(Inst::LookEnd)?;
+        let end = self.next();
+        self.patch(look, Inst::LookAhead { negate, end });
+        Ok(())
+    }
+
+    fn concat(&mut self, nodes: &[Node]) -> Result<(), RegexError> {
+        let mut nodes = nodes.iter().peekable();
+        while let Some(node) = nodes.next() {
+            if let Node::FoldedChar(c) = node {
+                // Consecutive case-insensitive literals are folded together, so
+                // that they can match characters that fold to several characters
+                let mut chars = alloc::vec![*c];
+                while let Some(Node::FoldedChar(c)) = nodes.peek() {
+                    chars.push(*c);
+                    nodes.next();
+                }
+                self.folded(&chars)?;
+            } else {
+                self.node(node)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn alternation(&mut self, branches: &[Node]) -> Result<(), RegexError> {
+        let mut jumps = Vec::new();
+        let mut branches = branches.iter().peekable();
+        while let Some(branch) = branches.next() {
+            if branches.peek().is_some() {
+                let split = self.push(Inst::Split(0, 0))?;
+                self.node(branch)?;
+                jumps.push(self.push(Inst::Jump(0))?);
+                let next = self.next();
+                self.patch(split, Inst::Split(split + 1, next));
+            } else {
+                self.node(branch)?;
+            }
+        }
+        let end = self.next();
+        for jump in jumps {
+            self.patch(jump, Inst::Jump(end));
+        }
+        Ok(())
+    }
+
+    fn repeat(
+        &mut self,
+        node: &Node,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    ) -> Result<(), RegexError> {
+        for _ in 0..min {
+            self.node(node)?;
+        }
+        match max {
+            Some(max) => {
+                let mut splits = Vec::new();
+                for _ in min..max {
+                    splits.push(self.push(Inst::Split(0, 0))?);
+                    self.node(node)?;
+                }
+                let end = self.next();
+                for split in splits {
+                    self.patch(split, self.split(split + 1, end, greedy));
+                }
+            }
+            None => {
+                let slot = self.slots;
+                self.slots += 1;
+                let split = self.push(Inst::Split(0, 0))?;
+                self.push(Inst::Save(slot))?;
+                self.node(node)?;
+                let check = self.push(Inst::CheckProgress { slot, exit: 0 })?;
+                self.push(Inst::Jump(split))?;
+                let exit = self.next();
+                self.patch(split, self.split(split + 1, exit, greedy));
+                self.patch(check, Inst::CheckProgress { slot, exit });
+            }
+        }
+        Ok(())
+    }
+
+    fn split(&self, body: usize, exit: usize, greedy: bool) -> Inst {
+        if greedy {
+            Inst::Split(body, exit)
+        } else {
+            Inst::Split(exit, body)
+        }
+    }
+
+    fn folded(&mut self, chars: &[char]) -> Result<(), RegexError> {
+        let s = chars.iter().collect::<String>();
+        self.push(Inst::Folded(self.mapper.fold_string(&s).into_owned()))?;
+        Ok(())
+    }
+}
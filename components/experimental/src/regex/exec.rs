@@ -0,0 +1,448 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use icu_segmenter::{GraphemeClusterSegmenterBorrowed, WordSegmenterBorrowed};
+use writeable::Writeable;
+
+use super::compile::Inst;
+use super::parse::Assertion;
+use super::{Regex, RegexMatchError};
+
+/// Text that can be matched against, indexed by code units.
+pub(crate) trait Input: Copy {
+    fn len(self) -> usize;
+
+    /// Returns the code point starting at `pos` and the position after it.
+    fn next(self, pos: usize) -> Option<(u32, usize)>;
+
+    /// Returns the code point ending at `pos` and the position before it.
+    fn prev(self, pos: usize) -> Option<(u32, usize)>;
+
+    fn grapheme_boundaries(self, segmenter: GraphemeClusterSegmenterBorrowed) -> Vec<usize>;
+
+    fn word_boundaries(self, segmenter: WordSegmenterBorrowed) -> Vec<usize>;
+}
+
+impl Input for &str {
+    fn len(self) -> usize {
+        str::len(self)
+    }
+
+    fn next(self, pos: usize) -> Option<(u32, usize)> {
+        let c = self.get(pos..)?.chars().next()?;
+        Some((c as u32, pos + c.len_utf8()))
+    }
+
+    fn prev(self, pos: usize) -> Option<(u32, usize)> {
+        let c = self.get(..pos)?.chars().next_back()?;
+        Some((c as u32, pos - c.len_utf8()))
+    }
+
+    fn grapheme_boundaries(self, segmenter: GraphemeClusterSegmenterBorrowed) -> Vec<usize> {
+        segmenter.segment_str(self).collect()
+    }
+
+    fn word_boundaries(self, segmenter: WordSegmenterBorrowed) -> Vec<usize> {
+        segmenter.segment_str(self).collect()
+    }
+}
+
+impl Input for &[u16] {
+    fn len(self) -> usize {
+        <[u16]>::len(self)
+    }
+
+    fn next(self, pos: usize) -> Option<(u32, usize)> {
+        let unit = *self.get(pos)?;
+        if (0xD800..0xDC00).contains(&unit) {
+            if let Some(&trail) = self
+                .get(pos + 1)
+                .filter(|&&t| (0xDC00..0xE000).contains(&t))
+            {
+                let c = 0x10000 + ((u32::from(unit) - 0xD800) << 10) + (u32::from(trail) - 0xDC00);
+                return Some((c, pos + 2));
+            }
+        }
+        Some((u32::from(unit), pos + 1))
+    }
+
+    fn prev(self, pos: usize) -> Option<(u32, usize)> {
+        let unit = *self.get(pos.checked_sub(1)?)?;
+        if (0xDC00..0xE000).contains(&unit) && pos >= 2 {
+            if let Some(&lead) = self
+                .get(pos - 2)
+                .filter(|&&l| (0xD800..0xDC00).contains(&l))
+            {
+                let c = 0x10000 + ((u32::from(lead) - 0xD800) << 10) + (u32::from(unit) - 0xDC00);
+                return Some((c, pos - 2));
+            }
+        }
+        Some((u32::from(unit), pos - 1))
+    }
+
+    fn grapheme_boundaries(self, segmenter: GraphemeClusterSegmenterBorrowed) -> Vec<usize> {
+        segmenter.segment_utf16(self).collect()
+    }
+
+    fn word_boundaries(self, segmenter: WordSegmenterBorrowed) -> Vec<usize> {
+        segmenter.segment_utf16(self).collect()
+    }
+}
+
+/// Whether a code point is a line terminator for `.`, `^` and `$`.
+fn is_line_terminator(c: u32) -> bool {
+    matches!(c, 0x0A..=0x0D | 0x85 | 0x2028 | 0x2029)
+}
+
+/// Strips the written text from the start of a string, failing if it is not a prefix.
+struct StripPrefix<'a>(&'a str);
+
+impl fmt::Write for StripPrefix<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+enum Frame {
+    /// Continue at an instruction and position.
+    Backtrack(usize, usize),
+    /// Restore the previous value of a slot.
+    Restore(usize, Option<usize>),
+}
+
+/// A backtracking matcher of a [`Regex`] over one input.
+#[derive(Debug)]
+pub(crate) struct Matcher<'r, I> {
+    regex: &'r Regex,
+    input: I,
+    /// Boundaries for `\X` and `\b{g}`, computed when first needed.
+    graphemes: Option<Vec<usize>>,
+    /// Boundaries for `\b{w}`, computed when first needed.
+    words: Option<Vec<usize>>,
+    /// The number of steps taken for the current search.
+    steps: usize,
+}
+
+impl<'r, I: Input> Matcher<'r, I> {
+    pub(crate) fn new(regex: &'r Regex, input: I) -> Self {
+        Self {
+            regex,
+            input,
+            graphemes: None,
+            words: None,
+            steps: 0,
+        }
+    }
+
+    pub(crate) fn input(&self) -> I {
+        self.input
+    }
+
+    /// Finds the leftmost match starting at or after `start`, returning the slots of the
+    /// capture groups.
+    pub(crate) fn search(
+        &mut self,
+        mut start: usize,
+    ) -> Result<Option<Vec<Option<usize>>>, RegexMatchError> {
+        let mut slots = vec![None; self.regex.program.slots];
+        self.steps = 0;
+        loop {
+            if self.run(0, start, &mut slots)?.is_some() {
+                slots.truncate(2 * self.regex.group_names.len());
+                return Ok(Some(slots));
+            }
+            let Some((_, next)) = self.input.next(start) else {
+                return Ok(None);
+            };
+            start = next;
+        }
+    }
+
+    /// Runs the program from an instruction and position, returning the position at
+    /// which [`Inst::Match`] or [`Inst::LookEnd`] was reached.
+    ///
+    /// On success, the slots hold the values of the successful path. On failure, they
+    /// are unchanged.
+    ///
+    /// Fails with an error if the step limit of the regex is exceeded.
+    fn run(
+        &mut self,
+        pc: usize,
+        pos: usize,
+        slots: &mut [Option<usize>],
+    ) -> Result<Option<usize>, RegexMatchError> {
+        let mut stack = vec![Frame::Backtrack(pc, pos)];
+        while let Some(frame) = stack.pop() {
+            let (mut pc, mut pos) = match frame {
+                Frame::Backtrack(pc, pos) => (pc, pos),
+                Frame::Restore(slot, value) => {
+                    if let Some(s) = slots.get_mut(slot) {
+                        *s = value;
+                    }
+                    continue;
+                }
+            };
+            loop {
+                self.steps += 1;
+                if self
+                    .regex
+                    .step_limit
+                    .is_some_and(|limit| self.steps > limit)
+                {
+                    return Err(RegexMatchError::StepLimitExceeded);
+                }
+                let Some(inst) = self.regex.program.insts.get(pc) else {
+                    break;
+                };
+                let next = match inst {
+                    Inst::Match | Inst::LookEnd => return Ok(Some(pos)),
+                    Inst::Char(c) => self
+                        .input
+                        .next(pos)
+                        .filter(|&(cp, _)| cp == *c as u32)
+                        .map(|(_, next)| next),
+                    Inst::Folded(s) => self.match_folded(s, pos),
+                    Inst::Set(i) => {
+                        let Some(set) = self.regex.sets.get(*i) else {
+                            break;
+                        };
+                        let single = self
+                            .input
+                            .next(pos)
+                            .filter(|&(c, _)| set.code_points.contains32(c))
+                            .map(|(_, next)| next);
+                        if set.strings.is_empty() {
+                            single
+                        } else {
+                            let mut ends = Vec::new();
+                            for s in &set.strings {
+                                let end = if set.case_insensitive {
+                                    self.match_folded(s, pos)
+                                } else {
+                                    self.match_exact(s, pos)
+                                };
+                                ends.extend(end);
+                            }
+                            ends.extend(single);
+                            // Try the longest match first
+                            let mut ends = ends.into_iter();
+                            let first = ends.next();
+                            for end in ends.rev() {
+                                stack.push(Frame::Backtrack(pc + 1, end));
+                            }
+                            first
+                        }
+                    }
+                    Inst::Any { dot_all } => self
+                        .input
+                        .next(pos)
+                        .filter(|&(c, _)| *dot_all || !is_line_terminator(c))
+                        .map(|(_, next)| next),
+                    Inst::Grapheme => {
+                        if pos >= self.input.len() {
+                            None
+                        } else {
+                            let boundaries = self.graphemes();
+                            let i = boundaries.partition_point(|&b| b <= pos);
+                            boundaries.get(i).copied()
+                        }
+                    }
+                    Inst::Assert(assertion) => self.assert(*assertion, pos).then_some(pos),
+                    Inst::Save(slot) => {
+                        if let Some(s) = slots.get_mut(*slot) {
+                            stack.push(Frame::Restore(*slot, *s));
+                            *s = Some(pos);
+                        }
+                        Some(pos)
+                    }
+                    Inst::Split(first, second) => {
+                        stack.push(Frame::Backtrack(*second, pos));
+                        pc = *first;
+                        continue;
+                    }
+                    Inst::Jump(target) => {
+                        pc = *target;
+                        continue;
+                    }
+                    Inst::CheckProgress { slot, exit } => {
+                        if slots.get(*slot) == Some(&Some(pos)) {
+                            pc = *exit;
+                            continue;
+                        }
+                        Some(pos)
+                    }
+                    Inst::BackReference {
+                        group,
+                        case_insensitive,
+                    } => self.match_back_reference(slots, *group, *case_insensitive, pos),
+                    Inst::LookAhead { negate, end } => {
+                        let saved = slots.to_vec();
+                        let matched = self.run(pc + 1, pos, slots)?.is_some();
+                        if matched && !negate {
+                            // Keep the captures, restoring them when backtracking
+                            for (slot, value) in saved.into_iter().enumerate() {
+                                if slots.get(slot) != Some(&value) {
+                                    stack.push(Frame::Restore(slot, value));
+                                }
+                            }
+                        } else {
+                            slots.copy_from_slice(&saved);
+                        }
+                        if matched == *negate {
+                            break;
+                        }
+                        pc = *end;
+                        continue;
+                    }
+                };
+                match next {
+                    Some(next) => {
+                        pos = next;
+                        pc += 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Matches a string exactly, returning the end position.
+    fn match_exact(&self, s: &str, mut pos: usize) -> Option<usize> {
+        for c in s.chars() {
+            let (cp, next) = self.input.next(pos)?;
+            if cp != c as u32 {
+                return None;
+            }
+            pos = next;
+        }
+        Some(pos)
+    }
+
+    /// Matches whole characters whose full case foldings concatenate to a folded string,
+    /// returning the end position.
+    fn match_folded(&self, s: &str, mut pos: usize) -> Option<usize> {
+        let mut rest = StripPrefix(s);
+        let mut buf = [0; 4];
+        while !rest.0.is_empty() {
+            let (cp, next) = self.input.next(pos)?;
+            let c = char::from_u32(cp)?;
+            self.regex
+                .casemapper
+                .as_borrowed()
+                .fold(c.encode_utf8(&mut buf))
+                .write_to(&mut rest)
+                .ok()?;
+            pos = next;
+        }
+        Some(pos)
+    }
+
+    fn match_back_reference(
+        &self,
+        slots: &[Option<usize>],
+        group: usize,
+        case_insensitive: bool,
+        mut pos: usize,
+    ) -> Option<usize> {
+        let (Some(Some(start)), Some(Some(end))) = (
+            slots.get(2 * group).copied(),
+            slots.get(2 * group + 1).copied(),
+        ) else {
+            // A reference to a group that did not participate fails
+            return None;
+        };
+        if case_insensitive {
+            let mut folded = String::new();
+            let mut buf = [0; 4];
+            let mut i = start;
+            while i < end {
+                let (cp, next) = self.input.next(i)?;
+                let c = char::from_u32(cp)?;
+                let _infallible = self
+                    .regex
+                    .casemapper
+                    .as_borrowed()
+                    .fold(c.encode_utf8(&mut buf))
+                    .write_to(&mut folded);
+                i = next;
+            }
+            return self.match_folded(&folded, pos);
+        }
+        let mut i = start;
+        while i < end {
+            let (expected, next) = self.input.next(i)?;
+            let (actual, after) = self.input.next(pos)?;
+            if expected != actual {
+                return None;
+            }
+            i = next;
+            pos = after;
+        }
+        Some(pos)
+    }
+
+    /// Returns no boundaries if the segmenter is not loaded, which is only the case for
+    /// programs without the instructions that call this.
+    fn graphemes(&mut self) -> &[usize] {
+        let (input, segmenter) = (self.input, self.regex.graphemes.as_ref());
+        self.graphemes.get_or_insert_with(|| {
+            segmenter
+                .map(|s| input.grapheme_boundaries(s.as_borrowed()))
+                .unwrap_or_default()
+        })
+    }
+
+    fn words(&mut self) -> &[usize] {
+        let (input, segmenter) = (self.input, self.regex.words.as_ref());
+        self.words.get_or_insert_with(|| {
+            segmenter
+                .map(|s| input.word_boundaries(s.as_borrowed()))
+                .unwrap_or_default()
+        })
+    }
+
+    fn is_word(&self, c: Option<(u32, usize)>) -> bool {
+        c.is_some_and(|(c, _)| self.regex.word.contains32(c))
+    }
+
+    fn assert(&mut self, assertion: Assertion, pos: usize) -> bool {
+        let len = self.input.len();
+        let prev = self.input.prev(pos);
+        let next = self.input.next(pos);
+        // Whether `pos` is between the CR and LF of a CR LF sequence
+        let in_crlf = prev.is_some_and(|(c, _)| c == 0x0D) && next.is_some_and(|(c, _)| c == 0x0A);
+        match assertion {
+            Assertion::TextStart => pos == 0,
+            Assertion::TextEnd => pos == len,
+            Assertion::TextEndLine => {
+                pos == len
+                    || next.is_some_and(|(c, after)| {
+                        is_line_terminator(c)
+                            && !in_crlf
+                            && (after == len
+                                || (c == 0x0D && self.input.next(after) == Some((0x0A, len))))
+                    })
+            }
+            Assertion::LineStart => {
+                pos == 0
+                    || (pos < len && prev.is_some_and(|(c, _)| is_line_terminator(c)) && !in_crlf)
+            }
+            Assertion::LineEnd => {
+                pos == len || (next.is_some_and(|(c, _)| is_line_terminator(c)) && !in_crlf)
+            }
+            Assertion::WordBoundary => self.is_word(prev) != self.is_word(next),
+            Assertion::NotWordBoundary => self.is_word(prev) == self.is_word(next),
+            Assertion::UnicodeWordBoundary => self.words().binary_search(&pos).is_ok(),
+            Assertion::GraphemeBoundary => self.graphemes().binary_search(&pos).is_ok(),
+        }
+    }
+}
@@ -0,0 +1,785 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Unicode-aware regular expressions, following [UTS #18: Unicode Regular Expressions](https://www.unicode.org/reports/tr18/)
+//! and the syntax of ICU regular expressions.
+//!
+//! Character classes are parsed as [UnicodeSets](crate::unicodeset_parse), so that they
+//! support properties and set operations. `\X` and `\b{w}` use the segmenters of
+//! [`icu_segmenter`], which are only loaded for patterns that use them, and
+//! case-insensitive matching uses full case folding.
+//!
+//! See [`Regex`] for the supported syntax.
+//!
+//! ✨ *Enabled with the `segmenter` Cargo feature.*
+//!
+//! # Examples
+//!
+//! ```
+//! use icu::experimental::regex::Regex;
+//!
+//! let regex = Regex::try_new(r"[\p{Script=Greek}&&\p{L}]+", Default::default()).unwrap();
+//! assert_eq!(regex.find("Ἀθῆναι 2004"), Ok(Some(0..14)));
+//! ```
+
+// https://github.com/unicode-org/icu4x/blob/main/documents/process/boilerplate.md#library-annotations
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::indexing_slicing,
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::exhaustive_structs,
+        clippy::exhaustive_enums,
+        clippy::trivially_copy_pass_by_ref,
+        missing_debug_implementations,
+    )
+)]
+#![warn(missing_docs)]
+
+mod compile;
+mod exec;
+mod parse;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use icu_casemap::provider::{CaseMapUnfoldV1, CaseMapV1};
+use icu_casemap::{CaseMapCloser, CaseMapper};
+use icu_collections::codepointinvlist::CodePointInversionList;
+use icu_properties::provider::*;
+use icu_provider::prelude::*;
+use icu_segmenter::options::WordBreakOptions;
+use icu_segmenter::provider::{
+    SegmenterBreakGraphemeClusterV1, SegmenterBreakWordOverrideV1, SegmenterBreakWordV1,
+    SegmenterDictionaryAutoV1, SegmenterDictionaryExtendedV1,
+};
+use icu_segmenter::{GraphemeClusterSegmenter, WordSegmenter};
+
+use crate::unicodeset_parse::{self, ParseErrorKind};
+use compile::{Compiler, Program};
+use exec::{Input, Matcher};
+use parse::{CharSet, Parser};
+
+/// The error type returned when compiling a [`Regex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, displaydoc::Display)]
+#[non_exhaustive]
+pub enum RegexError {
+    /// The pattern is invalid at the given byte offset.
+    #[displaydoc("Invalid pattern at offset {0}")]
+    Syntax(usize),
+    /// The pattern uses syntax at the given byte offset that is not supported.
+    #[displaydoc("Unsupported syntax at offset {0}")]
+    Unsupported(usize),
+    /// The character class at the given byte offset is not a valid UnicodeSet.
+    #[displaydoc("Invalid character class at offset {0}: {1}")]
+    Set(usize, ParseErrorKind),
+    /// The pattern compiles to a program that is too large, usually because of large
+    /// counted repetitions, or nests groups or classes too deeply.
+    #[displaydoc("The pattern is too large")]
+    TooLarge,
+    /// Data could not be loaded.
+    #[displaydoc("{0}")]
+    Data(DataError),
+}
+
+impl core::error::Error for RegexError {}
+
+impl From<DataError> for RegexError {
+    fn from(e: DataError) -> Self {
+        Self::Data(e)
+    }
+}
+
+/// The error type returned when matching a [`Regex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, displaydoc::Display)]
+#[non_exhaustive]
+pub enum RegexMatchError {
+    /// The match took more steps than allowed by [`RegexOptions::step_limit`].
+    #[displaydoc("The step limit was exceeded")]
+    StepLimitExceeded,
+}
+
+impl core::error::Error for RegexMatchError {}
+
+/// Options for compiling a [`Regex`].
+///
+/// These can also be set within a pattern with the `(?i)`, `(?m)` and `(?s)` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RegexOptions {
+    /// Match case-insensitively, using full case folding.
+    pub case_insensitive: bool,
+    /// Make `^` and `$` match at the start and end of lines, as well as of the text.
+    pub multiline: bool,
+    /// Make `.` match line terminators.
+    pub dot_matches_new_line: bool,
+    /// The maximum number of steps of the backtracking matcher for finding one match, or
+    /// `None` for no limit.
+    ///
+    /// A step executes one instruction of the compiled pattern, and records a bounded amount
+    /// of backtracking state, so this limits both the time and the memory used for a match.
+    /// If the limit is exceeded, matching fails with [`RegexMatchError::StepLimitExceeded`].
+    ///
+    /// Defaults to [`RegexOptions::DEFAULT_STEP_LIMIT`].
+    pub step_limit: Option<usize>,
+}
+
+impl RegexOptions {
+    /// The default [`RegexOptions::step_limit`].
+    pub const DEFAULT_STEP_LIMIT: usize = 1_000_000;
+}
+
+impl Default for RegexOptions {
+    fn default() -> Self {
+        Self {
+            case_insensitive: false,
+            multiline: false,
+            dot_matches_new_line: false,
+            step_limit: Some(Self::DEFAULT_STEP_LIMIT),
+        }
+    }
+}
+
+/// A compiled regular expression, which matches over UTF-8 and UTF-16 text.
+///
+/// This implements the syntax of ICU regular expressions, which conforms to
+/// [UTS #18](https://www.unicode.org/reports/tr18/) level 1 and the level 2
+/// requirements for grapheme clusters, word boundaries and full case folding:
+///
+/// | Syntax | Matches |
+/// |---|---|
+/// | `.` | Any character, except line terminators unless `(?s)` |
+/// | `[...]` | A [UnicodeSet](crate::unicodeset_parse), with `&&` and `--` as set operators and `\q{ab\|cd}` for strings |
+/// | `\p{...}`, `\P{...}`, `\pL` | A property, or its complement |
+/// | `\d`, `\s`, `\w` | `\p{Nd}`, `\p{White_Space}`, and word characters as in UTS #18 Annex C; uppercase for the complement |
+/// | `\X` | An extended grapheme cluster |
+/// | `^`, `$`, `\A`, `\Z`, `\z` | Start and end of text, or of lines with `(?m)` |
+/// | `\b`, `\B` | A boundary between `\w` and `\W`, or its absence |
+/// | `\b{w}`, `\b{g}` | A word or grapheme cluster boundary |
+/// | `(...)`, `(?<name>...)`, `(?:...)` | Capturing, named and non-capturing groups |
+/// | `(?=...)`, `(?!...)` | Lookahead |
+/// | `\1`, `\k<name>` | Backreferences |
+/// | `*`, `+`, `?`, `{n}`, `{n,}`, `{n,m}` | Repetition, lazy if followed by `?` |
+/// | `(?ims-ims)`, `(?ims-ims:...)` | Flags for the rest of the group, or for a group |
+///
+/// Characters can be escaped as `\t`, `\n`, `\r`, `\f`, `\a`, `\e`, `\0ooo`, `\xhh`,
+/// `\x{h...}`, `\uhhhh`, `\Uhhhhhhhh` and `\cX`, and literal text as `\Q...\E`.
+/// Lookbehind, atomic groups, possessive quantifiers and `\N{...}` are not supported.
+///
+/// Matching uses backtracking, so some patterns take exponential time on some inputs.
+/// [`RegexOptions::step_limit`] bounds the work done for one match, and can be lowered for
+/// untrusted patterns or text, or removed.
+/// Positions in results are byte offsets for UTF-8 and code unit offsets for UTF-16.
+///
+/// # Examples
+///
+/// ```
+/// use icu::experimental::regex::{Regex, RegexMatchError, RegexOptions};
+///
+/// let regex = Regex::try_new(r"(?<word>\w+)\s+\k<word>", Default::default()).unwrap();
+/// let captures = regex.captures("it is is").unwrap().unwrap();
+/// assert_eq!(captures.get(0), Some(3..8));
+/// assert_eq!(captures.name("word"), Some(3..5));
+///
+/// // Full case folding matches "ß" against "SS"
+/// let mut options = RegexOptions::default();
+/// options.case_insensitive = true;
+/// let regex = Regex::try_new("straße", options).unwrap();
+/// assert_eq!(regex.is_match("STRASSE"), Ok(true));
+///
+/// // `\X` matches a whole grapheme cluster, and `\b{w}` uses word segmentation
+/// let regex = Regex::try_new(r"\b{w}\X\b{w}", Default::default()).unwrap();
+/// assert_eq!(
+///     regex.find_iter("e\u{301} 👍🏽!").collect::<Result<Vec<_>, _>>(),
+///     Ok(vec![0..3, 3..4, 4..12, 12..13])
+/// );
+///
+/// // The same matches in UTF-16 are in code units
+/// let utf16 = "e\u{301} 👍🏽!".encode_utf16().collect::<Vec<_>>();
+/// assert_eq!(
+///     regex.find_iter_utf16(&utf16).collect::<Result<Vec<_>, _>>(),
+///     Ok(vec![0..2, 2..3, 3..7, 7..8])
+/// );
+///
+/// // Matching fails instead of backtracking for too long, unless the step limit is removed
+/// let regex = Regex::try_new("(a*)*b", Default::default()).unwrap();
+/// assert_eq!(
+///     regex.find(&"a".repeat(30)),
+///     Err(RegexMatchError::StepLimitExceeded)
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Regex {
+    program: Program,
+    sets: Vec<CharSet>,
+    group_names: Vec<Option<String>>,
+    /// The characters of `\w`, for `\b`.
+    word: CodePointInversionList<'static>,
+    casemapper: CaseMapper,
+    /// Only loaded if the pattern uses `\X` or `\b{g}`.
+    graphemes: Option<GraphemeClusterSegmenter>,
+    /// Only loaded if the pattern uses `\b{w}`.
+    words: Option<WordSegmenter>,
+    step_limit: Option<usize>,
+}
+
+impl Regex {
+    /// Compiles a pattern using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub fn try_new(pattern: &str, options: RegexOptions) -> Result<Self, RegexError> {
+        Self::try_new_unstable(
+            &icu_properties::provider::Baked,
+            &icu_casemap::provider::Baked,
+            &icu_segmenter::provider::Baked,
+            pattern,
+            options,
+        )
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc = icu_provider::gen_buffer_unstable_docs!(BUFFER, Self::try_new)]
+    pub fn try_new_with_buffer_provider(
+        provider: &(impl BufferProvider + ?Sized),
+        pattern: &str,
+        options: RegexOptions,
+    ) -> Result<Self, RegexError> {
+        Self::try_new_unstable(
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            pattern,
+            options,
+        )
+    }
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::try_new)]
+    pub fn try_new_unstable<PP, PC, PS>(
+        properties_provider: &PP,
+        casemap_provider: &PC,
+        segmenter_provider: &PS,
+        pattern: &str,
+        options: RegexOptions,
+    ) -> Result<Self, RegexError>
+    where
+        PP: ?Sized
+            + DataProvider<PropertyBinaryAlphabeticV1>
+            + DataProvider<PropertyBinaryAsciiHexDigitV1>
+            + DataProvider<PropertyBinaryBidiControlV1>
+            + DataProvider<PropertyBinaryBidiMirroredV1>
+            + DataProvider<PropertyBinaryCasedV1>
+            + DataProvider<PropertyBinaryCaseIgnorableV1>
+            + DataProvider<PropertyBinaryChangesWhenCasefoldedV1>
+            + DataProvider<PropertyBinaryChangesWhenCasemappedV1>
+            + DataProvider<PropertyBinaryChangesWhenLowercasedV1>
+            + DataProvider<PropertyBinaryChangesWhenNfkcCasefoldedV1>
+            + DataProvider<PropertyBinaryChangesWhenTitlecasedV1>
+            + DataProvider<PropertyBinaryChangesWhenUppercasedV1>
+            + DataProvider<PropertyBinaryDashV1>
+            + DataProvider<PropertyBinaryDefaultIgnorableCodePointV1>
+            + DataProvider<PropertyBinaryDeprecatedV1>
+            + DataProvider<PropertyBinaryDiacriticV1>
+            + DataProvider<PropertyBinaryEmojiComponentV1>
+            + DataProvider<PropertyBinaryEmojiModifierBaseV1>
+            + DataProvider<PropertyBinaryEmojiModifierV1>
+            + DataProvider<PropertyBinaryEmojiPresentationV1>
+            + DataProvider<PropertyBinaryEmojiV1>
+            + DataProvider<PropertyBinaryExtendedPictographicV1>
+            + DataProvider<PropertyBinaryExtenderV1>
+            + DataProvider<PropertyBinaryGraphemeBaseV1>
+            + DataProvider<PropertyBinaryGraphemeExtendV1>
+            + DataProvider<PropertyBinaryHexDigitV1>
+            + DataProvider<PropertyBinaryIdContinueV1>
+            + DataProvider<PropertyBinaryIdeographicV1>
+            + DataProvider<PropertyBinaryIdsBinaryOperatorV1>
+            + DataProvider<PropertyBinaryIdStartV1>
+            + DataProvider<PropertyBinaryIdsTrinaryOperatorV1>
+            + DataProvider<PropertyBinaryJoinControlV1>
+            + DataProvider<PropertyBinaryLogicalOrderExceptionV1>
+            + DataProvider<PropertyBinaryLowercaseV1>
+            + DataProvider<PropertyBinaryMathV1>
+            + DataProvider<PropertyBinaryNoncharacterCodePointV1>
+            + DataProvider<PropertyBinaryPatternSyntaxV1>
+            + DataProvider<PropertyBinaryPatternWhiteSpaceV1>
+            + DataProvider<PropertyBinaryQuotationMarkV1>
+            + DataProvider<PropertyBinaryRadicalV1>
+            + DataProvider<PropertyBinaryRegionalIndicatorV1>
+            + DataProvider<PropertyBinarySentenceTerminalV1>
+            + DataProvider<PropertyBinarySoftDottedV1>
+            + DataProvider<PropertyBinaryTerminalPunctuationV1>
+            + DataProvider<PropertyBinaryUnifiedIdeographV1>
+            + DataProvider<PropertyBinaryUppercaseV1>
+            + DataProvider<PropertyBinaryVariationSelectorV1>
+            + DataProvider<PropertyBinaryWhiteSpaceV1>
+            + DataProvider<PropertyBinaryXidContinueV1>
+            + DataProvider<PropertyBinaryXidStartV1>
+            + DataProvider<PropertyEnumCanonicalCombiningClassV1>
+            + DataProvider<PropertyEnumGeneralCategoryV1>
+            + DataProvider<PropertyEnumGraphemeClusterBreakV1>
+            + DataProvider<PropertyEnumLineBreakV1>
+            + DataProvider<PropertyEnumScriptV1>
+            + DataProvider<PropertyEnumSentenceBreakV1>
+            + DataProvider<PropertyEnumWordBreakV1>
+            + DataProvider<PropertyNameParseCanonicalCombiningClassV1>
+            + DataProvider<PropertyNameParseGeneralCategoryMaskV1>
+            + DataProvider<PropertyNameParseGraphemeClusterBreakV1>
+            + DataProvider<PropertyNameParseLineBreakV1>
+            + DataProvider<PropertyNameParseScriptV1>
+            + DataProvider<PropertyNameParseSentenceBreakV1>
+            + DataProvider<PropertyNameParseWordBreakV1>
+            + DataProvider<PropertyScriptWithExtensionsV1>,
+        PC: DataProvider<CaseMapV1> + DataProvider<CaseMapUnfoldV1> + ?Sized,
+        PS: DataProvider<SegmenterBreakGraphemeClusterV1>
+            + DataProvider<SegmenterBreakWordV1>
+            + DataProvider<SegmenterBreakWordOverrideV1>
+            + DataProvider<SegmenterDictionaryAutoV1>
+            + DataProvider<SegmenterDictionaryExtendedV1>
+            + ?Sized,
+    {
+        let closer = CaseMapCloser::try_new_unstable(casemap_provider)?;
+        let casemapper = CaseMapper::try_new_unstable(casemap_provider)?;
+        let parse_set =
+            |source: &str| unicodeset_parse::parse_unstable(source, properties_provider);
+
+        let (word, _) = parse_set(parse::WORD).map_err(|_| {
+            RegexError::Data(DataError::custom("Could not load the properties of \\w"))
+        })?;
+        let ast = Parser::new(
+            pattern,
+            options,
+            parse_set,
+            closer.as_borrowed(),
+            casemapper.as_borrowed(),
+        )
+        .parse()?;
+        let program =
+            Compiler::compile(&ast.node, ast.group_names.len(), casemapper.as_borrowed())?;
+
+        Ok(Self {
+            sets: ast.sets,
+            group_names: ast.group_names,
+            word: word.code_points().clone(),
+            casemapper,
+            graphemes: program
+                .uses_graphemes()
+                .then(|| GraphemeClusterSegmenter::try_new_unstable(segmenter_provider))
+                .transpose()?,
+            words: program
+                .uses_words()
+                .then(|| {
+                    WordSegmenter::try_new_dictionary_unstable(
+                        segmenter_provider,
+                        WordBreakOptions::default(),
+                    )
+                })
+                .transpose()?,
+            program,
+            step_limit: options.step_limit,
+        })
+    }
+
+    /// Returns whether the regular expression matches anywhere in a string.
+    pub fn is_match(&self, text: &str) -> Result<bool, RegexMatchError> {
+        self.find(text).map(|m| m.is_some())
+    }
+
+    /// Returns whether the regular expression matches anywhere in a UTF-16 string.
+    ///
+    /// Unpaired surrogates only match `.`, `\X` and complemented classes.
+    pub fn is_match_utf16(&self, text: &[u16]) -> Result<bool, RegexMatchError> {
+        self.find_utf16(text).map(|m| m.is_some())
+    }
+
+    /// Returns the byte range of the leftmost match in a string.
+    pub fn find(&self, text: &str) -> Result<Option<Range<usize>>, RegexMatchError> {
+        Ok(self.captures_at(text, 0)?.and_then(|c| c.get(0)))
+    }
+
+    /// Returns the code unit range of the leftmost match in a UTF-16 string.
+    pub fn find_utf16(&self, text: &[u16]) -> Result<Option<Range<usize>>, RegexMatchError> {
+        Ok(self.captures_at(text, 0)?.and_then(|c| c.get(0)))
+    }
+
+    /// Returns the byte ranges of the successive non-overlapping matches in a string.
+    ///
+    /// If a match fails with an error, the iterator returns the error and ends.
+    pub fn find_iter<'r, 't>(&'r self, text: &'t str) -> Matches<'r, &'t str> {
+        Matches {
+            matcher: Matcher::new(self, text),
+            pos: Some(0),
+        }
+    }
+
+    /// Returns the code unit ranges of the successive non-overlapping matches in a UTF-16
+    /// string.
+    ///
+    /// If a match fails with an error, the iterator returns the error and ends.
+    pub fn find_iter_utf16<'r, 't>(&'r self, text: &'t [u16]) -> Matches<'r, &'t [u16]> {
+        Matches {
+            matcher: Matcher::new(self, text),
+            pos: Some(0),
+        }
+    }
+
+    /// Returns the byte ranges of the capture groups of the leftmost match in a string.
+    pub fn captures(&self, text: &str) -> Result<Option<Captures<'_>>, RegexMatchError> {
+        self.captures_at(text, 0)
+    }
+
+    /// Returns the code unit ranges of the capture groups of the leftmost match in a
+    /// UTF-16 string.
+    pub fn captures_utf16(&self, text: &[u16]) -> Result<Option<Captures<'_>>, RegexMatchError> {
+        self.captures_at(text, 0)
+    }
+
+    /// Returns the number of capture groups, including group 0 for the whole match.
+    pub fn group_count(&self) -> usize {
+        self.group_names.len()
+    }
+
+    fn captures_at(
+        &self,
+        text: impl Input,
+        start: usize,
+    ) -> Result<Option<Captures<'_>>, RegexMatchError> {
+        self.captures_with(&mut Matcher::new(self, text), start)
+    }
+
+    fn captures_with<'r>(
+        &'r self,
+        matcher: &mut Matcher<'r, impl Input>,
+        start: usize,
+    ) -> Result<Option<Captures<'r>>, RegexMatchError> {
+        let Some(slots) = matcher.search(start)? else {
+            return Ok(None);
+        };
+        Ok(Some(Captures {
+            group_names: &self.group_names,
+            groups: slots
+                .chunks_exact(2)
+                .map(|slots| match *slots {
+                    [Some(start), Some(end)] => Some(start..end),
+                    _ => None,
+                })
+                .collect(),
+        }))
+    }
+}
+
+/// The capture groups of a match, returned by [`Regex::captures`] and
+/// [`Regex::captures_utf16`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'r> {
+    group_names: &'r [Option<String>],
+    groups: Vec<Option<Range<usize>>>,
+}
+
+impl Captures<'_> {
+    /// Returns the range of a capture group, where group 0 is the whole match.
+    ///
+    /// Returns `None` if there is no such group, or if it did not participate in the match.
+    pub fn get(&self, group: usize) -> Option<Range<usize>> {
+        self.groups.get(group).cloned().flatten()
+    }
+
+    /// Returns the range of a named capture group.
+    pub fn name(&self, name: &str) -> Option<Range<usize>> {
+        let group = self
+            .group_names
+            .iter()
+            .position(|n| n.as_deref() == Some(name))?;
+        self.get(group)
+    }
+
+    /// Returns the ranges of all capture groups, starting with group 0.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Option<Range<usize>>> + '_ {
+        self.groups.iter().cloned()
+    }
+}
+
+/// An iterator over the matches of a [`Regex`], returned by [`Regex::find_iter`] and
+/// [`Regex::find_iter_utf16`].
+#[derive(Debug)]
+pub struct Matches<'r, T> {
+    /// The matcher, which keeps the segmentation of the text between matches.
+    matcher: Matcher<'r, T>,
+    /// The position to search from, or `None` when done.
+    pos: Option<usize>,
+}
+
+impl<T: Input> Iterator for Matches<'_, T> {
+    type Item = Result<Range<usize>, RegexMatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slots = match self.matcher.search(self.pos?) {
+            Ok(slots) => slots,
+            Err(e) => {
+                self.pos = None;
+                return Some(Err(e));
+            }
+        };
+        let m = slots.and_then(|slots| match *slots {
+            [Some(start), Some(end), ..] => Some(start..end),
+            _ => None,
+        });
+        self.pos = match &m {
+            // After an empty match, continue after the next character
+            Some(m) if m.is_empty() => self.matcher.input().next(m.end).map(|(_, next)| next),
+            Some(m) => Some(m.end),
+            None => None,
+        };
+        m.map(Ok)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "compiled_data")]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, text: &str) -> Option<Range<usize>> {
+        Regex::try_new(pattern, Default::default())
+            .unwrap()
+            .find(text)
+            .unwrap()
+    }
+
+    fn find_ci(pattern: &str, text: &str) -> Option<Range<usize>> {
+        let options = RegexOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        Regex::try_new(pattern, options)
+            .unwrap()
+            .find(text)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_literals_and_escapes() {
+        assert_eq!(find("abc", "xxabcxx"), Some(2..5));
+        assert_eq!(find(r"a\.c", "abc a.c"), Some(4..7));
+        assert_eq!(find(r"\x41B\x{1F600}\U0001F601", "AB😀😁"), Some(0..10));
+        assert_eq!(find(r"\t\n\cA\0101", "\t\n\u{1}A"), Some(0..4));
+        assert_eq!(find(r"\Q.*\E+", ".**"), Some(0..3));
+        assert_eq!(find("(?#comment)a", "a"), Some(0..1));
+        assert_eq!(find("", "abc"), Some(0..0));
+    }
+
+    #[test]
+    fn test_classes() {
+        assert_eq!(find(r"\d+", "x١٢3"), Some(1..6));
+        assert_eq!(find(r"\w+", "  naïve_日本 "), Some(2..15));
+        assert_eq!(find(r"\W", "ab-c"), Some(2..3));
+        assert_eq!(find(r"\s", "a\u{3000}b"), Some(1..4));
+        assert_eq!(find(r"\p{Lu}\pL", "abCd"), Some(2..4));
+        assert_eq!(find(r"\P{L}", "abc1"), Some(3..4));
+        assert_eq!(find("[^a-c]", "abcd"), Some(3..4));
+        assert_eq!(find(r"[a-z&&[^aeiou]]+", "aeibcd"), Some(3..6));
+        assert_eq!(find(r"[\w--\d]+", "12ab34"), Some(2..4));
+        assert_eq!(find(r"[[:Script=Greek:]&[:L:]]+", "α1β"), Some(0..2));
+        assert_eq!(find(r"[\d\s]+", "a1 2b"), Some(1..4));
+        assert_eq!(find("[ a]+", "b a "), Some(1..4));
+        assert_eq!(find(r"[\q{ch|c}]h", "ch"), Some(0..2));
+        assert_eq!(find(r"[\q{ch|c}]$", "ch"), Some(0..2));
+        assert_eq!(find(".", "\n\r\u{2028}a"), Some(5..6));
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        assert_eq!(find("a*", "aaa"), Some(0..3));
+        assert_eq!(find("a*?", "aaa"), Some(0..0));
+        assert_eq!(find("a+?b", "aaab"), Some(0..4));
+        assert_eq!(find("a{2}", "aaa"), Some(0..2));
+        assert_eq!(find("a{2,}", "aaaa"), Some(0..4));
+        assert_eq!(find("a{1,2}?", "aaaa"), Some(0..1));
+        assert_eq!(find("(a|ab)(c|bcd)(d*)", "abcd"), Some(0..4));
+        assert_eq!(find("(a*)*b", "aab"), Some(0..3));
+        assert_eq!(find("(a*)+$", "aac"), Some(3..3));
+        assert_eq!(find("x(?:y|)*z", "xyyz"), Some(0..4));
+    }
+
+    #[test]
+    fn test_groups() {
+        let regex = Regex::try_new(r"(\d+)-(?<b>\d+)(x)?", Default::default()).unwrap();
+        let captures = regex.captures("tel 12-345").unwrap().unwrap();
+        assert_eq!(regex.group_count(), 4);
+        assert_eq!(
+            captures.iter().collect::<Vec<_>>(),
+            [Some(4..10), Some(4..6), Some(7..10), None]
+        );
+        assert_eq!(captures.name("b"), Some(7..10));
+        assert_eq!(captures.name("c"), None);
+
+        assert_eq!(find(r"(a|b)\1", "abba"), Some(1..3));
+        assert_eq!(find(r"(a)|\1b", "b"), None);
+        assert_eq!(find("foo(?=bar)", "foobaz foobar"), Some(7..10));
+        assert_eq!(find("foo(?!bar)", "foobar foobaz"), Some(7..10));
+        let regex = Regex::try_new("(?=(a+))a", Default::default()).unwrap();
+        assert_eq!(regex.captures("aaa").unwrap().unwrap().get(1), Some(0..3));
+    }
+
+    #[test]
+    fn test_anchors() {
+        assert_eq!(find("^b", "ab"), None);
+        assert_eq!(find("a$", "a\n"), Some(0..1));
+        assert_eq!(find("a$", "a\r\n"), Some(0..1));
+        assert_eq!(find(r"a\z", "a\n"), None);
+        assert_eq!(find("(?m)^b$", "a\r\nb\r\nc"), Some(3..4));
+        assert_eq!(find(r"(?m)$\n^", "a\nb"), Some(1..2));
+        assert_eq!(find(r"\bis\b", "this is"), Some(5..7));
+        assert_eq!(find(r"\Bis\b", "this is"), Some(2..4));
+        // `\b{w}` finds word boundaries without `\w`, such as in "can't"
+        assert_eq!(find(r"\b{w}.+?\b{w}", "can't stop"), Some(0..5));
+        assert_eq!(find(r"\b.+?\b", "can't stop"), Some(0..3));
+    }
+
+    #[test]
+    fn test_graphemes() {
+        assert_eq!(find(r"^\X$", "🇨🇭"), Some(0..8));
+        assert_eq!(find(r"^.$", "🇨🇭"), None);
+        assert_eq!(find(r"e\b{g}", "e\u{301}e"), Some(3..4));
+        assert_eq!(find(r"\X\X", "\r\na"), Some(0..3));
+
+        // The segmenters are only loaded for patterns that use them
+        let regex = Regex::try_new("a+", Default::default()).unwrap();
+        assert!(regex.graphemes.is_none() && regex.words.is_none());
+        let regex = Regex::try_new(r"\b{w}", Default::default()).unwrap();
+        assert!(regex.graphemes.is_none() && regex.words.is_some());
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert_eq!(find_ci("hello", "HeLLo"), Some(0..5));
+        assert_eq!(find_ci("ss", "ß"), Some(0..2));
+        assert_eq!(find_ci("ß", "SS"), Some(0..2));
+        assert_eq!(find_ci("s", "ß"), None);
+        assert_eq!(find_ci("Σ+", "σςΣ"), Some(0..6));
+        assert_eq!(find_ci("[a-c]+", "ABC"), Some(0..3));
+        assert_eq!(find_ci("[^a]", "Ab"), Some(1..2));
+        assert_eq!(find_ci(r"\p{Lu}", "k"), Some(0..1));
+        assert_eq!(find_ci("[k]", "\u{212A}"), Some(0..3));
+        assert_eq!(find_ci("[ß]", "xSs"), Some(1..3));
+        assert_eq!(find_ci(r"(a)\1", "aA"), Some(0..2));
+        assert_eq!(find("(?i)a(?-i)b", "AB Ab"), Some(3..5));
+        assert_eq!(find("(?i:a)b", "AB Ab"), Some(3..5));
+        assert_eq!(find_ci("ﬃ", "FFI"), Some(0..3));
+        assert_eq!(find_ci(r"[\p{L}--[a-z]]+", "aBé1"), Some(0..4));
+        assert_eq!(find_ci(r"\p{Lt}", "ǆ"), Some(0..2));
+    }
+
+    #[test]
+    fn test_utf16() {
+        let regex = Regex::try_new(r"\p{L}+", Default::default()).unwrap();
+        let text = "1𝒜b2".encode_utf16().collect::<Vec<_>>();
+        assert_eq!(regex.find_utf16(&text), Ok(Some(1..4)));
+        assert_eq!(regex.is_match_utf16(&[0xD835]), Ok(false));
+
+        let regex = Regex::try_new(".", Default::default()).unwrap();
+        assert_eq!(regex.find_utf16(&[0xDC00, 0x61]), Ok(Some(0..1)));
+
+        let regex = Regex::try_new("", Default::default()).unwrap();
+        assert_eq!(
+            regex.find_iter_utf16(&[0xD835, 0xDC9C]).collect::<Vec<_>>(),
+            [Ok(0..0), Ok(2..2)]
+        );
+    }
+
+    #[test]
+    fn test_find_iter() {
+        let regex = Regex::try_new(r"a*", Default::default()).unwrap();
+        assert_eq!(
+            regex.find_iter("baaé").collect::<Vec<_>>(),
+            [Ok(0..0), Ok(1..3), Ok(3..3), Ok(5..5)]
+        );
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let options = RegexOptions {
+            step_limit: Some(1000),
+            ..Default::default()
+        };
+        let regex = Regex::try_new("(a*)*b", options).unwrap();
+        assert_eq!(regex.find("aab"), Ok(Some(0..3)));
+        assert_eq!(
+            regex.find(&"a".repeat(20)),
+            Err(RegexMatchError::StepLimitExceeded)
+        );
+        // The limit applies to each match separately
+        let regex = Regex::try_new("a", options).unwrap();
+        assert_eq!(regex.find_iter(&"a".repeat(2000)).count(), 2000);
+
+        // Lookaheads count towards the limit, and the iterator ends after an error
+        let regex = Regex::try_new("x|(?=(a*)*b)", options).unwrap();
+        let text = alloc::format!("x{}", "a".repeat(20));
+        assert_eq!(
+            regex.find_iter(&text).collect::<Vec<_>>(),
+            [Ok(0..1), Err(RegexMatchError::StepLimitExceeded)]
+        );
+
+        // There is a limit by default
+        let regex = Regex::try_new("(a*)*b", Default::default()).unwrap();
+        assert_eq!(
+            regex.find(&"a".repeat(30)),
+            Err(RegexMatchError::StepLimitExceeded)
+        );
+        let options = RegexOptions {
+            step_limit: None,
+            ..Default::default()
+        };
+        let regex = Regex::try_new("a", options).unwrap();
+        assert_eq!(regex.find_iter(&"a".repeat(2000)).count(), 2000);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |pattern| {
+            Regex::try_new(pattern, Default::default())
+                .map(|_| pattern)
+                .unwrap_err()
+        };
+        assert_eq!(error("(a"), RegexError::Syntax(0));
+        assert_eq!(error("a)"), RegexError::Syntax(1));
+        assert_eq!(error("*"), RegexError::Syntax(0));
+        assert_eq!(error("a**"), RegexError::Syntax(2));
+        assert_eq!(error("a{2,1}"), RegexError::Syntax(1));
+        assert_eq!(error(r"\2()"), RegexError::Syntax(0));
+        assert_eq!(error(r"\k<x>"), RegexError::Syntax(0));
+        assert_eq!(error("(?<x>)(?<x>)"), RegexError::Syntax(11));
+        assert_eq!(error(r"\y"), RegexError::Syntax(0));
+        assert_eq!(error("(?<=a)"), RegexError::Unsupported(0));
+        assert_eq!(error("a++"), RegexError::Unsupported(1));
+        assert_eq!(error(r"a\N{DIGIT ONE}"), RegexError::Unsupported(1));
+        assert_eq!(
+            error(r"x\p{Foo}"),
+            RegexError::Set(1, ParseErrorKind::UnknownProperty)
+        );
+        assert_eq!(error("a{1000}{1000}"), RegexError::Syntax(7));
+        assert_eq!(error("(a{1000}){1000}"), RegexError::TooLarge);
+        assert_eq!(error("a{1001}"), RegexError::TooLarge);
+        // Deep nesting fails instead of overflowing the stack
+        let nested = alloc::format!("{}a{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert_eq!(error(&nested), RegexError::TooLarge);
+        let nested = alloc::format!("{}a{}", "(?=".repeat(251), ")".repeat(251));
+        assert_eq!(error(&nested), RegexError::TooLarge);
+        let nested = "[".repeat(100_000);
+        assert_eq!(error(&nested), RegexError::TooLarge);
+        // Repetitions count towards the nesting
+        let nested = alloc::format!("{}a{}", "(?:".repeat(130), ")*".repeat(130));
+        assert_eq!(error(&nested), RegexError::TooLarge);
+        let nested = alloc::format!("{}a{}", "(".repeat(249), ")".repeat(249));
+        assert_eq!(find(&nested, "a"), Some(0..1));
+        let nested = alloc::format!("{}a{}", "(?:".repeat(124), ")*".repeat(124));
+        assert_eq!(find(&nested, "aa"), Some(0..2));
+        let nested = alloc::format!("{}a{}", "(?=".repeat(249), ")".repeat(249));
+        assert_eq!(find(&nested, "a"), Some(0..0));
+    }
+}
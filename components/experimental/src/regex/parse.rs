@@ -0,0 +1,790 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use icu_casemap::{CaseMapCloserBorrowed, CaseMapperBorrowed, ClosureSink};
+use icu_collections::codepointinvlist::{CodePointInversionList, CodePointInversionListBuilder};
+use icu_collections::codepointinvliststringlist::CodePointInversionListAndStringList;
+use icu_provider::DataError;
+
+use super::{RegexError, RegexOptions};
+use crate::unicodeset_parse::ParseError;
+
+/// The largest allowed bound of a counted repetition.
+const MAX_REPETITION: u32 = 1000;
+
+/// The deepest allowed nesting of the syntax tree, and separately of brackets in a class.
+/// Parsing, compiling and matching recurse over the syntax tree, so this bounds their stack
+/// use.
+const MAX_NESTING: usize = 250;
+
+/// The set matched by `\w`, following UTS #18 Annex C.
+pub(crate) const WORD: &str = r"[\p{Alphabetic}\p{M}\p{Nd}\p{Pc}\u200C\u200D]";
+
+/// The code points that can have a nontrivial case closure. Other code points are only
+/// equivalent to themselves when matching case-insensitively.
+const CASE_SENSITIVE: &str = r"[\p{Changes_When_Casemapped}\p{Changes_When_Casefolded}]";
+
+/// A set of code points and strings, as matched by a property or a bracketed class.
+#[derive(Debug)]
+pub(crate) struct CharSet {
+    pub(crate) code_points: CodePointInversionList<'static>,
+    /// Strings of more than one code point, longest first. These are full case folded
+    /// if the set is case-insensitive.
+    pub(crate) strings: Vec<String>,
+    pub(crate) case_insensitive: bool,
+}
+
+/// A zero-width assertion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Assertion {
+    /// `\A`, or `^` outside multiline mode.
+    TextStart,
+    /// `\z`.
+    TextEnd,
+    /// `\Z`, or `$` outside multiline mode: the end of the text, or before a final line terminator.
+    TextEndLine,
+    /// `^` in multiline mode.
+    LineStart,
+    /// `$` in multiline mode.
+    LineEnd,
+    /// `\b`, a boundary between `\w` and `\W`.
+    WordBoundary,
+    /// `\B`.
+    NotWordBoundary,
+    /// `\b{w}`, a UAX #29 word boundary.
+    UnicodeWordBoundary,
+    /// `\b{g}`, a UAX #29 grapheme cluster boundary.
+    GraphemeBoundary,
+}
+
+/// The abstract syntax tree of a pattern.
+#[derive(Debug)]
+pub(crate) enum Node {
+    Empty,
+    /// A literal character, matched exactly.
+    Char(char),
+    /// A literal character, matched case-insensitively.
+    FoldedChar(char),
+    /// An index into [`Ast::sets`].
+    Set(usize),
+    /// `.`
+    Any {
+        dot_all: bool,
+    },
+    /// `\X`
+    Grapheme,
+    Assert(Assertion),
+    /// A group, capturing if it has an index.
+    Group(Option<usize>, Box<Node>),
+    LookAhead {
+        negate: bool,
+        node: Box<Node>,
+    },
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+    BackReference {
+        group: usize,
+        case_insensitive: bool,
+    },
+}
+
+impl Node {
+    /// Returns the number of nodes on the longest path from this node to a leaf, without
+    /// recursing.
+    fn depth(&self) -> usize {
+        let mut max = 0;
+        let mut stack = vec![(self, 1)];
+        while let Some((node, depth)) = stack.pop() {
+            max = max.max(depth);
+            match node {
+                Node::Group(_, node) | Node::LookAhead { node, .. } | Node::Repeat { node, .. } => {
+                    stack.push((node, depth + 1))
+                }
+                Node::Concat(nodes) | Node::Alternation(nodes) => {
+                    stack.extend(nodes.iter().map(|node| (node, depth + 1)))
+                }
+                _ => {}
+            }
+        }
+        max
+    }
+}
+
+/// A parsed pattern.
+#[derive(Debug)]
+pub(crate) struct Ast {
+    pub(crate) node: Node,
+    pub(crate) sets: Vec<CharSet>,
+    /// The names of the capture groups, where group 0 is the whole match.
+    pub(crate) group_names: Vec<Option<String>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Flags {
+    case_insensitive: bool,
+    multiline: bool,
+    dot_all: bool,
+}
+
+/// Collects a case closure into a set builder and a list of strings.
+struct Closure<'a> {
+    builder: &'a mut CodePointInversionListBuilder,
+    strings: &'a mut Vec<String>,
+}
+
+impl ClosureSink for Closure<'_> {
+    fn add_char(&mut self, c: char) {
+        self.builder.add_char(c);
+    }
+
+    fn add_string(&mut self, string: &str) {
+        self.strings.push(string.to_owned());
+    }
+}
+
+type SetResult = Result<(CodePointInversionListAndStringList<'static>, usize), ParseError>;
+
+pub(crate) struct Parser<'a, F> {
+    pattern: &'a str,
+    pos: usize,
+    flags: Flags,
+    parse_set: F,
+    closer: CaseMapCloserBorrowed<'a>,
+    mapper: CaseMapperBorrowed<'a>,
+    /// The set of [`CASE_SENSITIVE`], loaded when first needed.
+    case_sensitive: Option<CodePointInversionList<'static>>,
+    sets: Vec<CharSet>,
+    group_names: Vec<Option<String>>,
+    /// The number of enclosing groups, which is at most the depth of the syntax tree.
+    depth: usize,
+}
+
+impl<'a, F: FnMut(&str) -> SetResult> Parser<'a, F> {
+    pub(crate) fn new(
+        pattern: &'a str,
+        options: RegexOptions,
+        parse_set: F,
+        closer: CaseMapCloserBorrowed<'a>,
+        mapper: CaseMapperBorrowed<'a>,
+    ) -> Self {
+        Self {
+            pattern,
+            pos: 0,
+            flags: Flags {
+                case_insensitive: options.case_insensitive,
+                multiline: options.multiline,
+                dot_all: options.dot_matches_new_line,
+            },
+            parse_set,
+            closer,
+            mapper,
+            case_sensitive: None,
+            sets: Vec::new(),
+            group_names: vec![None],
+            depth: 0,
+        }
+    }
+
+    pub(crate) fn parse(mut self) -> Result<Ast, RegexError> {
+        let node = self.parse_alternation()?;
+        if self.pos < self.pattern.len() {
+            // An unbalanced `)`
+            return Err(RegexError::Syntax(self.pos));
+        }
+        if node.depth() > MAX_NESTING {
+            return Err(RegexError::TooLarge);
+        }
+        Ok(Ast {
+            node,
+            sets: self.sets,
+            group_names: self.group_names,
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.pattern.get(self.pos..)?.chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let eaten = self.peek() == Some(c);
+        if eaten {
+            self.pos += c.len_utf8();
+        }
+        eaten
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let eaten = self
+            .pattern
+            .get(self.pos..)
+            .is_some_and(|rest| rest.starts_with(s));
+        if eaten {
+            self.pos += s.len();
+        }
+        eaten
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, RegexError> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().unwrap_or(Node::Empty)
+        } else {
+            Node::Alternation(branches)
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, RegexError> {
+        let mut items = Vec::new();
+        while !matches!(self.peek(), None | Some('|' | ')')) {
+            if let Some(atom) = self.parse_atom()? {
+                items.push(self.parse_quantifier(atom)?);
+            }
+        }
+        Ok(match items.len() {
+            0 => Node::Empty,
+            1 => items.pop().unwrap_or(Node::Empty),
+            _ => Node::Concat(items),
+        })
+    }
+
+    fn parse_quantifier(&mut self, mut node: Node) -> Result<Node, RegexError> {
+        let start = self.pos;
+        if let Node::Concat(literals) = &mut node {
+            // A quantifier after `\Q...\E` only applies to the last character
+            if let Some(last) = literals.pop() {
+                let last = self.parse_quantifier(last)?;
+                literals.push(last);
+            }
+            return Ok(node);
+        }
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+                let min = self.parse_decimal().ok_or(RegexError::Syntax(start))?;
+                let max = if self.eat(',') {
+                    if self.peek() == Some('}') {
+                        None
+                    } else {
+                        Some(self.parse_decimal().ok_or(RegexError::Syntax(start))?)
+                    }
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some('}') || max.is_some_and(|max| max < min) {
+                    return Err(RegexError::Syntax(start));
+                }
+                if min > MAX_REPETITION || max.is_some_and(|max| max > MAX_REPETITION) {
+                    return Err(RegexError::TooLarge);
+                }
+                (min, max)
+            }
+            _ => return Ok(node),
+        };
+        self.pos += 1;
+        let greedy = !self.eat('?');
+        if self.peek() == Some('+') {
+            // Possessive quantifiers
+            return Err(RegexError::Unsupported(start));
+        }
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            return Err(RegexError::Syntax(self.pos));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    fn parse_decimal(&mut self) -> Option<u32> {
+        let mut value: Option<u32> = None;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            self.pos += 1;
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        }
+        value
+    }
+
+    /// Parses an atom, returning `None` for constructs that match nothing, such as
+    /// comments and flag changes.
+    fn parse_atom(&mut self) -> Result<Option<Node>, RegexError> {
+        let start = self.pos;
+        let c = self.bump().ok_or(RegexError::Syntax(start))?;
+        Ok(Some(match c {
+            '(' => return self.parse_group(start),
+            '[' => {
+                self.pos = start;
+                Node::Set(self.parse_bracket()?)
+            }
+            '.' => Node::Any {
+                dot_all: self.flags.dot_all,
+            },
+            '^' => Node::Assert(if self.flags.multiline {
+                Assertion::LineStart
+            } else {
+                Assertion::TextStart
+            }),
+            '$' => Node::Assert(if self.flags.multiline {
+                Assertion::LineEnd
+            } else {
+                Assertion::TextEndLine
+            }),
+            '\\' => return self.parse_escape(start),
+            '*' | '+' | '?' | '{' => return Err(RegexError::Syntax(start)),
+            c => self.literal(c),
+        }))
+    }
+
+    fn literal(&self, c: char) -> Node {
+        if self.flags.case_insensitive {
+            Node::FoldedChar(c)
+        } else {
+            Node::Char(c)
+        }
+    }
+
+    fn parse_group(&mut self, start: usize) -> Result<Option<Node>, RegexError> {
+        let saved = self.flags;
+        let mut index = None;
+        let mut lookahead = None;
+        if self.eat('?') {
+            if self.eat('#') {
+                while self.bump().ok_or(RegexError::Syntax(start))? != ')' {}
+                return Ok(None);
+            } else if self.eat('=') {
+                lookahead = Some(false);
+            } else if self.eat('!') {
+                lookahead = Some(true);
+            } else if self.eat_str("<=") || self.eat_str("<!") || self.eat('>') {
+                // Lookbehind and atomic groups
+                return Err(RegexError::Unsupported(start));
+            } else if self.eat('<') || self.eat_str("P<") {
+                let name = self.parse_name('>')?;
+                index = Some(self.new_group(Some(name))?);
+            } else if !self.eat(':') {
+                // Flags, either for the rest of the enclosing group or scoped to this group
+                let mut enable = true;
+                loop {
+                    match self.bump() {
+                        Some('i') => self.flags.case_insensitive = enable,
+                        Some('m') => self.flags.multiline = enable,
+                        Some('s') => self.flags.dot_all = enable,
+                        Some('-') if enable => enable = false,
+                        Some(')') => return Ok(None),
+                        Some(':') => break,
+                        Some('x' | 'w') => return Err(RegexError::Unsupported(start)),
+                        _ => return Err(RegexError::Syntax(start)),
+                    }
+                }
+            }
+        } else {
+            index = Some(self.new_group(None)?);
+        }
+
+        if self.depth >= MAX_NESTING {
+            return Err(RegexError::TooLarge);
+        }
+        self.depth += 1;
+        let node = self.parse_alternation()?;
+        self.depth -= 1;
+        self.flags = saved;
+        if !self.eat(')') {
+            return Err(RegexError::Syntax(start));
+        }
+        Ok(Some(match lookahead {
+            Some(negate) => Node::LookAhead {
+                negate,
+                node: Box::new(node),
+            },
+            None => Node::Group(index, Box::new(node)),
+        }))
+    }
+
+    fn new_group(&mut self, name: Option<String>) -> Result<usize, RegexError> {
+        if name.is_some() && self.group_names.contains(&name) {
+            return Err(RegexError::Syntax(self.pos));
+        }
+        self.group_names.push(name);
+        Ok(self.group_names.len() - 1)
+    }
+
+    /// Parses an ASCII group name up to the given terminator.
+    fn parse_name(&mut self, end: char) -> Result<String, RegexError> {
+        let start = self.pos;
+        let mut name = String::new();
+        loop {
+            match self.bump() {
+                Some(c) if c == end && !name.is_empty() => return Ok(name),
+                Some(c) if c.is_ascii_alphabetic() || (c.is_ascii_digit() && !name.is_empty()) => {
+                    name.push(c)
+                }
+                _ => return Err(RegexError::Syntax(start)),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self, start: usize) -> Result<Option<Node>, RegexError> {
+        let c = self.bump().ok_or(RegexError::Syntax(start))?;
+        Ok(Some(match c {
+            'd' | 'D' | 's' | 'S' | 'w' | 'W' => {
+                Node::Set(self.add_set(class_pattern(c), c.is_ascii_uppercase(), start)?)
+            }
+            'p' | 'P' => {
+                let body = self.parse_property_body(start)?;
+                let mut pattern = String::from(r"\p");
+                pattern.push_str(&body);
+                Node::Set(self.add_set(&pattern, c == 'P', start)?)
+            }
+            'b' if self.eat_str("{w}") => Node::Assert(Assertion::UnicodeWordBoundary),
+            'b' if self.eat_str("{g}") => Node::Assert(Assertion::GraphemeBoundary),
+            'b' => Node::Assert(Assertion::WordBoundary),
+            'B' => Node::Assert(Assertion::NotWordBoundary),
+            'A' => Node::Assert(Assertion::TextStart),
+            'z' => Node::Assert(Assertion::TextEnd),
+            'Z' => Node::Assert(Assertion::TextEndLine),
+            'X' => Node::Grapheme,
+            '1'..='9' => {
+                // As many digits as form the number of an existing group
+                let mut group = c as usize - '0' as usize;
+                if group >= self.group_names.len() {
+                    return Err(RegexError::Syntax(start));
+                }
+                while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+                    if group * 10 + digit as usize >= self.group_names.len() {
+                        break;
+                    }
+                    self.pos += 1;
+                    group = group * 10 + digit as usize;
+                }
+                self.back_reference(group)
+            }
+            'k' => {
+                if !self.eat('<') {
+                    return Err(RegexError::Syntax(start));
+                }
+                let name = Some(self.parse_name('>')?);
+                let group = self
+                    .group_names
+                    .iter()
+                    .position(|n| *n == name)
+                    .ok_or(RegexError::Syntax(start))?;
+                self.back_reference(group)
+            }
+            'Q' => {
+                let mut literals = Vec::new();
+                while !self.eat_str(r"\E") {
+                    match self.bump() {
+                        Some(c) => literals.push(self.literal(c)),
+                        None => break,
+                    }
+                }
+                Node::Concat(literals)
+            }
+            'E' => return Ok(None),
+            c => {
+                let c = self.parse_escaped_char(c, start)?;
+                self.literal(c)
+            }
+        }))
+    }
+
+    fn back_reference(&self, group: usize) -> Node {
+        Node::BackReference {
+            group,
+            case_insensitive: self.flags.case_insensitive,
+        }
+    }
+
+    /// Parses the character denoted by an escape sequence after the backslash and `c`.
+    fn parse_escaped_char(&mut self, c: char, start: usize) -> Result<char, RegexError> {
+        Ok(match c {
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            'f' => '\u{000C}',
+            'a' => '\u{0007}',
+            'e' => '\u{001B}',
+            '0' => {
+                let mut value = 0;
+                for _ in 0..3 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            self.pos += 1;
+                            value = value * 8 + digit;
+                        }
+                        None => break,
+                    }
+                }
+                char::from_u32(value).ok_or(RegexError::Syntax(start))?
+            }
+            'x' if self.eat('{') => {
+                let c = self.parse_hex(1, 6, start)?;
+                if !self.eat('}') {
+                    return Err(RegexError::Syntax(start));
+                }
+                c
+            }
+            'x' => self.parse_hex(2, 2, start)?,
+            'u' => self.parse_hex(4, 4, start)?,
+            'U' => self.parse_hex(8, 8, start)?,
+            'c' => match self.bump() {
+                Some(c) if c.is_ascii() => char::from(c as u8 & 0x1F),
+                _ => return Err(RegexError::Syntax(start)),
+            },
+            'N' | 'h' | 'H' | 'v' | 'V' | 'R' | 'G' => return Err(RegexError::Unsupported(start)),
+            c if c.is_ascii_alphanumeric() => return Err(RegexError::Syntax(start)),
+            c => c,
+        })
+    }
+
+    fn parse_hex(&mut self, min: usize, max: usize, start: usize) -> Result<char, RegexError> {
+        let mut value = 0u32;
+        let mut len = 0;
+        while len < max {
+            match self.peek().and_then(|c| c.to_digit(16)) {
+                Some(digit) => {
+                    self.pos += 1;
+                    len += 1;
+                    value = value.saturating_mul(16).saturating_add(digit);
+                }
+                None => break,
+            }
+        }
+        if len < min {
+            return Err(RegexError::Syntax(start));
+        }
+        char::from_u32(value).ok_or(RegexError::Syntax(start))
+    }
+
+    /// Parses the property after `\p` or `\P`, returning it in braces.
+    fn parse_property_body(&mut self, start: usize) -> Result<String, RegexError> {
+        let mut body = String::from("{");
+        if self.eat('{') {
+            loop {
+                match self.bump() {
+                    Some('}') => break,
+                    Some(c) => body.push(c),
+                    None => return Err(RegexError::Syntax(start)),
+                }
+            }
+        } else {
+            // A single-letter general category
+            body.push(self.bump().ok_or(RegexError::Syntax(start))?);
+        }
+        body.push('}');
+        Ok(body)
+    }
+
+    /// Parses a bracketed class, translating the regular expression syntax that
+    /// differs from UnicodeSet syntax.
+    fn parse_bracket(&mut self) -> Result<usize, RegexError> {
+        let start = self.pos;
+        let mut set = String::new();
+        // For each nesting level, the start of the current operand in `set` and whether
+        // the level has operators. UnicodeSet operators only take single sets, so the
+        // operands of a level with operators are enclosed in brackets.
+        let mut levels: Vec<(usize, bool)> = Vec::new();
+        loop {
+            let c = self.bump().ok_or(RegexError::Syntax(start))?;
+            match c {
+                '[' if self.eat(':') => {
+                    // A POSIX-like property
+                    set.push_str("[:");
+                    while !set.ends_with(":]") {
+                        set.push(self.bump().ok_or(RegexError::Syntax(start))?);
+                    }
+                    if levels.is_empty() {
+                        break;
+                    }
+                }
+                '[' => {
+                    set.push('[');
+                    if self.eat('^') {
+                        set.push('^');
+                    }
+                    levels.push((set.len(), false));
+                    if levels.len() > MAX_NESTING {
+                        return Err(RegexError::TooLarge);
+                    }
+                }
+                ']' => {
+                    if let Some((operand, true)) = levels.pop() {
+                        set.insert(operand, '[');
+                        set.push(']');
+                    }
+                    set.push(']');
+                    if levels.is_empty() {
+                        break;
+                    }
+                }
+                '\\' => {
+                    let c = self.bump().ok_or(RegexError::Syntax(start))?;
+                    match c {
+                        'd' => set.push_str(r"\p{Nd}"),
+                        'D' => set.push_str(r"\P{Nd}"),
+                        's' => set.push_str(r"\p{White_Space}"),
+                        'S' => set.push_str(r"\P{White_Space}"),
+                        'w' => set.push_str(WORD),
+                        'W' => {
+                            set.push_str("[^");
+                            set.push_str(WORD.get(1..).unwrap_or_default());
+                        }
+                        'p' | 'P' => {
+                            set.push('\\');
+                            set.push(c);
+                            set.push_str(&self.parse_property_body(start)?);
+                        }
+                        'N' | 'h' | 'H' | 'v' | 'V' | 'R' | 'G' => {
+                            return Err(RegexError::Unsupported(self.pos - 2))
+                        }
+                        'q' if self.eat('{') => {
+                            // Strings, separated by `|`
+                            set.push('{');
+                            loop {
+                                match self.bump().ok_or(RegexError::Syntax(start))? {
+                                    '}' => break,
+                                    '|' => set.push_str("}{"),
+                                    '\\' => {
+                                        let c = self.bump().ok_or(RegexError::Syntax(start))?;
+                                        push_escaped(&mut set, self.parse_escaped_char(c, start)?);
+                                    }
+                                    c => push_escaped(&mut set, c),
+                                }
+                            }
+                            set.push('}');
+                        }
+                        c => push_escaped(&mut set, self.parse_escaped_char(c, self.pos - 2)?),
+                    }
+                }
+                // `&&` and `--` are the intersection and difference operators of
+                // regular expressions, `&` and `-` in UnicodeSet syntax
+                '&' | '-' if self.peek() == Some(c) => {
+                    self.pos += 1;
+                    if let Some((operand, operators)) = levels.last_mut() {
+                        set.insert(*operand, '[');
+                        set.push(']');
+                        set.push(c);
+                        *operand = set.len();
+                        *operators = true;
+                    }
+                }
+                '{' | '}' | '$' | '^' => push_escaped(&mut set, c),
+                c if c.is_whitespace() => push_escaped(&mut set, c),
+                c => set.push(c),
+            }
+        }
+
+        // Parse the positive set, to take the case closure before complementing
+        let (negate, set) = match set.strip_prefix("[^") {
+            Some(rest) => (true, alloc::format!("[{rest}")),
+            None => (false, set),
+        };
+        self.add_set(&set, negate, start)
+    }
+
+    /// Parses a UnicodeSet pattern into a new [`CharSet`], returning its index.
+    fn add_set(&mut self, pattern: &str, negate: bool, start: usize) -> Result<usize, RegexError> {
+        let (set, consumed) =
+            (self.parse_set)(pattern).map_err(|e| RegexError::Set(start, e.kind()))?;
+        if consumed != pattern.len() {
+            return Err(RegexError::Syntax(start));
+        }
+
+        let mut builder = CodePointInversionListBuilder::new();
+        builder.add_set(set.code_points());
+        let mut strings = set.strings().iter().map(String::from).collect::<Vec<_>>();
+        let case_insensitive = self.flags.case_insensitive;
+        if case_insensitive {
+            // Only the case-sensitive code points of the set need to be closed over, which
+            // avoids iterating over all code points of large sets such as `\p{L}` or `[^a]`
+            if self.case_sensitive.is_none() {
+                let (case_sensitive, _) = (self.parse_set)(CASE_SENSITIVE).map_err(|_| {
+                    RegexError::Data(DataError::custom(
+                        "Could not load the case-sensitive code points",
+                    ))
+                })?;
+                self.case_sensitive = Some(case_sensitive.code_points().clone());
+            }
+            let mut sensitive = CodePointInversionListBuilder::new();
+            sensitive.add_set(set.code_points());
+            if let Some(case_sensitive) = &self.case_sensitive {
+                sensitive.retain_set(case_sensitive);
+            }
+
+            let mut closure = Closure {
+                builder: &mut builder,
+                strings: &mut strings,
+            };
+            for c in sensitive
+                .build()
+                .iter_ranges()
+                .flatten()
+                .filter_map(char::from_u32)
+            {
+                self.closer.add_case_closure_to(c, &mut closure);
+            }
+            for s in set.strings().iter() {
+                self.closer.add_string_case_closure_to(s, &mut closure);
+            }
+            for s in strings.iter_mut() {
+                *s = self.mapper.fold_string(s).into_owned();
+            }
+        }
+        if negate {
+            // Strings are not part of the complement
+            builder.complement();
+            strings.clear();
+        }
+        strings.retain(|s| s.chars().nth(1).is_some());
+        strings.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then(a.cmp(b)));
+        strings.dedup();
+
+        self.sets.push(CharSet {
+            code_points: builder.build(),
+            strings,
+            case_insensitive,
+        });
+        Ok(self.sets.len() - 1)
+    }
+}
+
+/// Appends a character to a UnicodeSet pattern, escaped as `\x{...}`.
+fn push_escaped(set: &mut String, c: char) {
+    let _infallible = write!(set, r"\x{{{:X}}}", c as u32);
+}
+
+/// Returns the UnicodeSet pattern for the positive form of a shorthand class.
+fn class_pattern(c: char) -> &'static str {
+    match c {
+        'd' | 'D' => r"\p{Nd}",
+        's' | 'S' => r"\p{White_Space}",
+        _ => WORD,
+    }
+}
@@ -0,0 +1,716 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use super::provider::{SpoofConfusablesV1, SpoofIdentifierPropertiesV1};
+use alloc::string::String;
+use icu_normalizer::provider::{NormalizerNfdDataV1, NormalizerNfdTablesV1};
+use icu_normalizer::DecomposingNormalizer;
+use icu_properties::props::{DefaultIgnorableCodePoint, Script};
+use icu_properties::provider::{
+    PropertyBinaryDefaultIgnorableCodePointV1, PropertyScriptWithExtensionsV1,
+};
+use icu_properties::script::ScriptWithExtensions;
+use icu_properties::CodePointSetData;
+use icu_provider::prelude::*;
+use smallvec::SmallVec;
+
+/// The `Identifier_Status` property of a character, from
+/// [UTS #39](https://www.unicode.org/reports/tr39/#Identifier_Status_and_Type).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentifierStatus {
+    /// The character is in the General Security Profile for identifiers.
+    Allowed,
+    /// The character is not in the General Security Profile for identifiers.
+    Restricted,
+}
+
+/// A value of the `Identifier_Type` property, from
+/// [UTS #39](https://www.unicode.org/reports/tr39/#Identifier_Status_and_Type).
+///
+/// A character can have multiple types, see [`IdentifierTypeSet`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentifierType {
+    /// `Not_Character`: unassigned characters, private use characters, surrogates,
+    /// and most control characters.
+    NotCharacter = 0,
+    /// `Deprecated`: characters with the `Deprecated` property.
+    Deprecated = 1,
+    /// `Default_Ignorable`: characters with the `Default_Ignorable_Code_Point` property.
+    DefaultIgnorable = 2,
+    /// `Not_NFKC`: characters that cannot occur in strings normalized to NFKC.
+    NotNfkc = 3,
+    /// `Not_XID`: characters that are not in `XID_Continue`.
+    NotXid = 4,
+    /// `Exclusion`: characters of scripts that are excluded from identifiers.
+    Exclusion = 5,
+    /// `Obsolete`: characters that are no longer in modern use.
+    Obsolete = 6,
+    /// `Technical`: characters for specialized usage.
+    Technical = 7,
+    /// `Uncommon_Use`: characters that are not in common use.
+    UncommonUse = 8,
+    /// `Limited_Use`: characters of scripts that are in limited use.
+    LimitedUse = 9,
+    /// `Inclusion`: exceptionally allowed characters, such as U+00B7 MIDDLE DOT.
+    Inclusion = 10,
+    /// `Recommended`: characters in widespread modern use.
+    Recommended = 11,
+}
+
+impl IdentifierType {
+    const ALL: [Self; 12] = [
+        Self::NotCharacter,
+        Self::Deprecated,
+        Self::DefaultIgnorable,
+        Self::NotNfkc,
+        Self::NotXid,
+        Self::Exclusion,
+        Self::Obsolete,
+        Self::Technical,
+        Self::UncommonUse,
+        Self::LimitedUse,
+        Self::Inclusion,
+        Self::Recommended,
+    ];
+
+    const fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// The set of `Identifier_Type` values of a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IdentifierTypeSet(u16);
+
+impl IdentifierTypeSet {
+    /// Returns whether the set contains the given type.
+    pub const fn contains(self, identifier_type: IdentifierType) -> bool {
+        self.0 & identifier_type.bit() != 0
+    }
+
+    /// Returns whether the set is empty.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the types in this set.
+    pub fn iter(self) -> impl Iterator<Item = IdentifierType> {
+        IdentifierType::ALL
+            .into_iter()
+            .filter(move |&t| self.contains(t))
+    }
+
+    /// Returns the bit representation of this set, as used in the data.
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Creates a set from its bit representation, as used in the data.
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+}
+
+impl FromIterator<IdentifierType> for IdentifierTypeSet {
+    fn from_iter<I: IntoIterator<Item = IdentifierType>>(iter: I) -> Self {
+        Self(iter.into_iter().fold(0, |bits, t| bits | t.bit()))
+    }
+}
+
+/// The restriction levels of [UTS #39](https://www.unicode.org/reports/tr39/#Restriction_Level_Detection),
+/// from most to least restrictive.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RestrictionLevel {
+    /// All characters are in the ASCII range.
+    AsciiOnly,
+    /// All characters are from a single script, or from the scripts of a single writing system,
+    /// such as Han, Hiragana and Katakana for Japanese.
+    SingleScript,
+    /// Characters are from Latin and one of the Chinese, Japanese or Korean writing systems.
+    HighlyRestrictive,
+    /// Characters are from Latin and one other script, except Cyrillic and Greek.
+    ModeratelyRestrictive,
+    /// Characters are from arbitrary scripts, but all of them are allowed in identifiers.
+    MinimallyRestrictive,
+    /// Some characters are not allowed in identifiers.
+    Unrestricted,
+}
+
+/// The kinds of confusables of [UTS #39](https://www.unicode.org/reports/tr39/#Confusable_Detection),
+/// as returned by [`SpoofChecker::are_confusable()`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfusableKind {
+    /// The strings are confusable, and have a script in common, such as "rn" and "m".
+    SingleScript,
+    /// The strings are confusable, and do not have a script in common, such as
+    /// "paypal" and "pаypal" with a Cyrillic "а", but at least one of them
+    /// uses several scripts.
+    MixedScript,
+    /// The strings are confusable, and are each written in a single script, but
+    /// not the same one, such as "scope" in Latin and "ѕсоре" in Cyrillic.
+    ///
+    /// Whole-script confusables are also mixed-script confusables.
+    WholeScript,
+}
+
+/// A set of scripts, as used in the resolved script set of a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScriptSet {
+    /// Whether the set contains all scripts, in which case `bits` is empty.
+    all: bool,
+    bits: SmallVec<[u64; 4]>,
+}
+
+impl ScriptSet {
+    fn all() -> Self {
+        Self {
+            all: true,
+            bits: SmallVec::new(),
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            all: false,
+            bits: SmallVec::new(),
+        }
+    }
+
+    fn insert(&mut self, script: Script) {
+        if self.all {
+            return;
+        }
+        let value = usize::from(script.to_icu4c_value());
+        let (word, bit) = (value / 64, value % 64);
+        if self.bits.len() <= word {
+            self.bits.resize(word + 1, 0);
+        }
+        if let Some(w) = self.bits.get_mut(word) {
+            *w |= 1 << bit;
+        }
+    }
+
+    fn contains(&self, script: Script) -> bool {
+        let value = usize::from(script.to_icu4c_value());
+        self.all
+            || self
+                .bits
+                .get(value / 64)
+                .is_some_and(|w| w & (1 << (value % 64)) != 0)
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.all && self.bits.iter().all(|&w| w == 0)
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        if other.all {
+            return;
+        }
+        if self.all {
+            *self = other.clone();
+            return;
+        }
+        self.bits.truncate(other.bits.len());
+        for (w, o) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *w &= o;
+        }
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        let mut intersection = self.clone();
+        intersection.intersect_with(other);
+        !intersection.is_empty()
+    }
+}
+
+/// `Hanb`, the script code for Han with Bopomofo.
+const HAN_WITH_BOPOMOFO: Script = Script::from_icu4c_value(172);
+/// `Jpan`, the script code for Japanese (Han, Hiragana and Katakana).
+const JAPANESE: Script = Script::from_icu4c_value(105);
+/// `Kore`, the script code for Korean (Han and Hangul).
+const KOREAN: Script = Script::from_icu4c_value(119);
+
+/// A checker for spoofing with visually confusable strings, following
+/// [UTS #39: Unicode Security Mechanisms](https://www.unicode.org/reports/tr39/).
+///
+/// It implements:
+/// * [confusable detection](https://www.unicode.org/reports/tr39/#Confusable_Detection)
+///   with skeletons, see [`SpoofChecker::skeleton()`] and [`SpoofChecker::are_confusable()`],
+/// * [mixed-script detection](https://www.unicode.org/reports/tr39/#Mixed_Script_Detection),
+///   see [`SpoofChecker::is_mixed_script()`],
+/// * [restriction level detection](https://www.unicode.org/reports/tr39/#Restriction_Level_Detection),
+///   see [`SpoofChecker::restriction_level()`],
+/// * the [`Identifier_Status` and `Identifier_Type`](https://www.unicode.org/reports/tr39/#Identifier_Status_and_Type)
+///   properties.
+///
+/// The UTS #39 data is not part of compiled data, and cannot be generated by `icu4x-datagen`
+/// yet. It has to be provided for the [`SpoofConfusablesV1`] and [`SpoofIdentifierPropertiesV1`]
+/// markers by a custom data provider, built from the
+/// [UTS #39 data files](https://www.unicode.org/Public/security/).
+///
+/// # Examples
+///
+/// ```
+/// use icu::experimental::spoof::provider::{
+///     SpoofConfusablesV1, SpoofIdentifierPropertiesV1,
+/// };
+/// use icu::experimental::spoof::{
+///     ConfusableKind, RestrictionLevel, SpoofChecker,
+/// };
+/// use icu::normalizer::provider::{NormalizerNfdDataV1, NormalizerNfdTablesV1};
+/// use icu::properties::provider::{
+///     PropertyBinaryDefaultIgnorableCodePointV1, PropertyScriptWithExtensionsV1,
+/// };
+/// use icu_provider::DataProvider;
+///
+/// fn example(
+///     provider: &(impl DataProvider<SpoofConfusablesV1>
+///           + DataProvider<SpoofIdentifierPropertiesV1>
+///           + DataProvider<PropertyScriptWithExtensionsV1>
+///           + DataProvider<PropertyBinaryDefaultIgnorableCodePointV1>
+///           + DataProvider<NormalizerNfdDataV1>
+///           + DataProvider<NormalizerNfdTablesV1>),
+/// ) {
+///     let checker = SpoofChecker::try_new_unstable(provider).unwrap();
+///
+///     assert_eq!(checker.skeleton("paypal"), checker.skeleton("pаypаl"));
+///     assert_eq!(
+///         checker.are_confusable("paypal", "pаypal"), // Cyrillic а
+///         Some(ConfusableKind::MixedScript)
+///     );
+///     assert_eq!(
+///         checker.are_confusable("scope", "ѕсоре"), // all Cyrillic
+///         Some(ConfusableKind::WholeScript)
+///     );
+///     assert_eq!(checker.are_confusable("apple", "orange"), None);
+///
+///     assert!(checker.is_mixed_script("pаypal"));
+///     assert_eq!(
+///         checker.restriction_level("paypal"),
+///         RestrictionLevel::AsciiOnly
+///     );
+///     assert_eq!(
+///         checker.restriction_level("pаypal"),
+///         RestrictionLevel::MinimallyRestrictive
+///     );
+///     assert_eq!(
+///         checker.restriction_level("東京tokyo"),
+///         RestrictionLevel::HighlyRestrictive
+///     );
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SpoofChecker {
+    confusables: DataPayload<SpoofConfusablesV1>,
+    identifiers: DataPayload<SpoofIdentifierPropertiesV1>,
+    script_extensions: ScriptWithExtensions,
+    default_ignorable: CodePointSetData,
+    nfd: DecomposingNormalizer,
+}
+
+impl SpoofChecker {
+    /// Creates a new [`SpoofChecker`] using a [`BufferProvider`].
+    ///
+    /// ✨ *Enabled with the `serde` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    ///
+    /// [`BufferProvider`]: icu_provider::buf::BufferProvider
+    #[cfg(feature = "serde")]
+    pub fn try_new_with_buffer_provider(
+        provider: &(impl icu_provider::buf::BufferProvider + ?Sized),
+    ) -> Result<Self, DataError> {
+        use icu_provider::buf::AsDeserializingBufferProvider;
+        Self::try_new_unstable(&provider.as_deserializing())
+    }
+
+    /// Creates a new [`SpoofChecker`] using a [`DataProvider`].
+    ///
+    /// <div class="stab unstable">
+    /// ⚠️ The bounds on <tt>provider</tt> may change over time, including in SemVer minor releases.
+    /// </div>
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    pub fn try_new_unstable<D>(provider: &D) -> Result<Self, DataError>
+    where
+        D: DataProvider<SpoofConfusablesV1>
+            + DataProvider<SpoofIdentifierPropertiesV1>
+            + DataProvider<PropertyScriptWithExtensionsV1>
+            + DataProvider<PropertyBinaryDefaultIgnorableCodePointV1>
+            + DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + ?Sized,
+    {
+        Ok(Self {
+            confusables: provider.load(Default::default())?.payload,
+            identifiers: provider.load(Default::default())?.payload,
+            script_extensions: ScriptWithExtensions::try_new_unstable(provider)?,
+            default_ignorable: CodePointSetData::try_new_unstable::<DefaultIgnorableCodePoint>(
+                provider,
+            )?,
+            nfd: DecomposingNormalizer::try_new_nfd_unstable(provider)?,
+        })
+    }
+
+    /// Returns the skeleton of a string: the string with each character replaced by its
+    /// prototype, so that confusable strings have the same skeleton.
+    ///
+    /// Skeletons are intended for comparisons only. They are not suitable for display,
+    /// and they change between Unicode versions.
+    pub fn skeleton(&self, s: &str) -> String {
+        let confusables = self.confusables.get();
+        let default_ignorable = self.default_ignorable.as_borrowed();
+        let mut prototypes = String::with_capacity(s.len());
+        for c in self.nfd.as_borrowed().normalize_iter(s.chars()) {
+            if default_ignorable.contains(c) {
+                continue;
+            }
+            match confusables.get(c) {
+                Some(prototype) => prototypes.push_str(prototype),
+                None => prototypes.push(c),
+            }
+        }
+        self.nfd.as_borrowed().normalize(&prototypes).into_owned()
+    }
+
+    /// Returns whether two strings are confusable, that is, have the same skeleton,
+    /// and if so, which kind of confusables they are.
+    pub fn are_confusable(&self, a: &str, b: &str) -> Option<ConfusableKind> {
+        if self.skeleton(a) != self.skeleton(b) {
+            return None;
+        }
+        let (a, b) = (self.resolved_scripts(a), self.resolved_scripts(b));
+        Some(if a.intersects(&b) {
+            ConfusableKind::SingleScript
+        } else if !a.is_empty() && !b.is_empty() {
+            ConfusableKind::WholeScript
+        } else {
+            ConfusableKind::MixedScript
+        })
+    }
+
+    /// Returns whether a string is mixed-script, that is, whether no single script
+    /// or writing system covers all of its characters.
+    ///
+    /// Characters of the `Common` and `Inherited` scripts, such as digits and combining marks,
+    /// count as any script, and characters count as all of their `Script_Extensions`.
+    pub fn is_mixed_script(&self, s: &str) -> bool {
+        self.resolved_scripts(s).is_empty()
+    }
+
+    /// Returns the restriction level of a string.
+    ///
+    /// Characters are allowed if they have `Identifier_Status=Allowed`, which excludes
+    /// characters of scripts that are not recommended for identifiers.
+    pub fn restriction_level(&self, s: &str) -> RestrictionLevel {
+        if s.chars()
+            .any(|c| self.identifier_status(c) != IdentifierStatus::Allowed)
+        {
+            return RestrictionLevel::Unrestricted;
+        }
+        if s.is_ascii() {
+            return RestrictionLevel::AsciiOnly;
+        }
+        if !self.resolved_scripts(s).is_empty() {
+            return RestrictionLevel::SingleScript;
+        }
+        let non_latin = self.resolved_scripts_without(s, Some(Script::Latin));
+        if [HAN_WITH_BOPOMOFO, JAPANESE, KOREAN]
+            .into_iter()
+            .any(|script| non_latin.contains(script))
+        {
+            RestrictionLevel::HighlyRestrictive
+        } else if !non_latin.is_empty()
+            && !non_latin.contains(Script::Cyrillic)
+            && !non_latin.contains(Script::Greek)
+        {
+            RestrictionLevel::ModeratelyRestrictive
+        } else {
+            RestrictionLevel::MinimallyRestrictive
+        }
+    }
+
+    /// Returns the `Identifier_Status` of a character.
+    pub fn identifier_status(&self, c: char) -> IdentifierStatus {
+        if self.identifiers.get().allowed.contains(c) {
+            IdentifierStatus::Allowed
+        } else {
+            IdentifierStatus::Restricted
+        }
+    }
+
+    /// Returns the `Identifier_Type` values of a character.
+    pub fn identifier_type(&self, c: char) -> IdentifierTypeSet {
+        IdentifierTypeSet(self.identifiers.get().types.get(c))
+    }
+
+    /// The resolved script set of a string: the intersection of the augmented script sets
+    /// of its characters.
+    fn resolved_scripts(&self, s: &str) -> ScriptSet {
+        self.resolved_scripts_without(s, None)
+    }
+
+    /// The resolved script set of the characters of a string that do not have the given script.
+    fn resolved_scripts_without(&self, s: &str, excluded: Option<Script>) -> ScriptSet {
+        let script_extensions = self.script_extensions.as_borrowed();
+        let mut resolved = ScriptSet::all();
+        for c in s.chars() {
+            let scx = script_extensions.get_script_extensions_val(c);
+            if scx.contains(&Script::Common) || scx.contains(&Script::Inherited) {
+                continue;
+            }
+            if excluded.is_some_and(|excluded| scx.contains(&excluded)) {
+                continue;
+            }
+            let mut augmented = ScriptSet::empty();
+            for script in scx.iter() {
+                augmented.insert(script);
+                match script {
+                    Script::Han => {
+                        augmented.insert(HAN_WITH_BOPOMOFO);
+                        augmented.insert(JAPANESE);
+                        augmented.insert(KOREAN);
+                    }
+                    Script::Hiragana | Script::Katakana => augmented.insert(JAPANESE),
+                    Script::Hangul => augmented.insert(KOREAN),
+                    Script::Bopomofo => augmented.insert(HAN_WITH_BOPOMOFO),
+                    _ => {}
+                }
+            }
+            resolved.intersect_with(&augmented);
+        }
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_set() {
+        let mut latin = ScriptSet::empty();
+        latin.insert(Script::Latin);
+        let mut japanese = ScriptSet::empty();
+        for script in [Script::Han, Script::Hiragana, JAPANESE] {
+            japanese.insert(script);
+        }
+        assert!(latin.contains(Script::Latin));
+        assert!(!latin.contains(Script::Han));
+        assert!(!latin.intersects(&japanese));
+        assert!(japanese.contains(JAPANESE));
+
+        let mut resolved = ScriptSet::all();
+        assert!(resolved.contains(Script::Greek));
+        resolved.intersect_with(&japanese);
+        assert_eq!(resolved, japanese);
+        resolved.intersect_with(&latin);
+        assert!(resolved.is_empty());
+    }
+
+    #[test]
+    fn test_identifier_type_set() {
+        let types = [IdentifierType::Technical, IdentifierType::NotXid]
+            .into_iter()
+            .collect::<IdentifierTypeSet>();
+        assert!(types.contains(IdentifierType::Technical));
+        assert!(!types.contains(IdentifierType::Recommended));
+        assert_eq!(
+            types.iter().collect::<alloc::vec::Vec<_>>(),
+            [IdentifierType::NotXid, IdentifierType::Technical]
+        );
+        assert_eq!(IdentifierTypeSet::from_bits(types.to_bits()), types);
+    }
+
+    /// A provider with a few entries of the UTS #39 data, and compiled data for the rest.
+    #[cfg(feature = "compiled_data")]
+    struct TestProvider;
+
+    #[cfg(feature = "compiled_data")]
+    impl DataProvider<SpoofConfusablesV1> for TestProvider {
+        fn load(&self, _: DataRequest) -> Result<DataResponse<SpoofConfusablesV1>, DataError> {
+            use super::super::provider::ConfusableMappings;
+            // From confusables.txt
+            let mappings = [
+                ('1', "l"),
+                ('I', "l"),
+                ('m', "rn"),
+                ('а', "a"),
+                ('е', "e"),
+                ('о', "o"),
+                ('р', "p"),
+                ('с', "c"),
+                ('ѕ', "s"),
+            ];
+            Ok(DataResponse {
+                metadata: Default::default(),
+                payload: DataPayload::from_owned(ConfusableMappings {
+                    characters: mappings.iter().map(|&(c, _)| c).collect(),
+                    prototypes: mappings
+                        .iter()
+                        .map(|&(_, p)| p)
+                        .collect::<alloc::vec::Vec<_>>()
+                        .as_slice()
+                        .into(),
+                }),
+            })
+        }
+    }
+
+    #[cfg(feature = "compiled_data")]
+    impl DataProvider<SpoofIdentifierPropertiesV1> for TestProvider {
+        fn load(
+            &self,
+            _: DataRequest,
+        ) -> Result<DataResponse<SpoofIdentifierPropertiesV1>, DataError> {
+            use super::super::provider::IdentifierProperties;
+            use icu_collections::codepointinvlist::CodePointInversionListBuilder;
+            let mut allowed = CodePointInversionListBuilder::new();
+            for range in [
+                'a'..='z',
+                'а'..='я',
+                'ѕ'..='ѕ',
+                'ա'..='և',
+                'ぁ'..='ゖ',
+                '一'..='鿿',
+            ] {
+                allowed.add_range(range);
+            }
+            // Recommended in the BMP, not characters elsewhere
+            let types = icu_collections::codepointtrie::planes::get_planes_trie()
+                .try_alloc_map_value(|plane| {
+                    Ok::<_, core::convert::Infallible>(if plane == 0 {
+                        IdentifierType::Recommended.bit()
+                    } else {
+                        IdentifierType::NotCharacter.bit()
+                    })
+                })
+                .unwrap_or_else(|e| match e {});
+            Ok(DataResponse {
+                metadata: Default::default(),
+                payload: DataPayload::from_owned(IdentifierProperties {
+                    allowed: allowed.build(),
+                    types,
+                }),
+            })
+        }
+    }
+
+    #[cfg(feature = "compiled_data")]
+    macro_rules! forward_to_baked {
+        ($($baked:path: $($marker:ty),+;)+) => {
+            $($(
+                impl DataProvider<$marker> for TestProvider {
+                    fn load(&self, req: DataRequest) -> Result<DataResponse<$marker>, DataError> {
+                        $baked.load(req)
+                    }
+                }
+            )+)+
+        };
+    }
+
+    #[cfg(feature = "compiled_data")]
+    forward_to_baked!(
+        icu_properties::provider::Baked:
+            PropertyScriptWithExtensionsV1, PropertyBinaryDefaultIgnorableCodePointV1;
+        icu_normalizer::provider::Baked: NormalizerNfdDataV1, NormalizerNfdTablesV1;
+    );
+
+    #[test]
+    #[cfg(feature = "compiled_data")]
+    fn test_confusables() {
+        let checker = SpoofChecker::try_new_unstable(&TestProvider).unwrap();
+
+        assert_eq!(checker.skeleton("paypal"), "paypal");
+        assert_eq!(checker.skeleton("pаypаl"), "paypal");
+        assert_eq!(checker.skeleton("mI1"), "rnll");
+        // Default ignorable code points are removed
+        assert_eq!(checker.skeleton("pay\u{200B}pal"), "paypal");
+        // Prototypes are applied to the canonical decomposition
+        assert_eq!(checker.skeleton("é"), "e\u{301}");
+        assert_eq!(checker.skeleton("е\u{301}"), "e\u{301}");
+
+        assert_eq!(
+            checker.are_confusable("rn", "m"),
+            Some(ConfusableKind::SingleScript)
+        );
+        assert_eq!(
+            checker.are_confusable("paypal", "pаypal"),
+            Some(ConfusableKind::MixedScript)
+        );
+        assert_eq!(
+            checker.are_confusable("scope", "ѕсоре"),
+            Some(ConfusableKind::WholeScript)
+        );
+        // Digits are Common, which does not make a string mixed-script
+        assert_eq!(
+            checker.are_confusable("l0", "10"),
+            Some(ConfusableKind::SingleScript)
+        );
+        assert_eq!(checker.are_confusable("apple", "orange"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "compiled_data")]
+    fn test_scripts() {
+        let checker = SpoofChecker::try_new_unstable(&TestProvider).unwrap();
+
+        assert!(!checker.is_mixed_script("paypal"));
+        assert!(!checker.is_mixed_script("ѕсоре"));
+        assert!(!checker.is_mixed_script("abc123"));
+        assert!(!checker.is_mixed_script("ひらがな漢字"));
+        assert!(checker.is_mixed_script("pаypal"));
+        assert!(checker.is_mixed_script("ひらがなհայ"));
+
+        assert_eq!(
+            checker.restriction_level("paypal"),
+            RestrictionLevel::AsciiOnly
+        );
+        assert_eq!(
+            checker.restriction_level("ѕсоре"),
+            RestrictionLevel::SingleScript
+        );
+        assert_eq!(
+            checker.restriction_level("ひらがな漢字"),
+            RestrictionLevel::SingleScript
+        );
+        assert_eq!(
+            checker.restriction_level("東京tokyo"),
+            RestrictionLevel::HighlyRestrictive
+        );
+        assert_eq!(
+            checker.restriction_level("abcաբ"),
+            RestrictionLevel::ModeratelyRestrictive
+        );
+        assert_eq!(
+            checker.restriction_level("pаypal"),
+            RestrictionLevel::MinimallyRestrictive
+        );
+        assert_eq!(
+            checker.restriction_level("pay pal"),
+            RestrictionLevel::Unrestricted
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "compiled_data")]
+    fn test_identifier_properties() {
+        let checker = SpoofChecker::try_new_unstable(&TestProvider).unwrap();
+
+        assert_eq!(checker.identifier_status('a'), IdentifierStatus::Allowed);
+        assert_eq!(checker.identifier_status('A'), IdentifierStatus::Restricted);
+        assert!(checker
+            .identifier_type('a')
+            .contains(IdentifierType::Recommended));
+        assert_eq!(
+            checker.identifier_type('\u{10000}').iter().next(),
+            Some(IdentifierType::NotCharacter)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Detection of spoofing with visually confusable strings, such as user names and
+//! domain labels, following [UTS #39: Unicode Security Mechanisms](https://www.unicode.org/reports/tr39/).
+//!
+//! See [`SpoofChecker`].
+
+#![warn(missing_docs)]
+
+mod checker;
+pub mod provider;
+
+pub use checker::{
+    ConfusableKind, IdentifierStatus, IdentifierType, IdentifierTypeSet, RestrictionLevel,
+    SpoofChecker,
+};
@@ -0,0 +1,93 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+// Provider structs must be stable
+#![allow(clippy::exhaustive_structs, clippy::exhaustive_enums)]
+
+//! 🚧 \[Unstable\] Data provider struct definitions for this ICU4X component.
+//!
+//! <div class="stab unstable">
+//! 🚧 This code is considered unstable; it may change at any time, in breaking or non-breaking ways,
+//! including in SemVer minor releases. While the serde representation of data structs is guaranteed
+//! to be stable, their Rust representation might not be. Use with caution.
+//! </div>
+//!
+//! Read more about data providers: [`icu_provider`]
+
+use icu_collections::codepointinvlist::CodePointInversionList;
+use icu_collections::codepointtrie::CodePointTrie;
+use icu_provider::prelude::*;
+use zerovec::vecs::Index32;
+use zerovec::{VarZeroVec, ZeroVec};
+
+icu_provider::data_marker!(
+    /// `SpoofConfusablesV1`
+    SpoofConfusablesV1,
+    ConfusableMappings<'static>,
+    is_singleton = true
+);
+
+icu_provider::data_marker!(
+    /// `SpoofIdentifierPropertiesV1`
+    SpoofIdentifierPropertiesV1,
+    IdentifierProperties<'static>,
+    is_singleton = true
+);
+
+/// The prototypes of confusable characters, from the
+/// [`confusables.txt`](https://www.unicode.org/reports/tr39/#Confusable_Detection) file of UTS #39.
+///
+/// <div class="stab unstable">
+/// 🚧 This code is considered unstable; it may change at any time, in breaking or non-breaking ways,
+/// including in SemVer minor releases. While the serde representation of data structs is guaranteed
+/// to be stable, their Rust representation might not be. Use with caution.
+/// </div>
+#[derive(Debug, Clone, PartialEq, yoke::Yokeable, zerofrom::ZeroFrom)]
+#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
+#[cfg_attr(feature = "datagen", databake(path = icu_experimental::spoof::provider))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct ConfusableMappings<'data> {
+    /// The characters that have a prototype, sorted.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub characters: ZeroVec<'data, char>,
+    /// The prototype of each character in `characters`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub prototypes: VarZeroVec<'data, str, Index32>,
+}
+
+icu_provider::data_struct!(ConfusableMappings<'_>, #[cfg(feature = "datagen")]);
+
+impl<'data> ConfusableMappings<'data> {
+    /// Returns the prototype of a character, if it is not its own prototype.
+    pub fn get(&self, c: char) -> Option<&str> {
+        self.characters
+            .binary_search(&c)
+            .ok()
+            .and_then(|i| self.prototypes.get(i))
+    }
+}
+
+/// The `Identifier_Status` and `Identifier_Type` properties from
+/// [UTS #39](https://www.unicode.org/reports/tr39/#Identifier_Status_and_Type).
+///
+/// <div class="stab unstable">
+/// 🚧 This code is considered unstable; it may change at any time, in breaking or non-breaking ways,
+/// including in SemVer minor releases. While the serde representation of data structs is guaranteed
+/// to be stable, their Rust representation might not be. Use with caution.
+/// </div>
+#[derive(Debug, Clone, PartialEq, yoke::Yokeable, zerofrom::ZeroFrom)]
+#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
+#[cfg_attr(feature = "datagen", databake(path = icu_experimental::spoof::provider))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct IdentifierProperties<'data> {
+    /// The code points with `Identifier_Status=Allowed`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub allowed: CodePointInversionList<'data>,
+    /// The `Identifier_Type` values of each code point, as a bit set of
+    /// [`IdentifierType`](crate::spoof::IdentifierType) discriminants.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub types: CodePointTrie<'data, u16>,
+}
+
+icu_provider::data_struct!(IdentifierProperties<'_>, #[cfg(feature = "datagen")]);
@@ -2,7 +2,9 @@
 // called LICENSE at the top level of the ICU4X source tree
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
-use crate::transliterate::provider::TransliteratorRulesV1;
+use crate::transliterate::provider::{
+    TransliteratorIds, TransliteratorIdsV1, TransliteratorRulesV1,
+};
 
 use alloc::collections::BTreeMap;
 use alloc::format;
@@ -416,6 +418,27 @@ where
     }
 }
 
+impl<PP: ?Sized, NP: ?Sized, NC: ?Sized> DataProvider<TransliteratorIdsV1>
+    for RuleCollectionProvider<'_, PP, NP, NC>
+{
+    fn load(&self, req: DataRequest) -> Result<DataResponse<TransliteratorIdsV1>, DataError> {
+        if !req.id.marker_attributes.is_empty() {
+            return Err(DataErrorKind::InvalidRequest.with_req(TransliteratorIdsV1::INFO, req));
+        }
+        Ok(DataResponse {
+            metadata: Default::default(),
+            payload: DataPayload::from_owned(TransliteratorIds {
+                legacy_to_bcp47: self
+                    .collection
+                    .id_mapping
+                    .iter()
+                    .map(|(legacy, bcp47)| (legacy.as_str(), bcp47.to_string()))
+                    .collect(),
+            }),
+        })
+    }
+}
+
 macro_rules! redirect {
     ($($marker:ty),*) => {
         $(
@@ -533,6 +556,255 @@ where
     }
 }
 
+/// An alias for the property data providers needed by [`compile_compound_id`], which are
+/// shared with the ID constructors of [`Transliterator`](crate::transliterate::Transliterator).
+///
+/// This is not exported, so that the bounds can change.
+pub trait CompoundIdPropertiesProvider:
+    DataProvider<PropertyBinaryAlphabeticV1>
+    + DataProvider<PropertyBinaryAsciiHexDigitV1>
+    + DataProvider<PropertyBinaryBidiControlV1>
+    + DataProvider<PropertyBinaryBidiMirroredV1>
+    + DataProvider<PropertyBinaryCasedV1>
+    + DataProvider<PropertyBinaryCaseIgnorableV1>
+    + DataProvider<PropertyBinaryChangesWhenCasefoldedV1>
+    + DataProvider<PropertyBinaryChangesWhenCasemappedV1>
+    + DataProvider<PropertyBinaryChangesWhenLowercasedV1>
+    + DataProvider<PropertyBinaryChangesWhenNfkcCasefoldedV1>
+    + DataProvider<PropertyBinaryChangesWhenTitlecasedV1>
+    + DataProvider<PropertyBinaryChangesWhenUppercasedV1>
+    + DataProvider<PropertyBinaryDashV1>
+    + DataProvider<PropertyBinaryDefaultIgnorableCodePointV1>
+    + DataProvider<PropertyBinaryDeprecatedV1>
+    + DataProvider<PropertyBinaryDiacriticV1>
+    + DataProvider<PropertyBinaryEmojiComponentV1>
+    + DataProvider<PropertyBinaryEmojiModifierBaseV1>
+    + DataProvider<PropertyBinaryEmojiModifierV1>
+    + DataProvider<PropertyBinaryEmojiPresentationV1>
+    + DataProvider<PropertyBinaryEmojiV1>
+    + DataProvider<PropertyBinaryExtendedPictographicV1>
+    + DataProvider<PropertyBinaryExtenderV1>
+    + DataProvider<PropertyBinaryGraphemeBaseV1>
+    + DataProvider<PropertyBinaryGraphemeExtendV1>
+    + DataProvider<PropertyBinaryHexDigitV1>
+    + DataProvider<PropertyBinaryIdContinueV1>
+    + DataProvider<PropertyBinaryIdeographicV1>
+    + DataProvider<PropertyBinaryIdsBinaryOperatorV1>
+    + DataProvider<PropertyBinaryIdStartV1>
+    + DataProvider<PropertyBinaryIdsTrinaryOperatorV1>
+    + DataProvider<PropertyBinaryJoinControlV1>
+    + DataProvider<PropertyBinaryLogicalOrderExceptionV1>
+    + DataProvider<PropertyBinaryLowercaseV1>
+    + DataProvider<PropertyBinaryMathV1>
+    + DataProvider<PropertyBinaryNoncharacterCodePointV1>
+    + DataProvider<PropertyBinaryPatternSyntaxV1>
+    + DataProvider<PropertyBinaryPatternWhiteSpaceV1>
+    + DataProvider<PropertyBinaryQuotationMarkV1>
+    + DataProvider<PropertyBinaryRadicalV1>
+    + DataProvider<PropertyBinaryRegionalIndicatorV1>
+    + DataProvider<PropertyBinarySentenceTerminalV1>
+    + DataProvider<PropertyBinarySoftDottedV1>
+    + DataProvider<PropertyBinaryTerminalPunctuationV1>
+    + DataProvider<PropertyBinaryUnifiedIdeographV1>
+    + DataProvider<PropertyBinaryUppercaseV1>
+    + DataProvider<PropertyBinaryVariationSelectorV1>
+    + DataProvider<PropertyBinaryWhiteSpaceV1>
+    + DataProvider<PropertyBinaryXidContinueV1>
+    + DataProvider<PropertyBinaryXidStartV1>
+    + DataProvider<PropertyEnumCanonicalCombiningClassV1>
+    + DataProvider<PropertyEnumGeneralCategoryV1>
+    + DataProvider<PropertyEnumGraphemeClusterBreakV1>
+    + DataProvider<PropertyEnumLineBreakV1>
+    + DataProvider<PropertyEnumScriptV1>
+    + DataProvider<PropertyEnumSentenceBreakV1>
+    + DataProvider<PropertyEnumWordBreakV1>
+    + DataProvider<PropertyNameParseCanonicalCombiningClassV1>
+    + DataProvider<PropertyNameParseGeneralCategoryMaskV1>
+    + DataProvider<PropertyNameParseGraphemeClusterBreakV1>
+    + DataProvider<PropertyNameParseLineBreakV1>
+    + DataProvider<PropertyNameParseScriptV1>
+    + DataProvider<PropertyNameParseSentenceBreakV1>
+    + DataProvider<PropertyNameParseWordBreakV1>
+    + DataProvider<PropertyScriptWithExtensionsV1>
+{
+}
+
+impl<T> CompoundIdPropertiesProvider for T where
+    T: ?Sized
+        + DataProvider<PropertyBinaryAlphabeticV1>
+        + DataProvider<PropertyBinaryAsciiHexDigitV1>
+        + DataProvider<PropertyBinaryBidiControlV1>
+        + DataProvider<PropertyBinaryBidiMirroredV1>
+        + DataProvider<PropertyBinaryCasedV1>
+        + DataProvider<PropertyBinaryCaseIgnorableV1>
+        + DataProvider<PropertyBinaryChangesWhenCasefoldedV1>
+        + DataProvider<PropertyBinaryChangesWhenCasemappedV1>
+        + DataProvider<PropertyBinaryChangesWhenLowercasedV1>
+        + DataProvider<PropertyBinaryChangesWhenNfkcCasefoldedV1>
+        + DataProvider<PropertyBinaryChangesWhenTitlecasedV1>
+        + DataProvider<PropertyBinaryChangesWhenUppercasedV1>
+        + DataProvider<PropertyBinaryDashV1>
+        + DataProvider<PropertyBinaryDefaultIgnorableCodePointV1>
+        + DataProvider<PropertyBinaryDeprecatedV1>
+        + DataProvider<PropertyBinaryDiacriticV1>
+        + DataProvider<PropertyBinaryEmojiComponentV1>
+        + DataProvider<PropertyBinaryEmojiModifierBaseV1>
+        + DataProvider<PropertyBinaryEmojiModifierV1>
+        + DataProvider<PropertyBinaryEmojiPresentationV1>
+        + DataProvider<PropertyBinaryEmojiV1>
+        + DataProvider<PropertyBinaryExtendedPictographicV1>
+        + DataProvider<PropertyBinaryExtenderV1>
+        + DataProvider<PropertyBinaryGraphemeBaseV1>
+        + DataProvider<PropertyBinaryGraphemeExtendV1>
+        + DataProvider<PropertyBinaryHexDigitV1>
+        + DataProvider<PropertyBinaryIdContinueV1>
+        + DataProvider<PropertyBinaryIdeographicV1>
+        + DataProvider<PropertyBinaryIdsBinaryOperatorV1>
+        + DataProvider<PropertyBinaryIdStartV1>
+        + DataProvider<PropertyBinaryIdsTrinaryOperatorV1>
+        + DataProvider<PropertyBinaryJoinControlV1>
+        + DataProvider<PropertyBinaryLogicalOrderExceptionV1>
+        + DataProvider<PropertyBinaryLowercaseV1>
+        + DataProvider<PropertyBinaryMathV1>
+        + DataProvider<PropertyBinaryNoncharacterCodePointV1>
+        + DataProvider<PropertyBinaryPatternSyntaxV1>
+        + DataProvider<PropertyBinaryPatternWhiteSpaceV1>
+        + DataProvider<PropertyBinaryQuotationMarkV1>
+        + DataProvider<PropertyBinaryRadicalV1>
+        + DataProvider<PropertyBinaryRegionalIndicatorV1>
+        + DataProvider<PropertyBinarySentenceTerminalV1>
+        + DataProvider<PropertyBinarySoftDottedV1>
+        + DataProvider<PropertyBinaryTerminalPunctuationV1>
+        + DataProvider<PropertyBinaryUnifiedIdeographV1>
+        + DataProvider<PropertyBinaryUppercaseV1>
+        + DataProvider<PropertyBinaryVariationSelectorV1>
+        + DataProvider<PropertyBinaryWhiteSpaceV1>
+        + DataProvider<PropertyBinaryXidContinueV1>
+        + DataProvider<PropertyBinaryXidStartV1>
+        + DataProvider<PropertyEnumCanonicalCombiningClassV1>
+        + DataProvider<PropertyEnumGeneralCategoryV1>
+        + DataProvider<PropertyEnumGraphemeClusterBreakV1>
+        + DataProvider<PropertyEnumLineBreakV1>
+        + DataProvider<PropertyEnumScriptV1>
+        + DataProvider<PropertyEnumSentenceBreakV1>
+        + DataProvider<PropertyEnumWordBreakV1>
+        + DataProvider<PropertyNameParseCanonicalCombiningClassV1>
+        + DataProvider<PropertyNameParseGeneralCategoryMaskV1>
+        + DataProvider<PropertyNameParseGraphemeClusterBreakV1>
+        + DataProvider<PropertyNameParseLineBreakV1>
+        + DataProvider<PropertyNameParseScriptV1>
+        + DataProvider<PropertyNameParseSentenceBreakV1>
+        + DataProvider<PropertyNameParseWordBreakV1>
+        + DataProvider<PropertyScriptWithExtensionsV1>
+{
+}
+
+/// Compiles an ICU compound transliterator ID, such as `"[:Latin:] Latin-ASCII; Lower"`, into a
+/// transliterator whose dependencies are the elements of the ID.
+///
+/// Elements are separated by top-level semicolons and have the syntax of `::` transform rules,
+/// so they may carry filters, and a leading (trailing) element consisting of only a (parenthesized)
+/// filter is a global (inverse) filter. Legacy IDs are resolved using `ids`.
+pub(crate) fn compile_compound_id<PP>(
+    id: &str,
+    ids: &TransliteratorIds,
+    reverse: bool,
+    properties_provider: &PP,
+) -> Result<DataPayload<TransliteratorRulesV1>, DataError>
+where
+    PP: ?Sized + CompoundIdPropertiesProvider,
+{
+    let mut source = String::new();
+    for element in split_compound_id(id) {
+        let element = element.trim();
+        let element = element.strip_prefix("::").unwrap_or(element).trim_start();
+        if !element.is_empty() {
+            source.push_str("::");
+            source.push_str(element);
+            source.push_str(";\n");
+        }
+    }
+    if source.is_empty() {
+        return Err(DataError::custom("Empty transliterator ID"));
+    }
+
+    let mut collection = RuleCollection::default();
+    for (legacy, bcp47) in ids.legacy_to_bcp47.iter() {
+        let bcp47 = Locale::try_from_str(bcp47).map_err(|_| {
+            DataError::custom("Invalid transliterator ID").with_display_context(bcp47)
+        })?;
+        collection.register_aliases(&bcp47, [legacy]);
+    }
+    collection.register_source(&Locale::UNKNOWN, source, [], reverse, true);
+
+    let provider = RuleCollectionProvider {
+        collection: &collection,
+        properties_provider,
+        normalizer_provider: &(),
+        casemap_provider: &(),
+        xid_start: CodePointSetData::try_new_unstable::<XidStart>(properties_provider)?,
+        xid_continue: CodePointSetData::try_new_unstable::<XidContinue>(properties_provider)?,
+        pat_ws: CodePointSetData::try_new_unstable::<PatternWhiteSpace>(properties_provider)?,
+    };
+    let payload = DataProvider::<TransliteratorRulesV1>::load(
+        &provider,
+        DataRequest {
+            id: DataIdentifierBorrowed::for_marker_attributes(
+                DataMarkerAttributes::from_str_or_panic("und"),
+            ),
+            ..Default::default()
+        },
+    )?
+    .payload;
+
+    // unknown IDs are compiled to `x-{id}`, see `Pass2::compile_single_id`
+    if let Some(unknown) = payload.get().deps().find(|dep| dep.starts_with("x-")) {
+        return Err(DataErrorKind::IdentifierNotFound
+            .with_str_context("unknown transliterator ID")
+            .with_display_context(unknown.trim_start_matches("x-")));
+    }
+    Ok(payload)
+}
+
+/// Splits a compound ID at the semicolons that are not part of a filter.
+fn split_compound_id(id: &str) -> impl Iterator<Item = &str> {
+    let mut set_depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    id.split(move |c| {
+        if escaped {
+            escaped = false;
+            return false;
+        }
+        match c {
+            '\\' => escaped = true,
+            '\'' => quoted = !quoted,
+            '[' if !quoted => set_depth += 1,
+            ']' if !quoted => set_depth = set_depth.saturating_sub(1),
+            ';' => return !quoted && set_depth == 0,
+            _ => {}
+        }
+        false
+    })
+}
+
+/// Returns the legacy ID of the inverse of the transliterator with the given legacy ID,
+/// e.g., `cyrillic-latin` for `latin-cyrillic`, or `any-nfd` for `any-nfc`.
+pub(crate) fn reverse_legacy_id(legacy_id: &str) -> String {
+    let (basic, variant) = match legacy_id.split_once('/') {
+        Some((basic, variant)) => (basic, Some(variant.to_string())),
+        None => (legacy_id, None),
+    };
+    let (source, target) = basic.split_once('-').unwrap_or(("any", basic));
+    parse::BasicId {
+        source: source.to_string(),
+        target: target.to_string(),
+        variant,
+    }
+    .reverse()
+    .to_string()
+}
+
 struct CompileError {
     /// offset is the index to an arbitrary byte in the last character in the source that makes sense
     /// to display as location for the error, e.g., the unexpected character itself or
@@ -29,7 +29,9 @@ mod transliterator;
 
 #[cfg(feature = "compiled_data")]
 pub use transliterator::TransliteratorBuilder;
-pub use transliterator::{CustomTransliterator, Transliterator};
+pub use transliterator::{
+    AnyTransliterator, CustomTransliterator, TransliterationPosition, Transliterator,
+};
 
 pub use compile::RuleCollection;
 pub use compile::RuleCollectionProvider;
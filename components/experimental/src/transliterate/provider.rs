@@ -61,6 +61,43 @@ pub struct RuleBasedTransliterator<'a> {
 
 icu_provider::data_struct!(RuleBasedTransliterator<'_>, #[cfg(feature = "datagen")]);
 
+icu_provider::data_marker!(
+    /// `TransliteratorIdsV1`
+    TransliteratorIdsV1,
+    TransliteratorIds<'static>,
+    is_singleton = true
+);
+
+/// The mapping from legacy ICU transliterator IDs (such as `Latin-Cyrillic` or `Any-Publishing`)
+/// to the BCP-47 IDs used to load [`RuleBasedTransliterator`]s.
+///
+/// <div class="stab unstable">
+/// 🚧 This code is considered unstable; it may change at any time, in breaking or non-breaking ways,
+/// including in SemVer minor releases. While the serde representation of data structs is guaranteed
+/// to be stable, their Rust representation might not be. Use with caution.
+/// </div>
+#[derive(Debug, Clone, PartialEq, yoke::Yokeable, zerofrom::ZeroFrom)]
+#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
+#[cfg_attr(feature = "datagen", databake(path = icu_experimental::transliterate::provider))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[yoke(prove_covariance_manually)]
+pub struct TransliteratorIds<'data> {
+    /// Maps ASCII-lowercased legacy IDs (`source-target` or `source-target/variant`)
+    /// to BCP-47 transform locales (`und-t-…`).
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub legacy_to_bcp47: ZeroMap<'data, str, str>,
+}
+
+icu_provider::data_struct!(TransliteratorIds<'_>, #[cfg(feature = "datagen")]);
+
+impl TransliteratorIds<'_> {
+    /// Returns the BCP-47 ID for a legacy ID, compared ASCII-case-insensitively.
+    pub fn get(&self, legacy_id: &str) -> Option<&str> {
+        self.legacy_to_bcp47
+            .get(legacy_id.to_ascii_lowercase().as_str())
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for RuleBasedTransliterator<'de> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
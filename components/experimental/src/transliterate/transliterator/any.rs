@@ -0,0 +1,359 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use super::replaceable::{Replaceable, TransliteratorBuffer};
+use super::Transliterator;
+use crate::transliterate::provider::TransliteratorRulesV1;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::{Debug, Formatter};
+use icu_casemap::provider::CaseMapV1;
+use icu_locale_core::Locale;
+use icu_normalizer::provider::*;
+use icu_properties::props::Script;
+use icu_properties::provider::{PropertyNameShortScriptV1, PropertyScriptWithExtensionsV1};
+use icu_properties::script::{ScriptWithExtensions, ScriptWithExtensionsBorrowed};
+use icu_properties::PropertyNamesShort;
+use icu_provider::prelude::*;
+use litemap::LiteMap;
+
+type Loader<'a> = dyn Fn(&Locale) -> Result<Transliterator, DataError> + 'a;
+
+/// A transliterator from any script into a target script, such as ICU's `Any-Latin`.
+///
+/// The input is split into runs of a single script using the `Script` and `Script_Extensions`
+/// properties, where characters with the `Common` or `Inherited` script belong to the preceding
+/// run (or the following one at the start of the input). Each run is transliterated with the
+/// CLDR transform from its script into the target script, i.e., `und-{target}-t-und-{source}`.
+/// If there is no such transform, the transforms from the source script into Latin and from Latin
+/// into the target script are used instead. Runs for which there are no transforms are left unchanged.
+///
+/// Transforms are only loaded from the provider once text in their source script is transliterated,
+/// so errors in their data are returned by [`AnyTransliterator::try_transliterate`].
+///
+/// # Example
+///
+/// ```
+/// use icu::experimental::transliterate::{AnyTransliterator, RuleCollection};
+/// use icu::properties::props::Script;
+///
+/// let mut collection = RuleCollection::default();
+/// collection.register_source(
+///     &"und-Latn-t-und-cyrl".parse().unwrap(),
+///     "М → M; о → o; с → s; к → k; в → v; а → a;".to_string(),
+///     ["Cyrillic-Latin"],
+///     false,
+///     true,
+/// );
+/// collection.register_source(
+///     &"und-Latn-t-und-grek".parse().unwrap(),
+///     "Α → A; θ → th; ή → i; ν → n; α → a;".to_string(),
+///     ["Greek-Latin"],
+///     false,
+///     true,
+/// );
+/// let provider = collection.as_provider();
+///
+/// let t = AnyTransliterator::try_new_unstable(
+///     &provider,
+///     &icu::properties::provider::Baked,
+///     &provider,
+///     &provider,
+///     Script::Latin,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     t.transliterate("Москва, Αθήνα, Paris".to_string()),
+///     "Moskva, Athina, Paris"
+/// );
+/// ```
+pub struct AnyTransliterator<'a> {
+    target: Script,
+    script_with_extensions: ScriptWithExtensions,
+    script_names: PropertyNamesShort<Script>,
+    load: Box<Loader<'a>>,
+    // source script -> transliterators that are applied in sequence, empty if there are none
+    transliterators: RefCell<LiteMap<Script, Result<Vec<Transliterator>, DataError>>>,
+}
+
+impl Debug for AnyTransliterator<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AnyTransliterator")
+            .field("target", &self.target)
+            .field("transliterators", &self.transliterators)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AnyTransliterator<'static> {
+    /// Construct an [`AnyTransliterator`] into the given target script.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub fn new(target: Script) -> Self {
+        Self::new_with_loader(
+            target,
+            ScriptWithExtensions::new().static_to_owned(),
+            PropertyNamesShort::new().static_to_owned(),
+            Box::new(Transliterator::try_new),
+        )
+    }
+}
+
+impl<'a> AnyTransliterator<'a> {
+    /// Creates a new [`AnyTransliterator`] using a [`BufferProvider`].
+    ///
+    /// The provider is borrowed, as transforms are loaded from it lazily.
+    ///
+    /// ✨ *Enabled with the `serde` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "serde")]
+    pub fn try_new_with_buffer_provider(
+        provider: &'a (impl BufferProvider + ?Sized),
+        target: Script,
+    ) -> Result<Self, DataError> {
+        Ok(Self::new_with_loader(
+            target,
+            ScriptWithExtensions::try_new_unstable(&provider.as_deserializing())?,
+            PropertyNamesShort::try_new_unstable(&provider.as_deserializing())?,
+            Box::new(move |locale| Transliterator::try_new_with_buffer_provider(provider, locale)),
+        ))
+    }
+
+    /// Creates a new [`AnyTransliterator`] using [`DataProvider`]s.
+    ///
+    /// The transliterator, normalizer and case mapping providers are borrowed, as transforms
+    /// are loaded from them lazily.
+    ///
+    /// <div class="stab unstable">
+    /// ⚠️ The bounds on <tt>provider</tt> may change over time, including in SemVer minor releases.
+    /// </div>
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    pub fn try_new_unstable<PT, PP, PN, PC>(
+        transliterator_provider: &'a PT,
+        properties_provider: &PP,
+        normalizer_provider: &'a PN,
+        casemap_provider: &'a PC,
+        target: Script,
+    ) -> Result<Self, DataError>
+    where
+        PT: DataProvider<TransliteratorRulesV1> + ?Sized,
+        PP: DataProvider<PropertyScriptWithExtensionsV1>
+            + DataProvider<PropertyNameShortScriptV1>
+            + ?Sized,
+        PC: DataProvider<CaseMapV1> + ?Sized,
+        PN: DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        Ok(Self::new_with_loader(
+            target,
+            ScriptWithExtensions::try_new_unstable(properties_provider)?,
+            PropertyNamesShort::try_new_unstable(properties_provider)?,
+            Box::new(move |locale| {
+                Transliterator::try_new_unstable(
+                    transliterator_provider,
+                    normalizer_provider,
+                    casemap_provider,
+                    locale,
+                )
+            }),
+        ))
+    }
+
+    fn new_with_loader(
+        target: Script,
+        script_with_extensions: ScriptWithExtensions,
+        script_names: PropertyNamesShort<Script>,
+        load: Box<Loader<'a>>,
+    ) -> Self {
+        Self {
+            target,
+            script_with_extensions,
+            script_names,
+            load,
+            transliterators: Default::default(),
+        }
+    }
+
+    /// Transliterates `input` and returns its transliteration.
+    ///
+    /// Runs whose transforms fail to load are left unchanged. Use
+    /// [`AnyTransliterator::try_transliterate`] to handle these errors.
+    pub fn transliterate(&self, input: String) -> String {
+        let mut buffer = TransliteratorBuffer::from_string(input);
+        if let Err(e) = self.transliterate_rep(Replaceable::new(&mut buffer)) {
+            icu_provider::log::warn!("Failed to load transliterator: {e}");
+        }
+        buffer.into_string()
+    }
+
+    /// Transliterates `input` and returns its transliteration, or the first error that occurred
+    /// while loading the transforms for its scripts.
+    ///
+    /// Transforms that are not found in the provider are not an error, the runs in their source
+    /// script are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use icu::experimental::transliterate::{AnyTransliterator, RuleCollection};
+    /// use icu::properties::props::Script;
+    ///
+    /// let mut collection = RuleCollection::default();
+    /// collection.register_source(
+    ///     &"und-Latn-t-und-grek".parse().unwrap(),
+    ///     "invalid".to_string(),
+    ///     [],
+    ///     false,
+    ///     true,
+    /// );
+    /// let provider = collection.as_provider();
+    ///
+    /// let t = AnyTransliterator::try_new_unstable(
+    ///     &provider,
+    ///     &icu::properties::provider::Baked,
+    ///     &provider,
+    ///     &provider,
+    ///     Script::Latin,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     t.try_transliterate("Москва".to_string()),
+    ///     Ok("Москва".to_string())
+    /// );
+    /// assert!(t.try_transliterate("Αθήνα".to_string()).is_err());
+    /// ```
+    pub fn try_transliterate(&self, input: String) -> Result<String, DataError> {
+        let mut buffer = TransliteratorBuffer::from_string(input);
+        self.transliterate_rep(Replaceable::new(&mut buffer))?;
+        Ok(buffer.into_string())
+    }
+
+    /// Transliterates all runs whose transforms can be loaded, and returns the first error.
+    fn transliterate_rep(&self, mut rep: Replaceable) -> Result<(), DataError> {
+        let script_with_extensions = self.script_with_extensions.as_borrowed();
+        let mut result = Ok(());
+        let mut start = rep.allowed_range().start;
+        while start < rep.allowed_upper_bound() {
+            let (script, run_len) = script_run(
+                script_with_extensions,
+                &rep.as_str()[start..rep.allowed_upper_bound()],
+            );
+            // the text after the run is not modified by the run's transliterators
+            let tail_len = rep.as_str().len() - (start + run_len);
+
+            self.load(script);
+            let transliterators = self.transliterators.borrow();
+            let transliterators = match transliterators.get(&script) {
+                Some(Ok(transliterators)) => transliterators.as_slice(),
+                Some(Err(e)) => {
+                    result = result.and(Err(*e));
+                    &[]
+                }
+                None => &[],
+            };
+            for t in transliterators {
+                let run_end = rep.as_str().len() - tail_len;
+                t.transliterator
+                    .get()
+                    .transliterate(rep.child_for_range(start..run_end), &t.env);
+            }
+
+            start = rep.as_str().len() - tail_len;
+        }
+        result
+    }
+
+    /// Loads the transliterators for `source` if they have not been loaded yet.
+    fn load(&self, source: Script) {
+        if self.transliterators.borrow().contains_key(&source) {
+            return;
+        }
+        let transliterators =
+            if matches!(source, Script::Common | Script::Inherited | Script::Unknown)
+                || source == self.target
+            {
+                Ok(Vec::new())
+            } else {
+                self.load_transforms(source)
+            };
+        self.transliterators
+            .borrow_mut()
+            .insert(source, transliterators);
+    }
+
+    /// Loads the transforms from `source` into the target script, pivoting through Latin if
+    /// there is no direct transform.
+    fn load_transforms(&self, source: Script) -> Result<Vec<Transliterator>, DataError> {
+        if let Some(t) = self.load_transform(source, self.target)? {
+            return Ok(vec![t]);
+        }
+        if source == Script::Latin || self.target == Script::Latin {
+            return Ok(Vec::new());
+        }
+        // pivot through Latin
+        Ok(self
+            .load_transform(source, Script::Latin)?
+            .zip(self.load_transform(Script::Latin, self.target)?)
+            .map(|(to_latin, from_latin)| vec![to_latin, from_latin])
+            .unwrap_or_default())
+    }
+
+    /// Loads the transform from `source` into `target`, or returns `None` if there is none.
+    fn load_transform(
+        &self,
+        source: Script,
+        target: Script,
+    ) -> Result<Option<Transliterator>, DataError> {
+        let names = self.script_names.as_borrowed();
+        let (Some(target), Some(source)) = (
+            names.get_locale_script(target),
+            names.get_locale_script(source),
+        ) else {
+            return Ok(None);
+        };
+        let Ok(locale) = Locale::try_from_str(&format!("und-{target}-t-und-{source}")) else {
+            return Ok(None);
+        };
+        match (self.load)(&locale) {
+            Ok(t) => Ok(Some(t)),
+            Err(e) if e.kind == DataErrorKind::IdentifierNotFound => Ok(None),
+            Err(e) => Err(e.with_display_context(&locale)),
+        }
+    }
+}
+
+/// Returns the script of the run at the start of `text`, and the length of that run.
+///
+/// Characters with the `Common` or `Inherited` script do not end a run, and neither do
+/// characters whose `Script_Extensions` contain the script of the run.
+fn script_run(script_with_extensions: ScriptWithExtensionsBorrowed, text: &str) -> (Script, usize) {
+    let mut run_script = Script::Common;
+    for (i, c) in text.char_indices() {
+        let script = script_with_extensions.get_script_val(c);
+        if matches!(script, Script::Common | Script::Inherited) || script == run_script {
+            continue;
+        }
+        if run_script == Script::Common {
+            run_script = script;
+        } else if !script_with_extensions.has_script(c, run_script) {
+            return (run_script, i);
+        }
+    }
+    (run_script, text.len())
+}
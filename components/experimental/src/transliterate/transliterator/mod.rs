@@ -4,17 +4,24 @@
 
 #![expect(clippy::indexing_slicing, clippy::unwrap_used)] // TODO(#3958): Remove.
 
+mod any;
 mod hardcoded;
 mod replaceable;
 
+pub use any::AnyTransliterator;
+
+use crate::transliterate::compile;
 use crate::transliterate::provider::{FunctionCall, Rule, RuleULE, SimpleId, VarTable};
-use crate::transliterate::provider::{RuleBasedTransliterator, Segment, TransliteratorRulesV1};
+use crate::transliterate::provider::{
+    RuleBasedTransliterator, Segment, TransliteratorIdsV1, TransliteratorRulesV1,
+};
 use crate::transliterate::transliterator::hardcoded::Case;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt::Debug;
+use core::mem;
 use core::ops::Range;
 use icu_casemap::provider::CaseMapV1;
 use icu_casemap::CaseMapper;
@@ -23,6 +30,7 @@ use icu_collections::codepointinvliststringlist::CodePointInversionListAndString
 use icu_locale::LanguageIdentifier;
 use icu_locale_core::Locale;
 use icu_normalizer::provider::*;
+use icu_normalizer::streaming::StreamingNormalizer;
 use icu_normalizer::{ComposingNormalizer, DecomposingNormalizer};
 use icu_provider::prelude::*;
 use litemap::LiteMap;
@@ -45,6 +53,10 @@ pub trait CustomTransliterator: Debug {
     ///
     /// The returned `String` must just be the transliteration of `input[range]`. The rest is
     /// there for context, if necessary.
+    ///
+    /// In incremental transliteration (see [`Transliterator::transliterate_incremental`]), more
+    /// input may follow `input`. The transliteration of `input[range]` is committed regardless,
+    /// so it should not depend on the text after `range`.
     fn transliterate(&self, input: &str, range: Range<usize>) -> String;
 }
 
@@ -62,26 +74,45 @@ enum InternalTransliterator {
 }
 
 impl InternalTransliterator {
-    fn transliterate(&self, mut rep: Replaceable, env: &Env) {
+    /// Transliterates the modifiable range of `rep` and returns where the transliteration
+    /// stopped. This is the end of the modifiable range, unless `rep` is incremental.
+    fn transliterate(&self, mut rep: Replaceable, env: &Env) -> usize {
         match self {
-            Self::RuleBased(rbt) => rbt.get().transliterate(rep, env),
+            Self::RuleBased(rbt) => return rbt.get().transliterate(rep, env),
             // TODO(#3910): internal hardcoded transliterators
+            Self::Composing(normalizer) if rep.is_incremental() => {
+                return normalize_incremental(normalizer.as_borrowed().streaming(), rep)
+            }
             Self::Composing(normalizer) => {
                 if let Cow::Owned(buf) = normalizer.as_borrowed().normalize(rep.as_str_modifiable())
                 {
                     rep.replace_modifiable_with_str(&buf);
                 }
             }
+            Self::Decomposing(normalizer) if rep.is_incremental() => {
+                return normalize_incremental(normalizer.as_borrowed().streaming(), rep)
+            }
             Self::Decomposing(normalizer) => {
                 if let Cow::Owned(buf) = normalizer.as_borrowed().normalize(rep.as_str_modifiable())
                 {
                     rep.replace_modifiable_with_str(&buf);
                 }
             }
+            Self::Lower(casemap) if rep.is_incremental() => {
+                // a final sigma depends on the following text, so it stays pending
+                let range = rep.allowed_range();
+                let committed =
+                    range.start + rep.as_str_modifiable().rfind('Σ').unwrap_or(range.len());
+                let mut buf = lowercase_with_context(casemap, rep.as_str(), range.start..committed)
+                    .into_owned();
+                let stop = range.start + buf.len();
+                buf.push_str(&rep.as_str()[committed..range.end]);
+                rep.replace_modifiable_with_str(&buf);
+                return stop;
+            }
             Self::Lower(casemap) => {
-                if let Cow::Owned(buf) = casemap
-                    .as_borrowed()
-                    .lowercase_to_string(rep.as_str_modifiable(), &LanguageIdentifier::UNKNOWN)
+                if let Cow::Owned(buf) =
+                    lowercase_with_context(casemap, rep.as_str(), rep.allowed_range())
                 {
                     rep.replace_modifiable_with_str(&buf);
                 }
@@ -94,7 +125,7 @@ impl InternalTransliterator {
                     rep.replace_modifiable_with_str(&buf);
                 }
             }
-            Self::Hex(t) => t.transliterate(rep),
+            Self::Hex(t) => t.transliterate(rep.child()),
             Self::Null => (),
             Self::Remove => rep.replace_modifiable_with_str(""),
             Self::Dyn(custom) => {
@@ -102,9 +133,56 @@ impl InternalTransliterator {
                 rep.replace_modifiable_with_str(&replacement)
             }
         }
+        rep.allowed_upper_bound()
     }
 }
 
+/// Lowercases `input[range]`.
+///
+/// Whether a 'Σ' is final depends on the text before it, which may already have been
+/// committed, so the preceding word is lowercased as well and then removed again. This works
+/// because all other lowercase mappings are independent of the context, and 'σ' and 'ς' have
+/// the same length.
+fn lowercase_with_context<'a>(
+    casemap: &CaseMapper,
+    input: &'a str,
+    range: Range<usize>,
+) -> Cow<'a, str> {
+    let casemap = casemap.as_borrowed();
+    if !input[range.clone()].contains('Σ') {
+        return casemap.lowercase_to_string(&input[range], &LanguageIdentifier::UNKNOWN);
+    }
+    // Whitespace is neither cased nor case-ignorable, so the final sigma condition does not
+    // look beyond it.
+    let context_start = input[..range.start].rfind(char::is_whitespace).unwrap_or(0);
+    let context_len = casemap
+        .lowercase_to_string(
+            &input[context_start..range.start],
+            &LanguageIdentifier::UNKNOWN,
+        )
+        .len();
+    let mut buf = casemap
+        .lowercase_to_string(
+            &input[context_start..range.end],
+            &LanguageIdentifier::UNKNOWN,
+        )
+        .into_owned();
+    buf.replace_range(..context_len, "");
+    Cow::Owned(buf)
+}
+
+/// Normalizes the modifiable range of an incremental `rep`, leaving the last segment pending,
+/// because it could still change with more input. Returns where the normalization stopped.
+fn normalize_incremental(mut normalizer: StreamingNormalizer, mut rep: Replaceable) -> usize {
+    let input = rep.as_str_modifiable();
+    let mut buf = String::with_capacity(input.len());
+    let _infallible = normalizer.push_str(input, &mut buf);
+    let stop = rep.allowed_range().start + buf.len();
+    buf.push_str(&input[input.len() - normalizer.pending().len()..]);
+    rep.replace_modifiable_with_str(&buf);
+    stop
+}
+
 type Env = LiteMap<String, InternalTransliterator>;
 
 /// A `Transliterator` allows transliteration based on [UTS #35 transform rules](https://unicode.org/reports/tr35/tr35-general.html#Transforms),
@@ -644,6 +722,336 @@ impl Transliterator {
         )
     }
 
+    /// Construct a [`Transliterator`] from an ICU transliterator ID, such as `"Latin-Cyrillic"`,
+    /// or a compound ID, such as `"Any-Hex/Unicode; [:^ASCII:] Remove; Lower"`.
+    ///
+    /// A compound ID is a semicolon-separated list of legacy IDs (`Source-Target/Variant`, where
+    /// `Any-` can be omitted), which are applied in order. Each ID can be preceded by a filter, and
+    /// the first element can be just a filter, which then applies to the whole transliterator.
+    /// Elements can also be written as `::` transform rules, such as `"::Null"`.
+    ///
+    /// Legacy IDs are mapped to the BCP-47 IDs of the data using [`TransliteratorIdsV1`].
+    /// Only visible transliterators can be referenced.
+    ///
+    /// See [`Transliterator::try_new_inverse_from_id_unstable`] for the inverse transliterator.
+    ///
+    /// # Example
+    /// ```
+    /// use icu::experimental::transliterate::{RuleCollection, Transliterator};
+    ///
+    /// let mut collection = RuleCollection::default();
+    /// collection.register_source(
+    ///     &"de-t-de-d0-ascii".parse().unwrap(),
+    ///     "ä → ae; ö → oe; ü → ue; ß → ss;".to_string(),
+    ///     ["de-ASCII"],
+    ///     false,
+    ///     true,
+    /// );
+    /// let provider = collection.as_provider();
+    ///
+    /// let t = Transliterator::try_new_from_id_unstable(
+    ///     &provider,
+    ///     &icu::properties::provider::Baked,
+    ///     &provider,
+    ///     &provider,
+    ///     "[:^Lu:] de-ASCII; Upper",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(t.transliterate("Übermäßig".to_string()), "ÜBERMAESSIG");
+    /// ```
+    ///
+    /// <div class="stab unstable">
+    /// ⚠️ The bounds on <tt>provider</tt> may change over time, including in SemVer minor releases.
+    /// </div>
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    pub fn try_new_from_id_unstable<PT, PP, PN, PC>(
+        transliterator_provider: &PT,
+        properties_provider: &PP,
+        normalizer_provider: &PN,
+        casemap_provider: &PC,
+        id: &str,
+    ) -> Result<Self, DataError>
+    where
+        PT: DataProvider<TransliteratorRulesV1> + DataProvider<TransliteratorIdsV1> + ?Sized,
+        PP: ?Sized + compile::CompoundIdPropertiesProvider,
+        PC: DataProvider<CaseMapV1> + ?Sized,
+        PN: DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        let ids =
+            DataProvider::<TransliteratorIdsV1>::load(transliterator_provider, Default::default())?
+                .payload;
+        Self::try_new_compound_unstable(
+            compile::compile_compound_id(id, ids.get(), false, properties_provider)?,
+            transliterator_provider,
+            normalizer_provider,
+            casemap_provider,
+        )
+    }
+
+    /// Creates a new [`Transliterator`] from an ICU transliterator ID using a [`BufferProvider`].
+    ///
+    /// See [`Transliterator::try_new_from_id_unstable`].
+    ///
+    /// ✨ *Enabled with the `serde` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "serde")]
+    pub fn try_new_from_id_with_buffer_provider(
+        provider: &(impl BufferProvider + ?Sized),
+        id: &str,
+    ) -> Result<Self, DataError> {
+        Self::try_new_from_id_unstable(
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            id,
+        )
+    }
+
+    /// Construct the inverse of the [`Transliterator`] with the given ICU transliterator ID.
+    ///
+    /// The inverse of a compound ID applies the inverses of its elements in reverse order, where
+    /// the inverse of `Source-Target/Variant` is `Target-Source/Variant`, and the inverses of
+    /// `Any-NFC`, `Any-NFKC` and `Any-Lower` are `Any-NFD`, `Any-NFKD` and `Any-Upper` (and vice versa).
+    /// Explicit inverses can be given in parentheses, such as `"Any-Hex/Unicode (Null)"`, and a trailing
+    /// parenthesized filter is used as the global filter of the inverse.
+    ///
+    /// # Example
+    /// ```
+    /// use icu::experimental::transliterate::{RuleCollection, Transliterator};
+    ///
+    /// let rules = "a ↔ α; b ↔ β;";
+    /// let mut collection = RuleCollection::default();
+    /// collection.register_source(
+    ///     &"und-Grek-t-und-latn".parse().unwrap(),
+    ///     rules.to_string(),
+    ///     ["Latin-Greek"],
+    ///     false,
+    ///     true,
+    /// );
+    /// collection.register_source(
+    ///     &"und-Latn-t-und-grek".parse().unwrap(),
+    ///     rules.to_string(),
+    ///     ["Greek-Latin"],
+    ///     true,
+    ///     true,
+    /// );
+    /// let provider = collection.as_provider();
+    ///
+    /// let t = Transliterator::try_new_inverse_from_id_unstable(
+    ///     &provider,
+    ///     &icu::properties::provider::Baked,
+    ///     &provider,
+    ///     &provider,
+    ///     "Latin-Greek; Upper",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(t.transliterate("ΑΒΓ".to_string()), "abγ");
+    /// ```
+    ///
+    /// <div class="stab unstable">
+    /// ⚠️ The bounds on <tt>provider</tt> may change over time, including in SemVer minor releases.
+    /// </div>
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    pub fn try_new_inverse_from_id_unstable<PT, PP, PN, PC>(
+        transliterator_provider: &PT,
+        properties_provider: &PP,
+        normalizer_provider: &PN,
+        casemap_provider: &PC,
+        id: &str,
+    ) -> Result<Self, DataError>
+    where
+        PT: DataProvider<TransliteratorRulesV1> + DataProvider<TransliteratorIdsV1> + ?Sized,
+        PP: ?Sized + compile::CompoundIdPropertiesProvider,
+        PC: DataProvider<CaseMapV1> + ?Sized,
+        PN: DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        let ids =
+            DataProvider::<TransliteratorIdsV1>::load(transliterator_provider, Default::default())?
+                .payload;
+        Self::try_new_compound_unstable(
+            compile::compile_compound_id(id, ids.get(), true, properties_provider)?,
+            transliterator_provider,
+            normalizer_provider,
+            casemap_provider,
+        )
+    }
+
+    /// Creates the inverse of the [`Transliterator`] with the given ICU transliterator ID using a [`BufferProvider`].
+    ///
+    /// See [`Transliterator::try_new_inverse_from_id_unstable`].
+    ///
+    /// ✨ *Enabled with the `serde` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "serde")]
+    pub fn try_new_inverse_from_id_with_buffer_provider(
+        provider: &(impl BufferProvider + ?Sized),
+        id: &str,
+    ) -> Result<Self, DataError> {
+        Self::try_new_inverse_from_id_unstable(
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            id,
+        )
+    }
+
+    /// Construct the inverse of the [`Transliterator`] with the given [`Locale`].
+    ///
+    /// The inverse is looked up through the legacy IDs in [`TransliteratorIdsV1`], so, for example,
+    /// the inverse of `und-Cyrl-t-und-latn` (`Latin-Cyrillic`) is `und-Latn-t-und-cyrl` (`Cyrillic-Latin`).
+    /// This fails if the data does not contain the reverse direction of the transliterator.
+    ///
+    /// # Example
+    /// ```
+    /// use icu::experimental::transliterate::{RuleCollection, Transliterator};
+    ///
+    /// let rules = "a ↔ α; b ↔ β;";
+    /// let mut collection = RuleCollection::default();
+    /// collection.register_source(
+    ///     &"und-Grek-t-und-latn".parse().unwrap(),
+    ///     rules.to_string(),
+    ///     ["Latin-Greek"],
+    ///     false,
+    ///     true,
+    /// );
+    /// collection.register_source(
+    ///     &"und-Latn-t-und-grek".parse().unwrap(),
+    ///     rules.to_string(),
+    ///     ["Greek-Latin"],
+    ///     true,
+    ///     true,
+    /// );
+    /// let provider = collection.as_provider();
+    ///
+    /// let t = Transliterator::try_new_inverse_unstable(
+    ///     &provider,
+    ///     &provider,
+    ///     &provider,
+    ///     &"und-Grek-t-und-latn".parse().unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(t.transliterate("αβ".to_string()), "ab");
+    /// ```
+    ///
+    /// <div class="stab unstable">
+    /// ⚠️ The bounds on <tt>provider</tt> may change over time, including in SemVer minor releases.
+    /// </div>
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    pub fn try_new_inverse_unstable<PT, PN, PC>(
+        transliterator_provider: &PT,
+        normalizer_provider: &PN,
+        casemap_provider: &PC,
+        locale: &Locale,
+    ) -> Result<Self, DataError>
+    where
+        PT: DataProvider<TransliteratorRulesV1> + DataProvider<TransliteratorIdsV1> + ?Sized,
+        PC: DataProvider<CaseMapV1> + ?Sized,
+        PN: DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        let ids =
+            DataProvider::<TransliteratorIdsV1>::load(transliterator_provider, Default::default())?
+                .payload;
+        let bcp47 = locale.to_string();
+        let inverse = ids
+            .get()
+            .legacy_to_bcp47
+            .iter()
+            .filter(|&(_, id)| id.eq_ignore_ascii_case(&bcp47))
+            .find_map(|(legacy_id, _)| ids.get().get(&compile::reverse_legacy_id(legacy_id)))
+            .ok_or_else(|| {
+                DataErrorKind::IdentifierNotFound
+                    .with_str_context("no inverse transliterator")
+                    .with_display_context(locale)
+            })?
+            .parse::<Locale>()
+            .map_err(|_| DataError::custom("Invalid transliterator ID"))?;
+        Self::internal_try_new_with_override_unstable(
+            &inverse,
+            None::<&fn(&Locale) -> Option<Result<Box<dyn CustomTransliterator>, DataError>>>,
+            transliterator_provider,
+            normalizer_provider,
+            casemap_provider,
+        )
+    }
+
+    /// Creates the inverse of the [`Transliterator`] with the given [`Locale`] using a [`BufferProvider`].
+    ///
+    /// See [`Transliterator::try_new_inverse_unstable`].
+    ///
+    /// ✨ *Enabled with the `serde` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "serde")]
+    pub fn try_new_inverse_with_buffer_provider(
+        provider: &(impl BufferProvider + ?Sized),
+        locale: &Locale,
+    ) -> Result<Self, DataError> {
+        Self::try_new_inverse_unstable(
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            &provider.as_deserializing(),
+            locale,
+        )
+    }
+
+    fn try_new_compound_unstable<PT, PN, PC>(
+        transliterator: DataPayload<TransliteratorRulesV1>,
+        transliterator_provider: &PT,
+        normalizer_provider: &PN,
+        casemap_provider: &PC,
+    ) -> Result<Self, DataError>
+    where
+        PT: DataProvider<TransliteratorRulesV1> + ?Sized,
+        PC: DataProvider<CaseMapV1> + ?Sized,
+        PN: DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        let mut env = LiteMap::new();
+        // the elements of a compound ID are user-facing, so they have to be visible
+        Transliterator::load_deps(
+            &transliterator,
+            None::<&fn(&Locale) -> Option<Result<Box<dyn CustomTransliterator>, DataError>>>,
+            transliterator_provider,
+            normalizer_provider,
+            casemap_provider,
+            false,
+            &mut env,
+        )?;
+        Ok(Transliterator {
+            transliterator,
+            env,
+        })
+    }
+
     fn internal_try_new_with_override_unstable<PN, PT, PC, F>(
         locale: &Locale,
         lookup: Option<&F>,
@@ -711,6 +1119,38 @@ impl Transliterator {
         }
         // Avoid recursive load
         env.insert(marker_attributes.to_string(), InternalTransliterator::Null);
+        Transliterator::load_deps(
+            &transliterator,
+            lookup,
+            transliterator_provider,
+            normalizer_provider,
+            casemap_provider,
+            true,
+            env,
+        )?;
+        Ok(transliterator)
+    }
+
+    fn load_deps<PT, PN, PC, F>(
+        transliterator: &DataPayload<TransliteratorRulesV1>,
+        lookup: Option<&F>,
+        transliterator_provider: &PT,
+        normalizer_provider: &PN,
+        casemap_provider: &PC,
+        allow_internal: bool,
+        env: &mut LiteMap<String, InternalTransliterator>,
+    ) -> Result<(), DataError>
+    where
+        PT: DataProvider<TransliteratorRulesV1> + ?Sized,
+        PC: DataProvider<CaseMapV1> + ?Sized,
+        PN: DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfkdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+        F: Fn(&Locale) -> Option<Result<Box<dyn CustomTransliterator>, DataError>>,
+    {
         for dep in transliterator.get().deps() {
             if !env.contains_key(&*dep) {
                 // Load the transliterator, by checking
@@ -728,14 +1168,14 @@ impl Transliterator {
                             transliterator_provider,
                             normalizer_provider,
                             casemap_provider,
-                            true,
+                            allow_internal,
                             env,
                         ).map(InternalTransliterator::RuleBased)
                     })?;
                 env.insert(dep.to_string(), internal_t);
             }
         }
-        Ok(transliterator)
+        Ok(())
     }
 
     fn load_special<PN, PD>(
@@ -803,13 +1243,252 @@ impl Transliterator {
     // use a data structure internally that works nicely with a &str, but if we don't, a String
     // is good to accept because the user might already have one.
     /// Transliterates `input` and returns its transliteration.
-    pub fn transliterate(&self, input: String) -> String {
+    pub fn transliterate(&self, mut input: String) -> String {
+        self.transliterate_in_place(&mut input);
+        input
+    }
+
+    /// Transliterates `buffer` in place.
+    ///
+    /// The allocation of `buffer` is reused, and text before the first replacement is
+    /// never moved or copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::experimental::transliterate::{RuleCollection, Transliterator};
+    ///
+    /// let mut collection = RuleCollection::default();
+    /// collection.register_source(
+    ///     &"und-t-und-x0-test".parse().unwrap(),
+    ///     "ß → ss;".to_string(),
+    ///     [],
+    ///     false,
+    ///     true,
+    /// );
+    /// let provider = collection.as_provider();
+    /// let t = Transliterator::try_new_unstable(
+    ///     &provider,
+    ///     &provider,
+    ///     &provider,
+    ///     &"und-t-und-x0-test".parse().unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut buffer = String::from("Straße");
+    /// t.transliterate_in_place(&mut buffer);
+    /// assert_eq!(buffer, "Strasse");
+    /// ```
+    pub fn transliterate_in_place(&self, buffer: &mut String) {
         // Thought: Seems too much work for the benefits, but maybe have a Cow buffer instead?
         //  Insertable would only actually to_owned if the replaced bytes differ from the ones already there
-        let mut buffer = TransliteratorBuffer::from_string(input);
-        let rep = Replaceable::new(&mut buffer);
+        let mut buf = TransliteratorBuffer::from_string(mem::take(buffer));
+        let rep = Replaceable::new(&mut buf);
         self.transliterator.get().transliterate(rep, &self.env);
-        buffer.into_string()
+        *buffer = buf.into_string();
+    }
+
+    /// Transliterates the UTF-16 `input` and returns its transliteration as UTF-16.
+    ///
+    /// Unpaired surrogates in `input` are replaced by U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::experimental::transliterate::{RuleCollection, Transliterator};
+    ///
+    /// let mut collection = RuleCollection::default();
+    /// collection.register_source(
+    ///     &"und-t-und-x0-test".parse().unwrap(),
+    ///     "ß → ss;".to_string(),
+    ///     [],
+    ///     false,
+    ///     true,
+    /// );
+    /// let provider = collection.as_provider();
+    /// let t = Transliterator::try_new_unstable(
+    ///     &provider,
+    ///     &provider,
+    ///     &provider,
+    ///     &"und-t-und-x0-test".parse().unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let input = "Straße".encode_utf16().collect::<Vec<_>>();
+    /// let output = t.transliterate_utf16(&input);
+    /// assert_eq!(String::from_utf16(&output).unwrap(), "Strasse");
+    /// ```
+    pub fn transliterate_utf16(&self, input: &[u16]) -> Vec<u16> {
+        let input = char::decode_utf16(input.iter().copied())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect();
+        self.transliterate(input).encode_utf16().collect()
+    }
+
+    /// Incrementally transliterates the pending text of `buffer`, as far as possible without
+    /// knowing the input that will follow. This is useful for input methods, which transliterate
+    /// while the user is typing.
+    ///
+    /// `position` divides `buffer` into three parts, all indices being byte offsets:
+    /// - `..position.context_start` is ignored.
+    /// - `position.context_start..position.start` is committed text, which has already been
+    ///   transliterated. It is not modified, but rules can match it as context.
+    /// - `position.start..` is pending text, which has not been transliterated yet.
+    ///
+    /// The pending text is transliterated up to the point where the result could depend on
+    /// input that is yet to come, for example because a rule matches a prefix of the rest of the
+    /// text. `position.start` is moved past the transliterated text, and the rest remains
+    /// pending, unmodified. Append new input to `buffer` and call this method again to continue,
+    /// and call [`Transliterator::finish_incremental`] at the end of the input.
+    ///
+    /// Returns the range of `buffer` that was committed by this call.
+    ///
+    /// Like in ICU4C/J, the pending text is only committed if all parts of a compound
+    /// transliterator commit it. Normalization keeps the last segment pending, so a transliterator
+    /// ending in a normalization will only commit text when finishing.
+    ///
+    /// If `position` is not valid for `buffer`, nothing is transliterated (GIGO).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::experimental::transliterate::{
+    ///     RuleCollection, TransliterationPosition, Transliterator,
+    /// };
+    ///
+    /// let mut collection = RuleCollection::default();
+    /// collection.register_source(
+    ///     &"und-t-und-x0-kana".parse().unwrap(),
+    ///     "ka → か; kya → きゃ; a → あ;".to_string(),
+    ///     [],
+    ///     false,
+    ///     true,
+    /// );
+    /// let provider = collection.as_provider();
+    /// let t = Transliterator::try_new_unstable(
+    ///     &provider,
+    ///     &provider,
+    ///     &provider,
+    ///     &"und-t-und-x0-kana".parse().unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut buffer = String::new();
+    /// let mut position = TransliterationPosition::default();
+    ///
+    /// // "k" could still become "ka" or "kya"
+    /// buffer.push('k');
+    /// assert_eq!(t.transliterate_incremental(&mut buffer, &mut position), 0..0);
+    /// assert_eq!(buffer, "k");
+    ///
+    /// buffer.push('a');
+    /// assert_eq!(t.transliterate_incremental(&mut buffer, &mut position), 0..3);
+    /// assert_eq!(buffer, "か");
+    ///
+    /// buffer.push_str("aky");
+    /// assert_eq!(t.transliterate_incremental(&mut buffer, &mut position), 3..6);
+    /// assert_eq!(buffer, "かあky");
+    /// assert_eq!(&buffer[position.start..], "ky");
+    ///
+    /// // the input has ended, so "ky" cannot become "kya" anymore
+    /// assert_eq!(t.finish_incremental(&mut buffer, &mut position), 6..8);
+    /// assert_eq!(buffer, "かあky");
+    /// ```
+    pub fn transliterate_incremental(
+        &self,
+        buffer: &mut String,
+        position: &mut TransliterationPosition,
+    ) -> Range<usize> {
+        let start = position.start;
+        if !position.is_valid_for(buffer) {
+            debug_assert!(false, "invalid position {position:?}");
+            return start..start;
+        }
+        // A transliteration that stops early may already have modified the text after where it
+        // stopped, which is why that text cannot simply remain pending. Like ICU4C/J, we instead
+        // transliterate ever longer prefixes of the pending text, only keeping the result if
+        // the full prefix is committed.
+        let pending = buffer.split_off(start);
+        let mut pending = pending.as_str();
+        let mut prefix_len = 0;
+        while let Some(c) = pending[prefix_len..].chars().next() {
+            prefix_len += c.len_utf8();
+            let pass_start = buffer.len();
+            buffer.push_str(&pending[..prefix_len]);
+            let stop =
+                self.transliterate_with_context(buffer, position.context_start, pass_start, true);
+            if stop == buffer.len() {
+                pending = &pending[prefix_len..];
+                prefix_len = 0;
+            } else {
+                buffer.truncate(pass_start);
+            }
+        }
+        position.start = buffer.len();
+        buffer.push_str(pending);
+        start..position.start
+    }
+
+    /// Transliterates all pending text of `buffer`, marking the end of the input of an
+    /// incremental transliteration. See [`Transliterator::transliterate_incremental`].
+    ///
+    /// Returns the range of `buffer` that was committed by this call.
+    ///
+    /// If `position` is not valid for `buffer`, nothing is transliterated (GIGO).
+    pub fn finish_incremental(
+        &self,
+        buffer: &mut String,
+        position: &mut TransliterationPosition,
+    ) -> Range<usize> {
+        let start = position.start;
+        if !position.is_valid_for(buffer) {
+            debug_assert!(false, "invalid position {position:?}");
+            return start..start;
+        }
+        position.start =
+            self.transliterate_with_context(buffer, position.context_start, start, false);
+        start..position.start
+    }
+
+    /// Transliterates `buffer[start..]`, using `buffer[context_start..start]` as context, and
+    /// returns where the transliteration stopped.
+    fn transliterate_with_context(
+        &self,
+        buffer: &mut String,
+        context_start: usize,
+        start: usize,
+        incremental: bool,
+    ) -> usize {
+        let mut buf = TransliteratorBuffer::from_string(mem::take(buffer));
+        let stop = match Replaceable::new_with_context(&mut buf, context_start, start, incremental)
+        {
+            Some(rep) => context_start + self.transliterator.get().transliterate(rep, &self.env),
+            None => start,
+        };
+        *buffer = buf.into_string();
+        stop
+    }
+}
+
+/// The state of an incremental transliteration, see [`Transliterator::transliterate_incremental`].
+///
+/// The positions are byte offsets into the buffer that is being transliterated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct TransliterationPosition {
+    /// The start of the text that is visible to the transliterator. Text before it is ignored,
+    /// and in particular cannot be matched as context by rules.
+    pub context_start: usize,
+    /// The start of the pending text, which is also the end of the committed text.
+    pub start: usize,
+}
+
+impl TransliterationPosition {
+    /// Returns whether the positions are ordered UTF-8 indices into `buffer`.
+    fn is_valid_for(&self, buffer: &str) -> bool {
+        self.context_start <= self.start
+            && buffer.is_char_boundary(self.context_start)
+            && buffer.is_char_boundary(self.start)
     }
 }
 
@@ -818,30 +1497,35 @@ impl RuleBasedTransliterator<'_> {
     /// 1. Split the input modifiable range of the Replaceable according into runs according to self.filter
     /// 2. Transliterate each run in sequence
     ///     1. Transliterate the first id_group, then the first rule_group, then the second id_group, etc.
-    fn transliterate(&self, mut rep: Replaceable, env: &Env) {
+    ///
+    /// In incremental runs, each transliteration only processes the text that the previous one
+    /// committed. Returns where the last one stopped.
+    fn transliterate(&self, mut rep: Replaceable, env: &Env) -> usize {
         // assumes the cursor is at the right position.
 
         rep.for_each_run(&self.filter, |run| {
             // eprintln!("got RBT filtered_run: {run:?}");
+            let mut stop = run.allowed_upper_bound();
             for (id_group, rule_group) in self.id_group_list.iter().zip(self.rule_group_list.iter())
             {
                 // first handle id_group
                 for single_id in id_group.iter() {
                     let id = SimpleId::zero_from(single_id);
-                    id.transliterate(run.child(), env);
+                    stop = id.transliterate(run.child_up_to(stop), env);
                 }
 
                 // then handle rule_group
                 let rule_group = RuleGroup::from(rule_group);
-                rule_group.transliterate(run.child(), &self.variable_table, env);
+                stop = rule_group.transliterate(run.child_up_to(stop), &self.variable_table, env);
             }
             // eprintln!("finished RBT filtered_run transliteration: {run:?}")
-        });
+            stop
+        })
     }
 }
 
 impl SimpleId<'_> {
-    fn transliterate(&self, mut rep: Replaceable, env: &Env) {
+    fn transliterate(&self, mut rep: Replaceable, env: &Env) -> usize {
         // eprintln!("transliterating SimpleId: {self:?}");
         // definitely loaded in the constructor
         let inner = env.get(self.id.as_ref()).unwrap();
@@ -861,12 +1545,12 @@ impl<'a> RuleGroup<'a> {
         Self { rules }
     }
 
-    fn transliterate(&self, mut rep: Replaceable, vt: &VarTable, env: &Env) {
+    fn transliterate(&self, mut rep: Replaceable, vt: &VarTable, env: &Env) -> usize {
         // no need to split into runs, because a RuleGroup has no filters.
 
         if self.rules.is_empty() {
             // empty rule group, nothing to do
-            return;
+            return rep.allowed_upper_bound();
         }
 
         // while the cursor has not reached the end yet, keep trying to apply each rule in order.
@@ -880,18 +1564,46 @@ impl<'a> RuleGroup<'a> {
                 let rule: Rule = Rule::zero_from(rule);
                 // eprintln!("trying rule: {rule:?}");
                 let matcher = rep.start_match();
-                if let Some((data, matcher)) = rule.matches(matcher, vt) {
-                    rule.apply(matcher.finish_match(), data, vt, env);
-                    // eprintln!("finished applying replacement: {rep:?}");
-                    // eprintln!("applied rule!");
-                    // rule application is responsible for updating the cursor
-                    continue 'main;
+                match rule.matches(matcher, vt) {
+                    RuleMatch::Match(data, matcher) => {
+                        rule.apply(matcher.finish_match(), data, vt, env);
+                        // eprintln!("finished applying replacement: {rep:?}");
+                        // eprintln!("applied rule!");
+                        // rule application is responsible for updating the cursor
+                        continue 'main;
+                    }
+                    // the rule could match once more input is known, so the rest stays pending.
+                    // this behavior is copied from ICU4C/J.
+                    RuleMatch::Partial => return rep.cursor(),
+                    RuleMatch::Mismatch => (),
                 }
             }
             // eprintln!("no rule matched, moving cursor forward");
             // no rule matched, so just move the cursor forward by one code point
             rep.step_cursor();
         }
+        rep.cursor()
+    }
+}
+
+/// The result of matching a [`Rule`].
+enum RuleMatch<'r1, 'r2> {
+    /// The rule matches, with the associated [`MatchData`] and [`RepMatcher`].
+    Match(MatchData, RepMatcher<'r1, 'r2, true>),
+    /// The rule does not match yet, but could match once more input is known. This only
+    /// happens for incremental runs.
+    Partial,
+    /// The rule does not match.
+    Mismatch,
+}
+
+impl RuleMatch<'_, '_> {
+    fn partial_if(needs_more_input: bool) -> Self {
+        if needs_more_input {
+            Self::Partial
+        } else {
+            Self::Mismatch
+        }
     }
 }
 
@@ -911,31 +1623,40 @@ impl Rule<'_> {
         replace_str_with_specials(&self.replacer, &mut dest, &data, vt, env);
     }
 
-    /// Returns `None` if there is no match. If there is a match, returns the associated
-    /// [`MatchData`] and [`RepMatcher`].
+    /// Matches this rule, see [`RuleMatch`].
     // Thought: RepMatcher<true> could be "FinishedRepMatcher"? but we can still match post..
     fn matches<'r1, 'r2>(
         &self,
         mut matcher: RepMatcher<'r1, 'r2, false>,
         vt: &VarTable,
-    ) -> Option<(MatchData, RepMatcher<'r1, 'r2, true>)> {
+    ) -> RuleMatch<'r1, 'r2> {
         let mut match_data = MatchData::new();
 
         if !self.ante_matches(&mut matcher, &mut match_data, vt) {
-            return None;
+            return RuleMatch::Mismatch;
         }
 
         if !self.key_matches(&mut matcher, &mut match_data, vt) {
-            return None;
+            return RuleMatch::partial_if(matcher.needs_more_input());
+        }
+
+        if !self.post.is_empty() && matcher.is_key_at_incremental_limit() {
+            // more input could extend the key, or change what the post context sees
+            return RuleMatch::Partial;
         }
 
         let mut matcher = matcher.finish_key();
 
         if !self.post_matches(&mut matcher, &mut match_data, vt) {
-            return None;
+            return RuleMatch::partial_if(matcher.needs_more_input());
+        }
+
+        if matcher.needs_more_input() {
+            // e.g. a quantifier could match more with more input
+            return RuleMatch::Partial;
         }
 
-        Some((match_data, matcher))
+        RuleMatch::Match(match_data, matcher)
     }
 
     /// Returns whether the ante context matches or not. Fills in `match_data` if applicable.
@@ -1747,6 +2468,45 @@ mod tests {
         assert_eq!(t.transliterate(input.to_string()), output);
     }
 
+    #[test]
+    fn test_in_place_and_utf16() {
+        let mut collection = RuleCollection::default();
+        collection.register_source(
+            &"und-x-test".parse().unwrap(),
+            include_str!("../../../tests/transliterate/data/transforms/Functionality.txt").into(),
+            [],
+            false,
+            true,
+        );
+        let t = Transliterator::try_new_unstable(
+            &collection.as_provider(),
+            &icu_normalizer::provider::Baked,
+            &icu_casemap::provider::Baked,
+            &"und-x-test".parse().unwrap(),
+        )
+        .unwrap();
+
+        let input = "abädefghijkl!";
+        let output = "FIfiunremovedtbxyzftbxyzxyzXYZjkT!";
+
+        let mut buffer = input.to_string();
+        t.transliterate_in_place(&mut buffer);
+        assert_eq!(buffer, output);
+
+        let input16 = input.encode_utf16().collect::<Vec<_>>();
+        let output16 = output.encode_utf16().collect::<Vec<_>>();
+        assert_eq!(t.transliterate_utf16(&input16), output16);
+
+        // unpaired surrogates are replaced
+        let mut input16 = input16;
+        input16.push(0xD800);
+        let output16 = t.transliterate_utf16(&input16);
+        assert_eq!(
+            String::from_utf16(&output16).unwrap(),
+            t.transliterate(input.to_string() + "\u{FFFD}")
+        );
+    }
+
     #[test]
     fn test_de_ascii() {
         let t = Transliterator::try_new(&"de-t-de-d0-ascii".parse().unwrap()).unwrap();
@@ -1841,4 +2601,386 @@ mod tests {
         let output = "うぃきぺでぃあへようこそ";
         assert_eq!(t.transliterate(input.to_string()), output);
     }
+    /// Transliterates `input` incrementally, one char at a time, and checks that the committed
+    /// text never changes and that the result matches the non-incremental transliteration.
+    fn check_incremental(t: &Transliterator, input: &str) {
+        let expected = t.transliterate(input.to_string());
+        let mut buffer = String::new();
+        let mut position = TransliterationPosition::default();
+        for c in input.chars() {
+            buffer.push(c);
+            let committed = t.transliterate_incremental(&mut buffer, &mut position);
+            assert_eq!(committed.end, position.start);
+            assert!(
+                expected.starts_with(&buffer[..position.start]),
+                "{input:?}: committed {:?}, expected {expected:?}",
+                &buffer[..position.start]
+            );
+        }
+        let committed = t.finish_incremental(&mut buffer, &mut position);
+        assert_eq!(committed.end, buffer.len());
+        assert_eq!(buffer, expected, "{input:?}");
+    }
+
+    #[test]
+    fn test_incremental() {
+        let mut collection = RuleCollection::default();
+        collection.register_source(
+            &"und-x-test".parse().unwrap(),
+            r"
+                ka → か;
+                kya → きゃ;
+                a → あ;
+                n } [^aeiou] → ん;
+                n { i → I;
+                x+ → X;
+                c } $ → C;
+            "
+            .into(),
+            [],
+            false,
+            true,
+        );
+        let t = Transliterator::try_new_unstable(
+            &collection.as_provider(),
+            &icu_normalizer::provider::Baked,
+            &icu_casemap::provider::Baked,
+            &"und-x-test".parse().unwrap(),
+        )
+        .unwrap();
+
+        let cases = [
+            // a prefix of a key stays pending
+            ("k", "", "k"),
+            ("ky", "", "ky"),
+            ("kya", "きゃ", ""),
+            ("kyak", "きゃ", "k"),
+            // a key at the end is pending if there is a post context
+            ("n", "", "n"),
+            ("nb", "んb", ""),
+            ("na", "nあ", ""),
+            // text is only committed once everything up to the end can be committed
+            ("nk", "", "nk"),
+            // a quantifier at the end could match more
+            ("xx", "", "xx"),
+            ("xxa", "Xあ", ""),
+            // an end anchor at the end is not the end of the input
+            ("c", "", "c"),
+            ("ca", "cあ", ""),
+            // unmatched text is committed
+            ("b", "b", ""),
+        ];
+        for (input, committed, pending) in cases {
+            let mut buffer = input.to_string();
+            let mut position = TransliterationPosition::default();
+            assert_eq!(
+                t.transliterate_incremental(&mut buffer, &mut position),
+                0..committed.len(),
+                "{input:?}"
+            );
+            assert_eq!(buffer[..position.start], *committed, "{input:?}");
+            assert_eq!(buffer[position.start..], *pending, "{input:?}");
+        }
+
+        // committed text is used as context, but not modified
+        let mut buffer = "ni".to_string();
+        let mut position = TransliterationPosition {
+            start: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            t.transliterate_incremental(&mut buffer, &mut position),
+            1..2
+        );
+        assert_eq!(buffer, "nI");
+
+        // text before the context start is not visible
+        let mut buffer = "ni".to_string();
+        let mut position = TransliterationPosition {
+            context_start: 1,
+            start: 1,
+        };
+        assert_eq!(
+            t.transliterate_incremental(&mut buffer, &mut position),
+            1..2
+        );
+        assert_eq!(buffer, "ni");
+
+        // at the end of the input, "ky" stays and the negated set matches the end
+        let mut buffer = "kyn".to_string();
+        let mut position = TransliterationPosition::default();
+        assert_eq!(
+            t.transliterate_incremental(&mut buffer, &mut position),
+            0..0
+        );
+        assert_eq!(t.finish_incremental(&mut buffer, &mut position), 0..5);
+        assert_eq!(buffer, "kyん");
+
+        for input in ["kyakkanxxnaxcxc", "kaaannkycni", "ccc"] {
+            check_incremental(&t, input);
+        }
+    }
+
+    #[test]
+    fn test_incremental_compound() {
+        let mut collection = RuleCollection::default();
+        collection.register_source(
+            &"und-x-test".parse().unwrap(),
+            r"
+                ::[a-z] Upper;
+                AB → X;
+                ::NFD;
+                X } C → Y;
+                ::NFC;
+            "
+            .into(),
+            [],
+            false,
+            true,
+        );
+        let t = Transliterator::try_new_unstable(
+            &collection.as_provider(),
+            &icu_normalizer::provider::Baked,
+            &icu_casemap::provider::Baked,
+            &"und-x-test".parse().unwrap(),
+        )
+        .unwrap();
+
+        for input in ["abc", "ab\u{301}cab", "aabbcc", "xabcé", "\u{301}"] {
+            check_incremental(&t, input);
+        }
+
+        // a normalization keeps the last segment pending
+        let mut buffer = "ab".to_string();
+        let mut position = TransliterationPosition::default();
+        assert_eq!(
+            t.transliterate_incremental(&mut buffer, &mut position),
+            0..0
+        );
+        assert_eq!(t.finish_incremental(&mut buffer, &mut position), 0..1);
+        assert_eq!(buffer, "X");
+
+        let mut collection = RuleCollection::default();
+        collection.register_source(
+            &"und-x-test".parse().unwrap(),
+            include_str!("../../../tests/transliterate/data/transforms/Functionality.txt").into(),
+            [],
+            false,
+            true,
+        );
+        let t = Transliterator::try_new_unstable(
+            &collection.as_provider(),
+            &icu_normalizer::provider::Baked,
+            &icu_casemap::provider::Baked,
+            &"und-x-test".parse().unwrap(),
+        )
+        .unwrap();
+        check_incremental(&t, "abädefghijkl!");
+
+        let mut collection = RuleCollection::default();
+        collection.register_source(
+            &"und-x-test".parse().unwrap(),
+            "::Lower;".into(),
+            [],
+            false,
+            true,
+        );
+        let t = Transliterator::try_new_unstable(
+            &collection.as_provider(),
+            &icu_normalizer::provider::Baked,
+            &icu_casemap::provider::Baked,
+            &"und-x-test".parse().unwrap(),
+        )
+        .unwrap();
+        // a final sigma depends on the committed text before it
+        for input in ["ΑΣ", "ΟΔΟΣ ΣΑΣ", "Σ", "ΑΣΑ", "Α.Σ."] {
+            check_incremental(&t, input);
+        }
+    }
+
+    fn compound_collection() -> RuleCollection {
+        let mut collection = RuleCollection::default();
+        let greek = "a ↔ α; b ↔ β; ';' ↔ '·';";
+        collection.register_source(
+            &"und-Grek-t-und-latn".parse().unwrap(),
+            greek.into(),
+            ["Latin-Greek"],
+            false,
+            true,
+        );
+        collection.register_source(
+            &"und-Latn-t-und-grek".parse().unwrap(),
+            greek.into(),
+            ["Greek-Latin"],
+            true,
+            true,
+        );
+        collection.register_source(
+            &"und-x-hidden".parse().unwrap(),
+            "a → b;".into(),
+            ["Any-Hidden"],
+            false,
+            false,
+        );
+        collection
+    }
+
+    #[test]
+    fn test_compound_id() {
+        let collection = compound_collection();
+        let provider = collection.as_provider();
+        let from_id = |id| {
+            Transliterator::try_new_from_id_unstable(
+                &provider,
+                &icu_properties::provider::Baked,
+                &provider,
+                &provider,
+                id,
+            )
+        };
+        let inverse_from_id = |id| {
+            Transliterator::try_new_inverse_from_id_unstable(
+                &provider,
+                &icu_properties::provider::Baked,
+                &provider,
+                &provider,
+                id,
+            )
+        };
+
+        let cases = [
+            ("Latin-Greek", "ab;AB", "αβ·AB"),
+            ("latin-greek; Upper", "ab;c", "ΑΒ·C"),
+            ("Upper; Latin-Greek", "ab;c", "AB·C"),
+            ("[a\\;] Latin-Greek", "ab;", "αb·"),
+            ("[\\;]; Latin-Greek; Upper", "ab;", "ab·"),
+            (":: Null ; ::[b] Latin-Greek;", "ab", "aβ"),
+            ("Any-Hex/Unicode (Null)", "a", "U+0061"),
+            ("NFD; Lower", "Ä", "a\u{308}"),
+        ];
+        for (id, input, output) in cases {
+            let t = from_id(id).unwrap();
+            assert_eq!(t.transliterate(input.into()), output, "{id}");
+        }
+
+        let inverse_cases = [
+            ("Latin-Greek", "αβ·", "ab;"),
+            ("Latin-Greek; Upper", "ΑΒ·Γ", "ab;γ"),
+            ("Any-Hex/Unicode (Null)", "U+0061", "U+0061"),
+            ("NFD; Lower", "a\u{308}", "Ä"),
+            ("[a]; Latin-Greek; ([β])", "αβ", "αb"),
+        ];
+        for (id, input, output) in inverse_cases {
+            let t = inverse_from_id(id).unwrap();
+            assert_eq!(t.transliterate(input.into()), output, "{id}");
+        }
+
+        for id in ["", " ; ", "Latin-Unknown", "Any-Hidden", "a → b", "[a"] {
+            assert!(from_id(id).is_err(), "{id}");
+        }
+        // the inverse of Any-Hex/Unicode is not defined
+        assert!(inverse_from_id("Any-Hex/Unicode").is_err());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let collection = compound_collection();
+        let provider = collection.as_provider();
+
+        let t = Transliterator::try_new_inverse_unstable(
+            &provider,
+            &provider,
+            &provider,
+            &"und-Latn-t-und-grek".parse().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(t.transliterate("ab;".into()), "αβ·");
+
+        assert!(Transliterator::try_new_inverse_unstable(
+            &provider,
+            &provider,
+            &provider,
+            &"und-x-hidden".parse().unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_any() {
+        use icu_properties::props::Script;
+
+        let mut collection = RuleCollection::default();
+        collection.register_source(
+            &"und-Latn-t-und-cyrl".parse().unwrap(),
+            r"д → d; а } [:Latin:] → 'a|'; а → a; \u0301 → ;".into(),
+            [],
+            false,
+            true,
+        );
+        let greek = "a ↔ α; d ↔ δ;";
+        collection.register_source(
+            &"und-Latn-t-und-grek".parse().unwrap(),
+            greek.into(),
+            [],
+            true,
+            true,
+        );
+        collection.register_source(
+            &"und-Grek-t-und-latn".parse().unwrap(),
+            greek.into(),
+            [],
+            false,
+            true,
+        );
+        // only fails if Arabic text is transliterated
+        collection.register_source(
+            &"und-Latn-t-und-arab".parse().unwrap(),
+            "invalid".into(),
+            [],
+            false,
+            true,
+        );
+        let provider = collection.as_provider();
+        let any = |target| {
+            AnyTransliterator::try_new_unstable(
+                &provider,
+                &icu_properties::provider::Baked,
+                &provider,
+                &provider,
+                target,
+            )
+            .unwrap()
+        };
+
+        let t = any(Script::Latin);
+        let cases = [
+            ("да", "da"),
+            // the combining mark belongs to the Cyrillic run
+            ("да\u{301}", "da"),
+            // Common characters belong to the preceding run
+            ("да, δα. да", "da, da. da"),
+            ("12 δα", "12 da"),
+            // context outside of the run is visible
+            ("даx", "da|x"),
+            // no transform, no change
+            ("ab 日本", "ab 日本"),
+        ];
+        for (input, output) in cases {
+            assert_eq!(
+                t.try_transliterate(input.into()),
+                Ok(output.into()),
+                "{input}"
+            );
+        }
+
+        // the error is returned whenever Arabic text is transliterated
+        assert!(t.try_transliterate("да دا".into()).is_err());
+        assert!(t.try_transliterate("دا".into()).is_err());
+        // the other runs are still transliterated
+        assert_eq!(t.transliterate("да دا".into()), "da دا");
+
+        // Cyrillic pivots through Latin
+        let t = any(Script::Greek);
+        assert_eq!(t.transliterate("да ad δ".into()), "δα αδ δ");
+    }
 }
@@ -34,6 +34,7 @@
 use super::Filter;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::cell::Cell;
 use core::fmt::{Debug, Formatter};
 use core::mem::ManuallyDrop;
 use core::ops::Range;
@@ -168,6 +169,7 @@ pub(crate) struct Replaceable<'a> {
     freeze_pre_len: usize,
     freeze_post_len: usize,
     cursor: usize,
+    incremental: bool,
 }
 
 impl<'a> Replaceable<'a> {
@@ -176,6 +178,43 @@ impl<'a> Replaceable<'a> {
         unsafe { Replaceable::from_hide(Hide::new(&mut buf.0)) }
     }
 
+    /// Returns a `Replaceable` whose modifiable range is `buf[start..]`. The text before
+    /// `context_start` is hidden, the text in `context_start..start` can only be used as context.
+    ///
+    /// If `incremental` is true, more input may follow the end of `buf`,
+    /// see [`is_incremental`](Replaceable::is_incremental).
+    ///
+    /// Returns `None` if `context_start` and `start` are not ordered UTF-8 indices into `buf`.
+    pub(crate) fn new_with_context(
+        buf: &'a mut TransliteratorBuffer,
+        context_start: usize,
+        start: usize,
+        incremental: bool,
+    ) -> Option<Self> {
+        // SAFETY: we have exclusive access to the buffer, so it must contain valid UTF-8
+        let text = unsafe { core::str::from_utf8_unchecked(&buf.0) };
+        if context_start > start
+            || !text.is_char_boundary(context_start)
+            || !text.is_char_boundary(start)
+        {
+            return None;
+        }
+        let content = Hide {
+            raw: &mut buf.0,
+            hide_pre_len: context_start,
+            hide_post_len: 0,
+        };
+        Some(Self {
+            content,
+            // SAFETY: these uphold the invariants, as `start` and `context_start` are valid
+            // UTF-8 indices
+            freeze_pre_len: start - context_start,
+            freeze_post_len: 0,
+            cursor: start - context_start,
+            incremental,
+        })
+    }
+
     /// # Safety
     /// The caller must ensure the visible portion of `content` is valid UTF-8.
     unsafe fn from_hide(content: Hide<'a>) -> Self {
@@ -186,6 +225,7 @@ impl<'a> Replaceable<'a> {
             freeze_pre_len: 0,
             freeze_post_len: 0,
             cursor: 0,
+            incremental: false,
         }
     }
 
@@ -252,6 +292,16 @@ impl<'a> Replaceable<'a> {
         self.cursor >= self.allowed_upper_bound()
     }
 
+    /// Returns whether more input may follow the modifiable range, i.e., whether this run
+    /// is transliterated incrementally.
+    ///
+    /// An incremental transliteration may stop before the end of the modifiable range if the
+    /// rest of it could be transliterated differently once more input is known. The text after
+    /// that position is left untransliterated, and is pending until the next transliteration.
+    pub(crate) fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+
     /// Returns a `Replaceable` with the same content as the current one.
     ///
     /// This is useful for repeated transliterations of the same modifiable range.
@@ -261,22 +311,89 @@ impl<'a> Replaceable<'a> {
             freeze_pre_len: self.freeze_pre_len,
             freeze_post_len: self.freeze_post_len,
             cursor: self.cursor,
+            incremental: self.incremental,
+        }
+    }
+
+    /// Returns a `Replaceable` with the same content as the current one, whose modifiable range
+    /// ends at `limit` instead.
+    ///
+    /// This is useful for incremental transliterations, where a transliteration may only
+    /// process what the previous one committed. `limit` must be a valid UTF-8 index into the
+    /// modifiable range, otherwise the full modifiable range is used.
+    pub(crate) fn child_up_to(&mut self, limit: usize) -> Replaceable<'_> {
+        let limit =
+            if self.allowed_range().contains(&limit) && self.as_str().is_char_boundary(limit) {
+                limit
+            } else {
+                debug_assert_eq!(limit, self.allowed_upper_bound(), "invalid limit");
+                self.allowed_upper_bound()
+            };
+        // SAFETY: limit is a valid UTF-8 index into the modifiable range
+        let freeze_post_len = self.content.len() - limit;
+        Replaceable {
+            content: self.content.child(),
+            freeze_pre_len: self.freeze_pre_len,
+            freeze_post_len,
+            cursor: self.cursor,
+            incremental: self.incremental,
+        }
+    }
+
+    /// Returns a `Replaceable` with the same content as the current one, whose modifiable range
+    /// is `range` instead.
+    ///
+    /// This is useful for transliterating parts of the modifiable range that are not defined
+    /// by a filter. `range` must be a valid UTF-8 range into the modifiable range, otherwise
+    /// the full modifiable range is used.
+    pub(crate) fn child_for_range(&mut self, range: Range<usize>) -> Replaceable<'_> {
+        let allowed_range = self.allowed_range();
+        let range = if allowed_range.start <= range.start
+            && range.start <= range.end
+            && range.end <= allowed_range.end
+            && self.as_str().is_char_boundary(range.start)
+            && self.as_str().is_char_boundary(range.end)
+        {
+            range
+        } else {
+            debug_assert_eq!(range, allowed_range, "invalid range");
+            allowed_range
+        };
+        // SAFETY: range is a valid UTF-8 range into the modifiable range
+        let freeze_post_len = self.content.len() - range.end;
+        // more input can only follow the range if it extends to the end of our run
+        let incremental = self.incremental && range.end == self.allowed_upper_bound();
+        Replaceable {
+            content: self.content.child(),
+            freeze_pre_len: range.start,
+            freeze_post_len,
+            cursor: range.start,
+            incremental,
         }
     }
 
     // Thought: could replace the F generic with a InternalTransliteratorTrait generic, but this is fine?
     /// Applies `f` to each sub-run as defined by `filter` of the current `Replaceable`'s run.
-    pub(crate) fn for_each_run<F>(&mut self, filter: &Filter, mut f: F)
+    ///
+    /// `f` returns where the transliteration of its run stopped, which is only before the end of
+    /// the run for incremental runs (see [`is_incremental`](Replaceable::is_incremental)).
+    /// Returns where the transliteration of the current `Replaceable`'s run stopped.
+    pub(crate) fn for_each_run<F>(&mut self, filter: &Filter, mut f: F) -> usize
     where
-        F: FnMut(&mut Replaceable),
+        F: FnMut(&mut Replaceable) -> usize,
     {
         // sub-runs must be part of *self*'s run, so we can only start in our modifiable range.
         let mut start = self.freeze_pre_len;
         // SAFETY: start is always the result of a function returning valid UTF-8 indices
         while let Some(mut run) = unsafe { self.next_filtered_run(start, filter) } {
-            f(&mut run);
+            let stop = f(&mut run);
             start = run.allowed_upper_bound();
+            if stop < start {
+                // only the last run can be incremental, the rest of it is pending
+                return stop;
+            }
         }
+        self.allowed_upper_bound()
     }
 
     /// Initiate the matching process for a single rule, starting at the current cursor.
@@ -288,6 +405,7 @@ impl<'a> Replaceable<'a> {
             forward_cursor: cursor,
             ante_match_len: 0,
             post_match_len: 0,
+            needs_more_input: Cell::new(false),
         }
     }
 
@@ -330,6 +448,8 @@ impl<'a> Replaceable<'a> {
         // eprintln!("computing filtered run for rep: {self:?}, start: {start}, run_start: {run_start}, run_end: {run_end}");
 
         let freeze_post_len = self.content.len() - run_end;
+        // more input can only follow the run if it extends to the end of our run
+        let incremental = self.incremental && run_end == self.allowed_upper_bound();
 
         Some(Replaceable {
             content: self.content.child(),
@@ -337,6 +457,7 @@ impl<'a> Replaceable<'a> {
             freeze_pre_len: run_start,
             freeze_post_len,
             cursor: run_start,
+            incremental,
         })
     }
 
@@ -398,6 +519,10 @@ impl Debug for Replaceable<'_> {
 /// After a successful match, the replacement can be applied using the [`Insertable`] returned by
 /// [`RepMatcher::finish_match`].
 ///
+/// When matching on an incremental `Replaceable` (see [`Replaceable::is_incremental`]), the
+/// `RepMatcher` records whether the match needed to look at the input after the known input,
+/// see [`RepMatcher::needs_more_input`].
+///
 /// # Safety
 /// The matched portions of the string (as defined by `rep.cursor`, `key_match_len`,
 /// `ante_match_len` and `post_match_len`) are all guaranteed to be valid UTF-8 subslices of
@@ -411,6 +536,7 @@ pub(super) struct RepMatcher<'a, 'b, const KEY_FINISHED: bool> {
     ante_match_len: usize, // relative to rep.cursor
     post_match_len: usize, // relative to rep.cursor + key_match_len
     forward_cursor: usize, // absolute
+    needs_more_input: Cell<bool>,
 }
 
 // we can only finish a KEY_FINISHED = true matcher
@@ -441,8 +567,15 @@ impl<'a, 'b> RepMatcher<'a, 'b, false> {
             ante_match_len: self.ante_match_len,
             post_match_len: self.post_match_len,
             forward_cursor: self.forward_cursor,
+            needs_more_input: self.needs_more_input,
         }
     }
+
+    /// Returns true if the key has been matched up to the end of an incremental run, i.e.,
+    /// if the key could be followed by more input.
+    pub(super) fn is_key_at_incremental_limit(&self) -> bool {
+        self.rep.incremental && self.forward_cursor == self.rep.allowed_upper_bound()
+    }
 }
 
 impl<const KEY_FINISHED: bool> RepMatcher<'_, '_, KEY_FINISHED> {
@@ -471,6 +604,19 @@ impl<const KEY_FINISHED: bool> RepMatcher<'_, '_, KEY_FINISHED> {
     fn remaining_ante_slice(&self) -> &str {
         &self.rep.as_str()[..self.ante_cursor()]
     }
+
+    /// Returns true if the result of this match could change once more input is known, because
+    /// matching tried to look at input after the end of an incremental run.
+    pub(super) fn needs_more_input(&self) -> bool {
+        self.needs_more_input.get()
+    }
+
+    /// Records that matching tried to look at input after the end of the key's or post's range.
+    fn hit_limit(&self) {
+        if self.rep.incremental {
+            self.needs_more_input.set(true);
+        }
+    }
 }
 
 impl<const KEY_FINISHED: bool> Utf8Matcher<Forward> for RepMatcher<'_, '_, KEY_FINISHED> {
@@ -483,11 +629,23 @@ impl<const KEY_FINISHED: bool> Utf8Matcher<Forward> for RepMatcher<'_, '_, KEY_F
     }
 
     fn is_empty(&self) -> bool {
-        self.remaining() == 0
+        let is_empty = self.remaining() == 0;
+        if is_empty {
+            self.hit_limit();
+        }
+        is_empty
     }
 
     fn match_str(&self, s: &str) -> bool {
-        self.remaining_forward_slice().starts_with(s)
+        let remaining = self.remaining_forward_slice();
+        if remaining.starts_with(s) {
+            return true;
+        }
+        if s.starts_with(remaining) {
+            // `s` could match once more input is known
+            self.hit_limit();
+        }
+        false
     }
 
     fn match_start_anchor(&self) -> bool {
@@ -496,7 +654,12 @@ impl<const KEY_FINISHED: bool> Utf8Matcher<Forward> for RepMatcher<'_, '_, KEY_F
 
     fn match_end_anchor(&self) -> bool {
         // no matter if we're matching key or post, we must be completely at the end of the string
-        self.forward_cursor == self.rep.content.len()
+        let is_end = self.forward_cursor == self.rep.content.len();
+        if is_end {
+            // the end of the known input need not be the end of the input
+            self.hit_limit();
+        }
+        is_end
     }
 
     fn consume(&mut self, len: usize) -> bool {
@@ -516,7 +679,11 @@ impl<const KEY_FINISHED: bool> Utf8Matcher<Forward> for RepMatcher<'_, '_, KEY_F
     }
 
     fn next_char(&self) -> Option<char> {
-        self.remaining_forward_slice().chars().next()
+        let next = self.remaining_forward_slice().chars().next();
+        if next.is_none() {
+            self.hit_limit();
+        }
+        next
     }
 }
 
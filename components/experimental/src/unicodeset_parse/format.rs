@@ -0,0 +1,405 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use icu_collections::codepointinvlist::{CodePointInversionList, CodePointInversionListBuilder};
+use icu_collections::codepointinvliststringlist::CodePointInversionListAndStringList;
+use icu_properties::props::{GeneralCategory, GeneralCategoryGroup, Script};
+use icu_properties::provider::{
+    PropertyEnumGeneralCategoryV1, PropertyEnumScriptV1, PropertyNameLongScriptV1,
+    PropertyNameShortGeneralCategoryV1,
+};
+use icu_properties::{CodePointMapData, PropertyNamesLong, PropertyNamesShort};
+use icu_provider::prelude::*;
+use writeable::Writeable;
+use zerovec::VarZeroSlice;
+
+/// How characters are escaped by a [`UnicodeSetFormatter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum EscapeMode {
+    /// Escape syntax characters, white space, control characters, noncharacters and
+    /// surrogates. This is what is needed for the pattern to be parsed back into the
+    /// same set, plus characters that are invisible in any context.
+    #[default]
+    Minimal,
+    /// Also escape all non-ASCII characters, so that the pattern is ASCII.
+    NonAscii,
+}
+
+/// Options for a [`UnicodeSetFormatter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct UnicodeSetFormatterOptions {
+    /// How characters are escaped.
+    pub escape: EscapeMode,
+}
+
+/// The aliases of the General_Category groups, from `PropertyValueAliases.txt`.
+const GENERAL_CATEGORY_GROUPS: [(GeneralCategoryGroup, &str); 8] = [
+    (GeneralCategoryGroup::Letter, "L"),
+    (GeneralCategoryGroup::CasedLetter, "LC"),
+    (GeneralCategoryGroup::Mark, "M"),
+    (GeneralCategoryGroup::Number, "N"),
+    (GeneralCategoryGroup::Punctuation, "P"),
+    (GeneralCategoryGroup::Symbol, "S"),
+    (GeneralCategoryGroup::Separator, "Z"),
+    (GeneralCategoryGroup::Other, "C"),
+];
+
+/// Formats sets of code points and strings as UnicodeSet patterns, which can be parsed
+/// back with [`parse`](super::parse()).
+///
+/// Ranges are written compactly, and sets that contain both U+0000 and U+10FFFF are
+/// written as the complement of the remaining code points. A formatter constructed with
+/// [`UnicodeSetFormatter::try_new_with_property_names`] writes sets that equal a
+/// General_Category or Script value, or their complement, with the property name.
+///
+/// Surrogate code points are escaped, but the parser does not accept them, so sets
+/// containing surrogates do not round-trip.
+///
+/// # Examples
+///
+/// ```
+/// use icu::experimental::unicodeset_parse::*;
+/// use writeable::assert_writeable_eq;
+///
+/// let (set, _) = parse(r"[a-zàé {ch} \-]").unwrap();
+/// let formatter = UnicodeSetFormatter::new(Default::default());
+/// assert_writeable_eq!(formatter.format(&set), r"[\-a-zàé{ch}]");
+///
+/// let mut options = UnicodeSetFormatterOptions::default();
+/// options.escape = EscapeMode::NonAscii;
+/// let formatter = UnicodeSetFormatter::new(options);
+/// assert_writeable_eq!(formatter.format(&set), r"[\-a-z\u00E0\u00E9{ch}]");
+///
+/// let formatter = UnicodeSetFormatter::try_new_with_property_names(Default::default()).unwrap();
+/// let (set, _) = parse(r"[\p{Lu}{ab}]").unwrap();
+/// assert_writeable_eq!(formatter.format(&set), "[[:Lu:]{ab}]");
+/// let (set, _) = parse(r"[^[:Script=Greek:]]").unwrap();
+/// assert_writeable_eq!(formatter.format(&set), "[:^Greek:]");
+/// ```
+#[derive(Debug)]
+pub struct UnicodeSetFormatter {
+    options: UnicodeSetFormatterOptions,
+    /// Named sets, in order of preference.
+    properties: Vec<(String, CodePointInversionList<'static>)>,
+}
+
+impl UnicodeSetFormatter {
+    /// Creates a formatter that writes code points and ranges, without property names.
+    pub const fn new(options: UnicodeSetFormatterOptions) -> Self {
+        Self {
+            options,
+            properties: Vec::new(),
+        }
+    }
+
+    /// Creates a formatter that also writes property names, using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub fn try_new_with_property_names(
+        options: UnicodeSetFormatterOptions,
+    ) -> Result<Self, DataError> {
+        Self::try_new_with_property_names_unstable(&icu_properties::provider::Baked, options)
+    }
+
+    #[cfg(feature = "serde")]
+    #[doc = icu_provider::gen_buffer_unstable_docs!(BUFFER, Self::try_new_with_property_names)]
+    pub fn try_new_with_property_names_with_buffer_provider(
+        provider: &(impl BufferProvider + ?Sized),
+        options: UnicodeSetFormatterOptions,
+    ) -> Result<Self, DataError> {
+        Self::try_new_with_property_names_unstable(&provider.as_deserializing(), options)
+    }
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::try_new_with_property_names)]
+    pub fn try_new_with_property_names_unstable<P>(
+        provider: &P,
+        options: UnicodeSetFormatterOptions,
+    ) -> Result<Self, DataError>
+    where
+        P: DataProvider<PropertyEnumGeneralCategoryV1>
+            + DataProvider<PropertyEnumScriptV1>
+            + DataProvider<PropertyNameShortGeneralCategoryV1>
+            + DataProvider<PropertyNameLongScriptV1>
+            + ?Sized,
+    {
+        let mut properties = Vec::new();
+
+        let general_category = CodePointMapData::<GeneralCategory>::try_new_unstable(provider)?;
+        let categories = GeneralCategory::ALL_VALUES
+            .iter()
+            .map(|&gc| {
+                let mut builder = CodePointInversionListBuilder::new();
+                for range in general_category.as_borrowed().iter_ranges_for_value(gc) {
+                    builder.add_range32(range);
+                }
+                (gc, builder.build())
+            })
+            .collect::<Vec<_>>();
+        for (group, name) in GENERAL_CATEGORY_GROUPS {
+            let mut builder = CodePointInversionListBuilder::new();
+            for (gc, set) in &categories {
+                if group.contains(*gc) {
+                    builder.add_set(set);
+                }
+            }
+            properties.push((String::from(name), builder.build()));
+        }
+        let names = PropertyNamesShort::<GeneralCategory>::try_new_unstable(provider)?;
+        for (gc, set) in categories {
+            if let Some(name) = names.as_borrowed().get(gc) {
+                properties.push((String::from(name), set));
+            }
+        }
+
+        let script = CodePointMapData::<Script>::try_new_unstable(provider)?;
+        let mut scripts = BTreeMap::<u16, CodePointInversionListBuilder>::new();
+        for range in script.as_borrowed().iter_ranges() {
+            scripts
+                .entry(range.value.to_icu4c_value())
+                .or_default()
+                .add_range32(range.range);
+        }
+        let names = PropertyNamesLong::<Script>::try_new_unstable(provider)?;
+        for (script, set) in scripts {
+            if let Some(name) = names.as_borrowed().get(Script::from_icu4c_value(script)) {
+                properties.push((String::from(name), set.build()));
+            }
+        }
+
+        Ok(Self {
+            options,
+            properties,
+        })
+    }
+
+    /// Formats a set of code points and strings.
+    pub fn format<'a>(
+        &'a self,
+        set: &'a CodePointInversionListAndStringList<'_>,
+    ) -> FormattedUnicodeSet<'a> {
+        FormattedUnicodeSet {
+            formatter: self,
+            code_points: set.code_points(),
+            strings: set.strings(),
+        }
+    }
+
+    /// Formats a set of code points.
+    pub fn format_code_points<'a>(
+        &'a self,
+        set: &'a CodePointInversionList<'_>,
+    ) -> FormattedUnicodeSet<'a> {
+        FormattedUnicodeSet {
+            formatter: self,
+            code_points: set,
+            strings: VarZeroSlice::new_empty(),
+        }
+    }
+
+    fn property_name(&self, set: &CodePointInversionList) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(_, p)| p.size() == set.size() && p == set)
+            .map(|(name, _)| name.as_str())
+    }
+
+    fn write_char<W: Write + ?Sized>(&self, c: u32, sink: &mut W) -> fmt::Result {
+        match char::from_u32(c) {
+            Some(c @ ('[' | ']' | '-' | '^' | '&' | '\\' | '{' | '}' | '$' | ':')) => {
+                sink.write_char('\\')?;
+                sink.write_char(c)
+            }
+            Some(c)
+                if !is_pattern_white_space(c)
+                    && !c.is_control()
+                    && !is_noncharacter(c)
+                    && (c.is_ascii() || self.options.escape == EscapeMode::Minimal) =>
+            {
+                sink.write_char(c)
+            }
+            _ if c <= 0xFFFF => write!(sink, "\\u{c:04X}"),
+            _ => write!(sink, "\\U{c:08X}"),
+        }
+    }
+}
+
+/// Whether a character is in Pattern_White_Space, which is ignored by the parser.
+///
+/// This property is immutable, see [UAX #31](https://www.unicode.org/reports/tr31/#Stability).
+fn is_pattern_white_space(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0009}'
+            ..='\u{000D}' | ' ' | '\u{0085}' | '\u{200E}' | '\u{200F}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+/// Whether a character is a noncharacter, U+FDD0..U+FDEF or the last two code points of a plane.
+fn is_noncharacter(c: char) -> bool {
+    matches!(c, '\u{FDD0}'..='\u{FDEF}') || (c as u32 & 0xFFFE) == 0xFFFE
+}
+
+/// A set formatted as a UnicodeSet pattern, returned by [`UnicodeSetFormatter`].
+#[derive(Debug)]
+pub struct FormattedUnicodeSet<'a> {
+    formatter: &'a UnicodeSetFormatter,
+    code_points: &'a CodePointInversionList<'a>,
+    strings: &'a VarZeroSlice<str>,
+}
+
+impl Writeable for FormattedUnicodeSet<'_> {
+    fn write_to<W: Write + ?Sized>(&self, sink: &mut W) -> fmt::Result {
+        let formatter = self.formatter;
+
+        if let Some(name) = formatter.property_name(self.code_points) {
+            if self.strings.is_empty() {
+                return write!(sink, "[:{name}:]");
+            }
+            write!(sink, "[[:{name}:]")?;
+        } else if let Some(name) = (!formatter.properties.is_empty() && self.strings.is_empty())
+            .then(|| {
+                let mut builder = CodePointInversionListBuilder::new();
+                builder.add_set(self.code_points);
+                builder.complement();
+                formatter.property_name(&builder.build())
+            })
+            .flatten()
+        {
+            return write!(sink, "[:^{name}:]");
+        } else {
+            sink.write_char('[')?;
+            let complement = self.strings.is_empty()
+                && self.code_points.contains32(0)
+                && self.code_points.contains32(0x10FFFF);
+            if complement {
+                sink.write_char('^')?;
+            }
+            let mut write_range = |range: core::ops::RangeInclusive<u32>| {
+                let (start, end) = range.into_inner();
+                formatter.write_char(start, sink)?;
+                if end > start + 1 {
+                    sink.write_char('-')?;
+                }
+                if end > start {
+                    formatter.write_char(end, sink)?;
+                }
+                Ok::<(), fmt::Error>(())
+            };
+            if complement {
+                self.code_points
+                    .iter_ranges_complemented()
+                    .try_for_each(&mut write_range)?;
+            } else {
+                self.code_points
+                    .iter_ranges()
+                    .try_for_each(&mut write_range)?;
+            }
+        }
+
+        for s in self.strings.iter() {
+            sink.write_char('{')?;
+            for c in s.chars() {
+                formatter.write_char(c as u32, sink)?;
+            }
+            sink.write_char('}')?;
+        }
+        sink.write_char(']')
+    }
+}
+
+writeable::impl_display_with_writeable!(FormattedUnicodeSet<'_>);
+
+#[cfg(test)]
+#[cfg(feature = "compiled_data")]
+mod tests {
+    use super::*;
+    use crate::unicodeset_parse::parse;
+    use writeable::assert_writeable_eq;
+
+    fn round_trip(formatter: &UnicodeSetFormatter, pattern: &str) -> String {
+        let (set, _) = parse(pattern).unwrap();
+        let formatted = formatter.format(&set).write_to_string().into_owned();
+        let (parsed, consumed) = parse(&formatted).unwrap();
+        assert_eq!(consumed, formatted.len(), "{pattern} → {formatted}");
+        assert_eq!(parsed, set, "{pattern} → {formatted}");
+        formatted
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let minimal = UnicodeSetFormatter::new(Default::default());
+        let non_ascii = UnicodeSetFormatter::new(UnicodeSetFormatterOptions {
+            escape: EscapeMode::NonAscii,
+        });
+        let named = UnicodeSetFormatter::try_new_with_property_names(Default::default()).unwrap();
+
+        for pattern in [
+            "[]",
+            "[a]",
+            "[ab]",
+            "[abc]",
+            "[a-cx-z]",
+            r"[\[\]\-\^\&\\\{\}\$\:]",
+            r"[\u0000- \u0085 ‎]",
+            r"[\U0010FFFF\U0001F600-\U0001F64F]",
+            "[^a]",
+            "[^]",
+            r"[\u0000 x-z]",
+            "[{ch}{}{a b}a-c]",
+            r"[{\}\{\\}]",
+            "[:L:]",
+            "[:Lu:]",
+            "[:Cn:]",
+            "[:^Lu:]",
+            "[:Greek:]",
+            "[:Zzzz:]",
+            "[[:Cyrillic:]{ab}]",
+            "[[:Lu:]-[A]]",
+            "[[:Lu:][:Ll:][:Lt:]]",
+            r"[[:Nd:]&[\u0000-ÿ]]",
+        ] {
+            round_trip(&minimal, pattern);
+            let ascii = round_trip(&non_ascii, pattern);
+            assert!(ascii.is_ascii(), "{ascii}");
+            round_trip(&named, pattern);
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let formatter = UnicodeSetFormatter::new(Default::default());
+        let format = |pattern| round_trip(&formatter, pattern);
+        assert_eq!(format("[cba]"), "[a-c]");
+        assert_eq!(format("[ab]"), "[ab]");
+        assert_eq!(format("[a b]"), "[ab]");
+        assert_eq!(format("[^a]"), r"[^a]");
+        assert_eq!(format(r"[^\u0000]"), r"[\u0001-\U0010FFFF]");
+        assert_eq!(format(r"[\u0000\u0009\u0020]"), r"[\u0000\u0009\u0020]");
+        assert_eq!(format("[é-ë]"), "[é-ë]");
+        assert_eq!(format("[{a b}]"), "[{ab}]");
+        assert_eq!(format(r"[^]"), "[^]");
+
+        let named = UnicodeSetFormatter::try_new_with_property_names(Default::default()).unwrap();
+        let format = |pattern| round_trip(&named, pattern);
+        assert_eq!(format(r"[\p{Letter}]"), "[:L:]");
+        assert_eq!(format(r"[[:Lu:][:Ll:][:Lt:]]"), "[:LC:]");
+        assert_eq!(format(r"\P{Script=Latn}"), "[:^Latin:]");
+        assert_eq!(format(r"[[:Lu:]{ab}]"), "[[:Lu:]{ab}]");
+        assert_eq!(format(r"[abc]"), "[a-c]");
+
+        let set =
+            CodePointInversionList::try_from_u32_inversion_list_slice(&[0xD800, 0xD802]).unwrap();
+        assert_writeable_eq!(formatter.format_code_points(&set), r"[\uD800\uD801]");
+    }
+}
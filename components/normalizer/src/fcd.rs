@@ -0,0 +1,195 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use crate::ccc_from_trie_value;
+use crate::provider::NormalizerNfdDataV1;
+use crate::provider::NormalizerNfdTablesV1;
+use crate::starter_and_decomposes_to_self_impl;
+use crate::trie_value_has_ccc;
+use crate::trie_value_indicates_special_non_starter_decomposition;
+use crate::DecomposingNormalizer;
+use crate::DecomposingNormalizerBorrowed;
+use icu_provider::prelude::*;
+
+#[cfg(feature = "utf16_iter")]
+use utf16_iter::Utf16CharsEx;
+
+#[cfg(feature = "utf8_iter")]
+use utf8_iter::Utf8CharsEx;
+
+/// Borrowed version of a checker for the FCD ("Fast C or D") form.
+///
+/// Text is in FCD (see [UTN #5](https://www.unicode.org/notes/tn5/)) if the canonical
+/// decompositions of its characters, concatenated without canonical reordering, are
+/// already in canonical order. Text in NFD or FCC is always in FCD, and operations such as
+/// collation can process FCD text as if it was in NFD.
+///
+/// Unlike the normalizers, this checker only reports whether the text is in FCD and does
+/// not transform it.
+///
+/// # Examples
+///
+/// ```
+/// let fcd = icu::normalizer::FcdCheckerBorrowed::new();
+///
+/// assert!(fcd.is_fcd("ệ"));
+/// assert!(fcd.is_fcd("e\u{0323}\u{0302}"));
+/// // The acute accent of U+00E1 would have to be reordered after U+0316.
+/// assert!(!fcd.is_fcd("\u{00E1}\u{0316}"));
+/// assert!(fcd.is_fcd("a\u{0316}\u{0301}"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FcdCheckerBorrowed<'a> {
+    nfd: DecomposingNormalizerBorrowed<'a>,
+}
+
+#[cfg(feature = "compiled_data")]
+impl Default for FcdCheckerBorrowed<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FcdCheckerBorrowed<'static> {
+    /// Cheaply converts a [`FcdCheckerBorrowed<'static>`] into a [`FcdChecker`].
+    ///
+    /// Note: Due to branching and indirection, using [`FcdChecker`] might inhibit some
+    /// compile-time optimizations that are possible with [`FcdCheckerBorrowed`].
+    pub const fn static_to_owned(self) -> FcdChecker {
+        FcdChecker {
+            nfd: self.nfd.static_to_owned(),
+        }
+    }
+
+    /// Constructs a new `FcdChecker` using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub const fn new() -> Self {
+        Self {
+            nfd: DecomposingNormalizerBorrowed::new_nfd(),
+        }
+    }
+}
+
+impl FcdCheckerBorrowed<'_> {
+    /// Returns the canonical combining classes of the first and the last
+    /// character of the canonical decomposition of `c`.
+    fn lead_and_trail_ccc(&self, c: char) -> (u8, u8) {
+        if u32::from(c) < u32::from(self.nfd.decomposition_passthrough_bound) {
+            return (0, 0);
+        }
+        let trie = &self.nfd.decompositions.trie;
+        let trie_value = trie.get(c);
+        if starter_and_decomposes_to_self_impl(trie_value) {
+            return (0, 0);
+        }
+        if trie_value_has_ccc(trie_value)
+            && !trie_value_indicates_special_non_starter_decomposition(trie_value)
+        {
+            // Non-starter that decomposes to itself.
+            let ccc = ccc_from_trie_value(trie_value).to_icu4c_value();
+            return (ccc, ccc);
+        }
+        let ccc = |d: char| ccc_from_trie_value(trie.get(d)).to_icu4c_value();
+        let mut decomposition = self.nfd.normalize_iter(core::iter::once(c));
+        let lead = decomposition.next().map_or(0, ccc);
+        let trail = decomposition.last().map_or(lead, ccc);
+        (lead, trail)
+    }
+
+    fn is_fcd_iter(&self, iter: impl Iterator<Item = char>) -> bool {
+        let mut previous_trail = 0;
+        for c in iter {
+            let (lead, trail) = self.lead_and_trail_ccc(c);
+            if lead != 0 && lead < previous_trail {
+                return false;
+            }
+            previous_trail = trail;
+        }
+        true
+    }
+
+    /// Checks whether a string slice is in FCD.
+    pub fn is_fcd(&self, text: &str) -> bool {
+        self.is_fcd_iter(text.chars())
+    }
+
+    /// Checks whether a slice of potentially-invalid UTF-16 is in FCD.
+    ///
+    /// Unpaired surrogates are treated as the REPLACEMENT CHARACTER.
+    ///
+    /// ✨ *Enabled with the `utf16_iter` Cargo feature.*
+    #[cfg(feature = "utf16_iter")]
+    pub fn is_fcd_utf16(&self, text: &[u16]) -> bool {
+        self.is_fcd_iter(text.chars())
+    }
+
+    /// Checks whether a slice of potentially-invalid UTF-8 is in FCD.
+    ///
+    /// Ill-formed byte sequences are mapped to the REPLACEMENT CHARACTER
+    /// according to the WHATWG Encoding Standard before checking.
+    ///
+    /// ✨ *Enabled with the `utf8_iter` Cargo feature.*
+    #[cfg(feature = "utf8_iter")]
+    pub fn is_fcd_utf8(&self, text: &[u8]) -> bool {
+        self.is_fcd_iter(text.chars())
+    }
+}
+
+/// A checker for the FCD ("Fast C or D") form.
+///
+/// See [`FcdCheckerBorrowed`].
+#[derive(Debug)]
+pub struct FcdChecker {
+    nfd: DecomposingNormalizer,
+}
+
+#[cfg(feature = "compiled_data")]
+impl Default for FcdChecker {
+    fn default() -> Self {
+        Self::new().static_to_owned()
+    }
+}
+
+impl FcdChecker {
+    /// Constructs a borrowed version of this type for more efficient querying.
+    pub fn as_borrowed(&self) -> FcdCheckerBorrowed<'_> {
+        FcdCheckerBorrowed {
+            nfd: self.nfd.as_borrowed(),
+        }
+    }
+
+    /// Constructs a new `FcdCheckerBorrowed` using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    #[expect(clippy::new_ret_no_self)]
+    pub const fn new() -> FcdCheckerBorrowed<'static> {
+        FcdCheckerBorrowed::new()
+    }
+
+    icu_provider::gen_buffer_data_constructors!(() -> error: DataError,
+        functions: [
+            new: skip,
+            try_new_with_buffer_provider,
+            try_new_unstable,
+            Self,
+        ]
+    );
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::new)]
+    pub fn try_new_unstable<D>(provider: &D) -> Result<Self, DataError>
+    where
+        D: DataProvider<NormalizerNfdDataV1> + DataProvider<NormalizerNfdTablesV1> + ?Sized,
+    {
+        Ok(FcdChecker {
+            nfd: DecomposingNormalizer::try_new_nfd_unstable(provider)?,
+        })
+    }
+}
@@ -3,7 +3,7 @@
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
 // https://github.com/unicode-org/icu4x/blob/main/documents/process/boilerplate.md#library-annotations
-#![cfg_attr(not(any(test, doc)), no_std)]
+#![cfg_attr(not(any(test, doc, feature = "std")), no_std)]
 #![cfg_attr(
     not(test),
     deny(
@@ -27,7 +27,9 @@
 //! # Functionality
 //!
 //! The top level of the crate provides normalization of input into the four normalization forms defined in [UAX #15: Unicode
-//! Normalization Forms](https://www.unicode.org/reports/tr15/): NFC, NFD, NFKC, and NFKD.
+//! Normalization Forms](https://www.unicode.org/reports/tr15/): NFC, NFD, NFKC, and NFKD. It also provides normalization
+//! into FCC and checking for FCD as defined in [UTN #5: Canonical Equivalence in
+//! Applications](https://www.unicode.org/notes/tn5/).
 //!
 //! Three kinds of contiguous inputs are supported: known-well-formed UTF-8 (`&str`), potentially-not-well-formed UTF-8,
 //! and potentially-not-well-formed UTF-16. Additionally, an iterator over `char` can be wrapped in a normalizing iterator.
@@ -35,7 +37,13 @@
 //! The `uts46` module provides the combination of mapping and normalization operations for [UTS #46: Unicode IDNA
 //! Compatibility Processing](https://www.unicode.org/reports/tr46/). This functionality is not meant to be used by
 //! applications directly. Instead, it is meant as a building block for a full implementation of UTS #46, such as the
-//! [`idna`](https://docs.rs/idna/latest/idna/) crate.
+//! [`idna`](https://docs.rs/idna/latest/idna/) crate. With the `icu_properties` Cargo feature, the module also provides
+//! such a full implementation, the ToASCII and ToUnicode operations on domain names, using the Punycode encoding of the
+//! `punycode` module.
+//!
+//! The `streaming` module provides normalization of input that arrives in chunks, holding back only the trailing
+//! part whose normalization may still be affected by subsequent input. It also provides adapters for
+//! `core::fmt::Write` and, with the `std` Cargo feature, for `std::io::Read` and `std::io::Write`.
 //!
 //! The `properties` module provides the non-recursive canonical decomposition operation on a per `char` basis and
 //! the canonical compositon operation given two `char`s. It also provides access to the Canonical Combining Class
@@ -99,10 +107,17 @@ macro_rules! ccc {
     };
 }
 
+mod fcd;
 pub mod properties;
 pub mod provider;
+pub mod punycode;
+#[cfg(any(feature = "utf8_iter", feature = "utf16_iter"))]
+pub mod streaming;
 pub mod uts46;
 
+pub use crate::fcd::FcdChecker;
+pub use crate::fcd::FcdCheckerBorrowed;
+
 use crate::provider::CanonicalCompositions;
 use crate::provider::DecompositionData;
 use crate::provider::NormalizerNfdDataV1;
@@ -1044,6 +1059,8 @@ where
     /// 2. Decomposition starts with a non-starter
     /// 3. Is not a backward-combining starter
     composition_passthrough_bound: u32,
+    /// Whether only contiguous sequences compose (FCC).
+    only_contiguous: bool,
 }
 
 impl<'data, I> Composition<'data, I>
@@ -1054,12 +1071,14 @@ where
         decomposition: Decomposition<'data, I>,
         canonical_compositions: Char16Trie<'data>,
         composition_passthrough_bound: u16,
+        only_contiguous: bool,
     ) -> Self {
         Self {
             decomposition,
             canonical_compositions,
             unprocessed_starter: None,
             composition_passthrough_bound: u32::from(composition_passthrough_bound),
+            only_contiguous,
         }
     }
 
@@ -1213,7 +1232,8 @@ where
                         return Some(starter);
                     }
                     debug_assert!(ccc >= most_recent_skipped_ccc);
-                    if ccc != most_recent_skipped_ccc {
+                    // FCC does not allow discontiguous matches.
+                    if ccc != most_recent_skipped_ccc && !self.only_contiguous {
                         // Using the non-Hangul version as a micro-optimization, since
                         // we already rejected the case where `second` is a starter
                         // above, and conjoining jamo are starters.
@@ -1389,7 +1409,8 @@ macro_rules! composing_normalize_to {
                                 continue 'bufferloop;
                             }
                             debug_assert!(ccc >= most_recent_skipped_ccc);
-                            if ccc != most_recent_skipped_ccc {
+                            // FCC does not allow discontiguous matches.
+                            if ccc != most_recent_skipped_ccc && !$composition.only_contiguous {
                                 // Using the non-Hangul version as a micro-optimization, since
                                 // we already rejected the case where `second` is a starter
                                 // above, and conjoining jamo are starters.
@@ -1659,7 +1680,7 @@ macro_rules! normalizer_methods {
 }
 
 /// Borrowed version of a normalizer for performing decomposing normalization.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct DecomposingNormalizerBorrowed<'a> {
     decompositions: &'a DecompositionData<'a>,
     tables: &'a DecompositionTables<'a>,
@@ -1848,6 +1869,30 @@ impl<'data> DecomposingNormalizerBorrowed<'data> {
         )
     }
 
+    /// Creates a normalizer for UTF-8 input that arrives in chunks.
+    ///
+    /// ✨ *Enabled with the `utf8_iter` Cargo feature.*
+    #[cfg(feature = "utf8_iter")]
+    pub fn streaming(&self) -> crate::streaming::StreamingNormalizer<'data> {
+        crate::streaming::StreamingNormalizer::new(*self)
+    }
+
+    /// Creates a normalizer for UTF-16 input that arrives in chunks.
+    ///
+    /// ✨ *Enabled with the `utf16_iter` Cargo feature.*
+    #[cfg(feature = "utf16_iter")]
+    pub fn streaming_utf16(&self) -> crate::streaming::StreamingNormalizerUtf16<'data> {
+        crate::streaming::StreamingNormalizerUtf16::new(*self)
+    }
+
+    /// Whether the decomposition of text never changes across a boundary
+    /// before `c`, i.e. `c` decomposes to a sequence that starts with a starter.
+    #[cfg(any(feature = "utf8_iter", feature = "utf16_iter"))]
+    pub(crate) fn has_boundary_before(&self, c: char) -> bool {
+        u32::from(c) < u32::from(self.decomposition_passthrough_bound)
+            || !decomposition_starts_with_non_starter(self.decompositions.trie.get(c))
+    }
+
     normalizer_methods!();
 
     decomposing_normalize_to!(
@@ -2418,10 +2463,11 @@ impl DecomposingNormalizer {
 }
 
 /// Borrowed version of a normalizer for performing composing normalization.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct ComposingNormalizerBorrowed<'a> {
     decomposing_normalizer: DecomposingNormalizerBorrowed<'a>,
     canonical_compositions: &'a CanonicalCompositions<'a>,
+    only_contiguous: bool,
 }
 
 impl ComposingNormalizerBorrowed<'static> {
@@ -2433,6 +2479,7 @@ impl ComposingNormalizerBorrowed<'static> {
         ComposingNormalizer {
             decomposing_normalizer: self.decomposing_normalizer.static_to_owned(),
             canonical_compositions: DataPayload::from_static_ref(self.canonical_compositions),
+            only_contiguous: self.only_contiguous,
         }
     }
 
@@ -2446,6 +2493,24 @@ impl ComposingNormalizerBorrowed<'static> {
         ComposingNormalizerBorrowed {
             decomposing_normalizer: DecomposingNormalizerBorrowed::new_nfd(),
             canonical_compositions: crate::provider::Baked::SINGLETON_NORMALIZER_NFC_V1,
+            only_contiguous: false,
+        }
+    }
+
+    /// FCC constructor using compiled data.
+    ///
+    /// FCC ("Fast C Contiguous", see [UTN #5](https://www.unicode.org/notes/tn5/)) is like NFC
+    /// except that only contiguous sequences compose, so that the output is always FCD.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub const fn new_fcc() -> Self {
+        ComposingNormalizerBorrowed {
+            decomposing_normalizer: DecomposingNormalizerBorrowed::new_nfd(),
+            canonical_compositions: crate::provider::Baked::SINGLETON_NORMALIZER_NFC_V1,
+            only_contiguous: true,
         }
     }
 
@@ -2459,6 +2524,7 @@ impl ComposingNormalizerBorrowed<'static> {
         ComposingNormalizerBorrowed {
             decomposing_normalizer: DecomposingNormalizerBorrowed::new_nfkd(),
             canonical_compositions: crate::provider::Baked::SINGLETON_NORMALIZER_NFC_V1,
+            only_contiguous: false,
         }
     }
 
@@ -2476,6 +2542,7 @@ impl ComposingNormalizerBorrowed<'static> {
         ComposingNormalizerBorrowed {
             decomposing_normalizer: DecomposingNormalizerBorrowed::new_uts46_decomposed(),
             canonical_compositions: crate::provider::Baked::SINGLETON_NORMALIZER_NFC_V1,
+            only_contiguous: false,
         }
     }
 }
@@ -2487,6 +2554,35 @@ impl<'data> ComposingNormalizerBorrowed<'data> {
         self.normalize_iter_private(iter, IgnorableBehavior::Unsupported)
     }
 
+    /// Creates a normalizer for UTF-8 input that arrives in chunks.
+    ///
+    /// ✨ *Enabled with the `utf8_iter` Cargo feature.*
+    #[cfg(feature = "utf8_iter")]
+    pub fn streaming(&self) -> crate::streaming::StreamingNormalizer<'data> {
+        crate::streaming::StreamingNormalizer::new(*self)
+    }
+
+    /// Creates a normalizer for UTF-16 input that arrives in chunks.
+    ///
+    /// ✨ *Enabled with the `utf16_iter` Cargo feature.*
+    #[cfg(feature = "utf16_iter")]
+    pub fn streaming_utf16(&self) -> crate::streaming::StreamingNormalizerUtf16<'data> {
+        crate::streaming::StreamingNormalizerUtf16::new(*self)
+    }
+
+    /// Whether the composition of text never changes across a boundary
+    /// before `c`, i.e. `c` decomposes to a sequence that starts with a starter
+    /// that does not combine backwards.
+    #[cfg(any(feature = "utf8_iter", feature = "utf16_iter"))]
+    pub(crate) fn has_boundary_before(&self, c: char) -> bool {
+        if u32::from(c) < u32::from(self.decomposing_normalizer.composition_passthrough_bound) {
+            return true;
+        }
+        let trie_value = self.decomposing_normalizer.decompositions.trie.get(c);
+        !decomposition_starts_with_non_starter(trie_value)
+            && (trie_value & BACKWARD_COMBINING_MARKER) == 0
+    }
+
     fn normalize_iter_private<I: Iterator<Item = char>>(
         &self,
         iter: I,
@@ -2503,6 +2599,7 @@ impl<'data> ComposingNormalizerBorrowed<'data> {
             ),
             self.canonical_compositions.canonical_compositions.clone(),
             self.decomposing_normalizer.composition_passthrough_bound,
+            self.only_contiguous,
         )
     }
 
@@ -2861,6 +2958,7 @@ impl<'data> ComposingNormalizerBorrowed<'data> {
 pub struct ComposingNormalizer {
     decomposing_normalizer: DecomposingNormalizer,
     canonical_compositions: DataPayload<NormalizerNfcV1>,
+    only_contiguous: bool,
 }
 
 impl ComposingNormalizer {
@@ -2869,6 +2967,7 @@ impl ComposingNormalizer {
         ComposingNormalizerBorrowed {
             decomposing_normalizer: self.decomposing_normalizer.as_borrowed(),
             canonical_compositions: self.canonical_compositions.get(),
+            only_contiguous: self.only_contiguous,
         }
     }
 
@@ -2908,6 +3007,43 @@ impl ComposingNormalizer {
         Ok(ComposingNormalizer {
             decomposing_normalizer,
             canonical_compositions,
+            only_contiguous: false,
+        })
+    }
+
+    /// FCC constructor using compiled data.
+    ///
+    /// See [`ComposingNormalizerBorrowed::new_fcc()`].
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub const fn new_fcc() -> ComposingNormalizerBorrowed<'static> {
+        ComposingNormalizerBorrowed::new_fcc()
+    }
+
+    icu_provider::gen_buffer_data_constructors!(
+        () -> error: DataError,
+        functions: [
+            new_fcc: skip,
+            try_new_fcc_with_buffer_provider,
+            try_new_fcc_unstable,
+            Self,
+        ]
+    );
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::new_fcc)]
+    pub fn try_new_fcc_unstable<D>(provider: &D) -> Result<Self, DataError>
+    where
+        D: DataProvider<NormalizerNfdDataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + ?Sized,
+    {
+        Ok(ComposingNormalizer {
+            only_contiguous: true,
+            ..Self::try_new_nfc_unstable(provider)?
         })
     }
 
@@ -2948,6 +3084,7 @@ impl ComposingNormalizer {
         Ok(ComposingNormalizer {
             decomposing_normalizer,
             canonical_compositions,
+            only_contiguous: false,
         })
     }
 
@@ -2970,6 +3107,7 @@ impl ComposingNormalizer {
         Ok(ComposingNormalizer {
             decomposing_normalizer,
             canonical_compositions,
+            only_contiguous: false,
         })
     }
 }
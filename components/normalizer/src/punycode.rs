@@ -0,0 +1,195 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! The Punycode encoding of [RFC 3492](https://www.rfc-editor.org/rfc/rfc3492),
+//! which represents Unicode domain name labels with ASCII letters, digits and
+//! hyphens.
+//!
+//! This module implements only the encoding: the `xn--` prefix of IDNA labels is
+//! neither added nor removed. See [`crate::uts46`] for complete domain name processing.
+//!
+//! # Examples
+//!
+//! ```
+//! use icu::normalizer::punycode;
+//!
+//! assert_eq!(punycode::encode("bücher").unwrap(), "bcher-kva");
+//! assert_eq!(punycode::decode("bcher-kva").unwrap(), "bücher");
+//! assert!(punycode::decode("bcher-kva!").is_err());
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 0x80;
+const DELIMITER: char = '-';
+
+/// An error returned when a string cannot be converted from or to Punycode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, displaydoc::Display)]
+#[non_exhaustive]
+pub enum PunycodeError {
+    /// The input is not valid Punycode
+    Invalid,
+    /// The input is too long to be converted
+    Overflow,
+}
+
+impl core::error::Error for PunycodeError {}
+
+/// The bias adaptation function of RFC 3492, section 6.1.
+fn adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (((BASE - T_MIN + 1) * delta) / (delta + SKEW))
+}
+
+/// The threshold for the digit at position `k` of a variable-length integer.
+fn threshold(k: u32, bias: u32) -> u32 {
+    k.saturating_sub(bias).clamp(T_MIN, T_MAX)
+}
+
+fn decode_digit(b: u8) -> Option<u32> {
+    match b {
+        b'a'..=b'z' => Some(u32::from(b - b'a')),
+        b'A'..=b'Z' => Some(u32::from(b - b'A')),
+        b'0'..=b'9' => Some(u32::from(b - b'0') + 26),
+        _ => None,
+    }
+}
+
+fn encode_digit(d: u32) -> char {
+    // Digits are always less than `BASE`.
+    debug_assert!(d < BASE);
+    match d {
+        0..=25 => char::from(b'a' + d as u8),
+        _ => char::from(b'0' + (d - 26) as u8),
+    }
+}
+
+/// Decodes a Punycode string into Unicode.
+///
+/// Letters in `input` may be upper or lower case; the basic code points before the
+/// last hyphen are copied unchanged.
+pub fn decode(input: &str) -> Result<String, PunycodeError> {
+    let (basic, extended) = match input.rfind(DELIMITER) {
+        Some(i) => (
+            input.get(..i).unwrap_or_default(),
+            input.get(i + 1..).unwrap_or_default(),
+        ),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return Err(PunycodeError::Invalid);
+    }
+    let mut output: Vec<char> = basic.chars().collect();
+
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut bytes = extended.bytes().peekable();
+    while bytes.peek().is_some() {
+        let old_i = i;
+        let mut w: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = bytes
+                .next()
+                .and_then(decode_digit)
+                .ok_or(PunycodeError::Invalid)?;
+            i = digit
+                .checked_mul(w)
+                .and_then(|d| i.checked_add(d))
+                .ok_or(PunycodeError::Overflow)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t).ok_or(PunycodeError::Overflow)?;
+            k += BASE;
+        }
+        let len = output.len() as u32 + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len).ok_or(PunycodeError::Overflow)?;
+        i %= len;
+        match char::from_u32(n) {
+            Some(c) if !c.is_ascii() => output.insert(i as usize, c),
+            _ => return Err(PunycodeError::Invalid),
+        }
+        i += 1;
+    }
+    Ok(output.into_iter().collect())
+}
+
+/// Encodes a Unicode string as Punycode.
+///
+/// The ASCII characters of `input` are copied to the start of the output unchanged,
+/// followed by a hyphen if there were any.
+pub fn encode(input: &str) -> Result<String, PunycodeError> {
+    let mut output = String::new();
+    encode_to(input, &mut output)?;
+    Ok(output)
+}
+
+/// Encodes a Unicode string as Punycode, appending to `output`.
+///
+/// See [`encode`].
+pub fn encode_to(input: &str, output: &mut String) -> Result<(), PunycodeError> {
+    let input_len = input.chars().count() as u32;
+    output.extend(input.chars().filter(char::is_ascii));
+    let basic_len = input.chars().filter(char::is_ascii).count() as u32;
+    if basic_len > 0 {
+        output.push(DELIMITER);
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut handled = basic_len;
+    while handled < input_len {
+        // The smallest code point not handled yet.
+        let Some(m) = input.chars().map(u32::from).filter(|&c| c >= n).min() else {
+            break;
+        };
+        delta = (m - n)
+            .checked_mul(handled + 1)
+            .and_then(|d| delta.checked_add(d))
+            .ok_or(PunycodeError::Overflow)?;
+        n = m;
+        for c in input.chars().map(u32::from) {
+            if c < n {
+                delta = delta.checked_add(1).ok_or(PunycodeError::Overflow)?;
+            } else if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_len);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta = delta.checked_add(1).ok_or(PunycodeError::Overflow)?;
+        n += 1;
+    }
+    Ok(())
+}
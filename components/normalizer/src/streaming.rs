@@ -0,0 +1,574 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Normalization of text that arrives in chunks.
+//!
+//! The methods on [`ComposingNormalizerBorrowed`] and [`DecomposingNormalizerBorrowed`]
+//! operate on complete strings. When text arrives piecewise, normalizing each piece on
+//! its own is incorrect, since a combining mark at the start of one chunk may need to be
+//! reordered with or composed into the end of the previous chunk.
+//!
+//! The streaming normalizers in this module accept input in chunks of any size. Each
+//! chunk is normalized up to the last position before which the normalization of the
+//! text can no longer change, and only the trailing unstable segment is held back until
+//! more input (or the end of input) arrives. Chunks may split UTF-8 byte sequences or
+//! UTF-16 surrogate pairs.
+//!
+//! In addition to the streaming normalizers themselves, this module provides adapters
+//! implementing [`core::fmt::Write`] and [`write16::Write16`], as well as
+//! [`std::io::Read`] and [`std::io::Write`] with the `std` Cargo feature.
+//!
+//! Note that a segment that never reaches a stable position (such as an unbounded
+//! sequence of combining marks) is buffered in full.
+//!
+//! # Examples
+//!
+//! ```
+//! use icu::normalizer::ComposingNormalizerBorrowed;
+//!
+//! let mut nfc = ComposingNormalizerBorrowed::new_nfc().streaming();
+//! let mut out = String::new();
+//!
+//! nfc.push_str("Caf", &mut out).unwrap();
+//! nfc.push_str("e", &mut out).unwrap();
+//! // The "e" is held back, since it could still compose with a combining mark.
+//! assert_eq!(out, "Caf");
+//!
+//! nfc.push_str("\u{0301} au lait", &mut out).unwrap();
+//! nfc.finish(&mut out).unwrap();
+//! assert_eq!(out, "Café au lait");
+//! ```
+
+use crate::ComposingNormalizerBorrowed;
+use crate::DecomposingNormalizerBorrowed;
+use crate::REPLACEMENT_CHARACTER;
+use alloc::vec::Vec;
+
+#[cfg(feature = "utf8_iter")]
+use utf8_iter::Utf8CharsEx;
+
+#[cfg(feature = "utf16_iter")]
+use utf16_iter::Utf16CharsEx;
+
+/// The normalizer wrapped by a streaming normalizer.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Normalizer<'a> {
+    Composing(ComposingNormalizerBorrowed<'a>),
+    Decomposing(DecomposingNormalizerBorrowed<'a>),
+}
+
+impl Normalizer<'_> {
+    /// Whether `c` starts a segment whose normalization is independent of
+    /// the text before it.
+    fn has_boundary_before(&self, c: char) -> bool {
+        // An error may turn out to be part of a sequence that is continued by
+        // a later chunk, so never split before the REPLACEMENT CHARACTER.
+        c != REPLACEMENT_CHARACTER
+            && match self {
+                Normalizer::Composing(n) => n.has_boundary_before(c),
+                Normalizer::Decomposing(n) => n.has_boundary_before(c),
+            }
+    }
+
+    #[cfg(feature = "utf8_iter")]
+    fn normalize_utf8_to<W: core::fmt::Write + ?Sized>(
+        &self,
+        text: &[u8],
+        sink: &mut W,
+    ) -> core::fmt::Result {
+        // Ill-formed input is decoded here, so that only `str` input is normalized.
+        let decoded: alloc::string::String;
+        let text = match core::str::from_utf8(text) {
+            Ok(text) => text,
+            Err(_) => {
+                decoded = text.chars().collect();
+                &decoded
+            }
+        };
+        match self {
+            Normalizer::Composing(n) => n.normalize_to(text, sink),
+            Normalizer::Decomposing(n) => n.normalize_to(text, sink),
+        }
+    }
+
+    #[cfg(feature = "utf16_iter")]
+    fn normalize_utf16_to<W: write16::Write16 + ?Sized>(
+        &self,
+        text: &[u16],
+        sink: &mut W,
+    ) -> core::fmt::Result {
+        match self {
+            Normalizer::Composing(n) => n.normalize_utf16_to(text, sink),
+            Normalizer::Decomposing(n) => n.normalize_utf16_to(text, sink),
+        }
+    }
+}
+
+impl<'a> From<ComposingNormalizerBorrowed<'a>> for Normalizer<'a> {
+    fn from(normalizer: ComposingNormalizerBorrowed<'a>) -> Self {
+        Normalizer::Composing(normalizer)
+    }
+}
+
+impl<'a> From<DecomposingNormalizerBorrowed<'a>> for Normalizer<'a> {
+    fn from(normalizer: DecomposingNormalizerBorrowed<'a>) -> Self {
+        Normalizer::Decomposing(normalizer)
+    }
+}
+
+/// A normalizer for potentially-invalid UTF-8 text that arrives in chunks.
+///
+/// Ill-formed byte sequences are mapped to the REPLACEMENT CHARACTER
+/// according to the WHATWG Encoding Standard. A byte sequence split
+/// across chunks is decoded as if the chunks had been concatenated.
+///
+/// Obtained from [`ComposingNormalizerBorrowed::streaming()`] or
+/// [`DecomposingNormalizerBorrowed::streaming()`].
+///
+/// ✨ *Enabled with the `utf8_iter` Cargo feature.*
+#[cfg(feature = "utf8_iter")]
+#[derive(Debug)]
+pub struct StreamingNormalizer<'a> {
+    normalizer: Normalizer<'a>,
+    /// Input that has not been normalized yet; starts at a segment boundary.
+    pending: Vec<u8>,
+    /// The offset in `pending` of the last character examined for a segment
+    /// boundary, which may be incomplete.
+    scanned: usize,
+}
+
+#[cfg(feature = "utf8_iter")]
+impl<'a> StreamingNormalizer<'a> {
+    pub(crate) fn new(normalizer: impl Into<Normalizer<'a>>) -> Self {
+        Self {
+            normalizer: normalizer.into(),
+            pending: Vec::new(),
+            scanned: 0,
+        }
+    }
+
+    /// Normalizes a chunk of text, writing to `sink` the normalized output
+    /// that can no longer be affected by subsequent chunks.
+    pub fn push_str<W: core::fmt::Write + ?Sized>(
+        &mut self,
+        chunk: &str,
+        sink: &mut W,
+    ) -> core::fmt::Result {
+        self.push_utf8(chunk.as_bytes(), sink)
+    }
+
+    /// Normalizes a chunk of potentially-invalid UTF-8, writing to `sink` the
+    /// normalized output that can no longer be affected by subsequent chunks.
+    pub fn push_utf8<W: core::fmt::Write + ?Sized>(
+        &mut self,
+        chunk: &[u8],
+        sink: &mut W,
+    ) -> core::fmt::Result {
+        self.pending.extend_from_slice(chunk);
+        // Only the new input and the last character before it, which may have
+        // been incomplete, need to be examined.
+        let mut stable = 0;
+        let start = self.scanned;
+        let new = self.pending.get(start..).unwrap_or_default();
+        for (i, c) in new.char_indices() {
+            let i = start + i;
+            if i > 0 && self.normalizer.has_boundary_before(c) {
+                stable = i;
+            }
+            self.scanned = i;
+        }
+        if stable == 0 {
+            return Ok(());
+        }
+        let result = self
+            .normalizer
+            .normalize_utf8_to(self.pending.get(..stable).unwrap_or_default(), sink);
+        self.pending.drain(..stable);
+        self.scanned -= stable;
+        result
+    }
+
+    /// Writes the normalization of all held-back text to `sink`, marking
+    /// the end of the input.
+    ///
+    /// The streaming normalizer can be reused for new input afterwards.
+    pub fn finish<W: core::fmt::Write + ?Sized>(&mut self, sink: &mut W) -> core::fmt::Result {
+        let result = self.normalizer.normalize_utf8_to(&self.pending, sink);
+        self.pending.clear();
+        self.scanned = 0;
+        result
+    }
+
+    /// Returns the input that has been held back so far.
+    pub fn pending(&self) -> &[u8] {
+        &self.pending
+    }
+}
+
+/// A normalizer for potentially-invalid UTF-16 text that arrives in chunks.
+///
+/// Unpaired surrogates are mapped to the REPLACEMENT CHARACTER. A surrogate
+/// pair split across chunks is decoded as if the chunks had been concatenated.
+///
+/// Obtained from [`ComposingNormalizerBorrowed::streaming_utf16()`] or
+/// [`DecomposingNormalizerBorrowed::streaming_utf16()`].
+///
+/// ✨ *Enabled with the `utf16_iter` Cargo feature.*
+#[cfg(feature = "utf16_iter")]
+#[derive(Debug)]
+pub struct StreamingNormalizerUtf16<'a> {
+    normalizer: Normalizer<'a>,
+    /// Input that has not been normalized yet; starts at a segment boundary.
+    pending: Vec<u16>,
+    /// The offset in `pending` of the last character examined for a segment
+    /// boundary, which may be incomplete.
+    scanned: usize,
+}
+
+#[cfg(feature = "utf16_iter")]
+impl<'a> StreamingNormalizerUtf16<'a> {
+    pub(crate) fn new(normalizer: impl Into<Normalizer<'a>>) -> Self {
+        Self {
+            normalizer: normalizer.into(),
+            pending: Vec::new(),
+            scanned: 0,
+        }
+    }
+
+    /// Normalizes a chunk of potentially-invalid UTF-16, writing to `sink` the
+    /// normalized output that can no longer be affected by subsequent chunks.
+    pub fn push_utf16<W: write16::Write16 + ?Sized>(
+        &mut self,
+        chunk: &[u16],
+        sink: &mut W,
+    ) -> core::fmt::Result {
+        self.pending.extend_from_slice(chunk);
+        // Only the new input and the last character before it, which may have
+        // been incomplete, need to be examined.
+        let mut stable = 0;
+        let start = self.scanned;
+        let new = self.pending.get(start..).unwrap_or_default();
+        for (i, c) in new.char_indices() {
+            let i = start + i;
+            if i > 0 && self.normalizer.has_boundary_before(c) {
+                stable = i;
+            }
+            self.scanned = i;
+        }
+        if stable == 0 {
+            return Ok(());
+        }
+        let result = self
+            .normalizer
+            .normalize_utf16_to(self.pending.get(..stable).unwrap_or_default(), sink);
+        self.pending.drain(..stable);
+        self.scanned -= stable;
+        result
+    }
+
+    /// Writes the normalization of all held-back text to `sink`, marking
+    /// the end of the input.
+    ///
+    /// The streaming normalizer can be reused for new input afterwards.
+    pub fn finish<W: write16::Write16 + ?Sized>(&mut self, sink: &mut W) -> core::fmt::Result {
+        let result = self.normalizer.normalize_utf16_to(&self.pending, sink);
+        self.pending.clear();
+        self.scanned = 0;
+        result
+    }
+
+    /// Returns the input that has been held back so far.
+    pub fn pending(&self) -> &[u16] {
+        &self.pending
+    }
+}
+
+/// A [`core::fmt::Write`] adapter that normalizes the text written to it
+/// before passing it on to an inner sink.
+///
+/// [`NormalizingFmtWriter::finish()`] must be called at the end of the input
+/// in order to write the held-back text.
+///
+/// ✨ *Enabled with the `utf8_iter` Cargo feature.*
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt::Write;
+/// use icu::normalizer::streaming::NormalizingFmtWriter;
+/// use icu::normalizer::DecomposingNormalizerBorrowed;
+///
+/// let nfd = DecomposingNormalizerBorrowed::new_nfd().streaming();
+/// let mut writer = NormalizingFmtWriter::new(nfd, String::new());
+/// write!(writer, "{}{}", "a\u{0323}", "\u{0302}\u{0301}").unwrap();
+/// assert_eq!(writer.finish().unwrap(), "a\u{0323}\u{0302}\u{0301}");
+/// ```
+#[cfg(feature = "utf8_iter")]
+#[derive(Debug)]
+pub struct NormalizingFmtWriter<'a, W> {
+    normalizer: StreamingNormalizer<'a>,
+    inner: W,
+}
+
+#[cfg(feature = "utf8_iter")]
+impl<'a, W: core::fmt::Write> NormalizingFmtWriter<'a, W> {
+    /// Creates an adapter that normalizes with `normalizer` and writes to `inner`.
+    pub fn new(normalizer: StreamingNormalizer<'a>, inner: W) -> Self {
+        Self { normalizer, inner }
+    }
+
+    /// Writes the held-back text and returns the inner sink.
+    pub fn finish(mut self) -> Result<W, core::fmt::Error> {
+        self.normalizer.finish(&mut self.inner)?;
+        Ok(self.inner)
+    }
+
+    /// Returns a reference to the inner sink.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "utf8_iter")]
+impl<W: core::fmt::Write> core::fmt::Write for NormalizingFmtWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.normalizer.push_str(s, &mut self.inner)
+    }
+}
+
+/// A [`write16::Write16`] adapter that normalizes the UTF-16 written to it
+/// before passing it on to an inner sink.
+///
+/// [`NormalizingWrite16::finish()`] must be called at the end of the input
+/// in order to write the held-back text.
+///
+/// ✨ *Enabled with the `utf16_iter` Cargo feature.*
+#[cfg(feature = "utf16_iter")]
+#[derive(Debug)]
+pub struct NormalizingWrite16<'a, W> {
+    normalizer: StreamingNormalizerUtf16<'a>,
+    inner: W,
+}
+
+#[cfg(feature = "utf16_iter")]
+impl<'a, W: write16::Write16> NormalizingWrite16<'a, W> {
+    /// Creates an adapter that normalizes with `normalizer` and writes to `inner`.
+    pub fn new(normalizer: StreamingNormalizerUtf16<'a>, inner: W) -> Self {
+        Self { normalizer, inner }
+    }
+
+    /// Writes the held-back text and returns the inner sink.
+    pub fn finish(mut self) -> Result<W, core::fmt::Error> {
+        self.normalizer.finish(&mut self.inner)?;
+        Ok(self.inner)
+    }
+
+    /// Returns a reference to the inner sink.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "utf16_iter")]
+impl<W: write16::Write16> write16::Write16 for NormalizingWrite16<'_, W> {
+    fn write_slice(&mut self, s: &[u16]) -> core::fmt::Result {
+        self.normalizer.push_utf16(s, &mut self.inner)
+    }
+}
+
+/// A [`std::io::Write`] adapter that normalizes the UTF-8 written to it
+/// before passing it on to an inner writer.
+///
+/// Ill-formed byte sequences are mapped to the REPLACEMENT CHARACTER
+/// according to the WHATWG Encoding Standard.
+///
+/// [`NormalizingWriter::finish()`] must be called at the end of the input
+/// in order to write the held-back text. [`std::io::Write::flush()`] flushes
+/// the inner writer but cannot write the held-back text.
+///
+/// If writing to the inner writer fails, the normalized output that has not
+/// been written is kept, and subsequent calls to `write`, `flush` or `finish`
+/// retry writing it before anything else.
+///
+/// ✨ *Enabled with the `std` and `utf8_iter` Cargo features.*
+///
+/// # Examples
+///
+/// ```
+/// use icu::normalizer::streaming::NormalizingWriter;
+/// use icu::normalizer::ComposingNormalizerBorrowed;
+/// use std::io::Write;
+///
+/// let nfc = ComposingNormalizerBorrowed::new_nfc().streaming();
+/// let mut writer = NormalizingWriter::new(nfc, Vec::new());
+/// // The chunks split the UTF-8 of U+0308 COMBINING DIAERESIS.
+/// writer.write_all(b"a\xCC").unwrap();
+/// writer.write_all(b"\x88").unwrap();
+/// assert_eq!(writer.finish().unwrap(), "ä".as_bytes());
+/// ```
+#[cfg(all(feature = "std", feature = "utf8_iter"))]
+#[derive(Debug)]
+pub struct NormalizingWriter<'a, W> {
+    normalizer: StreamingNormalizer<'a>,
+    inner: W,
+    /// Normalized output not written to `inner` yet, starting at `output_pos`.
+    output: alloc::string::String,
+    output_pos: usize,
+}
+
+#[cfg(all(feature = "std", feature = "utf8_iter"))]
+impl<'a, W: std::io::Write> NormalizingWriter<'a, W> {
+    /// Creates an adapter that normalizes with `normalizer` and writes to `inner`.
+    pub fn new(normalizer: StreamingNormalizer<'a>, inner: W) -> Self {
+        Self {
+            normalizer,
+            inner,
+            output: alloc::string::String::new(),
+            output_pos: 0,
+        }
+    }
+
+    /// Writes the held-back text and returns the inner writer.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.write_output()?;
+        // Writing to a `String` cannot fail.
+        let _ = self.normalizer.finish(&mut self.output);
+        self.write_output()?;
+        Ok(self.inner)
+    }
+
+    /// Writes the normalized output that has not been written to `inner` yet.
+    fn write_output(&mut self) -> std::io::Result<()> {
+        while let Some(unwritten) = self
+            .output
+            .as_bytes()
+            .get(self.output_pos..)
+            .filter(|unwritten| !unwritten.is_empty())
+        {
+            match self.inner.write(unwritten) {
+                Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+                Ok(len) => self.output_pos += len,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.output.clear();
+        self.output_pos = 0;
+        Ok(())
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+#[cfg(all(feature = "std", feature = "utf8_iter"))]
+impl<W: std::io::Write> std::io::Write for NormalizingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // Nothing is consumed from `buf` if earlier output cannot be written.
+        self.write_output()?;
+        // Writing to a `String` cannot fail.
+        let _ = self.normalizer.push_utf8(buf, &mut self.output);
+        // `buf` has been consumed, so an error is reported by the next call,
+        // which retries writing the output.
+        let _ = self.write_output();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_output()?;
+        self.inner.flush()
+    }
+}
+
+/// A [`std::io::Read`] adapter that reads UTF-8 from an inner reader and
+/// yields its normalization.
+///
+/// Ill-formed byte sequences are mapped to the REPLACEMENT CHARACTER
+/// according to the WHATWG Encoding Standard, so the output is always
+/// well-formed UTF-8. A single `read` call may, however, end in the middle
+/// of the UTF-8 of a character.
+///
+/// ✨ *Enabled with the `std` and `utf8_iter` Cargo features.*
+///
+/// # Examples
+///
+/// ```
+/// use icu::normalizer::streaming::NormalizingReader;
+/// use icu::normalizer::DecomposingNormalizerBorrowed;
+/// use std::io::Read;
+///
+/// let nfkd = DecomposingNormalizerBorrowed::new_nfkd().streaming();
+/// let mut reader = NormalizingReader::new(nfkd, "ﬁ①".as_bytes());
+/// let mut out = String::new();
+/// reader.read_to_string(&mut out).unwrap();
+/// assert_eq!(out, "fi1");
+/// ```
+#[cfg(all(feature = "std", feature = "utf8_iter"))]
+#[derive(Debug)]
+pub struct NormalizingReader<'a, R> {
+    normalizer: StreamingNormalizer<'a>,
+    inner: R,
+    /// Normalized output not read yet, starting at `output_pos`.
+    output: alloc::string::String,
+    output_pos: usize,
+    eof: bool,
+}
+
+#[cfg(all(feature = "std", feature = "utf8_iter"))]
+impl<'a, R: std::io::Read> NormalizingReader<'a, R> {
+    /// Creates an adapter that reads from `inner` and normalizes with `normalizer`.
+    pub fn new(normalizer: StreamingNormalizer<'a>, inner: R) -> Self {
+        Self {
+            normalizer,
+            inner,
+            output: alloc::string::String::new(),
+            output_pos: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(all(feature = "std", feature = "utf8_iter"))]
+impl<R: std::io::Read> std::io::Read for NormalizingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let available = self
+                .output
+                .as_bytes()
+                .get(self.output_pos..)
+                .unwrap_or_default();
+            if !available.is_empty() || buf.is_empty() {
+                let len = available.len().min(buf.len());
+                for (dst, src) in buf.iter_mut().zip(available) {
+                    *dst = *src;
+                }
+                self.output_pos += len;
+                return Ok(len);
+            }
+            if self.eof {
+                return Ok(0);
+            }
+            self.output.clear();
+            self.output_pos = 0;
+            let mut chunk = [0u8; 4096];
+            let len = self.inner.read(&mut chunk)?;
+            // Writing to a `String` cannot fail.
+            if len == 0 {
+                self.eof = true;
+                let _ = self.normalizer.finish(&mut self.output);
+            } else {
+                let _ = self
+                    .normalizer
+                    .push_utf8(chunk.get(..len).unwrap_or_default(), &mut self.output);
+            }
+        }
+    }
+}
@@ -5,8 +5,20 @@
 //! Bundles the part of UTS 46 that makes sense to implement as a
 //! normalization.
 //!
-//! This is meant to be used as a building block of an UTS 46
+//! [`Uts46Mapper`] is meant to be used as a building block of an UTS 46
 //! implementation, such as the `idna` crate.
+//!
+//! With the `icu_properties` Cargo feature, [`Uts46Processor`] provides complete
+//! ToASCII and ToUnicode processing of domain names on top of it, with
+//! [Punycode](crate::punycode) and the validity criteria of UTS 46.
+
+#[cfg(feature = "icu_properties")]
+mod processing;
+
+#[cfg(feature = "icu_properties")]
+pub use processing::{
+    Uts46Error, Uts46ErrorKind, Uts46Errors, Uts46Options, Uts46Processor, Uts46ProcessorBorrowed,
+};
 
 use crate::ComposingNormalizer;
 use crate::ComposingNormalizerBorrowed;
@@ -0,0 +1,645 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! The ToASCII and ToUnicode operations of UTS 46, built from the mapping of
+//! [`Uts46MapperBorrowed`], Punycode, and the validity criteria of UTS 46 and
+//! IDNA2008.
+
+use super::{Uts46Mapper, Uts46MapperBorrowed};
+use crate::properties::{CanonicalCombiningClassMap, CanonicalCombiningClassMapBorrowed};
+use crate::provider::{
+    NormalizerNfcV1, NormalizerNfdDataV1, NormalizerNfdTablesV1, NormalizerNfkdTablesV1,
+    NormalizerUts46DataV1,
+};
+use crate::punycode;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::char::REPLACEMENT_CHARACTER;
+use core::fmt;
+use icu_properties::props::{
+    BidiClass, GeneralCategory, GeneralCategoryGroup, JoiningType, Script,
+};
+use icu_properties::provider::{
+    PropertyEnumBidiClassV1, PropertyEnumGeneralCategoryV1, PropertyEnumJoiningTypeV1,
+    PropertyEnumScriptV1,
+};
+use icu_properties::{CodePointMapData, CodePointMapDataBorrowed};
+use icu_provider::prelude::*;
+
+/// The prefix of labels in Punycode.
+const ACE_PREFIX: &str = "xn--";
+const MAX_LABEL_LENGTH: usize = 63;
+const MAX_DOMAIN_LENGTH: usize = 253;
+const VIRAMA_CCC: u8 = 9;
+const ZWNJ: char = '\u{200C}';
+const ZWJ: char = '\u{200D}';
+
+/// The flags of UTS 46 processing.
+///
+/// The default is the strictest processing of UTS 46: all of its checks are on, and
+/// transitional processing is off, as in the conformance tests of UTS 46. The
+/// CONTEXTO rules of IDNA2008, which are not part of UTS 46, are not checked by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Uts46Options {
+    /// _CheckHyphens_: labels must not start or end with a hyphen, or have hyphens in
+    /// the third and fourth positions.
+    pub check_hyphens: bool,
+    /// _CheckBidi_: if the domain name contains right-to-left characters, all labels
+    /// must satisfy the Bidi Rule of RFC 5893.
+    pub check_bidi: bool,
+    /// _CheckJoiners_: ZERO WIDTH JOINER and ZERO WIDTH NON-JOINER are only allowed in
+    /// the contexts of RFC 5892, Appendix A.
+    pub check_joiners: bool,
+    /// The CONTEXTO rules of RFC 5892, Appendix A.3 to A.9: MIDDLE DOT, GREEK LOWER
+    /// NUMERAL SIGN, HEBREW PUNCTUATION GERESH and GERSHAYIM, KATAKANA MIDDLE DOT, and
+    /// Arabic-Indic digits are only allowed in certain contexts. This is an IDNA2008
+    /// check that UTS 46 does not make.
+    pub check_context_o: bool,
+    /// _UseSTD3ASCIIRules_: the only ASCII characters allowed in labels are lowercase
+    /// letters, digits and hyphens.
+    pub use_std3_ascii_rules: bool,
+    /// _Transitional_Processing_: map the deviation characters ß, ς, ZERO WIDTH JOINER
+    /// and ZERO WIDTH NON-JOINER as in IDNA2003. This is deprecated by UTS 46.
+    pub transitional_processing: bool,
+    /// _VerifyDnsLength_: in ToASCII, labels must have 1 to 63 bytes and the domain
+    /// name 1 to 253 bytes, not counting a final empty label.
+    pub verify_dns_length: bool,
+}
+
+impl Default for Uts46Options {
+    fn default() -> Self {
+        Self {
+            check_hyphens: true,
+            check_bidi: true,
+            check_joiners: true,
+            check_context_o: false,
+            use_std3_ascii_rules: true,
+            transitional_processing: false,
+            verify_dns_length: true,
+        }
+    }
+}
+
+/// The kinds of errors found by UTS 46 processing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, displaydoc::Display)]
+#[non_exhaustive]
+pub enum Uts46ErrorKind {
+    /// The label contains a code point that is disallowed or not in NFC
+    Disallowed,
+    /// The label starts with `xn--` but is not valid Punycode, or cannot be encoded as Punycode
+    Punycode,
+    /// The label has hyphens in the third and fourth positions
+    HyphensInThirdAndFourthPositions,
+    /// The label starts or ends with a hyphen
+    LeadingOrTrailingHyphen,
+    /// The label starts with a combining mark
+    LeadingCombiningMark,
+    /// The label contains an ASCII character other than a lowercase letter, digit or hyphen
+    Std3Ascii,
+    /// The label contains a joiner outside of the contexts that allow it
+    ContextJ,
+    /// The label contains a CONTEXTO character outside of the contexts that allow it
+    ContextO,
+    /// The label does not satisfy the Bidi Rule
+    Bidi,
+    /// The label is empty or longer than 63 bytes
+    LabelLength,
+    /// The domain name is empty or longer than 253 bytes
+    DomainLength,
+}
+
+/// An error found by UTS 46 processing, with the label it was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Uts46Error {
+    /// The index of the label among the labels of the domain name, or `None` for
+    /// errors of the domain name as a whole.
+    pub label: Option<usize>,
+    /// What is wrong.
+    pub kind: Uts46ErrorKind,
+}
+
+impl fmt::Display for Uts46Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.label {
+            Some(label) => write!(f, "label {label}: {}", self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl core::error::Error for Uts46Error {}
+
+/// The errors found by UTS 46 processing of a domain name. There is at least one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uts46Errors {
+    errors: Vec<Uts46Error>,
+}
+
+impl Uts46Errors {
+    /// The errors, ordered by label.
+    pub fn errors(&self) -> &[Uts46Error] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for Uts46Errors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for Uts46Errors {}
+
+fn into_result(mut errors: Vec<Uts46Error>) -> Result<(), Uts46Errors> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.sort_by_key(|error| error.label);
+        Err(Uts46Errors { errors })
+    }
+}
+
+/// A borrowed version of a processor for the ToASCII and ToUnicode operations of
+/// UTS 46 on domain names.
+///
+/// ✨ *Enabled with the `icu_properties` Cargo feature.*
+///
+/// # Examples
+///
+/// ```
+/// use icu::normalizer::uts46::{Uts46ErrorKind, Uts46Options, Uts46ProcessorBorrowed};
+///
+/// let processor = Uts46ProcessorBorrowed::new();
+/// let options = Uts46Options::default();
+///
+/// assert_eq!(
+///     processor.to_ascii("Bücher.EXAMPLE", options).unwrap(),
+///     "xn--bcher-kva.example"
+/// );
+///
+/// let (unicode, result) = processor.to_unicode("xn--bcher-kva.example", options);
+/// assert_eq!(unicode, "bücher.example");
+/// assert!(result.is_ok());
+///
+/// let errors = processor.to_ascii("a.-b", options).unwrap_err();
+/// assert_eq!(errors.errors()[0].label, Some(1));
+/// assert_eq!(
+///     errors.errors()[0].kind,
+///     Uts46ErrorKind::LeadingOrTrailingHyphen
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Uts46ProcessorBorrowed<'a> {
+    mapper: Uts46MapperBorrowed<'a>,
+    ccc: CanonicalCombiningClassMapBorrowed<'a>,
+    general_category: CodePointMapDataBorrowed<'a, GeneralCategory>,
+    bidi_class: CodePointMapDataBorrowed<'a, BidiClass>,
+    joining_type: CodePointMapDataBorrowed<'a, JoiningType>,
+    script: CodePointMapDataBorrowed<'a, Script>,
+}
+
+#[cfg(feature = "compiled_data")]
+impl Default for Uts46ProcessorBorrowed<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Uts46ProcessorBorrowed<'static> {
+    /// Cheaply converts a [`Uts46ProcessorBorrowed<'static>`] into a [`Uts46Processor`].
+    ///
+    /// Note: Due to branching and indirection, using [`Uts46Processor`] might inhibit some
+    /// compile-time optimizations that are possible with [`Uts46ProcessorBorrowed`].
+    pub const fn static_to_owned(self) -> Uts46Processor {
+        Uts46Processor {
+            mapper: self.mapper.static_to_owned(),
+            ccc: self.ccc.static_to_owned(),
+            general_category: self.general_category.static_to_owned(),
+            bidi_class: self.bidi_class.static_to_owned(),
+            joining_type: self.joining_type.static_to_owned(),
+            script: self.script.static_to_owned(),
+        }
+    }
+
+    /// Construct with compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub const fn new() -> Self {
+        Uts46ProcessorBorrowed {
+            mapper: Uts46MapperBorrowed::new(),
+            ccc: CanonicalCombiningClassMapBorrowed::new(),
+            general_category: CodePointMapData::<GeneralCategory>::new(),
+            bidi_class: CodePointMapData::<BidiClass>::new(),
+            joining_type: CodePointMapData::<JoiningType>::new(),
+            script: CodePointMapData::<Script>::new(),
+        }
+    }
+}
+
+impl Uts46ProcessorBorrowed<'_> {
+    /// Converts a domain name to its ASCII form: the ToASCII operation of UTS 46.
+    ///
+    /// Labels with non-ASCII characters are encoded as Punycode with the `xn--` prefix.
+    /// Returns all errors if there are any.
+    pub fn to_ascii(&self, domain: &str, options: Uts46Options) -> Result<String, Uts46Errors> {
+        let mut errors = Vec::new();
+        let labels = self.process(domain, options, &mut errors);
+        let mut ascii = String::with_capacity(domain.len());
+        for (index, label) in labels.iter().enumerate() {
+            if index > 0 {
+                ascii.push('.');
+            }
+            let start = ascii.len();
+            if label.is_ascii() {
+                ascii.push_str(label);
+            } else {
+                ascii.push_str(ACE_PREFIX);
+                if punycode::encode_to(label, &mut ascii).is_err() {
+                    errors.push(Uts46Error {
+                        label: Some(index),
+                        kind: Uts46ErrorKind::Punycode,
+                    });
+                }
+            }
+            let len = ascii.len() - start;
+            let is_root = index > 0 && index + 1 == labels.len() && len == 0;
+            if options.verify_dns_length && (len > MAX_LABEL_LENGTH || (len == 0 && !is_root)) {
+                errors.push(Uts46Error {
+                    label: Some(index),
+                    kind: Uts46ErrorKind::LabelLength,
+                });
+            }
+        }
+        if options.verify_dns_length {
+            let len = ascii.strip_suffix('.').unwrap_or(&ascii).len();
+            if len == 0 || len > MAX_DOMAIN_LENGTH {
+                errors.push(Uts46Error {
+                    label: None,
+                    kind: Uts46ErrorKind::DomainLength,
+                });
+            }
+        }
+        into_result(errors).map(|()| ascii)
+    }
+
+    /// Converts a domain name to its Unicode form: the ToUnicode operation of UTS 46.
+    ///
+    /// Labels in Punycode are decoded. The result is returned even if there are errors;
+    /// disallowed characters are replaced with U+FFFD, and labels that are not valid
+    /// Punycode are kept as they are.
+    pub fn to_unicode(
+        &self,
+        domain: &str,
+        options: Uts46Options,
+    ) -> (String, Result<(), Uts46Errors>) {
+        let mut errors = Vec::new();
+        let labels = self.process(domain, options, &mut errors);
+        (labels.join("."), into_result(errors))
+    }
+
+    /// The Processing steps of UTS 46: map, normalize, break into labels, and
+    /// convert and validate each label.
+    fn process(
+        &self,
+        domain: &str,
+        options: Uts46Options,
+        errors: &mut Vec<Uts46Error>,
+    ) -> Vec<String> {
+        let mapped: String = if options.transitional_processing {
+            let mut deviations_mapped = String::with_capacity(domain.len());
+            for c in domain.chars() {
+                match c {
+                    'ß' => deviations_mapped.push_str("ss"),
+                    'ς' => deviations_mapped.push('σ'),
+                    ZWJ | ZWNJ => {}
+                    c => deviations_mapped.push(c),
+                }
+            }
+            self.mapper
+                .map_normalize(deviations_mapped.chars())
+                .collect()
+        } else {
+            self.mapper.map_normalize(domain.chars()).collect()
+        };
+
+        let mut labels = Vec::new();
+        for (index, label) in mapped.split('.').enumerate() {
+            let mut report = |kind| {
+                errors.push(Uts46Error {
+                    label: Some(index),
+                    kind,
+                })
+            };
+            if let Some(encoded) = label.strip_prefix(ACE_PREFIX) {
+                match punycode::decode(encoded) {
+                    Ok(decoded) if label.is_ascii() && !decoded.is_ascii() => {
+                        self.validate(&decoded, options, &mut report);
+                        labels.push(decoded);
+                    }
+                    _ => {
+                        report(Uts46ErrorKind::Punycode);
+                        labels.push(String::from(label));
+                    }
+                }
+            } else {
+                self.validate(label, options, &mut report);
+                labels.push(String::from(label));
+            }
+        }
+
+        if options.check_bidi && labels.iter().any(|label| self.is_rtl(label)) {
+            for (index, label) in labels.iter().enumerate() {
+                if !self.satisfies_bidi_rule(label) {
+                    errors.push(Uts46Error {
+                        label: Some(index),
+                        kind: Uts46ErrorKind::Bidi,
+                    });
+                }
+            }
+        }
+        labels
+    }
+
+    /// The Validity Criteria of UTS 46, except for the Bidi Rule, which depends on
+    /// the other labels.
+    fn validate(
+        &self,
+        label: &str,
+        options: Uts46Options,
+        report: &mut impl FnMut(Uts46ErrorKind),
+    ) {
+        // Only labels decoded from Punycode can contain full stops, or change
+        // when mapped again.
+        if label.contains([REPLACEMENT_CHARACTER, '.'])
+            || !self
+                .mapper
+                .normalize_validate(label.chars())
+                .eq(label.chars())
+        {
+            report(Uts46ErrorKind::Disallowed);
+        }
+        if options.check_hyphens {
+            if label.get(2..4) == Some("--") {
+                report(Uts46ErrorKind::HyphensInThirdAndFourthPositions);
+            }
+            if label.starts_with('-') || label.ends_with('-') {
+                report(Uts46ErrorKind::LeadingOrTrailingHyphen);
+            }
+        } else if label.starts_with(ACE_PREFIX) {
+            report(Uts46ErrorKind::Punycode);
+        }
+        if label
+            .chars()
+            .next()
+            .is_some_and(|c| GeneralCategoryGroup::Mark.contains(self.general_category.get(c)))
+        {
+            report(Uts46ErrorKind::LeadingCombiningMark);
+        }
+        if options.use_std3_ascii_rules
+            && label.bytes().any(|b| {
+                b.is_ascii() && !(b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+            })
+        {
+            report(Uts46ErrorKind::Std3Ascii);
+        }
+        if options.check_joiners && !self.satisfies_context_j(label) {
+            report(Uts46ErrorKind::ContextJ);
+        }
+        if options.check_context_o && !self.satisfies_context_o(label) {
+            report(Uts46ErrorKind::ContextO);
+        }
+    }
+
+    /// Whether the joiners in `label` are in the contexts of RFC 5892, Appendix A.
+    fn satisfies_context_j(&self, label: &str) -> bool {
+        if !label.contains([ZWJ, ZWNJ]) {
+            return true;
+        }
+        let chars: Vec<char> = label.chars().collect();
+        for (i, &c) in chars.iter().enumerate() {
+            if c != ZWJ && c != ZWNJ {
+                continue;
+            }
+            let before = chars.get(..i).unwrap_or_default();
+            if before
+                .last()
+                .is_some_and(|&b| self.ccc.get_u8(b) == VIRAMA_CCC)
+            {
+                continue;
+            }
+            if c == ZWJ {
+                return false;
+            }
+            // (Joining_Type:{L,D})(Joining_Type:T)*‌(Joining_Type:T)*(Joining_Type:{R,D})
+            let non_transparent = |c: &char| {
+                let joining_type = self.joining_type.get(*c);
+                (joining_type != JoiningType::Transparent).then_some(joining_type)
+            };
+            let left = before.iter().rev().find_map(non_transparent);
+            let right = chars
+                .get(i + 1..)
+                .unwrap_or_default()
+                .iter()
+                .find_map(non_transparent);
+            if !matches!(
+                left,
+                Some(JoiningType::LeftJoining | JoiningType::DualJoining)
+            ) || !matches!(
+                right,
+                Some(JoiningType::RightJoining | JoiningType::DualJoining)
+            ) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether the characters of `label` with CONTEXTO rules are in the contexts
+    /// of RFC 5892, Appendix A.3 to A.9.
+    fn satisfies_context_o(&self, label: &str) -> bool {
+        let mut chars = label.chars();
+        let mut before = None;
+        while let Some(c) = chars.next() {
+            let after = chars.clone().next();
+            let satisfied = match c {
+                // A.3 MIDDLE DOT
+                '\u{B7}' => before == Some('l') && after == Some('l'),
+                // A.4 GREEK LOWER NUMERAL SIGN (KERAIA)
+                '\u{375}' => after.is_some_and(|a| self.script.get(a) == Script::Greek),
+                // A.5 HEBREW PUNCTUATION GERESH and A.6 HEBREW PUNCTUATION GERSHAYIM
+                '\u{5F3}' | '\u{5F4}' => {
+                    before.is_some_and(|b| self.script.get(b) == Script::Hebrew)
+                }
+                // A.7 KATAKANA MIDDLE DOT
+                '\u{30FB}' => label.chars().any(|l| {
+                    matches!(
+                        self.script.get(l),
+                        Script::Hiragana | Script::Katakana | Script::Han
+                    )
+                }),
+                // A.8 ARABIC-INDIC DIGITS
+                '\u{660}'..='\u{669}' => !label.contains(|l| matches!(l, '\u{6F0}'..='\u{6F9}')),
+                // A.9 EXTENDED ARABIC-INDIC DIGITS
+                '\u{6F0}'..='\u{6F9}' => !label.contains(|l| matches!(l, '\u{660}'..='\u{669}')),
+                _ => true,
+            };
+            if !satisfied {
+                return false;
+            }
+            before = Some(c);
+        }
+        true
+    }
+
+    /// Whether `label` makes its domain name a Bidi domain name.
+    fn is_rtl(&self, label: &str) -> bool {
+        label.chars().any(|c| {
+            matches!(
+                self.bidi_class.get(c),
+                BidiClass::RightToLeft | BidiClass::ArabicLetter | BidiClass::ArabicNumber
+            )
+        })
+    }
+
+    /// Whether `label` satisfies the Bidi Rule of RFC 5893, section 2.
+    fn satisfies_bidi_rule(&self, label: &str) -> bool {
+        use BidiClass as B;
+        let mut classes = label.chars().map(|c| self.bidi_class.get(c));
+        let Some(first) = classes.next() else {
+            return true;
+        };
+        let rtl = match first {
+            B::RightToLeft | B::ArabicLetter => true,
+            B::LeftToRight => false,
+            // 1. The first character must have the Bidi property L, R, or AL.
+            _ => return false,
+        };
+        let mut last = first;
+        let mut has_en = false;
+        let mut has_an = false;
+        for class in classes {
+            // 2. and 5. The characters allowed in right-to-left and left-to-right labels.
+            let allowed = match class {
+                B::EuropeanNumber
+                | B::EuropeanSeparator
+                | B::CommonSeparator
+                | B::EuropeanTerminator
+                | B::OtherNeutral
+                | B::BoundaryNeutral
+                | B::NonspacingMark => true,
+                B::RightToLeft | B::ArabicLetter | B::ArabicNumber => rtl,
+                B::LeftToRight => !rtl,
+                _ => false,
+            };
+            if !allowed {
+                return false;
+            }
+            has_en |= class == B::EuropeanNumber;
+            has_an |= class == B::ArabicNumber;
+            if class != B::NonspacingMark {
+                last = class;
+            }
+        }
+        if rtl {
+            // 3. The end of the label, and 4. EN and AN must not be mixed.
+            matches!(
+                last,
+                B::RightToLeft | B::ArabicLetter | B::EuropeanNumber | B::ArabicNumber
+            ) && !(has_en && has_an)
+        } else {
+            // 6. The end of the label.
+            matches!(last, B::LeftToRight | B::EuropeanNumber)
+        }
+    }
+}
+
+/// A processor for the ToASCII and ToUnicode operations of UTS 46 on domain names.
+///
+/// ✨ *Enabled with the `icu_properties` Cargo feature.*
+///
+/// See [`Uts46ProcessorBorrowed`] for details.
+#[derive(Debug)]
+pub struct Uts46Processor {
+    mapper: Uts46Mapper,
+    ccc: CanonicalCombiningClassMap,
+    general_category: CodePointMapData<GeneralCategory>,
+    bidi_class: CodePointMapData<BidiClass>,
+    joining_type: CodePointMapData<JoiningType>,
+    script: CodePointMapData<Script>,
+}
+
+#[cfg(feature = "compiled_data")]
+impl Default for Uts46Processor {
+    fn default() -> Self {
+        Self::new().static_to_owned()
+    }
+}
+
+impl Uts46Processor {
+    /// Constructs a borrowed version of this type for more efficient querying.
+    pub fn as_borrowed(&self) -> Uts46ProcessorBorrowed<'_> {
+        Uts46ProcessorBorrowed {
+            mapper: self.mapper.as_borrowed(),
+            ccc: self.ccc.as_borrowed(),
+            general_category: self.general_category.as_borrowed(),
+            bidi_class: self.bidi_class.as_borrowed(),
+            joining_type: self.joining_type.as_borrowed(),
+            script: self.script.as_borrowed(),
+        }
+    }
+
+    /// Construct with compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    #[expect(clippy::new_ret_no_self)]
+    pub const fn new() -> Uts46ProcessorBorrowed<'static> {
+        Uts46ProcessorBorrowed::new()
+    }
+
+    icu_provider::gen_buffer_data_constructors!(() -> error: DataError,
+        functions: [
+            new: skip,
+            try_new_with_buffer_provider,
+            try_new_unstable,
+            Self,
+    ]);
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::new)]
+    pub fn try_new_unstable<D>(provider: &D) -> Result<Self, DataError>
+    where
+        D: DataProvider<NormalizerUts46DataV1>
+            + DataProvider<NormalizerNfdTablesV1>
+            + DataProvider<NormalizerNfkdTablesV1>
+            + DataProvider<NormalizerNfcV1>
+            + DataProvider<NormalizerNfdDataV1>
+            + DataProvider<PropertyEnumGeneralCategoryV1>
+            + DataProvider<PropertyEnumBidiClassV1>
+            + DataProvider<PropertyEnumJoiningTypeV1>
+            + DataProvider<PropertyEnumScriptV1>
+            + ?Sized,
+    {
+        Ok(Uts46Processor {
+            mapper: Uts46Mapper::try_new(provider)?,
+            ccc: CanonicalCombiningClassMap::try_new_unstable(provider)?,
+            general_category: CodePointMapData::try_new_unstable(provider)?,
+            bidi_class: CodePointMapData::try_new_unstable(provider)?,
+            joining_type: CodePointMapData::try_new_unstable(provider)?,
+            script: CodePointMapData::try_new_unstable(provider)?,
+        })
+    }
+}
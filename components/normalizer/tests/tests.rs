@@ -9,12 +9,20 @@ use icu_normalizer::properties::CanonicalCompositionBorrowed;
 use icu_normalizer::properties::CanonicalDecomposition;
 use icu_normalizer::properties::CanonicalDecompositionBorrowed;
 use icu_normalizer::properties::Decomposed;
+use icu_normalizer::punycode;
+use icu_normalizer::punycode::PunycodeError;
+use icu_normalizer::streaming::NormalizingFmtWriter;
+use icu_normalizer::streaming::NormalizingWrite16;
+use icu_normalizer::streaming::StreamingNormalizer;
+use icu_normalizer::streaming::StreamingNormalizerUtf16;
 use icu_normalizer::uts46::Uts46Mapper;
 use icu_normalizer::uts46::Uts46MapperBorrowed;
 use icu_normalizer::ComposingNormalizer;
 use icu_normalizer::ComposingNormalizerBorrowed;
 use icu_normalizer::DecomposingNormalizer;
 use icu_normalizer::DecomposingNormalizerBorrowed;
+use icu_normalizer::FcdChecker;
+use icu_normalizer::FcdCheckerBorrowed;
 
 #[test]
 fn test_nfd_basic() {
@@ -128,6 +136,78 @@ fn test_nfc_owned() {
     assert_eq!(normalizer.normalize("\u{0345}"), "\u{0345}"); // Iota subscript
 }
 
+#[test]
+fn test_fcc_basic() {
+    let normalizer = ComposingNormalizerBorrowed::new_fcc();
+    assert_eq!(normalizer.normalize("a\u{0308}"), "ä");
+    assert_eq!(normalizer.normalize("e\u{0323}\u{0302}"), "ệ");
+    assert_eq!(normalizer.normalize("𝅗𝅥"), "𝅗\u{1D165}"); // Composition exclusion
+    assert_eq!(normalizer.normalize("\u{1100}\u{1161}\u{11A8}"), "각"); // Hangul
+    assert_eq!(normalizer.normalize("\u{2126}"), "Ω"); // ohm sign
+    assert_eq!(normalizer.normalize("ﬁ"), "ﬁ"); // ligature unchanged
+
+    // NFC composes discontiguously across the lower-ccc U+0316, FCC does not.
+    assert_eq!(
+        ComposingNormalizerBorrowed::new_nfc().normalize("a\u{0316}\u{0301}"),
+        "á\u{0316}"
+    );
+    assert_eq!(
+        normalizer.normalize("a\u{0316}\u{0301}"),
+        "a\u{0316}\u{0301}"
+    );
+    assert_eq!(normalizer.normalize("á\u{0316}"), "a\u{0316}\u{0301}");
+    assert!(normalizer.is_normalized("a\u{0316}\u{0301}"));
+    assert!(!normalizer.is_normalized("á\u{0316}"));
+
+    assert_eq!(
+        normalizer.normalize_utf8("á\u{0316}".as_bytes()),
+        "a\u{0316}\u{0301}"
+    );
+    let utf16: Vec<u16> = "á\u{0316}".encode_utf16().collect();
+    let expected: Vec<u16> = "a\u{0316}\u{0301}".encode_utf16().collect();
+    assert_eq!(&normalizer.normalize_utf16(&utf16)[..], &expected[..]);
+}
+
+#[test]
+fn test_fcc_owned() {
+    let owned =
+        ComposingNormalizer::try_new_fcc_unstable(&icu_normalizer::provider::Baked).unwrap();
+    let normalizer = owned.as_borrowed();
+    assert_eq!(normalizer.normalize("e\u{0323}\u{0302}"), "ệ");
+    assert_eq!(normalizer.normalize("á\u{0316}"), "a\u{0316}\u{0301}");
+}
+
+#[test]
+fn test_fcd() {
+    let owned = FcdChecker::try_new_unstable(&icu_normalizer::provider::Baked).unwrap();
+    for checker in [FcdCheckerBorrowed::new(), owned.as_borrowed()] {
+        for (text, expected) in [
+            ("", true),
+            ("abc", true),
+            ("ệ", true),
+            ("e\u{0323}\u{0302}", true),
+            ("e\u{0302}\u{0323}", false),
+            ("a\u{0316}\u{0301}", true),
+            ("\u{00E1}\u{0316}", false),
+            // U+0F73 decomposes to U+0F71 U+0F72; the trail ccc 130 exceeds 129.
+            ("\u{0F73}\u{0F71}", false),
+            ("\u{0F71}\u{0F73}", true),
+            ("각\u{0301}", true),
+        ] {
+            assert_eq!(checker.is_fcd(text), expected, "{text:?}");
+            assert_eq!(checker.is_fcd_utf8(text.as_bytes()), expected, "{text:?}");
+            let utf16: Vec<u16> = text.encode_utf16().collect();
+            assert_eq!(checker.is_fcd_utf16(&utf16), expected, "{text:?}");
+        }
+        for text in ["\u{00E1}\u{0316}", "e\u{0302}\u{0323}", "\u{0F73}\u{0F71}"] {
+            let fcc = ComposingNormalizerBorrowed::new_fcc().normalize(text);
+            assert!(checker.is_fcd(&fcc), "{fcc:?}");
+            let nfd = DecomposingNormalizerBorrowed::new_nfd().normalize(text);
+            assert!(checker.is_fcd(&nfd), "{nfd:?}");
+        }
+    }
+}
+
 #[test]
 fn test_nfkc_basic() {
     let normalizer = ComposingNormalizerBorrowed::new_nfkc();
@@ -431,6 +511,335 @@ fn test_uts46_normalize_validate() {
     );
 }
 
+#[test]
+fn test_punycode() {
+    // Samples from RFC 3492, section 7.1.
+    let samples = [
+        (
+            "\u{644}\u{64A}\u{647}\u{645}\u{627}\u{628}\u{62A}\u{643}\u{644}\u{645}\u{648}\u{634}\u{639}\u{631}\u{628}\u{64A}\u{61F}",
+            "egbpdaj6bu4bxfgehfvwxn",
+        ),
+        (
+            "\u{4ED6}\u{4EEC}\u{4E3A}\u{4EC0}\u{4E48}\u{4E0D}\u{8BF4}\u{4E2D}\u{6587}",
+            "ihqwcrb4cv8a8dqg056pqjye",
+        ),
+        (
+            "Pro\u{10D}prost\u{11B}nemluv\u{ED}\u{10D}esky",
+            "Proprostnemluvesky-uyb24dma41a",
+        ),
+        (
+            "3\u{5E74}B\u{7D44}\u{91D1}\u{516B}\u{5148}\u{751F}",
+            "3B-ww4c5e180e575a65lsy2b",
+        ),
+        ("-> $1.00 <-", "-> $1.00 <--"),
+    ];
+    for (unicode, encoded) in samples {
+        assert_eq!(punycode::encode(unicode).as_deref(), Ok(encoded));
+        assert_eq!(punycode::decode(encoded).as_deref(), Ok(unicode));
+    }
+    // Digits are case-insensitive.
+    assert_eq!(punycode::decode("bcher-KVA").as_deref(), Ok("bücher"));
+
+    assert_eq!(punycode::decode("ü-abc"), Err(PunycodeError::Invalid));
+    assert_eq!(punycode::decode("abc-!"), Err(PunycodeError::Invalid));
+    // A truncated variable-length integer.
+    assert_eq!(punycode::decode("bcher-kv"), Err(PunycodeError::Invalid));
+    assert_eq!(
+        punycode::decode("99999999999"),
+        Err(PunycodeError::Overflow)
+    );
+    assert_eq!(punycode::decode("").as_deref(), Ok(""));
+}
+
+#[cfg(feature = "icu_properties")]
+#[test]
+fn test_uts46_to_ascii() {
+    use icu_normalizer::uts46::{Uts46ErrorKind, Uts46Options, Uts46ProcessorBorrowed};
+
+    let processor = Uts46ProcessorBorrowed::new();
+    let options = Uts46Options::default();
+    let errors = |domain: &str, options| {
+        processor
+            .to_ascii(domain, options)
+            .unwrap_err()
+            .errors()
+            .iter()
+            .map(|e| (e.label, e.kind))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        processor.to_ascii("ÖBB.at", options).as_deref(),
+        Ok("xn--bb-eka.at")
+    );
+    // Full stops are mapped, and ignored characters removed.
+    assert_eq!(
+        processor.to_ascii("a\u{3002}b\u{AD}c.", options).as_deref(),
+        Ok("a.bc.")
+    );
+    assert_eq!(
+        processor.to_ascii("faß.de", options).as_deref(),
+        Ok("xn--fa-hia.de")
+    );
+    let mut transitional = Uts46Options::default();
+    transitional.transitional_processing = true;
+    assert_eq!(
+        processor.to_ascii("faß.de", transitional).as_deref(),
+        Ok("fass.de")
+    );
+
+    assert_eq!(
+        errors("a..b", options),
+        [(Some(1), Uts46ErrorKind::LabelLength)]
+    );
+    assert_eq!(
+        errors("ab--c.-d", options),
+        [
+            (Some(0), Uts46ErrorKind::HyphensInThirdAndFourthPositions),
+            (Some(1), Uts46ErrorKind::LeadingOrTrailingHyphen)
+        ]
+    );
+    let mut lax = Uts46Options::default();
+    lax.check_hyphens = false;
+    lax.use_std3_ascii_rules = false;
+    lax.verify_dns_length = false;
+    assert_eq!(
+        processor.to_ascii("ab--c.-d_", lax).as_deref(),
+        Ok("ab--c.-d_")
+    );
+    assert_eq!(
+        errors("a_b", options),
+        [(Some(0), Uts46ErrorKind::Std3Ascii)]
+    );
+    assert_eq!(
+        errors("\u{301}a", options),
+        [(Some(0), Uts46ErrorKind::LeadingCombiningMark)]
+    );
+    assert_eq!(
+        errors(&"a".repeat(64), options),
+        [(Some(0), Uts46ErrorKind::LabelLength)]
+    );
+    assert_eq!(
+        errors(&["a"; 128].join("."), options),
+        [(None, Uts46ErrorKind::DomainLength)]
+    );
+    assert_eq!(
+        errors("xn--a-.xn--zz", options),
+        [
+            (Some(0), Uts46ErrorKind::Punycode),
+            (Some(1), Uts46ErrorKind::Punycode)
+        ]
+    );
+    // Not in NFC after decoding.
+    assert_eq!(
+        errors("xn--a-ecp", options),
+        [(Some(0), Uts46ErrorKind::Disallowed)]
+    );
+}
+
+#[cfg(feature = "icu_properties")]
+#[test]
+fn test_uts46_to_unicode() {
+    use icu_normalizer::uts46::{Uts46ErrorKind, Uts46Options, Uts46Processor};
+
+    let owned = Uts46Processor::default();
+    let processor = owned.as_borrowed();
+    let options = Uts46Options::default();
+    let to_unicode = |domain| {
+        let (unicode, result) = processor.to_unicode(domain, options);
+        let errors = result
+            .err()
+            .map(|e| e.errors().iter().map(|e| (e.label, e.kind)).collect())
+            .unwrap_or_default();
+        (unicode, errors)
+    };
+
+    assert_eq!(
+        to_unicode("xn--bb-eka.AT"),
+        (String::from("öbb.at"), Vec::new())
+    );
+    // Disallowed characters are replaced, invalid Punycode kept.
+    assert_eq!(
+        to_unicode("a\u{61C}.xn--zz"),
+        (
+            String::from("a\u{FFFD}.xn--zz"),
+            vec![
+                (Some(0), Uts46ErrorKind::Disallowed),
+                (Some(1), Uts46ErrorKind::Punycode)
+            ]
+        )
+    );
+
+    // Joiners: ZWNJ after a virama, or between joining characters.
+    assert_eq!(to_unicode("\u{915}\u{94D}\u{200C}").1, []);
+    assert_eq!(to_unicode("\u{628}\u{200C}\u{628}").1, []);
+    assert_eq!(
+        to_unicode("a\u{200C}b").1,
+        [(Some(0), Uts46ErrorKind::ContextJ)]
+    );
+    assert_eq!(
+        to_unicode("a\u{200D}b").1,
+        [(Some(0), Uts46ErrorKind::ContextJ)]
+    );
+
+    // CONTEXTO rules are only checked if enabled.
+    assert_eq!(to_unicode("a\u{B7}b").1, []);
+    let mut context_o = Uts46Options::default();
+    context_o.check_context_o = true;
+    context_o.check_bidi = false;
+    let context_o_errors = |domain| -> Vec<_> {
+        processor
+            .to_unicode(domain, context_o)
+            .1
+            .err()
+            .map(|e| e.errors().iter().map(|e| (e.label, e.kind)).collect())
+            .unwrap_or_default()
+    };
+    for valid in [
+        "l\u{B7}l",
+        "\u{375}\u{3B1}",
+        "\u{5D0}\u{5F3}",
+        "\u{5D0}\u{5F4}",
+        "\u{30A2}\u{30FB}\u{30A4}",
+        "\u{4E00}\u{30FB}",
+        "\u{628}\u{660}\u{661}",
+        "\u{628}\u{6F0}\u{6F1}",
+    ] {
+        assert_eq!(context_o_errors(valid), [], "{valid}");
+    }
+    for invalid in [
+        "a\u{B7}b",
+        "l\u{B7}",
+        "\u{375}a",
+        "a\u{5F3}",
+        "a\u{5F4}",
+        "a\u{30FB}",
+        "\u{628}\u{660}\u{6F0}",
+    ] {
+        assert_eq!(
+            context_o_errors(invalid),
+            [(Some(0), Uts46ErrorKind::ContextO)],
+            "{invalid}"
+        );
+    }
+
+    // Bidi: all labels are checked in a domain name with a right-to-left label.
+    assert_eq!(to_unicode("\u{5D0}\u{5D1}.example").1, []);
+    assert_eq!(to_unicode("1a.example").1, []);
+    assert_eq!(
+        to_unicode("\u{5D0}\u{5D1}.1a").1,
+        [(Some(1), Uts46ErrorKind::Bidi)]
+    );
+    assert_eq!(to_unicode("\u{5D0}a").1, [(Some(0), Uts46ErrorKind::Bidi)]);
+    assert_eq!(
+        to_unicode("\u{5D0}1\u{660}").1,
+        [(Some(0), Uts46ErrorKind::Bidi)]
+    );
+}
+
+/// Unescapes the `\uXXXX` and `\x{XXXX}` escapes of `IdnaTestV2.txt`.
+#[cfg(feature = "icu_properties")]
+fn unescape_idna_test(s: &str) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('\\') {
+        result.push_str(&rest[..i]);
+        let escape = &rest[i..];
+        let (hex, len) = if let Some(braced) = escape.strip_prefix("\\x{") {
+            let end = braced.find('}').unwrap();
+            (&braced[..end], end + 4)
+        } else if escape.starts_with("\\u") {
+            (&escape[2..6], 6)
+        } else {
+            result.push('\\');
+            rest = &escape[1..];
+            continue;
+        };
+        result.push(char::from_u32(u32::from_str_radix(hex, 16).unwrap()).unwrap());
+        rest = &escape[len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(feature = "icu_properties")]
+#[test]
+fn test_uts46_conformance() {
+    use icu_normalizer::uts46::{Uts46Options, Uts46ProcessorBorrowed};
+
+    let processor = Uts46ProcessorBorrowed::new();
+    let nontransitional = Uts46Options::default();
+    let mut transitional = Uts46Options::default();
+    transitional.transitional_processing = true;
+    // U+FFFD in the output stands for any disallowed character.
+    let matches = |actual: &str, expected: &str| {
+        actual.chars().count() == expected.chars().count()
+            && actual
+                .chars()
+                .zip(expected.chars())
+                .all(|(a, e)| a == e || a == '\u{FFFD}')
+    };
+
+    let data = include_str!("data/IdnaTestV2.txt");
+    for line in data.lines() {
+        let line = line.split('#').next().unwrap();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let columns = line
+            .split(';')
+            .map(|c| unescape_idna_test(c.trim()))
+            .collect::<Vec<_>>();
+        let [source, to_unicode, to_unicode_status, to_ascii_n, to_ascii_n_status, to_ascii_t, to_ascii_t_status] =
+            &columns[..]
+        else {
+            panic!("Malformed line: {line}");
+        };
+        // Blank columns default to earlier columns.
+        let or = |value: &String, default: &String| {
+            if value.is_empty() {
+                default.clone()
+            } else {
+                value.clone()
+            }
+        };
+        let to_unicode = or(to_unicode, source);
+        let to_ascii_n = or(to_ascii_n, &to_unicode);
+        let to_ascii_n_status = or(to_ascii_n_status, to_unicode_status);
+        let to_ascii_t = or(to_ascii_t, &to_ascii_n);
+        let to_ascii_t_status = or(to_ascii_t_status, &to_ascii_n_status);
+        // ToUnicode does not verify DNS lengths, which the X4_2 and X3 codes stand for
+        // in its status (as A4_2 and A3 do in that of ToASCII).
+        let has_errors = |status: &str, ignored: &[&str]| {
+            status
+                .trim_matches(['[', ']'])
+                .split(',')
+                .map(str::trim)
+                .any(|code| !code.is_empty() && !ignored.contains(&code))
+        };
+
+        let (actual, result) = processor.to_unicode(source, nontransitional);
+        if has_errors(to_unicode_status, &["X4_2", "X3"]) {
+            assert!(result.is_err(), "ToUnicode of {source:?} has no errors");
+        } else {
+            assert_eq!(result, Ok(()), "ToUnicode of {source:?}");
+            assert!(matches(&actual, &to_unicode), "ToUnicode of {source:?}");
+        }
+
+        for (options, expected, status) in [
+            (nontransitional, &to_ascii_n, &to_ascii_n_status),
+            (transitional, &to_ascii_t, &to_ascii_t_status),
+        ] {
+            let result = processor.to_ascii(source, options);
+            if has_errors(status, &[]) {
+                assert!(result.is_err(), "ToASCII of {source:?} has no errors");
+            } else {
+                assert_eq!(result.as_deref(), Ok(&**expected), "ToASCII of {source:?}");
+            }
+        }
+    }
+}
+
 type StackString = arraystring::ArrayString<arraystring::typenum::U48>;
 
 #[test]
@@ -2081,3 +2490,230 @@ fn test_is_normalized_up_to() {
         0
     );
 }
+
+const STREAMING_SAMPLES: &[&str] = &[
+    "Café au lait",
+    "a\u{0323}\u{0302}\u{0301}b",
+    "\u{0301}\u{0323}leading marks",
+    "e\u{0301}\u{0327}\u{0301}",
+    "Å\u{030A}\u{0323}",
+    "ﬁ①\u{FDFA}ﾍﾞ",
+    "\u{1100}\u{1161}\u{11A8}\u{AC00}\u{11A8}",
+    "𝅗\u{1D165}\u{1D16E}𝅘𝅥𝅮",
+    "\u{0F40}\u{0F73}\u{0F71}\u{0F72}",
+    "\u{1E0A}\u{0323}\u{0307}",
+];
+
+fn check_streaming(
+    mut streaming: StreamingNormalizer,
+    mut streaming_utf16: StreamingNormalizerUtf16,
+    input: &[u8],
+    expected: &str,
+) {
+    for chunk_len in 1..=input.len() {
+        let mut out = String::new();
+        for chunk in input.chunks(chunk_len) {
+            streaming.push_utf8(chunk, &mut out).unwrap();
+        }
+        streaming.finish(&mut out).unwrap();
+        assert_eq!(out, expected, "chunk length {chunk_len}");
+        assert!(streaming.pending().is_empty());
+    }
+
+    let input16: Vec<u16> = String::from_utf8_lossy(input).encode_utf16().collect();
+    let expected16: Vec<u16> = expected.encode_utf16().collect();
+    for chunk_len in 1..=input16.len() {
+        let mut out = Vec::new();
+        for chunk in input16.chunks(chunk_len) {
+            streaming_utf16.push_utf16(chunk, &mut out).unwrap();
+        }
+        streaming_utf16.finish(&mut out).unwrap();
+        assert_eq!(out, expected16, "chunk length {chunk_len}");
+    }
+}
+
+#[test]
+fn test_streaming() {
+    let nfc = ComposingNormalizerBorrowed::new_nfc();
+    let nfkc = ComposingNormalizerBorrowed::new_nfkc();
+    let nfd = DecomposingNormalizerBorrowed::new_nfd();
+    let nfkd = DecomposingNormalizerBorrowed::new_nfkd();
+    let mut inputs: Vec<&[u8]> = STREAMING_SAMPLES.iter().map(|s| s.as_bytes()).collect();
+    // Ill-formed UTF-8, including a truncated sequence before a combining mark.
+    inputs.push(b"a\xE2\x82\xCC\x88b\xF0\x9F\x98\xCC\x81\xFF");
+
+    for input in inputs {
+        check_streaming(
+            nfc.streaming(),
+            nfc.streaming_utf16(),
+            input,
+            &nfc.normalize(&String::from_utf8_lossy(input)),
+        );
+        check_streaming(
+            nfkc.streaming(),
+            nfkc.streaming_utf16(),
+            input,
+            &nfkc.normalize(&String::from_utf8_lossy(input)),
+        );
+        check_streaming(
+            nfd.streaming(),
+            nfd.streaming_utf16(),
+            input,
+            &nfd.normalize(&String::from_utf8_lossy(input)),
+        );
+        check_streaming(
+            nfkd.streaming(),
+            nfkd.streaming_utf16(),
+            input,
+            &nfkd.normalize(&String::from_utf8_lossy(input)),
+        );
+    }
+}
+
+#[test]
+fn test_streaming_holds_back_unstable_segment() {
+    let mut nfc = ComposingNormalizerBorrowed::new_nfc().streaming();
+    let mut out = String::new();
+    nfc.push_str("Cafe", &mut out).unwrap();
+    assert_eq!(out, "Caf");
+    assert_eq!(nfc.pending(), b"e");
+    nfc.push_str("\u{0301}\u{0327}", &mut out).unwrap();
+    assert_eq!(out, "Caf");
+    nfc.push_str("!", &mut out).unwrap();
+    assert_eq!(out, "Caf\u{0229}\u{0301}");
+    assert_eq!(nfc.pending(), b"!");
+
+    // A split surrogate pair is not decoded before its second half arrives.
+    let mut nfd = DecomposingNormalizerBorrowed::new_nfd().streaming_utf16();
+    let mut out = Vec::new();
+    nfd.push_utf16(&[0x61, 0x62, 0xD834], &mut out).unwrap();
+    assert_eq!(out, [0x61]);
+    nfd.push_utf16(&[0xDD5F], &mut out).unwrap();
+    assert_eq!(out, [0x61, 0x62]);
+    nfd.finish(&mut out).unwrap();
+    assert_eq!(out, [0x61, 0x62, 0xD834, 0xDD58, 0xD834, 0xDD65]);
+}
+
+#[test]
+fn test_normalizing_fmt_writer() {
+    use core::fmt::Write;
+    let mut writer = NormalizingFmtWriter::new(
+        ComposingNormalizerBorrowed::new_nfc().streaming(),
+        String::new(),
+    );
+    for c in "e\u{0301}\u{0323}\u{1100}\u{1161}\u{11A8}".chars() {
+        writer.write_char(c).unwrap();
+    }
+    // The conjoining jamo are held back.
+    assert_eq!(writer.get_ref(), "\u{1EB9}\u{0301}");
+    assert_eq!(writer.finish().unwrap(), "\u{1EB9}\u{0301}\u{AC01}");
+
+    let mut writer = NormalizingWrite16::new(
+        DecomposingNormalizerBorrowed::new_nfd().streaming_utf16(),
+        Vec::new(),
+    );
+    write16::Write16::write_slice(&mut writer, &[0xE9, 0x62]).unwrap();
+    assert_eq!(writer.get_ref(), &[0x65, 0x301]);
+    assert_eq!(writer.finish().unwrap(), [0x65, 0x301, 0x62]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normalizing_io() {
+    use icu_normalizer::streaming::NormalizingReader;
+    use icu_normalizer::streaming::NormalizingWriter;
+    use std::io::Read;
+    use std::io::Write;
+
+    let input = "ﬁ\u{0323}e\u{0301}\u{0323}\u{AC00}\u{11A8}".as_bytes();
+    let nfkc = ComposingNormalizerBorrowed::new_nfkc();
+    let expected = nfkc.normalize_utf8(input);
+
+    let mut writer = NormalizingWriter::new(nfkc.streaming(), Vec::new());
+    for byte in input {
+        writer.write_all(core::slice::from_ref(byte)).unwrap();
+    }
+    writer.flush().unwrap();
+    assert_eq!(writer.finish().unwrap(), expected.as_bytes());
+
+    // Reads through a one-byte buffer.
+    let mut reader = NormalizingReader::new(nfkc.streaming(), input);
+    let mut out = Vec::new();
+    let mut buf = [0u8; 1];
+    loop {
+        match reader.read(&mut buf).unwrap() {
+            0 => break,
+            len => out.extend_from_slice(&buf[..len]),
+        }
+    }
+    assert_eq!(out, expected.as_bytes());
+}
+
+/// A writer that, while `flaky` is set, fails every other call and writes at
+/// most two bytes otherwise.
+#[cfg(feature = "std")]
+struct FlakyWriter {
+    written: Vec<u8>,
+    flaky: std::rc::Rc<core::cell::Cell<bool>>,
+    fail: bool,
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for FlakyWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if !self.flaky.get() {
+            self.written.extend_from_slice(buf);
+            return Ok(buf.len());
+        }
+        self.fail = !self.fail;
+        if self.fail {
+            return Err(std::io::ErrorKind::Other.into());
+        }
+        let len = buf.len().min(2);
+        self.written.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normalizing_writer_retries() {
+    use icu_normalizer::streaming::NormalizingWriter;
+    use std::io::Write;
+
+    let input = "ﬁ\u{0323}e\u{0301}\u{0323}\u{AC00}\u{11A8} Äpfel".as_bytes();
+    let nfkc = ComposingNormalizerBorrowed::new_nfkc();
+    let expected = nfkc.normalize_utf8(input);
+
+    let flaky = std::rc::Rc::new(core::cell::Cell::new(true));
+    let mut writer = NormalizingWriter::new(
+        nfkc.streaming(),
+        FlakyWriter {
+            written: Vec::new(),
+            flaky: flaky.clone(),
+            fail: false,
+        },
+    );
+    let mut rest = input;
+    let mut errors = 0;
+    while !rest.is_empty() {
+        match writer.write(&rest[..rest.len().min(3)]) {
+            Ok(len) => rest = &rest[len..],
+            Err(_) => errors += 1,
+        }
+    }
+    assert!(errors > 0);
+    while writer.flush().is_err() {}
+    assert!(expected.as_bytes().starts_with(&writer.get_ref().written));
+
+    // Output that failed to be written is kept for `finish`.
+    let _ = writer.write_all(b" \xCC");
+    flaky.set(false);
+    let mut expected = expected.into_owned();
+    expected.push_str(" \u{FFFD}");
+    assert_eq!(writer.finish().unwrap().written, expected.as_bytes());
+}
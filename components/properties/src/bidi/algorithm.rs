@@ -0,0 +1,1158 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use crate::props::{BidiClass, BidiMirroringGlyph, BidiPairedBracketType};
+use crate::provider::{PropertyEnumBidiClassV1, PropertyEnumBidiMirroringGlyphV1};
+use crate::{CodePointMapData, CodePointMapDataBorrowed};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+use icu_provider::prelude::*;
+
+/// The maximum explicit embedding depth (BD2).
+const MAX_DEPTH: u8 = 125;
+
+/// The maximum depth of the bracket pair stack (BD16).
+const MAX_BRACKET_DEPTH: usize = 63;
+
+/// A bidi embedding level (BD2).
+///
+/// Even levels are left-to-right, odd levels are right-to-left.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BidiLevel(u8);
+
+impl BidiLevel {
+    /// The left-to-right paragraph level, 0.
+    pub const LTR: Self = Self(0);
+    /// The right-to-left paragraph level, 1.
+    pub const RTL: Self = Self(1);
+
+    /// Creates a level from its number, returning `None` if it is larger than
+    /// 126, the highest level that can be resolved.
+    pub const fn new(level: u8) -> Option<Self> {
+        if level <= MAX_DEPTH + 1 {
+            Some(Self(level))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of this level.
+    pub const fn number(self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether this level is right-to-left, i.e. odd.
+    pub const fn is_rtl(self) -> bool {
+        self.0 % 2 == 1
+    }
+
+    /// Returns whether this level is left-to-right, i.e. even.
+    pub const fn is_ltr(self) -> bool {
+        !self.is_rtl()
+    }
+}
+
+/// A paragraph of text resolved by [`BidiResolverBorrowed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BidiParagraph {
+    /// The range of code units of the paragraph, including its paragraph separator.
+    pub range: Range<usize>,
+    /// The paragraph embedding level.
+    pub level: BidiLevel,
+}
+
+/// A sequence of code units with the same embedding level, see [`BidiLine::runs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BidiRun {
+    /// The range of code units of the run, in the original text.
+    pub range: Range<usize>,
+    /// The embedding level of the run.
+    pub level: BidiLevel,
+}
+
+/// Resolves bidi embedding levels using the [Unicode Bidirectional Algorithm].
+///
+/// See the [module-level documentation](crate::bidi) for an example.
+///
+/// [Unicode Bidirectional Algorithm]: https://www.unicode.org/reports/tr9/
+#[derive(Debug)]
+pub struct BidiResolver {
+    bidi_class: CodePointMapData<BidiClass>,
+    mirroring: CodePointMapData<BidiMirroringGlyph>,
+}
+
+/// A borrowed version of [`BidiResolver`] that can be used to resolve text.
+#[derive(Debug, Copy, Clone)]
+pub struct BidiResolverBorrowed<'a> {
+    bidi_class: CodePointMapDataBorrowed<'a, BidiClass>,
+    mirroring: CodePointMapDataBorrowed<'a, BidiMirroringGlyph>,
+}
+
+impl BidiResolver {
+    /// Creates a new [`BidiResolverBorrowed`] using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    #[expect(clippy::new_ret_no_self)]
+    pub const fn new() -> BidiResolverBorrowed<'static> {
+        BidiResolverBorrowed::new()
+    }
+
+    icu_provider::gen_buffer_data_constructors!(
+        () -> result: Result<BidiResolver, DataError>,
+        functions: [
+            new: skip,
+            try_new_with_buffer_provider,
+            try_new_unstable,
+            Self,
+        ]
+    );
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::new)]
+    pub fn try_new_unstable(
+        provider: &(impl DataProvider<PropertyEnumBidiClassV1>
+              + DataProvider<PropertyEnumBidiMirroringGlyphV1>
+              + ?Sized),
+    ) -> Result<Self, DataError> {
+        Ok(Self {
+            bidi_class: CodePointMapData::try_new_unstable(provider)?,
+            mirroring: CodePointMapData::try_new_unstable(provider)?,
+        })
+    }
+
+    /// Construct a borrowed version of this type that can be queried.
+    ///
+    /// This avoids a potential small underlying cost per API call by consolidating it
+    /// up front.
+    pub fn as_borrowed(&self) -> BidiResolverBorrowed<'_> {
+        BidiResolverBorrowed {
+            bidi_class: self.bidi_class.as_borrowed(),
+            mirroring: self.mirroring.as_borrowed(),
+        }
+    }
+}
+
+impl BidiResolverBorrowed<'static> {
+    /// Creates a new [`BidiResolverBorrowed`] using compiled data.
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub const fn new() -> Self {
+        Self {
+            bidi_class: CodePointMapDataBorrowed::new(),
+            mirroring: CodePointMapDataBorrowed::new(),
+        }
+    }
+
+    /// Cheaply converts a [`BidiResolverBorrowed<'static>`] into a [`BidiResolver`].
+    ///
+    /// Note: Due to branching and indirection, using [`BidiResolver`] might inhibit some
+    /// compile-time optimizations that are possible with [`BidiResolverBorrowed`].
+    pub const fn static_to_owned(self) -> BidiResolver {
+        BidiResolver {
+            bidi_class: self.bidi_class.static_to_owned(),
+            mirroring: self.mirroring.static_to_owned(),
+        }
+    }
+}
+
+#[cfg(feature = "compiled_data")]
+impl Default for BidiResolverBorrowed<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BidiResolverBorrowed<'_> {
+    /// Resolves the embedding levels of UTF-8 text.
+    ///
+    /// The text is split into paragraphs at paragraph separators (P1). If `paragraph_level`
+    /// is `None`, the level of each paragraph is determined by its first strong character
+    /// (P2, P3), otherwise all paragraphs use the given level, clamped to the maximum
+    /// explicit depth of 125. Levels are indexed by byte.
+    pub fn resolve_str(self, text: &str, paragraph_level: Option<BidiLevel>) -> BidiInfo {
+        self.resolve(
+            text.chars().map(|c| (u32::from(c), c.len_utf8() as u8)),
+            text.len(),
+            paragraph_level,
+        )
+    }
+
+    /// Resolves the embedding levels of UTF-16 text.
+    ///
+    /// This behaves like [`Self::resolve_str`], with levels indexed by UTF-16 code unit.
+    /// Unpaired surrogates are resolved using their own [`BidiClass`].
+    pub fn resolve_utf16(self, text: &[u16], paragraph_level: Option<BidiLevel>) -> BidiInfo {
+        self.resolve(
+            char::decode_utf16(text.iter().copied()).map(|c| match c {
+                Ok(c) => (u32::from(c), c.len_utf16() as u8),
+                Err(e) => (u32::from(e.unpaired_surrogate()), 1),
+            }),
+            text.len(),
+            paragraph_level,
+        )
+    }
+
+    fn resolve(
+        self,
+        code_points: impl Iterator<Item = (u32, u8)>,
+        len: usize,
+        paragraph_level: Option<BidiLevel>,
+    ) -> BidiInfo {
+        let mut code_points_and_lengths = Vec::with_capacity(len);
+        code_points_and_lengths.extend(code_points);
+        let (code_points, lengths): (Vec<u32>, Vec<u8>) =
+            code_points_and_lengths.into_iter().unzip();
+
+        let mut resolver = Resolver {
+            data: self,
+            original: code_points
+                .iter()
+                .map(|&cp| self.bidi_class.get32(cp))
+                .collect(),
+            classes: Vec::new(),
+            levels: vec![0; code_points.len()],
+            code_points,
+        };
+        resolver.classes.clone_from(&resolver.original);
+
+        // P1
+        let mut paragraphs = Vec::new();
+        let mut start = 0;
+        for (i, &class) in resolver.original.iter().enumerate() {
+            // CR LF is a single paragraph separator
+            if class == BidiClass::ParagraphSeparator
+                && !(resolver.code_points.get(i) == Some(&0x0D)
+                    && resolver.code_points.get(i + 1) == Some(&0x0A))
+            {
+                paragraphs.push(start..i + 1);
+                start = i + 1;
+            }
+        }
+        if start < resolver.code_points.len() {
+            paragraphs.push(start..resolver.code_points.len());
+        }
+
+        let paragraphs = paragraphs
+            .into_iter()
+            .map(|range| {
+                let level = resolver
+                    .resolve_paragraph(range.clone(), paragraph_level.map(|l| l.0.min(MAX_DEPTH)));
+                (range, BidiLevel(level))
+            })
+            .collect::<Vec<_>>();
+
+        // Expand the per-character data to code units
+        let mut info = BidiInfo {
+            levels: Vec::with_capacity(len),
+            classes: Vec::with_capacity(len),
+            lengths: Vec::with_capacity(len),
+            paragraphs: Vec::with_capacity(paragraphs.len()),
+        };
+        let mut offsets = Vec::with_capacity(lengths.len() + 1);
+        for ((&length, &level), &class) in lengths
+            .iter()
+            .zip(resolver.levels.iter())
+            .zip(resolver.original.iter())
+        {
+            offsets.push(info.levels.len());
+            for unit in 0..length {
+                info.levels.push(BidiLevel(level));
+                info.classes.push(class);
+                info.lengths.push(if unit == 0 { length } else { 0 });
+            }
+        }
+        offsets.push(info.levels.len());
+        for (range, level) in paragraphs {
+            info.paragraphs.push(BidiParagraph {
+                range: offsets.get(range.start).copied().unwrap_or(len)
+                    ..offsets.get(range.end).copied().unwrap_or(len),
+                level,
+            });
+        }
+        info
+    }
+}
+
+/// An entry of the directional status stack (X1).
+#[derive(Debug, Copy, Clone)]
+struct Status {
+    level: u8,
+    override_class: Option<BidiClass>,
+    isolate: bool,
+}
+
+/// The per-character state of the algorithm.
+struct Resolver<'a> {
+    data: BidiResolverBorrowed<'a>,
+    code_points: Vec<u32>,
+    /// The `Bidi_Class` of each character.
+    original: Vec<BidiClass>,
+    /// The current bidi class of each character, as modified by the rules.
+    classes: Vec<BidiClass>,
+    levels: Vec<u8>,
+}
+
+fn is_isolate_initiator(class: BidiClass) -> bool {
+    matches!(
+        class,
+        BidiClass::LeftToRightIsolate
+            | BidiClass::RightToLeftIsolate
+            | BidiClass::FirstStrongIsolate
+    )
+}
+
+/// Whether a character is removed by X9.
+fn is_removed_by_x9(class: BidiClass) -> bool {
+    matches!(
+        class,
+        BidiClass::LeftToRightEmbedding
+            | BidiClass::RightToLeftEmbedding
+            | BidiClass::LeftToRightOverride
+            | BidiClass::RightToLeftOverride
+            | BidiClass::PopDirectionalFormat
+            | BidiClass::BoundaryNeutral
+    )
+}
+
+/// The direction of a level, as a strong class.
+fn direction(level: u8) -> BidiClass {
+    if level % 2 == 1 {
+        BidiClass::RightToLeft
+    } else {
+        BidiClass::LeftToRight
+    }
+}
+
+/// The strong direction of a class for the purposes of N0 and N1, where numbers
+/// behave like `R`.
+fn strong_direction(class: BidiClass) -> Option<BidiClass> {
+    match class {
+        BidiClass::LeftToRight => Some(BidiClass::LeftToRight),
+        BidiClass::RightToLeft
+        | BidiClass::ArabicLetter
+        | BidiClass::EuropeanNumber
+        | BidiClass::ArabicNumber => Some(BidiClass::RightToLeft),
+        _ => None,
+    }
+}
+
+/// Maps the canonically equivalent angle brackets U+2329 and U+232A to U+3008 and U+3009.
+fn canonical_bracket(cp: u32) -> u32 {
+    match cp {
+        0x2329 => 0x3008,
+        0x232A => 0x3009,
+        cp => cp,
+    }
+}
+
+impl Resolver<'_> {
+    fn class(&self, i: usize) -> BidiClass {
+        self.classes
+            .get(i)
+            .copied()
+            .unwrap_or(BidiClass::OtherNeutral)
+    }
+
+    fn set_class(&mut self, i: usize, class: BidiClass) {
+        if let Some(c) = self.classes.get_mut(i) {
+            *c = class;
+        }
+    }
+
+    fn original(&self, i: usize) -> BidiClass {
+        self.original
+            .get(i)
+            .copied()
+            .unwrap_or(BidiClass::OtherNeutral)
+    }
+
+    fn level(&self, i: usize) -> u8 {
+        self.levels.get(i).copied().unwrap_or(0)
+    }
+
+    fn set_level(&mut self, i: usize, level: u8) {
+        if let Some(l) = self.levels.get_mut(i) {
+            *l = level;
+        }
+    }
+
+    /// Computes the matching PDI of each isolate initiator in the paragraph (BD9).
+    fn matching_pdis(&self, paragraph: Range<usize>) -> Vec<Option<usize>> {
+        let mut matching = vec![None; paragraph.len()];
+        let mut stack = Vec::new();
+        for i in paragraph.clone() {
+            match self.original(i) {
+                c if is_isolate_initiator(c) => stack.push(i),
+                BidiClass::PopDirectionalIsolate => {
+                    if let Some(initiator) = stack.pop() {
+                        if let Some(m) = matching.get_mut(initiator - paragraph.start) {
+                            *m = Some(i);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        matching
+    }
+
+    /// Finds the first strong character in the range, skipping isolates (P2, P3).
+    fn first_strong_level(
+        &self,
+        range: Range<usize>,
+        paragraph_start: usize,
+        matching_pdis: &[Option<usize>],
+    ) -> Option<u8> {
+        let mut i = range.start;
+        while i < range.end {
+            match self.original(i) {
+                BidiClass::LeftToRight => return Some(0),
+                BidiClass::RightToLeft | BidiClass::ArabicLetter => return Some(1),
+                c if is_isolate_initiator(c) => {
+                    match matching_pdis.get(i - paragraph_start).copied().flatten() {
+                        Some(pdi) => i = pdi,
+                        None => return None,
+                    }
+                }
+                BidiClass::ParagraphSeparator => return None,
+                _ => {}
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Resolves the levels of a paragraph, returning the paragraph level.
+    fn resolve_paragraph(&mut self, paragraph: Range<usize>, paragraph_level: Option<u8>) -> u8 {
+        let matching_pdis = self.matching_pdis(paragraph.clone());
+
+        // P2, P3
+        let paragraph_level = paragraph_level.unwrap_or_else(|| {
+            self.first_strong_level(paragraph.clone(), paragraph.start, &matching_pdis)
+                .unwrap_or(0)
+        });
+
+        self.resolve_explicit(paragraph.clone(), paragraph_level, &matching_pdis);
+
+        // X9
+        let kept = paragraph
+            .clone()
+            .filter(|&i| !is_removed_by_x9(self.original(i)))
+            .collect::<Vec<_>>();
+
+        for sequence in self.isolating_run_sequences(&paragraph, &kept, &matching_pdis) {
+            self.resolve_sequence(&sequence, &kept, paragraph_level);
+        }
+
+        // I1, I2, after all sequences since sos and eos depend on explicit levels
+        for &i in &kept {
+            let level = self.level(i);
+            let level = match (level % 2 == 1, self.class(i)) {
+                (false, BidiClass::RightToLeft) => level + 1,
+                (false, BidiClass::ArabicNumber | BidiClass::EuropeanNumber) => level + 2,
+                (
+                    true,
+                    BidiClass::LeftToRight | BidiClass::ArabicNumber | BidiClass::EuropeanNumber,
+                ) => level + 1,
+                _ => level,
+            };
+            self.set_level(i, level);
+        }
+
+        // Removed characters take the level of the preceding character, so that
+        // they do not break up runs.
+        let mut previous = paragraph_level;
+        for i in paragraph {
+            if is_removed_by_x9(self.original(i)) {
+                self.set_level(i, previous);
+            } else {
+                previous = self.level(i);
+            }
+        }
+
+        paragraph_level
+    }
+
+    /// X1-X8
+    fn resolve_explicit(
+        &mut self,
+        paragraph: Range<usize>,
+        paragraph_level: u8,
+        matching_pdis: &[Option<usize>],
+    ) {
+        let mut stack = Vec::with_capacity(usize::from(MAX_DEPTH) + 2);
+        let initial = Status {
+            level: paragraph_level,
+            override_class: None,
+            isolate: false,
+        };
+        stack.push(initial);
+        let mut overflow_isolates = 0usize;
+        let mut overflow_embeddings = 0usize;
+        let mut valid_isolates = 0usize;
+
+        for i in paragraph.clone() {
+            let last = stack.last().copied().unwrap_or(initial);
+            let class = self.original(i);
+            match class {
+                // X2-X5
+                BidiClass::RightToLeftEmbedding
+                | BidiClass::LeftToRightEmbedding
+                | BidiClass::RightToLeftOverride
+                | BidiClass::LeftToRightOverride => {
+                    self.set_level(i, last.level);
+                    let level = if matches!(
+                        class,
+                        BidiClass::RightToLeftEmbedding | BidiClass::RightToLeftOverride
+                    ) {
+                        (last.level + 1) | 1
+                    } else {
+                        (last.level + 2) & !1
+                    };
+                    if level <= MAX_DEPTH && overflow_isolates == 0 && overflow_embeddings == 0 {
+                        stack.push(Status {
+                            level,
+                            override_class: match class {
+                                BidiClass::RightToLeftOverride => Some(BidiClass::RightToLeft),
+                                BidiClass::LeftToRightOverride => Some(BidiClass::LeftToRight),
+                                _ => None,
+                            },
+                            isolate: false,
+                        });
+                    } else if overflow_isolates == 0 {
+                        overflow_embeddings += 1;
+                    }
+                }
+                // X5a-X5c
+                BidiClass::RightToLeftIsolate
+                | BidiClass::LeftToRightIsolate
+                | BidiClass::FirstStrongIsolate => {
+                    self.set_level(i, last.level);
+                    if let Some(class) = last.override_class {
+                        self.set_class(i, class);
+                    }
+                    let rtl = match class {
+                        BidiClass::RightToLeftIsolate => true,
+                        BidiClass::LeftToRightIsolate => false,
+                        _ => {
+                            let end = matching_pdis
+                                .get(i - paragraph.start)
+                                .copied()
+                                .flatten()
+                                .unwrap_or(paragraph.end);
+                            self.first_strong_level(i + 1..end, paragraph.start, matching_pdis)
+                                == Some(1)
+                        }
+                    };
+                    let level = if rtl {
+                        (last.level + 1) | 1
+                    } else {
+                        (last.level + 2) & !1
+                    };
+                    if level <= MAX_DEPTH && overflow_isolates == 0 && overflow_embeddings == 0 {
+                        valid_isolates += 1;
+                        stack.push(Status {
+                            level,
+                            override_class: None,
+                            isolate: true,
+                        });
+                    } else {
+                        overflow_isolates += 1;
+                    }
+                }
+                // X6a
+                BidiClass::PopDirectionalIsolate => {
+                    if overflow_isolates > 0 {
+                        overflow_isolates -= 1;
+                    } else if valid_isolates > 0 {
+                        overflow_embeddings = 0;
+                        while stack.last().is_some_and(|s| !s.isolate) {
+                            stack.pop();
+                        }
+                        stack.pop();
+                        valid_isolates -= 1;
+                    }
+                    let last = stack.last().copied().unwrap_or(initial);
+                    self.set_level(i, last.level);
+                    if let Some(class) = last.override_class {
+                        self.set_class(i, class);
+                    }
+                }
+                // X7
+                BidiClass::PopDirectionalFormat => {
+                    self.set_level(i, last.level);
+                    if overflow_isolates > 0 {
+                    } else if overflow_embeddings > 0 {
+                        overflow_embeddings -= 1;
+                    } else if !last.isolate && stack.len() >= 2 {
+                        stack.pop();
+                    }
+                }
+                // X8
+                BidiClass::ParagraphSeparator => self.set_level(i, paragraph_level),
+                BidiClass::BoundaryNeutral => self.set_level(i, last.level),
+                // X6
+                _ => {
+                    self.set_level(i, last.level);
+                    if let Some(class) = last.override_class {
+                        self.set_class(i, class);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Computes the isolating run sequences of a paragraph (BD13, X10), as lists
+    /// of indices into `kept`.
+    fn isolating_run_sequences(
+        &self,
+        paragraph: &Range<usize>,
+        kept: &[usize],
+        matching_pdis: &[Option<usize>],
+    ) -> Vec<Vec<usize>> {
+        // Level runs (BD7), as ranges of `kept`
+        let mut runs = Vec::<Range<usize>>::new();
+        for (k, &i) in kept.iter().enumerate() {
+            match runs.last_mut() {
+                Some(run)
+                    if self.level(kept.get(run.start).copied().unwrap_or(i)) == self.level(i) =>
+                {
+                    run.end = k + 1
+                }
+                _ => runs.push(k..k + 1),
+            }
+        }
+
+        // The run starting at each character, if any
+        let mut run_starting_at = vec![None; paragraph.len()];
+        for (r, run) in runs.iter().enumerate() {
+            if let Some(s) = kept
+                .get(run.start)
+                .and_then(|&i| run_starting_at.get_mut(i - paragraph.start))
+            {
+                *s = Some(r);
+            }
+        }
+
+        let mut linked = vec![false; runs.len()];
+        let mut sequences = Vec::new();
+        for r in 0..runs.len() {
+            if linked.get(r).copied().unwrap_or(true) {
+                continue;
+            }
+            let mut sequence = Vec::new();
+            let mut current = r;
+            while let Some(run) = runs.get(current) {
+                sequence.extend(run.clone());
+                let Some(&last) = kept.get(run.end - 1) else {
+                    break;
+                };
+                if !is_isolate_initiator(self.original(last)) {
+                    break;
+                }
+                let next = matching_pdis
+                    .get(last - paragraph.start)
+                    .copied()
+                    .flatten()
+                    .and_then(|pdi| {
+                        run_starting_at
+                            .get(pdi - paragraph.start)
+                            .copied()
+                            .flatten()
+                    });
+                match next {
+                    Some(next) => {
+                        if let Some(l) = linked.get_mut(next) {
+                            *l = true;
+                        }
+                        current = next;
+                    }
+                    None => break,
+                }
+            }
+            sequences.push(sequence);
+        }
+        sequences
+    }
+
+    /// Resolves the classes of an isolating run sequence, given as indices into `kept` (X10-N2).
+    fn resolve_sequence(&mut self, sequence: &[usize], kept: &[usize], paragraph_level: u8) {
+        let indices = sequence
+            .iter()
+            .filter_map(|&k| kept.get(k).copied())
+            .collect::<Vec<_>>();
+        let (Some(&first_k), Some(&last_k), Some(&first)) =
+            (sequence.first(), sequence.last(), indices.first())
+        else {
+            return;
+        };
+        let level = self.level(first);
+
+        // X10: sos and eos
+        let before = first_k
+            .checked_sub(1)
+            .and_then(|k| kept.get(k))
+            .map(|&i| self.level(i))
+            .unwrap_or(paragraph_level);
+        let sos = direction(level.max(before));
+        let after = match indices.last() {
+            Some(&last) if is_isolate_initiator(self.original(last)) => paragraph_level,
+            _ => kept
+                .get(last_k + 1)
+                .map(|&i| self.level(i))
+                .unwrap_or(paragraph_level),
+        };
+        let eos = direction(level.max(after));
+
+        // The characters that were NSM before W1, for N0
+        let nonspacing = indices
+            .iter()
+            .map(|&i| self.class(i) == BidiClass::NonspacingMark)
+            .collect::<Vec<_>>();
+
+        self.resolve_weak(&indices, sos);
+        self.resolve_brackets(&indices, &nonspacing, sos, level);
+        self.resolve_neutral(&indices, sos, eos, level);
+    }
+
+    /// W1-W7
+    fn resolve_weak(&mut self, indices: &[usize], sos: BidiClass) {
+        // W1
+        let mut previous = sos;
+        for &i in indices {
+            if self.class(i) == BidiClass::NonspacingMark {
+                self.set_class(
+                    i,
+                    if is_isolate_initiator(previous)
+                        || previous == BidiClass::PopDirectionalIsolate
+                    {
+                        BidiClass::OtherNeutral
+                    } else {
+                        previous
+                    },
+                );
+            }
+            previous = self.class(i);
+        }
+
+        // W2, W3
+        let mut last_strong = sos;
+        for &i in indices {
+            match self.class(i) {
+                c @ (BidiClass::LeftToRight | BidiClass::RightToLeft) => last_strong = c,
+                BidiClass::ArabicLetter => {
+                    last_strong = BidiClass::ArabicLetter;
+                    self.set_class(i, BidiClass::RightToLeft);
+                }
+                BidiClass::EuropeanNumber if last_strong == BidiClass::ArabicLetter => {
+                    self.set_class(i, BidiClass::ArabicNumber)
+                }
+                _ => {}
+            }
+        }
+
+        // W4
+        for w in indices.windows(3) {
+            let &[a, b, c] = w else { continue };
+            let (before, after) = (self.class(a), self.class(c));
+            match (self.class(b), before, after) {
+                (
+                    BidiClass::EuropeanSeparator | BidiClass::CommonSeparator,
+                    BidiClass::EuropeanNumber,
+                    BidiClass::EuropeanNumber,
+                ) => self.set_class(b, BidiClass::EuropeanNumber),
+                (BidiClass::CommonSeparator, BidiClass::ArabicNumber, BidiClass::ArabicNumber) => {
+                    self.set_class(b, BidiClass::ArabicNumber)
+                }
+                _ => {}
+            }
+        }
+
+        // W5
+        let mut k = 0;
+        while k < indices.len() {
+            if indices.get(k).map(|&i| self.class(i)) != Some(BidiClass::EuropeanTerminator) {
+                k += 1;
+                continue;
+            }
+            let start = k;
+            while indices.get(k).map(|&i| self.class(i)) == Some(BidiClass::EuropeanTerminator) {
+                k += 1;
+            }
+            let adjacent_number = start
+                .checked_sub(1)
+                .and_then(|s| indices.get(s))
+                .map(|&i| self.class(i))
+                == Some(BidiClass::EuropeanNumber)
+                || indices.get(k).map(|&i| self.class(i)) == Some(BidiClass::EuropeanNumber);
+            if adjacent_number {
+                for &i in indices.get(start..k).unwrap_or_default() {
+                    self.set_class(i, BidiClass::EuropeanNumber);
+                }
+            }
+        }
+
+        // W6
+        for &i in indices {
+            if matches!(
+                self.class(i),
+                BidiClass::EuropeanSeparator
+                    | BidiClass::EuropeanTerminator
+                    | BidiClass::CommonSeparator
+            ) {
+                self.set_class(i, BidiClass::OtherNeutral);
+            }
+        }
+
+        // W7
+        let mut last_strong = sos;
+        for &i in indices {
+            match self.class(i) {
+                c @ (BidiClass::LeftToRight | BidiClass::RightToLeft) => last_strong = c,
+                BidiClass::EuropeanNumber if last_strong == BidiClass::LeftToRight => {
+                    self.set_class(i, BidiClass::LeftToRight)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// N0, using the bracket pairs identified by BD16.
+    fn resolve_brackets(
+        &mut self,
+        indices: &[usize],
+        nonspacing: &[bool],
+        sos: BidiClass,
+        level: u8,
+    ) {
+        // BD16
+        let mut stack = Vec::<(u32, usize)>::new();
+        let mut pairs = Vec::new();
+        for (k, &i) in indices.iter().enumerate() {
+            if self.class(i) != BidiClass::OtherNeutral {
+                continue;
+            }
+            let cp = self.code_points.get(i).copied().unwrap_or_default();
+            let mirroring = self.data.mirroring.get32(cp);
+            match mirroring.paired_bracket_type {
+                BidiPairedBracketType::Open => {
+                    if stack.len() == MAX_BRACKET_DEPTH {
+                        break;
+                    }
+                    let closing = mirroring.mirroring_glyph.map(u32::from).unwrap_or(cp);
+                    stack.push((canonical_bracket(closing), k));
+                }
+                BidiPairedBracketType::Close => {
+                    let cp = canonical_bracket(cp);
+                    if let Some(position) = stack.iter().rposition(|&(closing, _)| closing == cp) {
+                        if let Some(&(_, opening)) = stack.get(position) {
+                            pairs.push((opening, k));
+                        }
+                        stack.truncate(position);
+                    }
+                }
+                _ => {}
+            }
+        }
+        pairs.sort_unstable();
+
+        let embedding = direction(level);
+        for (opening, closing) in pairs {
+            let mut found_embedding = false;
+            let mut found_opposite = false;
+            for &i in indices.get(opening + 1..closing).unwrap_or_default() {
+                match strong_direction(self.class(i)) {
+                    Some(d) if d == embedding => {
+                        found_embedding = true;
+                        break;
+                    }
+                    Some(_) => found_opposite = true,
+                    None => {}
+                }
+            }
+            let resolved = if found_embedding {
+                // N0 b
+                embedding
+            } else if found_opposite {
+                // N0 c
+                let context = indices
+                    .get(..opening)
+                    .unwrap_or_default()
+                    .iter()
+                    .rev()
+                    .find_map(|&i| strong_direction(self.class(i)))
+                    .unwrap_or(sos);
+                if context == embedding {
+                    embedding
+                } else {
+                    context
+                }
+            } else {
+                // N0 d
+                continue;
+            };
+            for bracket in [opening, closing] {
+                if let Some(&i) = indices.get(bracket) {
+                    self.set_class(i, resolved);
+                }
+                // Nonspacing marks following a bracket take its direction
+                for (&i, _) in indices
+                    .iter()
+                    .zip(nonspacing)
+                    .skip(bracket + 1)
+                    .take_while(|(_, &nsm)| nsm)
+                {
+                    self.set_class(i, resolved);
+                }
+            }
+        }
+    }
+
+    /// N1, N2
+    fn resolve_neutral(&mut self, indices: &[usize], sos: BidiClass, eos: BidiClass, level: u8) {
+        let mut k = 0;
+        while k < indices.len() {
+            let is_neutral = |k: usize| {
+                indices
+                    .get(k)
+                    .is_some_and(|&i| strong_direction(self.class(i)).is_none())
+            };
+            if !is_neutral(k) {
+                k += 1;
+                continue;
+            }
+            let start = k;
+            while is_neutral(k) {
+                k += 1;
+            }
+            let before = start
+                .checked_sub(1)
+                .and_then(|s| indices.get(s))
+                .and_then(|&i| strong_direction(self.class(i)))
+                .unwrap_or(sos);
+            let after = indices
+                .get(k)
+                .and_then(|&i| strong_direction(self.class(i)))
+                .unwrap_or(eos);
+            let resolved = if before == after {
+                before
+            } else {
+                direction(level)
+            };
+            for &i in indices.get(start..k).unwrap_or_default() {
+                self.set_class(i, resolved);
+            }
+        }
+    }
+}
+
+/// The resolved embedding levels of a text, returned by [`BidiResolverBorrowed`].
+///
+/// All indices are code unit indices into the original text, i.e. bytes for
+/// [`BidiResolverBorrowed::resolve_str`] and UTF-16 code units for
+/// [`BidiResolverBorrowed::resolve_utf16`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidiInfo {
+    levels: Vec<BidiLevel>,
+    /// The `Bidi_Class` of each code unit's character.
+    classes: Vec<BidiClass>,
+    /// The length of the character starting at each code unit, or 0 for
+    /// continuation code units.
+    lengths: Vec<u8>,
+    paragraphs: Vec<BidiParagraph>,
+}
+
+impl BidiInfo {
+    /// Returns the paragraphs of the text.
+    pub fn paragraphs(&self) -> &[BidiParagraph] {
+        &self.paragraphs
+    }
+
+    /// Returns the resolved embedding level of each code unit, before any line-based
+    /// processing (see [`BidiLine::levels`]).
+    ///
+    /// Characters removed by rule X9, such as explicit embeddings, have the level of
+    /// the preceding character.
+    pub fn levels(&self) -> &[BidiLevel] {
+        &self.levels
+    }
+
+    /// Returns whether the text contains any right-to-left levels, i.e. whether it needs
+    /// to be reordered.
+    pub fn has_rtl(&self) -> bool {
+        self.levels.iter().any(|l| l.is_rtl())
+    }
+
+    /// Returns the line occupying the given range of code units.
+    ///
+    /// A line should not cross paragraph boundaries, and should start and end on
+    /// character boundaries; the range is clamped to the text.
+    pub fn line(&self, range: Range<usize>) -> BidiLine<'_> {
+        let end = range.end.min(self.levels.len());
+        let start = range.start.min(end);
+
+        // L1
+        let mut levels = self.levels.get(start..end).unwrap_or_default().to_vec();
+        let mut paragraph = self.paragraphs.partition_point(|p| p.range.end <= end);
+        let mut trailing = true;
+        for (i, level) in (start..end).zip(levels.iter_mut()).rev() {
+            while paragraph > 0
+                && self
+                    .paragraphs
+                    .get(paragraph)
+                    .is_none_or(|p| p.range.start > i)
+            {
+                paragraph -= 1;
+            }
+            let paragraph_level = self
+                .paragraphs
+                .get(paragraph)
+                .map(|p| p.level)
+                .unwrap_or_default();
+            match self
+                .classes
+                .get(i)
+                .copied()
+                .unwrap_or(BidiClass::OtherNeutral)
+            {
+                BidiClass::SegmentSeparator | BidiClass::ParagraphSeparator => {
+                    *level = paragraph_level;
+                    trailing = true;
+                }
+                c if c == BidiClass::WhiteSpace
+                    || c == BidiClass::PopDirectionalIsolate
+                    || is_isolate_initiator(c)
+                    || is_removed_by_x9(c) =>
+                {
+                    if trailing {
+                        *level = paragraph_level;
+                    }
+                }
+                _ => trailing = false,
+            }
+        }
+
+        // Level runs, in logical order
+        let mut runs = Vec::<BidiRun>::new();
+        for (i, &level) in (start..end).zip(levels.iter()) {
+            match runs.last_mut() {
+                Some(run) if run.level == level => run.range.end = i + 1,
+                _ => runs.push(BidiRun {
+                    range: i..i + 1,
+                    level,
+                }),
+            }
+        }
+
+        // L2
+        let highest = levels.iter().map(|l| l.0).max().unwrap_or(0);
+        let lowest_odd = levels.iter().map(|l| l.0).min().unwrap_or(0) | 1;
+        for level in (lowest_odd..=highest).rev() {
+            let mut k = 0;
+            while k < runs.len() {
+                if runs.get(k).is_none_or(|r| r.level.0 < level) {
+                    k += 1;
+                    continue;
+                }
+                let start = k;
+                while runs.get(k).is_some_and(|r| r.level.0 >= level) {
+                    k += 1;
+                }
+                if let Some(runs) = runs.get_mut(start..k) {
+                    runs.reverse();
+                }
+            }
+        }
+
+        BidiLine {
+            info: self,
+            range: start..end,
+            levels,
+            runs,
+        }
+    }
+}
+
+/// A line of a [`BidiInfo`], with its levels adjusted by rule L1 and reordered by rule L2.
+///
+/// Returned by [`BidiInfo::line`].
+#[derive(Debug, Clone)]
+pub struct BidiLine<'a> {
+    info: &'a BidiInfo,
+    range: Range<usize>,
+    levels: Vec<BidiLevel>,
+    runs: Vec<BidiRun>,
+}
+
+impl BidiLine<'_> {
+    /// Returns the range of code units of this line.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the embedding level of each code unit of the line, after applying rule L1.
+    ///
+    /// Segment and paragraph separators, as well as whitespace and isolate formatting
+    /// characters preceding them or the end of the line, are reset to the paragraph level.
+    pub fn levels(&self) -> &[BidiLevel] {
+        &self.levels
+    }
+
+    /// Returns the level runs of the line in visual order, from left to right.
+    ///
+    /// The code units of runs with a right-to-left level are displayed in reverse order.
+    pub fn runs(&self) -> &[BidiRun] {
+        &self.runs
+    }
+
+    /// Returns, for each visual position in the line, the logical index of the
+    /// code unit displayed there, relative to the start of the line.
+    ///
+    /// The code units of a character always remain in logical order.
+    pub fn visual_to_logical(&self) -> Vec<usize> {
+        let mut map = Vec::with_capacity(self.range.len());
+        let mut characters = Vec::<Range<usize>>::new();
+        for run in &self.runs {
+            characters.clear();
+            for i in run.range.clone() {
+                match characters.last_mut() {
+                    Some(character) if self.info.lengths.get(i) == Some(&0) => {
+                        *character = character.start..i + 1
+                    }
+                    _ => characters.push(i..i + 1),
+                }
+            }
+            if run.level.is_rtl() {
+                characters.reverse();
+            }
+            for character in &characters {
+                map.extend(character.clone().map(|i| i - self.range.start));
+            }
+        }
+        map
+    }
+
+    /// Returns, for each code unit in the line, its visual position, relative to the
+    /// start of the line.
+    ///
+    /// This is the inverse of [`Self::visual_to_logical`].
+    pub fn logical_to_visual(&self) -> Vec<usize> {
+        let visual_to_logical = self.visual_to_logical();
+        let mut map = vec![0; visual_to_logical.len()];
+        for (visual, &logical) in visual_to_logical.iter().enumerate() {
+            if let Some(m) = map.get_mut(logical) {
+                *m = visual;
+            }
+        }
+        map
+    }
+}
@@ -0,0 +1,69 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use crate::{props::EnumeratedProperty, provider::PropertyEnumBidiMirroringGlyphV1};
+use icu_collections::codepointtrie::TrieValue;
+use zerovec::ule::{AsULE, RawBytesULE};
+
+/// This is a bitpacked combination of the `Bidi_Mirroring_Glyph`,
+/// `Bidi_Mirrored`, and `Bidi_Paired_Bracket_Type` properties.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
+#[cfg_attr(feature = "datagen", databake(path = icu_properties::props))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[allow(clippy::exhaustive_structs)] // needed for baked construction
+pub struct BidiMirroringGlyph {
+    /// The mirroring glyph
+    pub mirroring_glyph: Option<char>,
+    /// Whether the glyph is mirrored
+    pub mirrored: bool,
+    /// The paired bracket type
+    pub paired_bracket_type: BidiPairedBracketType,
+}
+
+impl EnumeratedProperty for BidiMirroringGlyph {
+    type DataMarker = PropertyEnumBidiMirroringGlyphV1;
+    #[cfg(feature = "compiled_data")]
+    const SINGLETON: &'static crate::provider::PropertyCodePointMap<'static, Self> =
+        crate::provider::Baked::SINGLETON_PROPERTY_ENUM_BIDI_MIRRORING_GLYPH_V1;
+    const NAME: &'static [u8] = b"Bidi_Mirroring_Glyph";
+    const SHORT_NAME: &'static [u8] = b"Bidi_Mirroring_Glyph";
+}
+
+impl crate::private::Sealed for BidiMirroringGlyph {}
+
+impl AsULE for BidiMirroringGlyph {
+    type ULE = zerovec::ule::RawBytesULE<3>;
+
+    fn to_unaligned(self) -> Self::ULE {
+        let [a, b, c, _] = TrieValue::to_u32(self).to_le_bytes();
+        RawBytesULE([a, b, c])
+    }
+    fn from_unaligned(unaligned: Self::ULE) -> Self {
+        let [a, b, c] = unaligned.0;
+        TrieValue::try_from_u32(u32::from_le_bytes([a, b, c, 0])).unwrap_or_default()
+    }
+}
+
+/// The enum represents Bidi_Paired_Bracket_Type.
+///
+/// It does not implement [`EnumeratedProperty`], instead it can be obtained
+/// through the bitpacked [`BidiMirroringGlyph`] property.
+///
+/// If you have a use case this property without also needing the [`BidiMirroringGlyph`]
+/// property, and need to optimize data size, please file an issue.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
+#[cfg_attr(feature = "datagen", databake(path = icu_properties::props))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[non_exhaustive]
+pub enum BidiPairedBracketType {
+    /// Represents Bidi_Paired_Bracket_Type=Open.
+    Open,
+    /// Represents Bidi_Paired_Bracket_Type=Close.
+    Close,
+    /// Represents Bidi_Paired_Bracket_Type=None.
+    #[default]
+    None,
+}
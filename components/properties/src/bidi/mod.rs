@@ -2,71 +2,53 @@
 // called LICENSE at the top level of the ICU4X source tree
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
-use crate::{props::EnumeratedProperty, provider::PropertyEnumBidiMirroringGlyphV1};
-use icu_collections::codepointtrie::TrieValue;
-use zerovec::ule::{AsULE, RawBytesULE};
+//! The [Unicode Bidirectional Algorithm] (UAX #9).
+//!
+//! [`BidiResolver`] resolves the embedding levels of UTF-8 or UTF-16 text using the
+//! [`BidiClass`] and [`BidiMirroringGlyph`] properties, and [`BidiInfo::line`] reorders
+//! lines of a paragraph for display.
+//!
+//! ✨ *Enabled with the `alloc` Cargo feature.*
+//!
+//! # Examples
+//!
+//! ```
+//! use icu::properties::bidi::{BidiLevel, BidiResolver};
+//!
+//! // This example text is defined using `concat!` because some browsers
+//! // and text editors have trouble displaying bidi strings.
+//! let text = concat!["א", "ב", "ג", "abc"];
+//!
+//! // Pass `None` to detect the paragraph level from the first strong character.
+//! let info = BidiResolver::new().resolve_str(text, None);
+//!
+//! assert_eq!(info.paragraphs().len(), 1);
+//! assert_eq!(info.paragraphs()[0].level, BidiLevel::RTL);
+//!
+//! // A single line spanning the whole paragraph, displayed as "abc" followed
+//! // by the Hebrew letters in reverse order.
+//! let line = info.line(info.paragraphs()[0].range.clone());
+//! assert_eq!(line.runs().len(), 2);
+//! assert_eq!(line.runs()[0].range, 6..9);
+//! assert_eq!(line.runs()[1].range, 0..6);
+//! assert_eq!(
+//!     line.visual_to_logical(),
+//!     [6, 7, 8, 4, 5, 2, 3, 0, 1]
+//! );
+//! ```
+//!
+//! [Unicode Bidirectional Algorithm]: https://www.unicode.org/reports/tr9/
+//! [`BidiClass`]: crate::props::BidiClass
+//! [`BidiMirroringGlyph`]: crate::props::BidiMirroringGlyph
 
-/// This is a bitpacked combination of the `Bidi_Mirroring_Glyph`,
-/// `Bidi_Mirrored`, and `Bidi_Paired_Bracket_Type` properties.
-#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
-#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
-#[cfg_attr(feature = "datagen", databake(path = icu_properties::props))]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
-#[allow(clippy::exhaustive_structs)] // needed for baked construction
-pub struct BidiMirroringGlyph {
-    /// The mirroring glyph
-    pub mirroring_glyph: Option<char>,
-    /// Whether the glyph is mirrored
-    pub mirrored: bool,
-    /// The paired bracket type
-    pub paired_bracket_type: BidiPairedBracketType,
-}
-
-impl EnumeratedProperty for BidiMirroringGlyph {
-    type DataMarker = PropertyEnumBidiMirroringGlyphV1;
-    #[cfg(feature = "compiled_data")]
-    const SINGLETON: &'static crate::provider::PropertyCodePointMap<'static, Self> =
-        crate::provider::Baked::SINGLETON_PROPERTY_ENUM_BIDI_MIRRORING_GLYPH_V1;
-    const NAME: &'static [u8] = b"Bidi_Mirroring_Glyph";
-    const SHORT_NAME: &'static [u8] = b"Bidi_Mirroring_Glyph";
-}
-
-impl crate::private::Sealed for BidiMirroringGlyph {}
-
-impl AsULE for BidiMirroringGlyph {
-    type ULE = zerovec::ule::RawBytesULE<3>;
-
-    fn to_unaligned(self) -> Self::ULE {
-        let [a, b, c, _] = TrieValue::to_u32(self).to_le_bytes();
-        RawBytesULE([a, b, c])
-    }
-    fn from_unaligned(unaligned: Self::ULE) -> Self {
-        let [a, b, c] = unaligned.0;
-        TrieValue::try_from_u32(u32::from_le_bytes([a, b, c, 0])).unwrap_or_default()
-    }
-}
+#[cfg(feature = "alloc")]
+mod algorithm;
+pub(crate) mod mirroring;
 
-/// The enum represents Bidi_Paired_Bracket_Type.
-///
-/// It does not implement [`EnumeratedProperty`], instead it can be obtained
-/// through the bitpacked [`BidiMirroringGlyph`] property.
-///
-/// If you have a use case this property without also needing the [`BidiMirroringGlyph`]
-/// property, and need to optimize data size, please file an issue.
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
-#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
-#[cfg_attr(feature = "datagen", databake(path = icu_properties::props))]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
-#[non_exhaustive]
-pub enum BidiPairedBracketType {
-    /// Represents Bidi_Paired_Bracket_Type=Open.
-    Open,
-    /// Represents Bidi_Paired_Bracket_Type=Close.
-    Close,
-    /// Represents Bidi_Paired_Bracket_Type=None.
-    #[default]
-    None,
-}
+#[cfg(feature = "alloc")]
+pub use algorithm::{
+    BidiInfo, BidiLevel, BidiLine, BidiParagraph, BidiResolver, BidiResolverBorrowed, BidiRun,
+};
 
 /// Implements [`unicode_bidi::BidiDataSource`] on [`CodePointMapDataBorrowed<BidiClass>`](crate::CodePointMapDataBorrowed).
 ///
@@ -0,0 +1,556 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use crate::provider::character_names::{
+    tokenize, EncodedTokens, PropertyCharacterNames, PropertyCharacterNamesV1,
+};
+use core::fmt;
+use core::ops::RangeInclusive;
+use icu_provider::prelude::*;
+
+pub(crate) const HANGUL_SYLLABLES: RangeInclusive<u32> = 0xAC00..=0xD7A3;
+
+/// The short names of the leading consonants, vowels, and trailing consonants
+/// that make up Hangul syllable names, see `Jamo.txt`.
+const JAMO_L: [&str; 19] = [
+    "G", "GG", "N", "D", "DD", "R", "M", "B", "BB", "S", "SS", "", "J", "JJ", "C", "K", "T", "P",
+    "H",
+];
+const JAMO_V: [&str; 21] = [
+    "A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U", "WEO", "WE",
+    "WI", "YU", "EU", "YI", "I",
+];
+const JAMO_T: [&str; 28] = [
+    "", "G", "GG", "GS", "N", "NJ", "NH", "D", "L", "LG", "LM", "LB", "LS", "LT", "LP", "LH", "M",
+    "B", "BS", "S", "SS", "NG", "J", "C", "K", "T", "P", "H",
+];
+
+/// Longer than any character name or alias after loose matching.
+const MAX_LOOSE_KEY_LEN: usize = 128;
+
+/// The type of a name alias, as given in `NameAliases.txt`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NameAliasType {
+    /// A correction of a serious problem in a character name.
+    Correction = 0,
+    /// An ISO 6429 name for a C0 or C1 control function, or another commonly occurring
+    /// name for a control code.
+    Control = 1,
+    /// A widely used alternate name for a format character.
+    Alternate = 2,
+    /// A documented label for a C1 control code point that was never actually approved
+    /// in any standard.
+    Figment = 3,
+    /// A commonly occurring abbreviation (or acronym) for a control code, format character,
+    /// space, or variation selector.
+    Abbreviation = 4,
+}
+
+impl NameAliasType {
+    fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => Self::Correction,
+            1 => Self::Control,
+            2 => Self::Alternate,
+            3 => Self::Figment,
+            4 => Self::Abbreviation,
+            _ => return None,
+        })
+    }
+}
+
+/// A wrapper around character name data, for the `Name` and `Name_Alias` properties.
+///
+/// Character name data is not part of compiled data, because of its size. Use
+/// `icu4x-datagen` with the `PropertyCharacterNamesV1` marker to generate it from the
+/// Unicode Character Database.
+///
+/// Most useful methods are on [`CharacterNamesBorrowed`] obtained by calling [`CharacterNames::as_borrowed()`]
+///
+/// # Examples
+///
+/// ```
+/// use icu::properties::provider::PropertyCharacterNamesV1;
+/// use icu::properties::{CharacterNames, NameAliasType};
+/// use icu_provider::DataProvider;
+///
+/// fn example(provider: &impl DataProvider<PropertyCharacterNamesV1>) {
+///     let names = CharacterNames::try_new_unstable(provider).unwrap();
+///     let names = names.as_borrowed();
+///
+///     assert_eq!(
+///         names.get('ß').unwrap().to_string(),
+///         "LATIN SMALL LETTER SHARP S"
+///     );
+///     assert_eq!(
+///         names.get('中').unwrap().to_string(),
+///         "CJK UNIFIED IDEOGRAPH-4E2D"
+///     );
+///     assert_eq!(
+///         names.get('한').unwrap().to_string(),
+///         "HANGUL SYLLABLE HAN"
+///     );
+///     // Control characters do not have names, only aliases
+///     assert!(names.get('\n').is_none());
+///     assert_eq!(
+///         names.aliases('\n').collect::<Vec<_>>(),
+///         [
+///             (NameAliasType::Control, "LINE FEED"),
+///             (NameAliasType::Control, "NEW LINE"),
+///             (NameAliasType::Control, "END OF LINE"),
+///             (NameAliasType::Abbreviation, "LF"),
+///             (NameAliasType::Abbreviation, "NL"),
+///             (NameAliasType::Abbreviation, "EOL"),
+///         ]
+///     );
+///
+///     assert_eq!(names.get_loose("latin small letter sharp s"), Some('ß'));
+///     assert_eq!(names.get_loose("Line_Feed"), Some('\n'));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CharacterNames {
+    data: DataPayload<PropertyCharacterNamesV1>,
+}
+
+/// A borrowed wrapper around character name data, returned by
+/// [`CharacterNames::as_borrowed()`]. More efficient to query.
+#[derive(Clone, Copy, Debug)]
+pub struct CharacterNamesBorrowed<'a> {
+    data: &'a PropertyCharacterNames<'a>,
+}
+
+impl CharacterNames {
+    /// Creates a new instance of [`CharacterNames`] using a [`BufferProvider`].
+    ///
+    /// ✨ *Enabled with the `serde` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    ///
+    /// [`BufferProvider`]: icu_provider::buf::BufferProvider
+    #[cfg(feature = "serde")]
+    pub fn try_new_with_buffer_provider(
+        provider: &(impl icu_provider::buf::BufferProvider + ?Sized),
+    ) -> Result<Self, DataError> {
+        use icu_provider::buf::AsDeserializingBufferProvider;
+        Self::try_new_unstable(&provider.as_deserializing())
+    }
+
+    /// Creates a new instance of [`CharacterNames`] using a [`DataProvider`].
+    ///
+    /// <div class="stab unstable">
+    /// ⚠️ The bounds on <tt>provider</tt> may change over time, including in SemVer minor releases.
+    /// </div>
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    pub fn try_new_unstable(
+        provider: &(impl DataProvider<PropertyCharacterNamesV1> + ?Sized),
+    ) -> Result<Self, DataError> {
+        Ok(Self {
+            data: provider.load(Default::default())?.payload,
+        })
+    }
+
+    /// Construct a borrowed version of this type that can be queried.
+    ///
+    /// This avoids a potential small underlying cost per API call (ex: `get()`) by consolidating it
+    /// up front.
+    #[inline]
+    pub fn as_borrowed(&self) -> CharacterNamesBorrowed<'_> {
+        CharacterNamesBorrowed {
+            data: self.data.get(),
+        }
+    }
+}
+
+impl<'a> CharacterNamesBorrowed<'a> {
+    /// Returns the `Name` property value of a code point, if it has one.
+    ///
+    /// Names of Hangul syllables and of CJK, Tangut, and similar ideographs are derived
+    /// algorithmically. Control characters, private use characters, surrogates, noncharacters,
+    /// and unassigned code points do not have a name; see [`Self::aliases()`] for names of
+    /// control characters.
+    pub fn get(self, c: char) -> Option<CharacterName<'a>> {
+        let cp = u32::from(c);
+        if HANGUL_SYLLABLES.contains(&cp) {
+            return Some(CharacterName(Name::Hangul(cp - HANGUL_SYLLABLES.start())));
+        }
+        if let Ok(i) = self.data.code_points.binary_search(&cp) {
+            return Some(CharacterName(Name::Explicit(self.data.name_tokens(i))));
+        }
+        let i = match self.data.range_starts.binary_search(&cp) {
+            Ok(i) => i,
+            Err(i) => i.checked_sub(1)?,
+        };
+        if self.data.range_ends.get(i)? < cp {
+            return None;
+        }
+        Some(CharacterName(Name::Derived(
+            self.data.range_prefixes.get(i)?,
+            cp,
+        )))
+    }
+
+    /// Returns the `Name_Alias` property values of a code point, in the order
+    /// given by `NameAliases.txt`.
+    pub fn aliases(self, c: char) -> impl Iterator<Item = (NameAliasType, &'a str)> + 'a {
+        let data = self.data;
+        let cp = u32::from(c);
+        let start = match data.alias_code_points.binary_search(&cp) {
+            Ok(mut i) => {
+                while i > 0 && data.alias_code_points.get(i - 1) == Some(cp) {
+                    i -= 1;
+                }
+                i
+            }
+            Err(i) => i,
+        };
+        (start..data.alias_code_points.len())
+            .take_while(move |&i| data.alias_code_points.get(i) == Some(cp))
+            .filter_map(move |i| {
+                Some((
+                    NameAliasType::from_u8(data.alias_types.get(i)?)?,
+                    data.aliases.get(i)?,
+                ))
+            })
+    }
+
+    /// Returns the code point whose name or name alias matches `name` under the loose
+    /// matching rule [UAX44-LM2].
+    ///
+    /// This ignores case, whitespace, underscores, and hyphens between letters or digits
+    /// (except in `HANGUL JUNGSEONG O-E`).
+    ///
+    /// Lookup of names that are not derived algorithmically is linear in the number of names.
+    ///
+    /// [UAX44-LM2]: https://www.unicode.org/reports/tr44/#UAX44-LM2
+    pub fn get_loose(self, name: &str) -> Option<char> {
+        let mut buffer = [0; MAX_LOOSE_KEY_LEN];
+        let key = loose_key(name, false, &mut buffer)?;
+
+        if key == b"HANGULJUNGSEONGOE" {
+            // The only two names that differ just in a medial hyphen
+            let mut buffer = [0; MAX_LOOSE_KEY_LEN];
+            return Some(
+                if loose_key(name, true, &mut buffer)? == b"HANGULJUNGSEONGO-E" {
+                    '\u{1180}'
+                } else {
+                    '\u{116C}'
+                },
+            );
+        }
+
+        if let Some(rest) = strip_loose(key, tokenize("HANGUL SYLLABLE")) {
+            if let Some(c) = hangul_from_loose(rest) {
+                return Some(c);
+            }
+        }
+
+        for (i, prefix) in self.data.range_prefixes.iter().enumerate() {
+            // The hyphen before the hexadecimal digits is medial
+            let stem = prefix
+                .strip_suffix('-')
+                .filter(|s| s.ends_with(|c: char| c.is_ascii_alphanumeric()))
+                .unwrap_or(prefix);
+            let Some(hex) = strip_loose(key, tokenize(stem)) else {
+                continue;
+            };
+            let Some(cp) = hex_from_loose(hex) else {
+                continue;
+            };
+            if self.data.range_starts.get(i).is_some_and(|s| s <= cp)
+                && self.data.range_ends.get(i).is_some_and(|e| cp <= e)
+            {
+                return char::from_u32(cp);
+            }
+        }
+
+        for i in 0..self.data.names.len() {
+            if strip_loose(key, self.data.name_tokens(i)) == Some(&[]) {
+                return self.data.code_points.get(i).and_then(char::from_u32);
+            }
+        }
+
+        for (i, alias) in self.data.aliases.iter().enumerate() {
+            if strip_loose(key, tokenize(alias)) == Some(&[]) {
+                return self.data.alias_code_points.get(i).and_then(char::from_u32);
+            }
+        }
+
+        None
+    }
+}
+
+/// The name of a character, returned by [`CharacterNamesBorrowed::get()`].
+///
+/// Use the [`Display`](fmt::Display) implementation to obtain the name as a string.
+#[derive(Clone, Debug)]
+pub struct CharacterName<'a>(Name<'a>);
+
+#[derive(Clone, Debug)]
+enum Name<'a> {
+    /// The index of a Hangul syllable
+    Hangul(u32),
+    /// A prefix followed by the code point in hexadecimal
+    Derived(&'a str, u32),
+    Explicit(EncodedTokens<'a>),
+}
+
+impl fmt::Display for CharacterName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Name::Hangul(s) => {
+                let (l, v, t) = (s / 588, s % 588 / 28, s % 28);
+                f.write_str("HANGUL SYLLABLE ")?;
+                for jamo in [
+                    JAMO_L.get(l as usize),
+                    JAMO_V.get(v as usize),
+                    JAMO_T.get(t as usize),
+                ] {
+                    f.write_str(jamo.copied().unwrap_or_default())?;
+                }
+                Ok(())
+            }
+            Name::Derived(prefix, cp) => write!(f, "{prefix}{cp:04X}"),
+            Name::Explicit(ref tokens) => {
+                for (i, (glued, token)) in tokens.clone().enumerate() {
+                    if i != 0 && !glued {
+                        f.write_str(" ")?;
+                    }
+                    f.write_str(token)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Writes the UAX44-LM2 matching key of `name` to `buffer`: uppercase, without whitespace,
+/// underscores, and (unless `keep_medial_hyphens` is set) hyphens between letters or digits.
+///
+/// Returns `None` for names that cannot match any character name.
+fn loose_key<'b>(
+    name: &str,
+    keep_medial_hyphens: bool,
+    buffer: &'b mut [u8; MAX_LOOSE_KEY_LEN],
+) -> Option<&'b [u8]> {
+    let mut len = 0;
+    let mut prev = None;
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        let keep = match c {
+            '_' => false,
+            '-' => {
+                keep_medial_hyphens
+                    || !(prev.is_some_and(char::is_alphanumeric)
+                        && chars.peek().copied().is_some_and(char::is_alphanumeric))
+            }
+            _ => !c.is_whitespace(),
+        };
+        prev = Some(c);
+        if keep {
+            if !c.is_ascii() {
+                return None;
+            }
+            *buffer.get_mut(len)? = c.to_ascii_uppercase() as u8;
+            len += 1;
+        }
+    }
+    buffer.get(..len)
+}
+
+/// Strips the UAX44-LM2 matching key of the name made of `tokens` from the start of `key`.
+fn strip_loose<'k, 't>(
+    key: &'k [u8],
+    tokens: impl Iterator<Item = (bool, &'t str)>,
+) -> Option<&'k [u8]> {
+    let mut rest = key;
+    let mut prev_alphanumeric = false;
+    for (glued, token) in tokens {
+        let mut bytes = token.as_bytes();
+        if let [b'-', tail @ ..] = bytes {
+            if glued && prev_alphanumeric && tail.first().is_some_and(u8::is_ascii_alphanumeric) {
+                bytes = tail;
+            }
+        }
+        let (head, tail) = rest.split_at_checked(bytes.len())?;
+        if !head.eq_ignore_ascii_case(bytes) {
+            return None;
+        }
+        rest = tail;
+        prev_alphanumeric = token
+            .as_bytes()
+            .last()
+            .is_some_and(u8::is_ascii_alphanumeric);
+    }
+    Some(rest)
+}
+
+fn hangul_from_loose(key: &[u8]) -> Option<char> {
+    for (l, l_name) in JAMO_L.iter().enumerate() {
+        let Some(key) = key.strip_prefix(l_name.as_bytes()) else {
+            continue;
+        };
+        for (v, v_name) in JAMO_V.iter().enumerate() {
+            let Some(key) = key.strip_prefix(v_name.as_bytes()) else {
+                continue;
+            };
+            if let Some(t) = JAMO_T.iter().position(|t_name| key == t_name.as_bytes()) {
+                return char::from_u32(HANGUL_SYLLABLES.start() + ((l * 21 + v) * 28 + t) as u32);
+            }
+        }
+    }
+    None
+}
+
+/// Parses the hexadecimal digits of a derived name, which have at least four digits and no
+/// further leading zeros.
+fn hex_from_loose(key: &[u8]) -> Option<u32> {
+    if !(4..=6).contains(&key.len()) || (key.len() > 4 && key.first() == Some(&b'0')) {
+        return None;
+    }
+    key.iter()
+        .try_fold(0, |acc, &b| Some(acc << 4 | char::from(b).to_digit(16)?))
+}
+
+#[cfg(all(test, feature = "datagen"))]
+mod tests {
+    use super::*;
+
+    fn names() -> PropertyCharacterNames<'static> {
+        PropertyCharacterNames::try_new(
+            [
+                (0x0020, "SPACE"),
+                (0x002D, "HYPHEN-MINUS"),
+                (0x0041, "LATIN CAPITAL LETTER A"),
+                (0x0061, "LATIN SMALL LETTER A"),
+                (0x00DF, "LATIN SMALL LETTER SHARP S"),
+                (0x0F39, "TIBETAN MARK TSA -PHRU"),
+                (0x0F68, "TIBETAN LETTER A"),
+                (0x0F60, "TIBETAN LETTER -A"),
+                (0x116C, "HANGUL JUNGSEONG OE"),
+                (0x1180, "HANGUL JUNGSEONG O-E"),
+                (0xAC00, "HANGUL SYLLABLE GA"),
+                (0xF900, "CJK COMPATIBILITY IDEOGRAPH-F900"),
+                (0xF901, "CJK COMPATIBILITY IDEOGRAPH-F901"),
+                (0x18800, "TANGUT COMPONENT-001"),
+                (0x18801, "TANGUT COMPONENT-002"),
+            ],
+            [
+                (0x4E00..=0x9FFF, "CJK UNIFIED IDEOGRAPH-"),
+                (0x20000..=0x2A6DF, "CJK UNIFIED IDEOGRAPH-"),
+            ],
+            [
+                (0x000A, NameAliasType::Control, "LINE FEED"),
+                (0x000A, NameAliasType::Control, "NEW LINE"),
+                (0x000A, NameAliasType::Abbreviation, "LF"),
+                (0x0009, NameAliasType::Control, "CHARACTER TABULATION"),
+                (0xFEFF, NameAliasType::Alternate, "BYTE ORDER MARK"),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get() {
+        let data = names();
+        let names = CharacterNamesBorrowed { data: &data };
+        let name = |c| names.get(c).map(|n| n.to_string());
+
+        assert_eq!(name('A').as_deref(), Some("LATIN CAPITAL LETTER A"));
+        assert_eq!(name('-').as_deref(), Some("HYPHEN-MINUS"));
+        assert_eq!(name('\u{0F39}').as_deref(), Some("TIBETAN MARK TSA -PHRU"));
+        assert_eq!(name('\u{0F60}').as_deref(), Some("TIBETAN LETTER -A"));
+        assert_eq!(name('\u{1180}').as_deref(), Some("HANGUL JUNGSEONG O-E"));
+        assert_eq!(name('\u{18801}').as_deref(), Some("TANGUT COMPONENT-002"));
+        assert_eq!(name('가').as_deref(), Some("HANGUL SYLLABLE GA"));
+        assert_eq!(name('힣').as_deref(), Some("HANGUL SYLLABLE HIH"));
+        assert_eq!(name('아').as_deref(), Some("HANGUL SYLLABLE A"));
+        assert_eq!(name('中').as_deref(), Some("CJK UNIFIED IDEOGRAPH-4E2D"));
+        assert_eq!(
+            name('\u{20000}').as_deref(),
+            Some("CJK UNIFIED IDEOGRAPH-20000")
+        );
+        assert_eq!(
+            name('\u{F901}').as_deref(),
+            Some("CJK COMPATIBILITY IDEOGRAPH-F901")
+        );
+        assert_eq!(name('\n'), None);
+        assert_eq!(name('\u{A000}'), None);
+        assert_eq!(name('\u{2A6E0}'), None);
+
+        // Derived names are stored as ranges
+        assert_eq!(data.range_prefixes.len(), 3);
+        assert_eq!(data.code_points.len(), 12);
+    }
+
+    #[test]
+    fn test_aliases() {
+        let data = names();
+        let names = CharacterNamesBorrowed { data: &data };
+
+        assert_eq!(
+            names.aliases('\n').collect::<Vec<_>>(),
+            [
+                (NameAliasType::Control, "LINE FEED"),
+                (NameAliasType::Control, "NEW LINE"),
+                (NameAliasType::Abbreviation, "LF"),
+            ]
+        );
+        assert_eq!(
+            names.aliases('\t').collect::<Vec<_>>(),
+            [(NameAliasType::Control, "CHARACTER TABULATION")]
+        );
+        assert_eq!(names.aliases('A').count(), 0);
+    }
+
+    #[test]
+    fn test_get_loose() {
+        let data = names();
+        let names = CharacterNamesBorrowed { data: &data };
+
+        assert_eq!(names.get_loose("LATIN CAPITAL LETTER A"), Some('A'));
+        assert_eq!(names.get_loose("latin capital letter a"), Some('A'));
+        assert_eq!(names.get_loose("Latin_Capital_Letter_A"), Some('A'));
+        assert_eq!(names.get_loose("LatinCapitalLetterA"), Some('A'));
+        assert_eq!(names.get_loose("LATIN CAPITAL LETTER"), None);
+        assert_eq!(names.get_loose("LATIN CAPITAL LETTER AA"), None);
+
+        assert_eq!(names.get_loose("hyphen minus"), Some('-'));
+        assert_eq!(names.get_loose("hyphenminus"), Some('-'));
+        assert_eq!(names.get_loose("hyphen-minus"), Some('-'));
+        assert_eq!(names.get_loose("tibetan mark tsa -phru"), Some('\u{0F39}'));
+        assert_eq!(names.get_loose("tibetan mark tsa phru"), None);
+        assert_eq!(names.get_loose("tibetan letter a"), Some('\u{0F68}'));
+        assert_eq!(names.get_loose("tibetan letter -a"), Some('\u{0F60}'));
+        assert_eq!(names.get_loose("tangut component 001"), Some('\u{18800}'));
+
+        assert_eq!(names.get_loose("HANGUL JUNGSEONG OE"), Some('\u{116C}'));
+        assert_eq!(names.get_loose("hangul jungseong o-e"), Some('\u{1180}'));
+        assert_eq!(names.get_loose("hangul syllable gag"), Some('각'));
+        assert_eq!(names.get_loose("hangul syllable hih"), Some('힣'));
+        assert_eq!(names.get_loose("hangul syllable a"), Some('아'));
+        assert_eq!(names.get_loose("hangul syllable x"), None);
+
+        assert_eq!(names.get_loose("CJK UNIFIED IDEOGRAPH-4E2D"), Some('中'));
+        assert_eq!(names.get_loose("cjk unified ideograph 4e2d"), Some('中'));
+        assert_eq!(names.get_loose("CJK UNIFIED IDEOGRAPH-04E2D"), None);
+        assert_eq!(names.get_loose("CJK UNIFIED IDEOGRAPH-A000"), None);
+        assert_eq!(
+            names.get_loose("CJK UNIFIED IDEOGRAPH-20000"),
+            Some('\u{20000}')
+        );
+        assert_eq!(
+            names.get_loose("CJK COMPATIBILITY IDEOGRAPH-F900"),
+            Some('\u{F900}')
+        );
+
+        assert_eq!(names.get_loose("line feed"), Some('\n'));
+        assert_eq!(names.get_loose("LF"), Some('\n'));
+        assert_eq!(names.get_loose("byte order mark"), Some('\u{FEFF}'));
+        assert_eq!(names.get_loose("ŁATIN CAPITAL LETTER A"), None);
+        assert_eq!(names.get_loose(""), None);
+    }
+}
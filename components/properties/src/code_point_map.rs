@@ -8,6 +8,8 @@ use crate::props::GeneralCategory;
 use crate::props::GeneralCategoryGroup;
 use crate::provider::*;
 use core::ops::RangeInclusive;
+#[cfg(feature = "alloc")]
+use icu_collections::codepointtrie::MutableCodePointTrie;
 use icu_collections::codepointtrie::{CodePointMapRange, CodePointTrie, TrieValue};
 use icu_provider::marker::ErasedMarker;
 use icu_provider::prelude::*;
@@ -139,6 +141,38 @@ impl<T: TrieValue> CodePointMapData<T> {
     pub fn to_code_point_trie(&self) -> CodePointTrie<'_, T> {
         self.data.get().to_code_point_trie()
     }
+
+    /// Copy this data into a [`MutableCodePointTrie`], which can be modified and turned
+    /// back into [`CodePointMapData`] with [`CodePointMapData::from_code_point_trie()`].
+    ///
+    /// ✨ *Enabled with the `alloc` Cargo feature.*
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use icu::collections::codepointtrie::TrieType;
+    /// use icu::properties::props::GeneralCategory;
+    /// use icu::properties::CodePointMapData;
+    ///
+    /// let mut builder = CodePointMapData::<GeneralCategory>::new()
+    ///     .static_to_owned()
+    ///     .to_mutable_code_point_trie();
+    /// // Treat the private use characters of the BMP as letters.
+    /// builder.set_range(0xE000..=0xF8FF, GeneralCategory::OtherLetter);
+    ///
+    /// let gc = CodePointMapData::from_code_point_trie(
+    ///     builder.build(TrieType::Small).expect("valid trie"),
+    /// );
+    /// let gc = gc.as_borrowed();
+    /// assert_eq!(gc.get('\u{E000}'), GeneralCategory::OtherLetter);
+    /// assert_eq!(gc.get('\u{F0000}'), GeneralCategory::PrivateUse);
+    /// assert_eq!(gc.get('木'), GeneralCategory::OtherLetter);
+    /// assert_eq!(gc.get('a'), GeneralCategory::LowercaseLetter);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_mutable_code_point_trie(&self) -> MutableCodePointTrie<T> {
+        MutableCodePointTrie::from_code_point_trie(&self.to_code_point_trie())
+    }
 }
 
 /// A borrowed wrapper around code point set data, returned by
@@ -71,6 +71,8 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod character_names;
+pub use character_names::{CharacterName, CharacterNames, CharacterNamesBorrowed, NameAliasType};
 mod code_point_set;
 pub use code_point_set::{CodePointSetData, CodePointSetDataBorrowed};
 mod code_point_map;
@@ -84,6 +86,8 @@ pub use names::{
 };
 mod runtime;
 
+pub mod bidi;
+
 // NOTE: The Pernosco debugger has special knowledge
 // of the `CanonicalCombiningClass` struct inside the `props`
 // module. Please do not change the crate-module-qualified
@@ -92,7 +96,6 @@ pub mod props;
 pub mod provider;
 pub mod script;
 
-mod bidi;
 mod trievalue;
 
 mod private {
@@ -19,7 +19,7 @@
 
 pub use crate::names::{NamedEnumeratedProperty, ParseableEnumeratedProperty};
 
-pub use crate::bidi::{BidiMirroringGlyph, BidiPairedBracketType};
+pub use crate::bidi::mirroring::{BidiMirroringGlyph, BidiPairedBracketType};
 
 /// See [`test_enumerated_property_completeness`] for usage.
 /// Example input:
@@ -15,8 +15,11 @@
 //!
 //! Read more about data providers: [`icu_provider`]
 
+pub mod character_names;
 pub mod names;
 
+pub use character_names::PropertyCharacterNamesV1;
+
 pub use names::{
     PropertyNameLongBidiClassV1, PropertyNameLongCanonicalCombiningClassV1,
     PropertyNameLongEastAsianWidthV1, PropertyNameLongGeneralCategoryV1,
@@ -671,7 +674,7 @@ icu_provider::data_marker!(
 icu_provider::data_marker!(
     /// Data marker for the 'BidiMirroringGlyph' Unicode property
     PropertyEnumBidiMirroringGlyphV1,
-    PropertyCodePointMap<'static, crate::bidi::BidiMirroringGlyph>,
+    PropertyCodePointMap<'static, crate::props::BidiMirroringGlyph>,
     is_singleton = true,
 );
 icu_provider::data_marker!(
@@ -0,0 +1,307 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! 🚧 \[Unstable\] Character name data for this component
+//!
+//! <div class="stab unstable">
+//! 🚧 This code is considered unstable; it may change at any time, in breaking or non-breaking ways,
+//! including in SemVer minor releases. While the serde representation of data structs is guaranteed
+//! to be stable, their Rust representation might not be. Use with caution.
+//! </div>
+//!
+//! Read more about data providers: [`icu_provider`]
+
+use icu_provider::prelude::*;
+use zerovec::vecs::Index32;
+use zerovec::{VarZeroVec, ZeroVec};
+
+icu_provider::data_marker!(
+    /// `PropertyCharacterNamesV1`
+    PropertyCharacterNamesV1,
+    PropertyCharacterNames<'static>,
+    is_singleton = true
+);
+
+/// Data for the `Name` and `Name_Alias` properties.
+///
+/// Names are split into tokens at spaces and before hyphens, so that
+/// `"TANGUT COMPONENT-001"` becomes `["TANGUT", "COMPONENT", "-001"]`. Each token is
+/// stored once in `words`, and each name is stored in `names` as a sequence of indices
+/// into `words`. An index `i` is stored as the value `i << 1 | glued`, where `glued`
+/// is set if the token directly follows the previous token without a space in between.
+/// Values are stored in a variable-length big-endian encoding:
+///
+/// ```text
+/// 0xxxxxxx                     values below 0x80
+/// 10xxxxxx xxxxxxxx            values below 0x4000
+/// 11xxxxxx xxxxxxxx xxxxxxxx   values below 0x400000
+/// ```
+///
+/// Names that are derived from the code point by appending its hexadecimal value to a prefix
+/// (rule NR2 of UAX #44, as for `CJK UNIFIED IDEOGRAPH-4E00`) are stored as ranges. Hangul
+/// syllable names (rule NR1) are computed and are not part of the data.
+///
+/// <div class="stab unstable">
+/// 🚧 This code is considered unstable; it may change at any time, in breaking or non-breaking ways,
+/// including in SemVer minor releases. While the serde representation of data structs is guaranteed
+/// to be stable, their Rust representation might not be. Use with caution.
+/// </div>
+#[derive(Debug, Eq, PartialEq, Clone, yoke::Yokeable, zerofrom::ZeroFrom)]
+#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
+#[cfg_attr(feature = "datagen", databake(path = icu_properties::provider::character_names))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct PropertyCharacterNames<'data> {
+    /// The tokens that names are made of, most frequent first.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub words: VarZeroVec<'data, str, Index32>,
+    /// The code points that have an explicitly stored name, in ascending order.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub code_points: ZeroVec<'data, u32>,
+    /// The encoded names of the code points in `code_points`, in the same order.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub names: VarZeroVec<'data, [u8], Index32>,
+    /// The first code points of the ranges with derived names, in ascending order.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub range_starts: ZeroVec<'data, u32>,
+    /// The last code points (inclusive) of the ranges with derived names.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub range_ends: ZeroVec<'data, u32>,
+    /// The name prefixes of the ranges with derived names, such as `CJK UNIFIED IDEOGRAPH-`.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub range_prefixes: VarZeroVec<'data, str>,
+    /// The code points that have name aliases, in ascending order. A code point
+    /// appears once for each of its aliases.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub alias_code_points: ZeroVec<'data, u32>,
+    /// The types of the aliases in `aliases`, see [`NameAliasType`](crate::NameAliasType).
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub alias_types: ZeroVec<'data, u8>,
+    /// The name aliases of the code points in `alias_code_points`, in the same order.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub aliases: VarZeroVec<'data, str>,
+}
+
+icu_provider::data_struct!(
+    PropertyCharacterNames<'_>,
+    #[cfg(feature = "datagen")]
+);
+
+impl<'data> PropertyCharacterNames<'data> {
+    /// Returns the tokens of the explicitly stored name at `index` as
+    /// `(glued, token)` pairs.
+    pub(crate) fn name_tokens(&self, index: usize) -> EncodedTokens<'_> {
+        EncodedTokens {
+            words: &self.words,
+            encoded: self.names.get(index).unwrap_or_default(),
+        }
+    }
+}
+
+/// An iterator over the tokens of an encoded name.
+#[derive(Debug, Clone)]
+pub(crate) struct EncodedTokens<'a> {
+    words: &'a VarZeroVec<'a, str, Index32>,
+    encoded: &'a [u8],
+}
+
+impl<'a> Iterator for EncodedTokens<'a> {
+    type Item = (bool, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, rest) = self.encoded.split_first()?;
+        let (value, rest) = match first {
+            0..0x80 => (first as usize, rest),
+            0x80..0xC0 => match rest {
+                [b, rest @ ..] => (((first & 0x3F) as usize) << 8 | *b as usize, rest),
+                _ => (usize::MAX, rest),
+            },
+            _ => match rest {
+                [b, c, rest @ ..] => (
+                    ((first & 0x3F) as usize) << 16 | (*b as usize) << 8 | *c as usize,
+                    rest,
+                ),
+                _ => (usize::MAX, rest),
+            },
+        };
+        self.encoded = rest;
+        // GIGO: invalid indices produce empty tokens
+        let word = self.words.get(value >> 1).unwrap_or_default();
+        Some((value & 1 == 1, word))
+    }
+}
+
+/// Splits a name into `(glued, token)` pairs, where tokens are separated by spaces,
+/// or start with a hyphen. `glued` is set if a token is not preceded by a space.
+pub(crate) fn tokenize(name: &str) -> impl Iterator<Item = (bool, &str)> + Clone + '_ {
+    let mut rest = name;
+    let mut glued = false;
+    core::iter::from_fn(move || loop {
+        if let Some(r) = rest.strip_prefix(' ') {
+            rest = r;
+            glued = false;
+            continue;
+        }
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == ' ' || c == '-')
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        let (token, r) = rest.split_at(end);
+        rest = r;
+        let g = glued;
+        glued = true;
+        return Some((g, token));
+    })
+}
+
+#[cfg(feature = "datagen")]
+impl PropertyCharacterNames<'static> {
+    /// Builds character name data from the contents of `UnicodeData.txt` and `NameAliases.txt`.
+    ///
+    /// * `names` contains the explicit names of code points. Names of Hangul syllables are
+    ///   ignored, as they are computed at runtime. Names that consist of a prefix ending in
+    ///   a hyphen followed by the code point in hexadecimal are stored as ranges.
+    /// * `ranges` contains the ranges whose names are derived by appending the code point in
+    ///   hexadecimal to a prefix, such as `CJK UNIFIED IDEOGRAPH-`.
+    /// * `aliases` contains the name aliases with their types.
+    pub fn try_new<'a>(
+        names: impl IntoIterator<Item = (u32, &'a str)>,
+        ranges: impl IntoIterator<Item = (core::ops::RangeInclusive<u32>, &'a str)>,
+        aliases: impl IntoIterator<Item = (u32, crate::NameAliasType, &'a str)>,
+    ) -> Result<Self, DataError> {
+        use alloc::collections::BTreeMap;
+        use alloc::format;
+        use alloc::string::String;
+        use alloc::vec::Vec;
+
+        let mut explicit = BTreeMap::new();
+        let mut derived = Vec::<(u32, u32, &str)>::new();
+
+        for (cp, name) in names {
+            if crate::character_names::HANGUL_SYLLABLES.contains(&cp) {
+                continue;
+            }
+            let hex = format!("{cp:04X}");
+            match name.strip_suffix(hex.as_str()) {
+                Some(prefix) if prefix.ends_with('-') => derived.push((cp, cp, prefix)),
+                _ => {
+                    if explicit.insert(cp, name).is_some() {
+                        return Err(DataError::custom("Duplicate character name")
+                            .with_display_context(&format!("U+{cp:04X}")));
+                    }
+                }
+            }
+        }
+        derived.extend(
+            ranges
+                .into_iter()
+                .map(|(range, prefix)| (*range.start(), *range.end(), prefix)),
+        );
+        derived.sort_unstable();
+
+        let mut merged = Vec::<(u32, u32, &str)>::new();
+        for (start, end, prefix) in derived {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => {
+                    return Err(DataError::custom("Overlapping character name ranges")
+                        .with_display_context(&format!("U+{start:04X}")));
+                }
+                Some(last) if start == last.1 + 1 && prefix == last.2 => last.1 = end,
+                _ => merged.push((start, end, prefix)),
+            }
+        }
+        if let Some(&cp) = explicit.keys().find(|&&cp| {
+            merged
+                .iter()
+                .any(|&(start, end, _)| (start..=end).contains(&cp))
+        }) {
+            return Err(DataError::custom("Explicit name in a derived name range")
+                .with_display_context(&format!("U+{cp:04X}")));
+        }
+
+        let mut frequencies = BTreeMap::<&str, usize>::new();
+        for name in explicit.values() {
+            for (_, token) in tokenize(name) {
+                *frequencies.entry(token).or_default() += 1;
+            }
+        }
+        let mut words = frequencies.into_iter().collect::<Vec<_>>();
+        words.sort_by(|(a, m), (b, n)| n.cmp(m).then(a.cmp(b)));
+        let indices = words
+            .iter()
+            .enumerate()
+            .map(|(i, &(word, _))| (word, i))
+            .collect::<BTreeMap<_, _>>();
+        let words = words.into_iter().map(|(w, _)| w).collect::<Vec<_>>();
+
+        let words = VarZeroVec::<str, Index32>::from(&words);
+
+        let mut encoded_names = Vec::<Vec<u8>>::with_capacity(explicit.len());
+        for (&cp, name) in &explicit {
+            let mut encoded = Vec::new();
+            for (glued, token) in tokenize(name) {
+                let value = indices.get(token).copied().unwrap_or_default() << 1 | glued as usize;
+                if value < 0x80 {
+                    encoded.push(value as u8);
+                } else if value < 0x4000 {
+                    encoded.extend([0x80 | (value >> 8) as u8, value as u8]);
+                } else if value < 0x40_0000 {
+                    encoded.extend([0xC0 | (value >> 16) as u8, (value >> 8) as u8, value as u8]);
+                } else {
+                    return Err(DataError::custom(
+                        "Too many distinct words in character names",
+                    ));
+                }
+            }
+
+            let decoded = EncodedTokens {
+                words: &words,
+                encoded: &encoded,
+            }
+            .enumerate()
+            .fold(String::new(), |mut s, (i, (glued, token))| {
+                if i != 0 && !glued {
+                    s.push(' ');
+                }
+                s.push_str(token);
+                s
+            });
+            if decoded != *name {
+                return Err(DataError::custom("Character name does not round-trip")
+                    .with_display_context(&format!("U+{cp:04X} {name}")));
+            }
+            encoded_names.push(encoded);
+        }
+
+        let mut aliases = aliases.into_iter().collect::<Vec<_>>();
+        // Stable, to keep the order of aliases of the same code point
+        aliases.sort_by_key(|&(cp, _, _)| cp);
+
+        Ok(Self {
+            words,
+            code_points: explicit.keys().copied().collect(),
+            names: VarZeroVec::from(&encoded_names),
+            range_starts: merged.iter().map(|&(start, _, _)| start).collect(),
+            range_ends: merged.iter().map(|&(_, end, _)| end).collect(),
+            range_prefixes: VarZeroVec::from(
+                &merged
+                    .iter()
+                    .map(|&(_, _, prefix)| prefix)
+                    .collect::<Vec<_>>(),
+            ),
+            alias_code_points: aliases.iter().map(|&(cp, _, _)| cp).collect(),
+            alias_types: aliases.iter().map(|&(_, t, _)| t as u8).collect(),
+            aliases: VarZeroVec::from(
+                &aliases
+                    .iter()
+                    .map(|&(_, _, alias)| alias)
+                    .collect::<Vec<_>>(),
+            ),
+        })
+    }
+}
@@ -2,7 +2,7 @@
 // called LICENSE at the top level of the ICU4X source tree
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
-use crate::bidi::BidiMirroringGlyph;
+use crate::props::BidiMirroringGlyph;
 use crate::props::{
     BidiClass, CanonicalCombiningClass, EastAsianWidth, GeneralCategory, GeneralCategoryGroup,
     GraphemeClusterBreak, HangulSyllableType, IndicConjunctBreak, IndicSyllabicCategory,
@@ -276,9 +276,9 @@ impl TrieValue for BidiMirroringGlyph {
         let paired_bracket_type = {
             let value = ((i >> 22) & 0x3) as u8;
             match value {
-                0 => crate::bidi::BidiPairedBracketType::None,
-                1 => crate::bidi::BidiPairedBracketType::Open,
-                2 => crate::bidi::BidiPairedBracketType::Close,
+                0 => crate::props::BidiPairedBracketType::None,
+                1 => crate::props::BidiPairedBracketType::Open,
+                2 => crate::props::BidiPairedBracketType::Close,
                 _ => return Err(i),
             }
         };
@@ -293,9 +293,9 @@ impl TrieValue for BidiMirroringGlyph {
         self.mirroring_glyph.unwrap_or_default() as u32
             | ((self.mirrored as u32) << 21)
             | (match self.paired_bracket_type {
-                crate::bidi::BidiPairedBracketType::None => 0,
-                crate::bidi::BidiPairedBracketType::Open => 1,
-                crate::bidi::BidiPairedBracketType::Close => 2,
+                crate::props::BidiPairedBracketType::None => 0,
+                crate::props::BidiPairedBracketType::Open => 1,
+                crate::props::BidiPairedBracketType::Close => 2,
             } << 22)
     }
 }
@@ -0,0 +1,316 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use icu_properties::bidi::{BidiInfo, BidiLevel, BidiResolver};
+
+/// Returns the levels after rule L1 and the visual order of the whole text, reordering
+/// each paragraph separately.
+fn levels_and_order(info: &BidiInfo) -> (Vec<BidiLevel>, Vec<usize>) {
+    let mut levels = Vec::new();
+    let mut order = Vec::new();
+    for paragraph in info.paragraphs() {
+        let line = info.line(paragraph.range.clone());
+        levels.extend_from_slice(line.levels());
+        order.extend(
+            line.visual_to_logical()
+                .into_iter()
+                .map(|i| i + paragraph.range.start),
+        );
+    }
+    (levels, order)
+}
+
+/// Compares resolved levels and a visual order against conformance test data, where
+/// `None` levels are characters removed by rule X9 that are not part of the order.
+fn check(
+    levels: &[Option<u8>],
+    order: &[usize],
+    actual_levels: &[BidiLevel],
+    actual_order: &[usize],
+    line: &str,
+) {
+    assert_eq!(levels.len(), actual_levels.len(), "{line}");
+    for (expected, actual) in levels.iter().zip(actual_levels) {
+        if let Some(expected) = expected {
+            assert_eq!(*expected, actual.number(), "{line}");
+        }
+    }
+    let actual_order = actual_order
+        .iter()
+        .copied()
+        .filter(|&i| levels[i].is_some())
+        .collect::<Vec<_>>();
+    assert_eq!(order, actual_order, "{line}");
+}
+
+fn parse_levels(levels: &str) -> Vec<Option<u8>> {
+    levels
+        .split_whitespace()
+        .map(|l| (l != "x").then(|| l.parse().unwrap()))
+        .collect()
+}
+
+fn parse_order(order: &str) -> Vec<usize> {
+    order
+        .split_whitespace()
+        .map(|i| i.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_bidi_conformance() {
+    let resolver = BidiResolver::new();
+
+    let mut levels = Vec::new();
+    let mut order = Vec::new();
+    for line in include_str!("data/BidiTest.txt").lines() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(l) = line.strip_prefix("@Levels:") {
+            levels = parse_levels(l);
+            continue;
+        }
+        if let Some(o) = line.strip_prefix("@Reorder:") {
+            order = parse_order(o);
+            continue;
+        }
+        if line.starts_with('@') {
+            continue;
+        }
+
+        let (classes, bitset) = line.split_once(';').unwrap();
+        // A representative character for each class, avoiding paired brackets
+        let text = classes
+            .split_whitespace()
+            .map(|class| match class {
+                "L" => 'a',
+                "R" => '\u{05D0}',
+                "AL" => '\u{0628}',
+                "EN" => '1',
+                "ES" => '+',
+                "ET" => '$',
+                "AN" => '\u{0660}',
+                "CS" => ',',
+                "NSM" => '\u{0300}',
+                "BN" => '\u{00AD}',
+                "B" => '\u{2029}',
+                "S" => '\t',
+                "WS" => ' ',
+                "ON" => '!',
+                "LRE" => '\u{202A}',
+                "RLE" => '\u{202B}',
+                "PDF" => '\u{202C}',
+                "LRO" => '\u{202D}',
+                "RLO" => '\u{202E}',
+                "LRI" => '\u{2066}',
+                "RLI" => '\u{2067}',
+                "FSI" => '\u{2068}',
+                "PDI" => '\u{2069}',
+                _ => panic!("unknown class {class}"),
+            })
+            .collect::<Vec<_>>();
+        let utf16 = text.iter().map(|&c| c as u16).collect::<Vec<_>>();
+
+        let bitset = bitset.trim().parse::<u8>().unwrap();
+        for (bit, paragraph_level) in [
+            (1, None),
+            (2, Some(BidiLevel::LTR)),
+            (4, Some(BidiLevel::RTL)),
+        ] {
+            if bitset & bit == 0 {
+                continue;
+            }
+            let info = resolver.resolve_utf16(&utf16, paragraph_level);
+            let (actual_levels, actual_order) = levels_and_order(&info);
+            check(&levels, &order, &actual_levels, &actual_order, line);
+        }
+    }
+}
+
+#[test]
+fn test_bidi_character_conformance() {
+    let resolver = BidiResolver::new();
+
+    for line in include_str!("data/BidiCharacterTest.txt").lines() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields = line.split(';').collect::<Vec<_>>();
+        let [code_points, direction, paragraph_level, levels, order] = fields[..] else {
+            panic!("invalid line {line}");
+        };
+        let text = code_points
+            .split_whitespace()
+            .map(|cp| char::from_u32(u32::from_str_radix(cp, 16).unwrap()).unwrap())
+            .collect::<String>();
+        let direction = match direction {
+            "0" => Some(BidiLevel::LTR),
+            "1" => Some(BidiLevel::RTL),
+            _ => None,
+        };
+        let levels = parse_levels(levels);
+        let order = parse_order(order);
+
+        // UTF-8, with levels and order indexed by character
+        let info = resolver.resolve_str(&text, direction);
+        assert_eq!(
+            info.paragraphs()[0].level.number().to_string(),
+            paragraph_level,
+            "{line}"
+        );
+        let (actual_levels, actual_order) = levels_and_order(&info);
+        let starts = text.char_indices().map(|(i, _)| i).collect::<Vec<_>>();
+        check(
+            &levels,
+            &order,
+            &starts.iter().map(|&i| actual_levels[i]).collect::<Vec<_>>(),
+            &actual_order
+                .iter()
+                .filter_map(|i| starts.binary_search(i).ok())
+                .collect::<Vec<_>>(),
+            line,
+        );
+
+        // UTF-16
+        let utf16 = text.encode_utf16().collect::<Vec<_>>();
+        let info = resolver.resolve_utf16(&utf16, direction);
+        let (actual_levels, actual_order) = levels_and_order(&info);
+        let starts = text
+            .chars()
+            .scan(0, |i, c| {
+                *i += c.len_utf16();
+                Some(*i - c.len_utf16())
+            })
+            .collect::<Vec<_>>();
+        check(
+            &levels,
+            &order,
+            &starts.iter().map(|&i| actual_levels[i]).collect::<Vec<_>>(),
+            &actual_order
+                .iter()
+                .filter_map(|i| starts.binary_search(i).ok())
+                .collect::<Vec<_>>(),
+            line,
+        );
+    }
+}
+
+#[test]
+fn test_brackets_across_isolates() {
+    // a ب RLI ) PDI [ 1 ]: the brackets take the direction of the Arabic letter
+    // preceding the isolate, as the Arabic number between them counts as R.
+    let text = "a\u{0628}\u{2067})\u{2069}[1]";
+    let info = BidiResolver::new().resolve_str(text, None);
+    assert_eq!(info.paragraphs()[0].level, BidiLevel::LTR);
+    assert_eq!(
+        text.char_indices()
+            .map(|(i, _)| info.levels()[i].number())
+            .collect::<Vec<_>>(),
+        [0, 1, 1, 1, 1, 1, 2, 1]
+    );
+}
+
+#[test]
+fn test_paragraphs() {
+    let resolver = BidiResolver::new();
+
+    // CR LF is a single paragraph separator, and each paragraph has its own level
+    let info = resolver.resolve_str("\u{05D0}\r\na\u{2029}!", None);
+    assert_eq!(
+        info.paragraphs()
+            .iter()
+            .map(|p| (p.range.clone(), p.level))
+            .collect::<Vec<_>>(),
+        [
+            (0..4, BidiLevel::RTL),
+            (4..8, BidiLevel::LTR),
+            (8..9, BidiLevel::LTR)
+        ]
+    );
+
+    // An explicit level applies to all paragraphs
+    let info = resolver.resolve_str("a\nb", Some(BidiLevel::RTL));
+    assert!(info.paragraphs().iter().all(|p| p.level == BidiLevel::RTL));
+}
+
+#[test]
+fn test_line() {
+    // "abc אבג 123." in an RTL paragraph, followed by trailing whitespace
+    let text = "abc \u{05D0}\u{05D1}\u{05D2} 123.  ";
+    let info = BidiResolver::new().resolve_str(text, Some(BidiLevel::RTL));
+
+    let line = info.line(0..text.len());
+    // Trailing whitespace is reset to the paragraph level
+    assert_eq!(
+        line.levels().iter().map(|l| l.number()).collect::<Vec<_>>(),
+        [2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1]
+    );
+    assert_eq!(
+        line.runs()
+            .iter()
+            .map(|r| (r.range.clone(), r.level.number()))
+            .collect::<Vec<_>>(),
+        [(14..17, 1), (11..14, 2), (3..11, 1), (0..3, 2)]
+    );
+    // The Hebrew letters are reversed, but their UTF-8 code units are not
+    assert_eq!(
+        line.visual_to_logical(),
+        [16, 15, 14, 11, 12, 13, 10, 8, 9, 6, 7, 4, 5, 3, 0, 1, 2]
+    );
+    let logical_to_visual = line.logical_to_visual();
+    for (visual, logical) in line.visual_to_logical().into_iter().enumerate() {
+        assert_eq!(logical_to_visual[logical], visual);
+    }
+
+    // Lines are reordered independently
+    let line = info.line(0..4);
+    assert_eq!(line.visual_to_logical(), [3, 0, 1, 2]);
+}
+
+#[test]
+fn test_utf16() {
+    let resolver = BidiResolver::new();
+
+    // U+10800 CYPRIOT SYLLABLE A is R, an unpaired surrogate is L
+    let text = [0x61, 0xD802, 0xDC00, 0x05D0, 0xDC00];
+    let info = resolver.resolve_utf16(&text, None);
+    assert_eq!(
+        info.levels().iter().map(|l| l.number()).collect::<Vec<_>>(),
+        [0, 1, 1, 1, 0]
+    );
+    assert!(info.has_rtl());
+    assert_eq!(
+        info.line(0..text.len()).visual_to_logical(),
+        [0, 3, 1, 2, 4]
+    );
+}
+
+#[test]
+fn test_explicit_embeddings() {
+    let resolver = BidiResolver::new();
+
+    // a RLO b c PDF d: the override makes b and c right-to-left
+    let info = resolver.resolve_str("a\u{202E}bc\u{202C}d", None);
+    let line = info.line(0..info.levels().len());
+    assert_eq!(
+        line.visual_to_logical()
+            .into_iter()
+            .filter(|&i| !(1..4).contains(&i) && !(6..9).contains(&i))
+            .collect::<Vec<_>>(),
+        [0, 5, 4, 9]
+    );
+
+    // Embeddings deeper than 125 levels overflow, and the letter at level 125 is raised to 126
+    let text = "\u{202B}\u{202A}".repeat(70) + "a";
+    let info = resolver.resolve_str(&text, None);
+    assert_eq!(info.levels().last().unwrap().number(), 126);
+
+    // Explicit paragraph levels are clamped to the maximum depth
+    let info = resolver.resolve_str("a", BidiLevel::new(126));
+    assert_eq!(info.paragraphs()[0].level.number(), 125);
+    assert_eq!(BidiLevel::new(127), None);
+}
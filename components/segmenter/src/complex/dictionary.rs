@@ -6,8 +6,10 @@ use crate::grapheme::*;
 use crate::indices::Utf16Indices;
 use crate::provider::*;
 use crate::scaffold::{Utf16, Utf8};
+use alloc::vec;
+use alloc::vec::Vec;
 use core::str::CharIndices;
-use icu_collections::char16trie::{Char16Trie, TrieResult};
+use icu_collections::char16trie::{Char16Trie, Char16TrieIterator, TrieResult};
 
 /// A trait for dictionary based iterator
 trait DictionaryType {
@@ -174,11 +176,172 @@ impl<'l> DictionarySegmenter<'l> {
     }
 }
 
+/// Maximum length of a dictionary word, in code points.
+pub(crate) const MAX_WORD_LENGTH: usize = 20;
+
+/// Cost of a grapheme cluster that is not part of any dictionary word. This is the largest
+/// cost used by the ICU dictionaries.
+const UNKNOWN_COST: u32 = 255;
+
+/// The score of a segmentation: the number of code points not covered by user words, and the
+/// total cost.
+type Score = (usize, u32);
+
+fn relax(best: &mut [Option<(Score, usize)>], end: usize, score: Score, start: usize) {
+    if let Some(b) = best.get_mut(end) {
+        if b.is_none_or(|(s, _)| score < s) {
+            *b = Some((score, start));
+        }
+    }
+}
+
+/// A dictionary segmenter that finds the lowest-cost segmentation, using both the compiled
+/// dictionary and a user dictionary.
+///
+/// Dictionary values are word costs (lower is more likely). Segmentations are compared first
+/// by the amount of text not covered by user words, so that user words always take precedence
+/// over the compiled dictionary, and then by their total cost.
+pub(super) struct WeightedDictionarySegmenter<'l> {
+    dict: Option<&'l UCharDictionaryBreakData<'l>>,
+    user: &'l UCharDictionaryBreakData<'l>,
+    grapheme: GraphemeClusterSegmenterBorrowed<'l>,
+}
+
+impl<'l> WeightedDictionarySegmenter<'l> {
+    pub(super) fn new(
+        dict: Option<&'l UCharDictionaryBreakData<'l>>,
+        user: &'l UCharDictionaryBreakData<'l>,
+        grapheme: GraphemeClusterSegmenterBorrowed<'l>,
+    ) -> Self {
+        Self {
+            dict,
+            user,
+            grapheme,
+        }
+    }
+
+    /// Returns the break opportunities of an `str` (a UTF-8 string), excluding 0.
+    pub(super) fn segment_str(&self, input: &str) -> Vec<usize> {
+        self.segment::<char>(
+            input.char_indices(),
+            self.grapheme.segment_str(input),
+            input.len(),
+        )
+    }
+
+    /// Returns the break opportunities of a UTF-16 string, excluding 0.
+    pub(super) fn segment_utf16(&self, input: &[u16]) -> Vec<usize> {
+        self.segment::<u32>(
+            Utf16Indices::new(input),
+            self.grapheme.segment_utf16(input),
+            input.len(),
+        )
+    }
+
+    fn segment<Y: DictionaryType>(
+        &self,
+        iter: Y::IterAttr<'_>,
+        graphemes: impl Iterator<Item = usize>,
+        len: usize,
+    ) -> Vec<usize> {
+        let chars: Vec<(usize, Y::CharType)> = iter.collect();
+        let offset = |i: usize| chars.get(i).map(|&(offset, _)| offset).unwrap_or(len);
+
+        // Words have to match grapheme cluster boundaries.
+        let mut is_boundary = vec![false; chars.len() + 1];
+        let mut i = 0;
+        for boundary in graphemes {
+            while offset(i) < boundary {
+                i += 1;
+            }
+            if let Some(b) = is_boundary.get_mut(i) {
+                *b = offset(i) == boundary;
+            }
+        }
+
+        let tries = [(&*self.user.trie_data, true)]
+            .into_iter()
+            .chain(self.dict.map(|dict| (&*dict.trie_data, false)));
+
+        // For each position, the best score of a segmentation up to it and the start of its
+        // last segment.
+        let mut best: Vec<Option<(Score, usize)>> = vec![None; chars.len() + 1];
+        if let Some(b) = best.first_mut() {
+            *b = Some(((0, 0), 0));
+        }
+        for start in 0..chars.len() {
+            let Some(Some(((uncovered, cost), _))) = best.get(start).copied() else {
+                continue;
+            };
+            if is_boundary.get(start) != Some(&true) {
+                continue;
+            }
+            for (trie, is_user) in tries.clone() {
+                let mut trie_iter = Char16TrieIterator::new(trie);
+                for (end, &(_, c)) in chars
+                    .iter()
+                    .enumerate()
+                    .skip(start)
+                    .take(MAX_WORD_LENGTH)
+                    .map(|(i, c)| (i + 1, c))
+                {
+                    let (value, is_final) = match trie_iter.next(Y::to_char(c)) {
+                        TrieResult::NoMatch => break,
+                        TrieResult::NoValue => continue,
+                        TrieResult::Intermediate(value) => (value, false),
+                        TrieResult::FinalValue(value) => (value, true),
+                    };
+                    if is_boundary.get(end) == Some(&true) {
+                        let uncovered = if is_user {
+                            uncovered
+                        } else {
+                            uncovered + end - start
+                        };
+                        relax(
+                            &mut best,
+                            end,
+                            (uncovered, cost + value.unsigned_abs()),
+                            start,
+                        );
+                    }
+                    if is_final {
+                        break;
+                    }
+                }
+            }
+            // Fall back to a single grapheme cluster.
+            let end = (start + 1..=chars.len())
+                .find(|&i| is_boundary.get(i) == Some(&true))
+                .unwrap_or(chars.len());
+            relax(
+                &mut best,
+                end,
+                (uncovered + end - start, cost + UNKNOWN_COST),
+                start,
+            );
+        }
+
+        let mut result = Vec::new();
+        let mut end = chars.len();
+        while end > 0 {
+            result.push(offset(end));
+            end = match best.get(end) {
+                Some(Some((_, start))) => *start,
+                _ => 0,
+            };
+        }
+        result.reverse();
+        result
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "serde")]
 mod tests {
     use super::*;
-    use crate::{GraphemeClusterSegmenter, LineSegmenter, WordSegmenter};
+    use crate::{
+        GraphemeClusterSegmenter, LineSegmenter, UserDictionary, UserDictionaryError, WordSegmenter,
+    };
     use icu_provider::prelude::*;
 
     #[test]
@@ -241,6 +404,53 @@ mod tests {
         assert_eq!(result, vec![0, 8]);
     }
 
+    #[test]
+    fn user_dictionary_test() {
+        let mut user = UserDictionary::new();
+        user.insert("東京", 100).unwrap();
+        user.insert("京都", 200).unwrap();
+        user.insert("東京都庁", 10).unwrap();
+
+        // Words that could never match are rejected.
+        assert_eq!(user.insert("", 0), Err(UserDictionaryError::EmptyWord));
+        assert_eq!(user.insert(&"東".repeat(MAX_WORD_LENGTH), 0), Ok(()),);
+        assert_eq!(
+            user.insert(&"東".repeat(MAX_WORD_LENGTH + 1), 0),
+            Err(UserDictionaryError::WordTooLong {
+                max: MAX_WORD_LENGTH
+            })
+        );
+
+        let user = user.build();
+        let seg = WeightedDictionarySegmenter::new(None, &user, GraphemeClusterSegmenter::new());
+
+        // The longest coverage by user words wins, regardless of weight.
+        let s = "東京都庁舎";
+        assert_eq!(seg.segment_str(s), [12, 15]);
+        let s_utf16: Vec<u16> = s.encode_utf16().collect();
+        assert_eq!(seg.segment_utf16(&s_utf16), [4, 5]);
+
+        // Between overlapping words, the higher weight wins.
+        assert_eq!(seg.segment_str("東京都"), [3, 9]);
+
+        // Word boundaries have to match grapheme cluster boundaries.
+        assert_eq!(seg.segment_str("東京\u{3099}"), [3, 9]);
+
+        // With the compiled dictionary, user words still take precedence.
+        let segmenter = WordSegmenter::new_dictionary(Default::default())
+            .static_to_owned()
+            .with_user_dictionary({
+                let mut user = UserDictionary::new();
+                user.insert("龟山岛龟", 0).unwrap();
+                user
+            });
+        let result: Vec<usize> = segmenter
+            .as_borrowed()
+            .segment_str("龟山岛龟山岛")
+            .collect();
+        assert_eq!(result[..2], [0, 12]);
+    }
+
     #[test]
     fn khmer_dictionary_test() {
         let segmenter = LineSegmenter::new_dictionary(Default::default());
@@ -3,14 +3,18 @@
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
 use crate::provider::*;
+use crate::UserDictionary;
 use crate::{GraphemeClusterSegmenter, GraphemeClusterSegmenterBorrowed};
 use alloc::vec::Vec;
 use icu_provider::prelude::*;
 
 mod dictionary;
+pub(crate) use dictionary::MAX_WORD_LENGTH;
 use dictionary::*;
 mod language;
 use language::*;
+mod phrase;
+pub(crate) use phrase::is_phrase_char;
 #[cfg(feature = "lstm")]
 mod lstm;
 #[cfg(feature = "lstm")]
@@ -55,6 +59,8 @@ pub(crate) struct ComplexPayloads {
     lo: Option<DictOrLstm>,
     th: Option<DictOrLstm>,
     ja: Option<DataPayload<UCharDictionaryBreakDataV1>>,
+    user: Option<DataPayload<UCharDictionaryBreakDataV1>>,
+    phrase: Option<DataPayload<SegmenterPhraseJapaneseV1>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -65,6 +71,8 @@ pub(crate) struct ComplexPayloadsBorrowed<'data> {
     lo: Option<DictOrLstmBorrowed<'data>>,
     th: Option<DictOrLstmBorrowed<'data>>,
     ja: Option<&'data UCharDictionaryBreakData<'data>>,
+    user: Option<&'data UCharDictionaryBreakData<'data>>,
+    phrase: Option<&'data PhraseBreakData<'data>>,
 }
 
 #[cfg(feature = "lstm")]
@@ -113,6 +121,12 @@ impl<'data> ComplexPayloadsBorrowed<'data> {
         let mut result = Vec::new();
         let mut offset = 0;
         for (slice, lang) in LanguageIterator::new(input) {
+            if let (Language::ChineseOrJapanese, Some(user)) = (lang, self.user) {
+                let seg = WeightedDictionarySegmenter::new(self.ja, user, self.grapheme);
+                result.extend(seg.segment_str(slice).into_iter().map(|n| offset + n));
+                offset += slice.len();
+                continue;
+            }
             match self.select(lang) {
                 Some(DictOrLstmBorrowed::Dict(dict)) => {
                     let seg = DictionarySegmenter::new(dict, self.grapheme);
@@ -136,6 +150,12 @@ impl<'data> ComplexPayloadsBorrowed<'data> {
         let mut result = Vec::new();
         let mut offset = 0;
         for (slice, lang) in LanguageIteratorUtf16::new(input) {
+            if let (Language::ChineseOrJapanese, Some(user)) = (lang, self.user) {
+                let seg = WeightedDictionarySegmenter::new(self.ja, user, self.grapheme);
+                result.extend(seg.segment_utf16(slice).into_iter().map(|n| offset + n));
+                offset += slice.len();
+                continue;
+            }
             match self.select(lang) {
                 Some(DictOrLstmBorrowed::Dict(dict)) => {
                     let seg = DictionarySegmenter::new(dict, self.grapheme);
@@ -154,6 +174,35 @@ impl<'data> ComplexPayloadsBorrowed<'data> {
         }
         result
     }
+
+    /// Whether dictionary and phrase data for phrase-based breaking of Japanese is available.
+    pub(crate) fn has_phrase_data(&self) -> bool {
+        self.ja.is_some() && self.phrase.is_some()
+    }
+
+    /// Return UTF-8 phrase boundaries of a run of Chinese or Japanese text, including the end
+    /// of the run.
+    pub(crate) fn phrase_segment_str(&self, input: &str) -> Vec<usize> {
+        match (self.ja, self.phrase) {
+            (Some(dict), Some(phrase)) => {
+                let seg = DictionarySegmenter::new(dict, self.grapheme);
+                phrase.phrase_breaks_str(input, seg.segment_str(input))
+            }
+            _ => alloc::vec![input.len()],
+        }
+    }
+
+    /// Return UTF-16 phrase boundaries of a run of Chinese or Japanese text, including the end
+    /// of the run.
+    pub(crate) fn phrase_segment_utf16(&self, input: &[u16]) -> Vec<usize> {
+        match (self.ja, self.phrase) {
+            (Some(dict), Some(phrase)) => {
+                let seg = DictionarySegmenter::new(dict, self.grapheme);
+                phrase.phrase_breaks_utf16(input, seg.segment_utf16(input))
+            }
+            _ => alloc::vec![input.len()],
+        }
+    }
 }
 impl ComplexPayloadsBorrowed<'static> {
     #[cfg(feature = "lstm")]
@@ -176,6 +225,8 @@ impl ComplexPayloadsBorrowed<'static> {
                 .unwrap()
                 .map(DictOrLstmBorrowed::Lstm),
             ja: None,
+            user: None,
+            phrase: None,
         }
     }
     #[cfg(feature = "auto")]
@@ -219,9 +270,21 @@ impl ComplexPayloadsBorrowed<'static> {
             .map(DictOrLstmBorrowed::Dict),
             ja: try_load_static::<SegmenterDictionaryAutoV1, _>(&crate::provider::Baked, CJ_DICT)
                 .unwrap(),
+            user: None,
+            phrase: None,
         }
     }
 
+    #[cfg(feature = "compiled_data")]
+    #[expect(clippy::unwrap_used)]
+    pub(crate) fn with_phrase_data(mut self) -> Self {
+        // try_load is infallible if the provider only returns `MissingLocale`.
+        self.ja = try_load_static::<SegmenterDictionaryAutoV1, _>(&crate::provider::Baked, CJ_DICT)
+            .unwrap();
+        self.phrase = Some(crate::provider::Baked::SINGLETON_SEGMENTER_PHRASE_JAPANESE_V1);
+        self
+    }
+
     #[cfg(feature = "compiled_data")]
     pub(crate) fn new_southeast_asian() -> Self {
         #[expect(clippy::unwrap_used)]
@@ -253,6 +316,8 @@ impl ComplexPayloadsBorrowed<'static> {
             .unwrap()
             .map(DictOrLstmBorrowed::Dict),
             ja: None,
+            user: None,
+            phrase: None,
         }
     }
 
@@ -264,6 +329,8 @@ impl ComplexPayloadsBorrowed<'static> {
             lo: self.lo.map(fromstatic_dictor),
             th: self.th.map(fromstatic_dictor),
             ja: self.ja.map(DataPayload::from_static_ref),
+            user: self.user.map(DataPayload::from_static_ref),
+            phrase: self.phrase.map(DataPayload::from_static_ref),
         }
     }
 }
@@ -277,6 +344,8 @@ impl ComplexPayloads {
             lo: self.lo.as_ref().map(borrow_dictor),
             th: self.th.as_ref().map(borrow_dictor),
             ja: self.ja.as_ref().map(|p| p.get()),
+            user: self.user.as_ref().map(|p| p.get()),
+            phrase: self.phrase.as_ref().map(|p| p.get()),
         }
     }
 
@@ -302,6 +371,8 @@ impl ComplexPayloads {
                 .map(DataPayload::cast)
                 .map(DictOrLstm::Lstm),
             ja: None,
+            user: None,
+            phrase: None,
         })
     }
 
@@ -327,6 +398,8 @@ impl ComplexPayloads {
                 .map(DataPayload::cast)
                 .map(DictOrLstm::Dict),
             ja: try_load::<SegmenterDictionaryAutoV1, D>(provider, CJ_DICT)?.map(DataPayload::cast),
+            user: None,
+            phrase: None,
         })
     }
 
@@ -353,9 +426,41 @@ impl ComplexPayloads {
                 .map(DataPayload::cast)
                 .map(DictOrLstm::Lstm),
             ja: try_load::<SegmenterDictionaryAutoV1, D>(provider, CJ_DICT)?.map(DataPayload::cast),
+            user: None,
+            phrase: None,
         })
     }
 
+    /// Loads the dictionary and phrase data for phrase-based breaking of Japanese.
+    ///
+    /// Unlike the other dictionaries, these are required.
+    pub(crate) fn try_load_phrase_data<D>(&mut self, provider: &D) -> Result<(), DataError>
+    where
+        D: DataProvider<SegmenterDictionaryAutoV1>
+            + DataProvider<SegmenterPhraseJapaneseV1>
+            + ?Sized,
+    {
+        self.ja = Some(
+            try_load::<SegmenterDictionaryAutoV1, D>(provider, CJ_DICT)?
+                .ok_or_else(|| {
+                    DataErrorKind::IdentifierNotFound.with_req(
+                        SegmenterDictionaryAutoV1::INFO,
+                        DataRequest {
+                            id: DataIdentifierBorrowed::for_marker_attributes(CJ_DICT),
+                            ..Default::default()
+                        },
+                    )
+                })?
+                .cast(),
+        );
+        self.phrase = Some(provider.load(Default::default())?.payload);
+        Ok(())
+    }
+
+    pub(crate) fn set_user_dictionary(&mut self, dictionary: UserDictionary) {
+        self.user = (!dictionary.is_empty()).then(|| DataPayload::from_owned(dictionary.build()));
+    }
+
     pub(crate) fn try_new_southeast_asian<D>(provider: &D) -> Result<Self, DataError>
     where
         D: DataProvider<SegmenterDictionaryExtendedV1>
@@ -377,6 +482,8 @@ impl ComplexPayloads {
                 .map(DataPayload::cast)
                 .map(DictOrLstm::Dict),
             ja: None,
+            user: None,
+            phrase: None,
         })
     }
 }
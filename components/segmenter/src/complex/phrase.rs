@@ -0,0 +1,146 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+//! Phrase (bunsetsu) grouping for Japanese, used by `word-break: auto-phrase`.
+//!
+//! The words found by the dictionary are grouped into phrases with [`PhraseBreakData`]:
+//! content words start new phrases, while function words such as particles and auxiliary
+//! verbs attach to the preceding word.
+
+use super::language::*;
+use crate::provider::PhraseBreakData;
+use alloc::vec::Vec;
+use core::ops::Range;
+use zerovec::VarZeroSlice;
+
+/// Whether a code point is part of the text that phrase breaking applies to.
+pub(crate) fn is_phrase_char(codepoint: u32) -> bool {
+    get_language(codepoint) == Language::ChineseOrJapanese
+}
+
+/// Whether a sorted list of words contains the word consisting of the given characters.
+fn contains_word(words: &VarZeroSlice<str>, word: impl Iterator<Item = char> + Clone) -> bool {
+    words
+        .binary_search_by(|w| w.chars().cmp(word.clone()))
+        .is_ok()
+}
+
+impl PhraseBreakData<'_> {
+    fn is_prefix(&self, word: impl Iterator<Item = char> + Clone) -> bool {
+        contains_word(&self.prefixes, word)
+    }
+
+    fn is_attached(&self, word: impl Iterator<Item = char> + Clone) -> bool {
+        let mut chars = word.clone();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if self.attached_characters.contains(c) {
+                return true;
+            }
+        }
+        contains_word(&self.attached_words, word)
+    }
+
+    /// Whether the boundary between two adjacent words is also a phrase boundary.
+    fn is_phrase_boundary<I: Iterator<Item = char> + Clone>(&self, left: I, right: I) -> bool {
+        !self.is_prefix(left) && (self.is_prefix(right.clone()) || !self.is_attached(right))
+    }
+
+    /// Filters word boundaries down to phrase boundaries, given a function that returns the
+    /// characters of the word in a range of the input.
+    ///
+    /// The end of the input is always retained.
+    fn phrase_breaks<I: Iterator<Item = char> + Clone>(
+        &self,
+        words: impl Iterator<Item = usize>,
+        word: impl Fn(Range<usize>) -> Option<I>,
+    ) -> Vec<usize> {
+        let mut words = words.peekable();
+        let mut result = Vec::new();
+        let mut start = 0;
+        while let Some(end) = words.next() {
+            let is_boundary = match words.peek() {
+                Some(&next) => match (word(start..end), word(end..next)) {
+                    (Some(left), Some(right)) => self.is_phrase_boundary(left, right),
+                    _ => true,
+                },
+                None => true,
+            };
+            if is_boundary {
+                result.push(end);
+            }
+            start = end;
+        }
+        result
+    }
+
+    /// Filters the word boundaries of a UTF-8 string down to phrase boundaries.
+    ///
+    /// The end of the string is always retained.
+    pub(super) fn phrase_breaks_str(
+        &self,
+        input: &str,
+        words: impl Iterator<Item = usize>,
+    ) -> Vec<usize> {
+        self.phrase_breaks(words, |range| input.get(range).map(str::chars))
+    }
+
+    /// Filters the word boundaries of a UTF-16 string down to phrase boundaries.
+    ///
+    /// The end of the string is always retained.
+    pub(super) fn phrase_breaks_utf16(
+        &self,
+        input: &[u16],
+        words: impl Iterator<Item = usize>,
+    ) -> Vec<usize> {
+        self.phrase_breaks(words, |range| {
+            input.get(range).map(|word| {
+                char::decode_utf16(word.iter().copied())
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icu_collections::codepointinvlist::CodePointInversionList;
+    use zerovec::VarZeroVec;
+
+    #[test]
+    fn phrase_grouping() {
+        let data = PhraseBreakData {
+            attached_words: VarZeroVec::from(&["ます", "ません"]),
+            attached_characters: CodePointInversionList::try_from_u32_inversion_list_slice(&[
+                0x3041, 0x3097,
+            ])
+            .unwrap(),
+            prefixes: VarZeroVec::from(&["お"]),
+        };
+
+        // 私|は|東京|に|行き|ます
+        let s = "私は東京に行きます";
+        let words = [3, 6, 12, 15, 21, 27];
+        assert_eq!(data.phrase_breaks_str(s, words.into_iter()), [6, 15, 27]);
+
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let words = [1, 2, 4, 5, 7, 9];
+        assert_eq!(
+            data.phrase_breaks_utf16(&utf16, words.into_iter()),
+            [2, 5, 9]
+        );
+
+        // Prefixes start a phrase and attach to the following word:
+        // 私|は|お|茶|を|飲み|ません
+        let s = "私はお茶を飲みません";
+        let words = [3, 6, 9, 12, 15, 21, 30];
+        assert_eq!(data.phrase_breaks_str(s, words.into_iter()), [6, 15, 30]);
+
+        // Words of several hiragana only attach if they are listed:
+        // 東京|に|いる|ます
+        let s = "東京にいるます";
+        let words = [6, 9, 15, 21];
+        assert_eq!(data.phrase_breaks_str(s, words.into_iter()), [9, 21]);
+    }
+}
@@ -133,6 +133,8 @@ mod grapheme;
 mod line;
 /// [`SentenceSegmenter`] and its related iterators, borrowed types, and options.
 mod sentence;
+/// [`UserDictionary`] for custom words in Chinese and Japanese segmentation.
+mod user_dictionary;
 /// [`WordSegmenter`] and its related iterators, borrowed types, and options.
 mod word;
 
@@ -145,6 +147,8 @@ pub use crate::line::LineSegmenter;
 pub use crate::line::LineSegmenterBorrowed;
 pub use crate::sentence::SentenceSegmenter;
 pub use crate::sentence::SentenceSegmenterBorrowed;
+pub use crate::user_dictionary::UserDictionary;
+pub use crate::user_dictionary::UserDictionaryError;
 pub use crate::word::WordSegmenter;
 pub use crate::word::WordSegmenterBorrowed;
 
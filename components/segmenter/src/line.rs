@@ -183,6 +183,21 @@ pub enum LineBreakWordOption {
     /// Breaking is forbidden within "word".
     /// <https://drafts.csswg.org/css-text-3/#valdef-word-break-keep-all>
     KeepAll,
+
+    /// Breaking within Japanese text is only allowed between phrases (bunsetsu), so that a
+    /// content word and its attached particles stay on the same line. Other text breaks as
+    /// with [`LineBreakWordOption::Normal`].
+    ///
+    /// The phrases are determined with the Chinese/Japanese dictionary and
+    /// [`SegmenterPhraseJapaneseV1`](crate::provider::SegmenterPhraseJapaneseV1) data. Phrase
+    /// boundaries are still subject to the [`LineBreakStrictness`] rules.
+    ///
+    /// This only applies if the content locale is Japanese (`ja`); otherwise, this behaves like
+    /// [`LineBreakWordOption::Normal`]. The compiled data constructors load the required data,
+    /// but with a data provider, only [`LineSegmenter::try_new_phrase_unstable`] (and its
+    /// buffer provider variant) do; the other constructors return an error.
+    /// <https://drafts.csswg.org/css-text-4/#valdef-word-break-auto-phrase>
+    AutoPhrase,
 }
 
 /// Options to tailor line-breaking behavior.
@@ -213,23 +228,36 @@ struct ResolvedLineBreakOptions {
     strictness: LineBreakStrictness,
     word_option: LineBreakWordOption,
     ja_zh: bool,
+    auto_phrase: bool,
 }
 
 impl From<LineBreakOptions<'_>> for ResolvedLineBreakOptions {
     fn from(options: LineBreakOptions<'_>) -> Self {
-        let ja_zh = if let Some(content_locale) = options.content_locale.as_ref() {
-            content_locale.language == language!("ja") || content_locale.language == language!("zh")
+        let (ja_zh, ja) = if let Some(content_locale) = options.content_locale.as_ref() {
+            (
+                content_locale.language == language!("ja")
+                    || content_locale.language == language!("zh"),
+                content_locale.language == language!("ja"),
+            )
         } else {
-            false
+            (false, false)
         };
+        let word_option = options.word_option.unwrap_or_default();
         Self {
             strictness: options.strictness.unwrap_or_default(),
-            word_option: options.word_option.unwrap_or_default(),
+            word_option,
             ja_zh,
+            auto_phrase: ja && word_option == LineBreakWordOption::AutoPhrase,
         }
     }
 }
 
+/// The error returned by data provider constructors that don't load the data needed for
+/// [`LineBreakWordOption::AutoPhrase`].
+const AUTO_PHRASE_UNSUPPORTED: DataError =
+    DataError::custom("AutoPhrase requires a phrase constructor")
+        .with_marker(SegmenterPhraseJapaneseV1::INFO);
+
 /// Supports loading line break data, and creating line break iterators for different string
 /// encodings.
 ///
@@ -426,10 +454,15 @@ impl LineSegmenter {
     #[cfg(feature = "lstm")]
     #[cfg(feature = "compiled_data")]
     pub fn new_lstm(options: LineBreakOptions) -> LineSegmenterBorrowed<'static> {
+        let options: ResolvedLineBreakOptions = options.into();
+        let mut complex = ComplexPayloadsBorrowed::new_lstm();
+        if options.auto_phrase {
+            complex = complex.with_phrase_data();
+        }
         LineSegmenterBorrowed {
-            options: options.into(),
+            options,
             data: crate::provider::Baked::SINGLETON_SEGMENTER_BREAK_LINE_V1,
-            complex: ComplexPayloadsBorrowed::new_lstm(),
+            complex,
         }
     }
 
@@ -456,8 +489,12 @@ impl LineSegmenter {
             + DataProvider<SegmenterBreakGraphemeClusterV1>
             + ?Sized,
     {
+        let options: ResolvedLineBreakOptions = options.into();
+        if options.auto_phrase {
+            return Err(AUTO_PHRASE_UNSUPPORTED);
+        }
         Ok(Self {
-            options: options.into(),
+            options,
             payload: provider.load(Default::default())?.payload,
             complex: ComplexPayloads::try_new_lstm(provider)?,
         })
@@ -476,16 +513,21 @@ impl LineSegmenter {
     /// [📚 Help choosing a constructor](icu_provider::constructors)
     #[cfg(feature = "compiled_data")]
     pub fn new_dictionary(options: LineBreakOptions) -> LineSegmenterBorrowed<'static> {
+        let options: ResolvedLineBreakOptions = options.into();
+        // Line segmenter doesn't need to load CJ dictionary because UAX 14 rules handles CJK
+        // characters [1], unless breaking Japanese by phrases. Southeast Asian languages however
+        // require complex context analysis [2].
+        //
+        // [1]: https://www.unicode.org/reports/tr14/#ID
+        // [2]: https://www.unicode.org/reports/tr14/#SA
+        let mut complex = ComplexPayloadsBorrowed::new_southeast_asian();
+        if options.auto_phrase {
+            complex = complex.with_phrase_data();
+        }
         LineSegmenterBorrowed {
-            options: options.into(),
+            options,
             data: crate::provider::Baked::SINGLETON_SEGMENTER_BREAK_LINE_V1,
-            // Line segmenter doesn't need to load CJ dictionary because UAX 14 rules handles CJK
-            // characters [1]. Southeast Asian languages however require complex context analysis
-            // [2].
-            //
-            // [1]: https://www.unicode.org/reports/tr14/#ID
-            // [2]: https://www.unicode.org/reports/tr14/#SA
-            complex: ComplexPayloadsBorrowed::new_southeast_asian(),
+            complex,
         }
     }
 
@@ -510,8 +552,12 @@ impl LineSegmenter {
             + DataProvider<SegmenterBreakGraphemeClusterV1>
             + ?Sized,
     {
+        let options: ResolvedLineBreakOptions = options.into();
+        if options.auto_phrase {
+            return Err(AUTO_PHRASE_UNSUPPORTED);
+        }
         Ok(Self {
-            options: options.into(),
+            options,
             payload: provider.load(Default::default())?.payload,
             // Line segmenter doesn't need to load CJ dictionary because UAX 14 rules handles CJK
             // characters [1]. Southeast Asian languages however require complex context analysis
@@ -523,6 +569,69 @@ impl LineSegmenter {
         })
     }
 
+    /// Constructs a [`LineSegmenter`] with an invariant locale, custom [`LineBreakOptions`],
+    /// compiled dictionary data for complex scripts (Khmer, Lao, Myanmar, and Thai), and
+    /// compiled dictionary and phrase data for breaking Japanese text by phrases.
+    ///
+    /// With compiled data, this behaves like [`Self::new_dictionary`]. With a data provider,
+    /// this is the only constructor that loads the data needed by
+    /// [`LineBreakWordOption::AutoPhrase`]; the other constructors return an error for that
+    /// option.
+    ///
+    /// See also [`Self::new_dictionary`].
+    ///
+    /// ✨ *Enabled with the `compiled_data` Cargo feature.*
+    ///
+    /// [📚 Help choosing a constructor](icu_provider::constructors)
+    #[cfg(feature = "compiled_data")]
+    pub fn new_phrase(options: LineBreakOptions) -> LineSegmenterBorrowed<'static> {
+        let options: ResolvedLineBreakOptions = options.into();
+        let mut complex = ComplexPayloadsBorrowed::new_southeast_asian();
+        if options.auto_phrase {
+            complex = complex.with_phrase_data();
+        }
+        LineSegmenterBorrowed {
+            options,
+            data: crate::provider::Baked::SINGLETON_SEGMENTER_BREAK_LINE_V1,
+            complex,
+        }
+    }
+
+    icu_provider::gen_buffer_data_constructors!(
+        (options: LineBreakOptions) -> error: DataError,
+        functions: [
+            new_phrase: skip,
+            try_new_phrase_with_buffer_provider,
+            try_new_phrase_unstable,
+            Self,
+        ]
+    );
+
+    #[doc = icu_provider::gen_buffer_unstable_docs!(UNSTABLE, Self::new_phrase)]
+    pub fn try_new_phrase_unstable<D>(
+        provider: &D,
+        options: LineBreakOptions,
+    ) -> Result<Self, DataError>
+    where
+        D: DataProvider<SegmenterBreakLineV1>
+            + DataProvider<SegmenterDictionaryExtendedV1>
+            + DataProvider<SegmenterDictionaryAutoV1>
+            + DataProvider<SegmenterPhraseJapaneseV1>
+            + DataProvider<SegmenterBreakGraphemeClusterV1>
+            + ?Sized,
+    {
+        let options: ResolvedLineBreakOptions = options.into();
+        let mut complex = ComplexPayloads::try_new_southeast_asian(provider)?;
+        if options.auto_phrase {
+            complex.try_load_phrase_data(provider)?;
+        }
+        Ok(Self {
+            options,
+            payload: provider.load(Default::default())?.payload,
+            complex,
+        })
+    }
+
     /// Constructs a borrowed version of this type for more efficient querying.
     ///
     /// Most useful methods for segmentation are on this type.
@@ -545,6 +654,7 @@ impl<'data> LineSegmenterBorrowed<'data> {
             len: input.len(),
             current_pos_data: None,
             result_cache: Vec::new(),
+            phrase_cache: Vec::new(),
             data: self.data,
             options: self.options,
             complex: self.complex,
@@ -564,6 +674,7 @@ impl<'data> LineSegmenterBorrowed<'data> {
             len: input.len(),
             current_pos_data: None,
             result_cache: Vec::new(),
+            phrase_cache: Vec::new(),
             data: self.data,
             options: self.options,
             complex: self.complex,
@@ -578,6 +689,7 @@ impl<'data> LineSegmenterBorrowed<'data> {
             len: input.len(),
             current_pos_data: None,
             result_cache: Vec::new(),
+            phrase_cache: Vec::new(),
             data: self.data,
             options: self.options,
             complex: self.complex,
@@ -593,6 +705,7 @@ impl<'data> LineSegmenterBorrowed<'data> {
             len: input.len(),
             current_pos_data: None,
             result_cache: Vec::new(),
+            phrase_cache: Vec::new(),
             data: self.data,
             options: self.options,
             complex: self.complex,
@@ -743,6 +856,12 @@ pub trait LineBreakType: crate::private::Sealed + Sized + RuleBreakType {
         iterator: &mut LineBreakIterator<'_, '_, Self>,
         left_codepoint: Self::CharType,
     ) -> Option<usize>;
+
+    #[doc(hidden)]
+    fn line_segment_phrases(
+        iterator: &LineBreakIterator<'_, '_, Self>,
+        left_codepoint: Self::CharType,
+    ) -> Vec<usize>;
 }
 
 /// Implements the [`Iterator`] trait over the line break opportunities of the given string.
@@ -763,6 +882,9 @@ pub struct LineBreakIterator<'data, 's, Y: LineBreakType> {
     len: usize,
     current_pos_data: Option<(usize, Y::CharType)>,
     result_cache: Vec<usize>,
+    /// Phrase boundaries of the current run of Chinese or Japanese text when breaking
+    /// by phrases, ending with the end of the run.
+    phrase_cache: Vec<usize>,
     data: &'data RuleBreakData<'data>,
     options: ResolvedLineBreakOptions,
     complex: ComplexPayloadsBorrowed<'data>,
@@ -849,6 +971,17 @@ impl<Y: LineBreakType> Iterator for LineBreakIterator<'_, '_, Y> {
                 _ => (),
             }
 
+            // Within Japanese text, only phrase boundaries are break opportunities. They are
+            // still subject to the rules below, so that e.g. small kana don't start a line.
+            if self.options.auto_phrase
+                && self.complex.has_phrase_data()
+                && is_phrase_char(left_codepoint.into())
+                && is_phrase_char(right_codepoint.into())
+                && !self.is_phrase_break(left_codepoint)
+            {
+                continue;
+            }
+
             // CSS line-break property handling
             match self.options.strictness {
                 LineBreakStrictness::Normal => {
@@ -1062,6 +1195,19 @@ impl<Y: LineBreakType> LineBreakIterator<'_, '_, Y> {
             .map(|c| self.get_linebreak_property(c))
     }
 
+    /// Whether the current position is a phrase boundary, given that it lies within a run of
+    /// Chinese or Japanese text starting at or before `left_codepoint`.
+    fn is_phrase_break(&mut self, left_codepoint: Y::CharType) -> bool {
+        let Some(current) = self.get_current_position() else {
+            return false;
+        };
+        if self.phrase_cache.last().is_none_or(|&end| end <= current) {
+            // Entered a new run
+            self.phrase_cache = Y::line_segment_phrases(self, left_codepoint);
+        }
+        self.phrase_cache.binary_search(&current).is_ok()
+    }
+
     fn is_break_by_normal(&self, codepoint: Y::CharType) -> bool {
         match codepoint.into() {
             0x301C | 0x30A0 => self.options.ja_zh,
@@ -1090,6 +1236,13 @@ impl LineBreakType for Utf8 {
     ) -> Option<usize> {
         line_handle_complex_language_utf8(iter, left_codepoint)
     }
+
+    fn line_segment_phrases(
+        iter: &LineBreakIterator<'_, '_, Self>,
+        left_codepoint: char,
+    ) -> Vec<usize> {
+        line_segment_phrases_utf8(iter, left_codepoint)
+    }
 }
 
 impl LineBreakType for PotentiallyIllFormedUtf8 {
@@ -1112,6 +1265,13 @@ impl LineBreakType for PotentiallyIllFormedUtf8 {
     ) -> Option<usize> {
         line_handle_complex_language_utf8(iter, left_codepoint)
     }
+
+    fn line_segment_phrases(
+        iter: &LineBreakIterator<'_, '_, Self>,
+        left_codepoint: char,
+    ) -> Vec<usize> {
+        line_segment_phrases_utf8(iter, left_codepoint)
+    }
 }
 /// line_handle_complex_language impl for UTF8 iterators
 fn line_handle_complex_language_utf8<T>(
@@ -1167,6 +1327,32 @@ where
     }
 }
 
+/// line_segment_phrases impl for UTF8 iterators
+fn line_segment_phrases_utf8<T>(
+    iter: &LineBreakIterator<'_, '_, T>,
+    left_codepoint: char,
+) -> Vec<usize>
+where
+    T: LineBreakType<CharType = char>,
+{
+    let Some((start, _)) = iter.current_pos_data else {
+        return Vec::new();
+    };
+    let start = start - left_codepoint.len_utf8();
+    let mut s = String::new();
+    s.push(left_codepoint);
+    s.extend(
+        iter.current_pos_data
+            .into_iter()
+            .chain(iter.iter.clone())
+            .map(|(_, c)| c)
+            .take_while(|&c| is_phrase_char(c as u32)),
+    );
+    let mut breaks = iter.complex.phrase_segment_str(&s);
+    breaks.iter_mut().for_each(|b| *b += start);
+    breaks
+}
+
 impl LineBreakType for Latin1 {
     fn get_linebreak_property_with_rule(iterator: &LineBreakIterator<Self>, c: u8) -> u8 {
         // No CJ on Latin1
@@ -1185,6 +1371,11 @@ impl LineBreakType for Latin1 {
     ) -> Option<usize> {
         unreachable!()
     }
+
+    fn line_segment_phrases(_: &LineBreakIterator<Self>, _: Self::CharType) -> Vec<usize> {
+        // No CJK on Latin1
+        Vec::new()
+    }
 }
 
 impl LineBreakType for Utf16 {
@@ -1255,6 +1446,31 @@ impl LineBreakType for Utf16 {
             }
         }
     }
+
+    fn line_segment_phrases(
+        iterator: &LineBreakIterator<Self>,
+        left_codepoint: Self::CharType,
+    ) -> Vec<usize> {
+        let Some((start, _)) = iterator.current_pos_data else {
+            return Vec::new();
+        };
+        let start = start - Self::char_len(left_codepoint);
+        let mut s = Vec::new();
+        for c in core::iter::once(left_codepoint).chain(
+            iterator
+                .current_pos_data
+                .into_iter()
+                .chain(iterator.iter.clone())
+                .map(|(_, c)| c)
+                .take_while(|&c| is_phrase_char(c)),
+        ) {
+            let c = char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER);
+            s.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+        }
+        let mut breaks = iterator.complex.phrase_segment_utf16(&s);
+        breaks.iter_mut().for_each(|b| *b += start);
+        breaks
+    }
 }
 
 #[cfg(test)]
@@ -1653,4 +1869,108 @@ mod tests {
         let breaks: Vec<usize> = segmenter.segment_str("").collect();
         assert_eq!(breaks, [0]);
     }
+
+    /// Compiled data, with a small Chinese/Japanese dictionary and phrase data.
+    struct PhraseTestProvider;
+
+    impl DataProvider<SegmenterBreakLineV1> for PhraseTestProvider {
+        fn load(&self, req: DataRequest) -> Result<DataResponse<SegmenterBreakLineV1>, DataError> {
+            crate::provider::Baked.load(req)
+        }
+    }
+
+    impl DataProvider<SegmenterBreakGraphemeClusterV1> for PhraseTestProvider {
+        fn load(
+            &self,
+            req: DataRequest,
+        ) -> Result<DataResponse<SegmenterBreakGraphemeClusterV1>, DataError> {
+            crate::provider::Baked.load(req)
+        }
+    }
+
+    impl DataProvider<SegmenterDictionaryExtendedV1> for PhraseTestProvider {
+        fn load(
+            &self,
+            req: DataRequest,
+        ) -> Result<DataResponse<SegmenterDictionaryExtendedV1>, DataError> {
+            crate::provider::Baked.load(req)
+        }
+    }
+
+    impl DataProvider<SegmenterDictionaryAutoV1> for PhraseTestProvider {
+        fn load(
+            &self,
+            _: DataRequest,
+        ) -> Result<DataResponse<SegmenterDictionaryAutoV1>, DataError> {
+            let mut dictionary = crate::UserDictionary::new();
+            for word in ["東京", "行き", "ます"] {
+                dictionary.insert(word, 0).unwrap();
+            }
+            Ok(DataResponse {
+                metadata: Default::default(),
+                payload: DataPayload::from_owned(dictionary.build()),
+            })
+        }
+    }
+
+    impl DataProvider<SegmenterPhraseJapaneseV1> for PhraseTestProvider {
+        fn load(
+            &self,
+            _: DataRequest,
+        ) -> Result<DataResponse<SegmenterPhraseJapaneseV1>, DataError> {
+            Ok(DataResponse {
+                metadata: Default::default(),
+                payload: DataPayload::from_owned(PhraseBreakData {
+                    attached_words: zerovec::VarZeroVec::from(&["ます"]),
+                    attached_characters:
+                        icu_collections::codepointinvlist::CodePointInversionList::try_from_u32_inversion_list_slice(
+                            &[0x3041, 0x3097],
+                        )
+                        .unwrap(),
+                    prefixes: zerovec::VarZeroVec::from(&["お"]),
+                }),
+            })
+        }
+    }
+
+    #[test]
+    fn auto_phrase() {
+        static JA: LanguageIdentifier = icu_locale_core::langid!("ja");
+        let mut options = LineBreakOptions {
+            word_option: Some(LineBreakWordOption::AutoPhrase),
+            content_locale: Some(&JA),
+            ..Default::default()
+        };
+
+        let segmenter = LineSegmenter::try_new_phrase_unstable(&PhraseTestProvider, options)
+            .expect("Loading should succeed!");
+
+        // 東京に|行きます
+        let s = "東京に行きます";
+        let breaks: Vec<usize> = segmenter.as_borrowed().segment_str(s).collect();
+        assert_eq!(breaks, [0, 9, 21]);
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let breaks: Vec<usize> = segmenter.as_borrowed().segment_utf16(&utf16).collect();
+        assert_eq!(breaks, [0, 3, 7]);
+
+        // Every character of コーヒー is a phrase, but phrase boundaries are still subject to
+        // the strictness: the prolonged sound mark can't start a line with strict line breaking.
+        let s = "東京コーヒー";
+        options.strictness = Some(LineBreakStrictness::Strict);
+        let segmenter = LineSegmenter::try_new_phrase_unstable(&PhraseTestProvider, options)
+            .expect("Loading should succeed!");
+        let breaks: Vec<usize> = segmenter.as_borrowed().segment_str(s).collect();
+        assert_eq!(breaks, [0, 6, 12, 18]);
+
+        options.strictness = Some(LineBreakStrictness::Loose);
+        let segmenter = LineSegmenter::try_new_phrase_unstable(&PhraseTestProvider, options)
+            .expect("Loading should succeed!");
+        let breaks: Vec<usize> = segmenter.as_borrowed().segment_str(s).collect();
+        assert_eq!(breaks, [0, 6, 9, 12, 15, 18]);
+
+        // Other constructors can't load the phrase data.
+        assert!(LineSegmenter::try_new_dictionary_unstable(&PhraseTestProvider, options).is_err());
+        options.content_locale = None;
+        assert!(LineSegmenter::try_new_dictionary_unstable(&PhraseTestProvider, options).is_ok());
+    }
 }
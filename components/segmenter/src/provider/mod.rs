@@ -19,9 +19,10 @@ mod lstm;
 pub use lstm::*;
 
 use crate::options::WordType;
+use icu_collections::codepointinvlist::CodePointInversionList;
 use icu_collections::codepointtrie::CodePointTrie;
 use icu_provider::prelude::*;
-use zerovec::ZeroVec;
+use zerovec::{VarZeroVec, ZeroVec};
 
 #[cfg(feature = "compiled_data")]
 #[derive(Debug)]
@@ -54,6 +55,7 @@ const _: () = {
     impl_segmenter_break_word_v1!(Baked);
     impl_segmenter_break_word_override_v1!(Baked);
     impl_segmenter_break_sentence_override_v1!(Baked);
+    impl_segmenter_phrase_japanese_v1!(Baked);
 };
 
 icu_provider::data_marker!(
@@ -120,6 +122,13 @@ icu_provider::data_marker!(
     RuleBreakData<'static>,
     is_singleton = true
 );
+icu_provider::data_marker!(
+    /// `SegmenterPhraseJapaneseV1`
+    SegmenterPhraseJapaneseV1,
+    "segmenter/phrase/japanese/v1",
+    PhraseBreakData<'static>,
+    is_singleton = true
+);
 
 pub use crate::word::inner::WordTypeULE;
 
@@ -135,6 +144,7 @@ pub const MARKERS: &[DataMarkerInfo] = &[
     SegmenterDictionaryAutoV1::INFO,
     SegmenterDictionaryExtendedV1::INFO,
     SegmenterLstmAutoV1::INFO,
+    SegmenterPhraseJapaneseV1::INFO,
 ];
 
 /// Pre-processed Unicode data in the form of tables to be used for rule-based breaking.
@@ -208,6 +218,41 @@ icu_provider::data_struct!(
 
 pub(crate) struct UCharDictionaryBreakDataV1;
 
+/// Data for grouping the words of Japanese text into phrases (bunsetsu), used for
+/// [`LineBreakWordOption::AutoPhrase`](crate::options::LineBreakWordOption::AutoPhrase).
+///
+/// A boundary between two words is a phrase boundary, unless the first word is a prefix, or
+/// the second word attaches to the first one (like particles and auxiliary verbs do) and is
+/// not a prefix itself.
+///
+/// <div class="stab unstable">
+/// 🚧 This code is considered unstable; it may change at any time, in breaking or non-breaking ways,
+/// including in SemVer minor releases. While the serde representation of data structs is guaranteed
+/// to be stable, their Rust representation might not be. Use with caution.
+/// </div>
+#[derive(Debug, PartialEq, Clone, yoke::Yokeable, zerofrom::ZeroFrom)]
+#[cfg_attr(feature = "datagen", derive(serde::Serialize, databake::Bake))]
+#[cfg_attr(feature = "datagen", databake(path = icu_segmenter::provider))]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct PhraseBreakData<'data> {
+    /// Words that attach to the preceding word, sorted.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub attached_words: VarZeroVec<'data, str>,
+
+    /// Characters that attach to the preceding word when they form a word by themselves.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub attached_characters: CodePointInversionList<'data>,
+
+    /// Words that attach to the following word, sorted.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub prefixes: VarZeroVec<'data, str>,
+}
+
+icu_provider::data_struct!(
+    PhraseBreakData<'_>,
+    #[cfg(feature = "datagen")]
+);
+
 impl DynamicDataMarker for UCharDictionaryBreakDataV1 {
     type DataStruct = UCharDictionaryBreakData<'static>;
 }
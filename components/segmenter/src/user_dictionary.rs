@@ -0,0 +1,97 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use crate::complex::MAX_WORD_LENGTH;
+use crate::provider::UCharDictionaryBreakData;
+use icu_collections::char16trie::Char16TrieBuilder;
+
+/// An error returned when a word cannot be added to a [`UserDictionary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, displaydoc::Display)]
+#[non_exhaustive]
+pub enum UserDictionaryError {
+    /// The word is empty
+    EmptyWord,
+    /// The word is longer than the maximum of {max} code points
+    WordTooLong {
+        /// The maximum length of a word, in code points.
+        max: usize,
+    },
+}
+
+impl core::error::Error for UserDictionaryError {}
+
+/// A list of custom words, such as product or person names, used when segmenting
+/// Chinese and Japanese text.
+///
+/// Each word has a weight from 0 to 255. Words from a user dictionary take precedence over
+/// the words of the compiled dictionary: wherever a user word matches, it is kept as a single
+/// segment. Where user words overlap, segmentations using words with higher weights are
+/// preferred.
+///
+/// Words can be at most 20 code points long, like the words of the compiled dictionary.
+///
+/// The dictionary is attached to a segmenter with [`WordSegmenter::with_user_dictionary`].
+///
+/// [`WordSegmenter::with_user_dictionary`]: crate::WordSegmenter::with_user_dictionary
+///
+/// # Examples
+///
+/// ```
+/// use icu::segmenter::{options::WordBreakInvariantOptions, UserDictionary, WordSegmenter};
+///
+/// let mut dictionary = UserDictionary::new();
+/// dictionary.insert("東京都庁", 200).unwrap();
+///
+/// let segmenter = WordSegmenter::new_dictionary(WordBreakInvariantOptions::default())
+///     .static_to_owned()
+///     .with_user_dictionary(dictionary);
+///
+/// let breakpoints: Vec<usize> = segmenter
+///     .as_borrowed()
+///     .segment_str("東京都庁に行く")
+///     .collect();
+/// assert_eq!(breakpoints[..2], [0, 12]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UserDictionary {
+    builder: Char16TrieBuilder,
+}
+
+impl UserDictionary {
+    /// Creates an empty [`UserDictionary`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a word with the given weight, replacing the weight if the word was already added.
+    ///
+    /// Higher weights make a word more likely to be chosen over overlapping user words.
+    ///
+    /// Returns an error, without adding the word, if it is empty or longer than 20 code
+    /// points.
+    pub fn insert(&mut self, word: &str, weight: u8) -> Result<(), UserDictionaryError> {
+        if word.is_empty() {
+            return Err(UserDictionaryError::EmptyWord);
+        }
+        if word.chars().nth(MAX_WORD_LENGTH).is_some() {
+            return Err(UserDictionaryError::WordTooLong {
+                max: MAX_WORD_LENGTH,
+            });
+        }
+        // Dictionary values are costs, for which lower is better.
+        self.builder.insert(word, i32::from(u8::MAX - weight));
+        Ok(())
+    }
+
+    /// Whether no words have been added to the dictionary.
+    pub fn is_empty(&self) -> bool {
+        self.builder.is_empty()
+    }
+
+    pub(crate) fn build(self) -> UCharDictionaryBreakData<'static> {
+        UCharDictionaryBreakData {
+            trie_data: self.builder.build().data,
+        }
+    }
+}
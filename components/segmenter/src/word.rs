@@ -7,6 +7,7 @@ use crate::indices::{Latin1Indices, Utf16Indices};
 use crate::iterator_helpers::derive_usize_iterator_with_type;
 use crate::provider::*;
 use crate::rule_segmenter::*;
+use crate::UserDictionary;
 use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
@@ -473,6 +474,20 @@ impl WordSegmenter {
             locale_override: self.payload_locale_override.as_ref().map(|p| p.get()),
         }
     }
+
+    /// Adds custom words for segmenting Chinese and Japanese text, replacing any previously
+    /// added [`UserDictionary`].
+    ///
+    /// Chinese and Japanese text is then segmented by finding the most likely segmentation
+    /// using the words of both the user dictionary and the dictionary data of this segmenter,
+    /// where words of the user dictionary take precedence. This works with all constructors;
+    /// with the LSTM constructors, only the user dictionary is used for Chinese and Japanese.
+    ///
+    /// See [`UserDictionary`] for an example.
+    pub fn with_user_dictionary(mut self, dictionary: UserDictionary) -> Self {
+        self.complex.set_user_dictionary(dictionary);
+        self
+    }
 }
 
 impl<'data> WordSegmenterBorrowed<'data> {
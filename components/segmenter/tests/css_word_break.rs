@@ -2,19 +2,32 @@
 // called LICENSE at the top level of the ICU4X source tree
 // (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
 
+use icu_locale_core::{langid, LanguageIdentifier};
 use icu_segmenter::options::LineBreakOptions;
 use icu_segmenter::options::LineBreakStrictness;
 use icu_segmenter::options::LineBreakWordOption;
-use icu_segmenter::LineSegmenter;
+use icu_segmenter::{LineSegmenter, LineSegmenterBorrowed};
 
 fn check_with_options(
     s: &str,
-    mut expect_utf8: Vec<usize>,
-    mut expect_utf16: Vec<usize>,
+    expect_utf8: Vec<usize>,
+    expect_utf16: Vec<usize>,
     options: LineBreakOptions,
 ) {
-    let segmenter = LineSegmenter::new_dictionary(options);
+    check_with_segmenter(
+        s,
+        expect_utf8,
+        expect_utf16,
+        LineSegmenter::new_dictionary(options),
+    );
+}
 
+fn check_with_segmenter(
+    s: &str,
+    mut expect_utf8: Vec<usize>,
+    mut expect_utf16: Vec<usize>,
+    segmenter: LineSegmenterBorrowed,
+) {
     let iter = segmenter.segment_str(s);
     let result: Vec<usize> = iter.collect();
     expect_utf8.insert(0, 0);
@@ -51,6 +64,21 @@ fn normal(s: &str, expect_utf8: Vec<usize>, expect_utf16: Vec<usize>) {
     check_with_options(s, expect_utf8, expect_utf16, options);
 }
 
+static JA: LanguageIdentifier = langid!("ja");
+
+fn auto_phrase(s: &str, ja: bool, expect_utf8: Vec<usize>, expect_utf16: Vec<usize>) {
+    let mut options = LineBreakOptions::default();
+    options.strictness = Some(LineBreakStrictness::Strict);
+    options.word_option = Some(LineBreakWordOption::AutoPhrase);
+    options.content_locale = ja.then_some(&JA);
+    check_with_segmenter(
+        s,
+        expect_utf8,
+        expect_utf16,
+        LineSegmenter::new_phrase(options),
+    );
+}
+
 #[test]
 fn wordbreak_breakall() {
     // from css/css-text/word-break/word-break-break-all-000.html
@@ -181,3 +209,34 @@ fn wordbreak_normal_lo() {
     let _s = "ພາສາລາວພາສາລາວພາສາລາວ";
     normal(_s, vec![12, 21, 33, 42, 54, 63], vec![4, 7, 11, 14, 18, 21]);
 }
+
+#[test]
+fn wordbreak_auto_phrase() {
+    // 私は|東京に|行きます。
+    let s = "私は東京に行きます。";
+    auto_phrase(s, true, vec![6, 15, 30], vec![2, 5, 10]);
+
+    // Without a Japanese content locale, this behaves like `normal`.
+    auto_phrase(
+        s,
+        false,
+        vec![3, 6, 9, 12, 15, 18, 21, 24, 30],
+        vec![1, 2, 3, 4, 5, 6, 7, 8, 10],
+    );
+
+    // Text in other scripts is not affected.
+    let s = "東京に行きます hello world";
+    auto_phrase(s, true, vec![9, 22, 28, 33], vec![3, 8, 14, 19]);
+
+    // The other compiled data constructors load the phrase data, too.
+    let mut options = LineBreakOptions::default();
+    options.strictness = Some(LineBreakStrictness::Strict);
+    options.word_option = Some(LineBreakWordOption::AutoPhrase);
+    options.content_locale = Some(&JA);
+    check_with_options(
+        "私は東京に行きます。",
+        vec![6, 15, 30],
+        vec![2, 5, 10],
+        options,
+    );
+}
@@ -15,6 +15,7 @@ typedef enum LineBreakWordOption {
   LineBreakWordOption_Normal = 0,
   LineBreakWordOption_BreakAll = 1,
   LineBreakWordOption_KeepAll = 2,
+  LineBreakWordOption_AutoPhrase = 3,
 } LineBreakWordOption;
 
 typedef struct LineBreakWordOption_option {union { LineBreakWordOption ok; }; bool is_ok; } LineBreakWordOption_option;
@@ -18,6 +18,7 @@ namespace capi {
       LineBreakWordOption_Normal = 0,
       LineBreakWordOption_BreakAll = 1,
       LineBreakWordOption_KeepAll = 2,
+      LineBreakWordOption_AutoPhrase = 3,
     };
 
     typedef struct LineBreakWordOption_option {union { LineBreakWordOption ok; }; bool is_ok; } LineBreakWordOption_option;
@@ -34,6 +35,7 @@ public:
         Normal = 0,
         BreakAll = 1,
         KeepAll = 2,
+        AutoPhrase = 3,
     };
 
     LineBreakWordOption(): value(Value::Normal) {}
@@ -31,6 +31,7 @@ inline icu4x::LineBreakWordOption icu4x::LineBreakWordOption::FromFFI(icu4x::cap
         case icu4x::capi::LineBreakWordOption_Normal:
         case icu4x::capi::LineBreakWordOption_BreakAll:
         case icu4x::capi::LineBreakWordOption_KeepAll:
+        case icu4x::capi::LineBreakWordOption_AutoPhrase:
             return static_cast<icu4x::LineBreakWordOption::Value>(c_enum);
         default:
             std::abort();
@@ -116,6 +116,24 @@ public:
    */
   inline static diplomat::result<std::unique_ptr<icu4x::LineSegmenter>, icu4x::DataError> create_dictionary_with_options_v2_and_provider(const icu4x::DataProvider& provider, const icu4x::Locale* content_locale, icu4x::LineBreakOptionsV2 options);
 
+  /**
+     * Construct a {@link LineSegmenter} with custom options, dictionary payload data for
+     * Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+     * by phrases, using compiled data.
+     *
+     * See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+   */
+  inline static std::unique_ptr<icu4x::LineSegmenter> create_phrase_with_options(const icu4x::Locale* content_locale, icu4x::LineBreakOptionsV2 options);
+
+  /**
+     * Construct a {@link LineSegmenter} with custom options, dictionary payload data for
+     * Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+     * by phrases, using a particular data source.
+     *
+     * See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+   */
+  inline static diplomat::result<std::unique_ptr<icu4x::LineSegmenter>, icu4x::DataError> create_phrase_with_options_and_provider(const icu4x::DataProvider& provider, const icu4x::Locale* content_locale, icu4x::LineBreakOptionsV2 options);
+
   /**
      * Segments a string.
      *
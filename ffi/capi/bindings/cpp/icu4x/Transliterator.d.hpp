@@ -0,0 +1,103 @@
+#ifndef icu4x_Transliterator_D_HPP
+#define icu4x_Transliterator_D_HPP
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <functional>
+#include <optional>
+#include <cstdlib>
+#include "../diplomat_runtime.hpp"
+
+namespace icu4x {
+namespace capi { struct DataProvider; }
+class DataProvider;
+namespace capi { struct Locale; }
+class Locale;
+namespace capi { struct Transliterator; }
+class Transliterator;
+class DataError;
+}
+
+
+namespace icu4x {
+namespace capi {
+    struct Transliterator;
+} // namespace capi
+} // namespace
+
+namespace icu4x {
+/**
+ * 🚧 This API is experimental and may experience breaking changes outside major releases.
+ *
+ * A transliterator that transforms text according to a CLDR transform.
+ *
+ * See the [Rust documentation for `Transliterator`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html) for more information.
+ */
+class Transliterator {
+public:
+
+  /**
+     * 🚧 This API is experimental and may experience breaking changes outside major releases.
+     *
+     * Creates a new `Transliterator` for the transform with the given BCP-47 ID
+     * (such as `und-Latn-t-und-cyrl`) using compiled data.
+     *
+     * See the [Rust documentation for `try_new`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html#method.try_new) for more information.
+   */
+  inline static diplomat::result<std::unique_ptr<icu4x::Transliterator>, icu4x::DataError> create(const icu4x::Locale& locale);
+
+  /**
+     * 🚧 This API is experimental and may experience breaking changes outside major releases.
+     *
+     * Creates a new `Transliterator` for the transform with the given BCP-47 ID
+     * (such as `und-Latn-t-und-cyrl`) using a particular data source.
+     *
+     * See the [Rust documentation for `try_new`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html#method.try_new) for more information.
+   */
+  inline static diplomat::result<std::unique_ptr<icu4x::Transliterator>, icu4x::DataError> create_with_provider(const icu4x::DataProvider& provider, const icu4x::Locale& locale);
+
+  /**
+     * 🚧 This API is experimental and may experience breaking changes outside major releases.
+     *
+     * Transliterates the given string.
+     *
+     * See the [Rust documentation for `transliterate`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html#method.transliterate) for more information.
+   */
+  inline std::string transliterate(std::string_view input) const;
+  template<typename W>
+  inline void transliterate_write(std::string_view input, W& writeable_output) const;
+
+  /**
+     * 🚧 This API is experimental and may experience breaking changes outside major releases.
+     *
+     * Transliterates the given string.
+     *
+     * Unpaired surrogates are treated as if they had been replaced with REPLACEMENT CHARACTERs.
+     *
+     * Like all strings written to a `DiplomatWrite`, the output is UTF-8.
+     *
+     * See the [Rust documentation for `transliterate_utf16`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html#method.transliterate_utf16) for more information.
+   */
+  inline std::string transliterate16(std::u16string_view input) const;
+  template<typename W>
+  inline void transliterate16_write(std::u16string_view input, W& writeable_output) const;
+
+    inline const icu4x::capi::Transliterator* AsFFI() const;
+    inline icu4x::capi::Transliterator* AsFFI();
+    inline static const icu4x::Transliterator* FromFFI(const icu4x::capi::Transliterator* ptr);
+    inline static icu4x::Transliterator* FromFFI(icu4x::capi::Transliterator* ptr);
+    inline static void operator delete(void* ptr);
+private:
+    Transliterator() = delete;
+    Transliterator(const icu4x::Transliterator&) = delete;
+    Transliterator(icu4x::Transliterator&&) noexcept = delete;
+    Transliterator operator=(const icu4x::Transliterator&) = delete;
+    Transliterator operator=(icu4x::Transliterator&&) noexcept = delete;
+    static void operator delete[](void*, size_t) = delete;
+};
+
+} // namespace
+#endif // icu4x_Transliterator_D_HPP
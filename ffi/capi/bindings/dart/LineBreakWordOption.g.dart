@@ -10,7 +10,9 @@ enum LineBreakWordOption {
 
   breakAll,
 
-  keepAll;
+  keepAll,
+
+  autoPhrase;
 
 }
 
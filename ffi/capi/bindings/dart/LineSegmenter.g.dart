@@ -128,6 +128,33 @@ final class LineSegmenter implements ffi.Finalizable {
     return LineSegmenter._fromFfi(result.union.ok, []);
   }
 
+  /// Construct a [LineSegmenter] with custom options, dictionary payload data for
+  /// Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+  /// by phrases, using compiled data.
+  ///
+  /// See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+  factory LineSegmenter.phraseWithOptions(LineBreakOptions options, [Locale? contentLocale]) {
+    final temp = _FinalizedArena();
+    final result = _icu4x_LineSegmenter_create_phrase_with_options_mv1(contentLocale?._ffi ?? ffi.Pointer.fromAddress(0), options._toFfi(temp.arena));
+    return LineSegmenter._fromFfi(result, []);
+  }
+
+  /// Construct a [LineSegmenter] with custom options, dictionary payload data for
+  /// Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+  /// by phrases, using a particular data source.
+  ///
+  /// See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+  ///
+  /// Throws [DataError] on failure.
+  factory LineSegmenter.phraseWithOptionsAndProvider(DataProvider provider, LineBreakOptions options, [Locale? contentLocale]) {
+    final temp = _FinalizedArena();
+    final result = _icu4x_LineSegmenter_create_phrase_with_options_and_provider_mv1(provider._ffi, contentLocale?._ffi ?? ffi.Pointer.fromAddress(0), options._toFfi(temp.arena));
+    if (!result.isOk) {
+      throw DataError.values[result.union.err];
+    }
+    return LineSegmenter._fromFfi(result.union.ok, []);
+  }
+
   /// Segments a string.
   ///
   /// Ill-formed input is treated as if errors had been replaced with REPLACEMENT CHARACTERs according
@@ -194,6 +221,16 @@ external ffi.Pointer<ffi.Opaque> _icu4x_LineSegmenter_create_dictionary_with_opt
 // ignore: non_constant_identifier_names
 external _ResultOpaqueInt32 _icu4x_LineSegmenter_create_dictionary_with_options_v2_and_provider_mv1(ffi.Pointer<ffi.Opaque> provider, ffi.Pointer<ffi.Opaque> contentLocale, _LineBreakOptionsFfi options);
 
+@_DiplomatFfiUse('icu4x_LineSegmenter_create_phrase_with_options_mv1')
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Pointer<ffi.Opaque>, _LineBreakOptionsFfi)>(isLeaf: true, symbol: 'icu4x_LineSegmenter_create_phrase_with_options_mv1')
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _icu4x_LineSegmenter_create_phrase_with_options_mv1(ffi.Pointer<ffi.Opaque> contentLocale, _LineBreakOptionsFfi options);
+
+@_DiplomatFfiUse('icu4x_LineSegmenter_create_phrase_with_options_and_provider_mv1')
+@ffi.Native<_ResultOpaqueInt32 Function(ffi.Pointer<ffi.Opaque>, ffi.Pointer<ffi.Opaque>, _LineBreakOptionsFfi)>(isLeaf: true, symbol: 'icu4x_LineSegmenter_create_phrase_with_options_and_provider_mv1')
+// ignore: non_constant_identifier_names
+external _ResultOpaqueInt32 _icu4x_LineSegmenter_create_phrase_with_options_and_provider_mv1(ffi.Pointer<ffi.Opaque> provider, ffi.Pointer<ffi.Opaque> contentLocale, _LineBreakOptionsFfi options);
+
 @_DiplomatFfiUse('icu4x_LineSegmenter_segment_utf16_mv1')
 @ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Pointer<ffi.Opaque>, _SliceUtf16)>(isLeaf: true, symbol: 'icu4x_LineSegmenter_segment_utf16_mv1')
 // ignore: non_constant_identifier_names
@@ -149,6 +149,7 @@ part 'TitlecaseMapper.g.dart';
 part 'TitlecaseOptions.g.dart';
 part 'TrailingCase.g.dart';
 part 'TransformResult.g.dart';
+part 'Transliterator.g.dart';
 part 'UtcOffset.g.dart';
 part 'VariantOffsets.g.dart';
 part 'VariantOffsetsCalculator.g.dart';
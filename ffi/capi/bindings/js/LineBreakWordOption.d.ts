@@ -19,6 +19,7 @@ export class LineBreakWordOption {
     static Normal : LineBreakWordOption;
     static BreakAll : LineBreakWordOption;
     static KeepAll : LineBreakWordOption;
+    static AutoPhrase : LineBreakWordOption;
 
 
     constructor(value: LineBreakWordOption | string );
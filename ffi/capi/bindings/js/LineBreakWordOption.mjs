@@ -13,7 +13,8 @@ export class LineBreakWordOption {
     static #values = new Map([
         ["Normal", 0],
         ["BreakAll", 1],
-        ["KeepAll", 2]
+        ["KeepAll", 2],
+        ["AutoPhrase", 3]
     ]);
 
     static getAllEntries() {
@@ -62,11 +63,13 @@ export class LineBreakWordOption {
         new LineBreakWordOption(diplomatRuntime.internalConstructor, diplomatRuntime.internalConstructor, 0),
         new LineBreakWordOption(diplomatRuntime.internalConstructor, diplomatRuntime.internalConstructor, 1),
         new LineBreakWordOption(diplomatRuntime.internalConstructor, diplomatRuntime.internalConstructor, 2),
+        new LineBreakWordOption(diplomatRuntime.internalConstructor, diplomatRuntime.internalConstructor, 3),
     ];
 
     static Normal = LineBreakWordOption.#objectValues[0];
     static BreakAll = LineBreakWordOption.#objectValues[1];
     static KeepAll = LineBreakWordOption.#objectValues[2];
+    static AutoPhrase = LineBreakWordOption.#objectValues[3];
 
 
     constructor(value) {
@@ -93,6 +93,24 @@ export class LineSegmenter {
      */
     static dictionaryWithOptionsAndProvider(provider: DataProvider, contentLocale: Locale | null, options: LineBreakOptions_obj): LineSegmenter;
 
+    /**
+     * Construct a {@link LineSegmenter} with custom options, dictionary payload data for
+     * Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+     * by phrases, using compiled data.
+     *
+     * See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+     */
+    static phraseWithOptions(contentLocale: Locale | null, options: LineBreakOptions_obj): LineSegmenter;
+
+    /**
+     * Construct a {@link LineSegmenter} with custom options, dictionary payload data for
+     * Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+     * by phrases, using a particular data source.
+     *
+     * See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+     */
+    static phraseWithOptionsAndProvider(provider: DataProvider, contentLocale: Locale | null, options: LineBreakOptions_obj): LineSegmenter;
+
     /**
      * Segments a string.
      *
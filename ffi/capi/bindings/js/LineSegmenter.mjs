@@ -252,6 +252,59 @@ export class LineSegmenter {
         }
     }
 
+    /**
+     * Construct a {@link LineSegmenter} with custom options, dictionary payload data for
+     * Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+     * by phrases, using compiled data.
+     *
+     * See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+     */
+    static phraseWithOptions(contentLocale, options) {
+        let functionCleanupArena = new diplomatRuntime.CleanupArena();
+
+
+        const result = wasm.icu4x_LineSegmenter_create_phrase_with_options_mv1(contentLocale.ffiValue ?? 0, LineBreakOptions._fromSuppliedValue(diplomatRuntime.internalConstructor, options)._intoFFI(functionCleanupArena, {}, false));
+
+        try {
+            return new LineSegmenter(diplomatRuntime.internalConstructor, result, []);
+        }
+
+        finally {
+            functionCleanupArena.free();
+
+        }
+    }
+
+    /**
+     * Construct a {@link LineSegmenter} with custom options, dictionary payload data for
+     * Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+     * by phrases, using a particular data source.
+     *
+     * See the [Rust documentation for `new_phrase`](https://docs.rs/icu/2.0.0/icu/segmenter/struct.LineSegmenter.html#method.new_phrase) for more information.
+     */
+    static phraseWithOptionsAndProvider(provider, contentLocale, options) {
+        let functionCleanupArena = new diplomatRuntime.CleanupArena();
+
+        const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 5, 4, true);
+
+
+        const result = wasm.icu4x_LineSegmenter_create_phrase_with_options_and_provider_mv1(diplomatReceive.buffer, provider.ffiValue, contentLocale.ffiValue ?? 0, LineBreakOptions._fromSuppliedValue(diplomatRuntime.internalConstructor, options)._intoFFI(functionCleanupArena, {}, false));
+
+        try {
+            if (!diplomatReceive.resultFlag) {
+                const cause = new DataError(diplomatRuntime.internalConstructor, diplomatRuntime.enumDiscriminant(wasm, diplomatReceive.buffer));
+                throw new globalThis.Error('DataError.' + cause.value, { cause });
+            }
+            return new LineSegmenter(diplomatRuntime.internalConstructor, diplomatRuntime.ptrRead(wasm, diplomatReceive.buffer), []);
+        }
+
+        finally {
+            functionCleanupArena.free();
+
+            diplomatReceive.free();
+        }
+    }
+
     /**
      * Segments a string.
      *
@@ -0,0 +1,53 @@
+// generated by diplomat-tool
+import type { DataError } from "./DataError"
+import type { DataProvider } from "./DataProvider"
+import type { Locale } from "./Locale"
+import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+
+
+
+/**
+ * 🚧 This API is experimental and may experience breaking changes outside major releases.
+ *
+ * A transliterator that transforms text according to a CLDR transform.
+ *
+ * See the [Rust documentation for `Transliterator`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html) for more information.
+ */
+export class Transliterator {
+    /** @internal */
+    get ffiValue(): pointer;
+
+
+    /**
+     * 🚧 This API is experimental and may experience breaking changes outside major releases.
+     *
+     * Creates a new `Transliterator` for the transform with the given BCP-47 ID
+     * (such as `und-Latn-t-und-cyrl`) using a particular data source.
+     *
+     * See the [Rust documentation for `try_new`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html#method.try_new) for more information.
+     */
+    static createWithProvider(provider: DataProvider, locale: Locale): Transliterator;
+
+    /**
+     * 🚧 This API is experimental and may experience breaking changes outside major releases.
+     *
+     * Transliterates the given string.
+     *
+     * Unpaired surrogates are treated as if they had been replaced with REPLACEMENT CHARACTERs.
+     *
+     * Like all strings written to a `DiplomatWrite`, the output is UTF-8.
+     *
+     * See the [Rust documentation for `transliterate_utf16`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html#method.transliterate_utf16) for more information.
+     */
+    transliterate(input: string): string;
+
+    /**
+     * 🚧 This API is experimental and may experience breaking changes outside major releases.
+     *
+     * Creates a new `Transliterator` for the transform with the given BCP-47 ID
+     * (such as `und-Latn-t-und-cyrl`) using compiled data.
+     *
+     * See the [Rust documentation for `try_new`](https://docs.rs/icu/2.0.0/icu/experimental/transliterate/struct.Transliterator.html#method.try_new) for more information.
+     */
+    constructor(locale: Locale);
+}
\ No newline at end of file
@@ -194,6 +194,8 @@ export { TimeZoneInfo } from "./TimeZoneInfo"
 
 export { TimeZoneFormatter } from "./TimeZoneFormatter"
 
+export { Transliterator } from "./Transliterator"
+
 export { UtcOffset } from "./UtcOffset"
 
 export { VariantOffsetsCalculator } from "./VariantOffsetsCalculator"
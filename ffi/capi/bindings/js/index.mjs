@@ -192,6 +192,8 @@ export { TimeZoneInfo } from "./TimeZoneInfo.mjs"
 
 export { TimeZoneFormatter } from "./TimeZoneFormatter.mjs"
 
+export { Transliterator } from "./Transliterator.mjs"
+
 export { UtcOffset } from "./UtcOffset.mjs"
 
 export { VariantOffsetsCalculator } from "./VariantOffsetsCalculator.mjs"
@@ -155,6 +155,8 @@ pub mod unstable {
     pub mod timezone;
     #[cfg(feature = "datetime")]
     pub mod timezone_formatter;
+    #[cfg(feature = "experimental")]
+    pub mod transliterate;
     #[cfg(any(feature = "datetime", feature = "timezone"))]
     pub mod variant_offset;
     #[cfg(feature = "calendar")]
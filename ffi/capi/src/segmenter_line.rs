@@ -42,6 +42,7 @@ pub mod ffi {
         Normal,
         BreakAll,
         KeepAll,
+        AutoPhrase,
     }
 
     #[diplomat::rust_link(icu::segmenter::options::LineBreakOptions, Struct)]
@@ -225,6 +226,44 @@ pub mod ffi {
                 )?,
             )))
         }
+        /// Construct a [`LineSegmenter`] with custom options, dictionary payload data for
+        /// Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+        /// by phrases, using compiled data.
+        #[diplomat::rust_link(icu::segmenter::LineSegmenter::new_phrase, FnInStruct)]
+        #[diplomat::attr(auto, named_constructor = "phrase_with_options")]
+        #[cfg(feature = "compiled_data")]
+        pub fn create_phrase_with_options(
+            content_locale: Option<&Locale>,
+            options: LineBreakOptionsV2,
+        ) -> Box<LineSegmenter> {
+            let mut options: LineBreakOptions = options.into();
+            options.content_locale = content_locale.map(|c| &c.0.id);
+
+            Box::new(LineSegmenter(
+                icu_segmenter::LineSegmenter::new_phrase(options).static_to_owned(),
+            ))
+        }
+        /// Construct a [`LineSegmenter`] with custom options, dictionary payload data for
+        /// Burmese, Khmer, Lao, and Thai, and dictionary payload data for breaking Japanese
+        /// by phrases, using a particular data source.
+        #[diplomat::rust_link(icu::segmenter::LineSegmenter::new_phrase, FnInStruct)]
+        #[diplomat::attr(all(supports = fallible_constructors, supports = named_constructors), named_constructor = "phrase_with_options_and_provider")]
+        #[cfg(feature = "buffer_provider")]
+        pub fn create_phrase_with_options_and_provider(
+            provider: &DataProvider,
+            content_locale: Option<&Locale>,
+            options: LineBreakOptionsV2,
+        ) -> Result<Box<LineSegmenter>, DataError> {
+            let mut options: LineBreakOptions = options.into();
+            options.content_locale = content_locale.map(|c| &c.0.id);
+
+            Ok(Box::new(LineSegmenter(
+                icu_segmenter::LineSegmenter::try_new_phrase_with_buffer_provider(
+                    provider.get()?,
+                    options,
+                )?,
+            )))
+        }
         /// Segments a string.
         ///
         /// Ill-formed input is treated as if errors had been replaced with REPLACEMENT CHARACTERs according
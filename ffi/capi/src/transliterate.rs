@@ -0,0 +1,107 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+#[diplomat::bridge]
+#[diplomat::abi_rename = "icu4x_{0}_mv1"]
+#[diplomat::attr(auto, namespace = "icu4x")]
+pub mod ffi {
+    use alloc::boxed::Box;
+
+    #[cfg(any(feature = "compiled_data", feature = "buffer_provider"))]
+    use crate::unstable::errors::ffi::DataError;
+    #[cfg(any(feature = "compiled_data", feature = "buffer_provider"))]
+    use crate::unstable::locale_core::ffi::Locale;
+    #[cfg(feature = "buffer_provider")]
+    use crate::unstable::provider::ffi::DataProvider;
+
+    use writeable::Writeable;
+
+    /// 🚧 This API is experimental and may experience breaking changes outside major releases.
+    ///
+    /// A transliterator that transforms text according to a CLDR transform.
+    #[diplomat::opaque]
+    #[diplomat::rust_link(icu::experimental::transliterate::Transliterator, Struct)]
+    pub struct Transliterator(pub icu_experimental::transliterate::Transliterator);
+
+    impl Transliterator {
+        /// 🚧 This API is experimental and may experience breaking changes outside major releases.
+        ///
+        /// Creates a new `Transliterator` for the transform with the given BCP-47 ID
+        /// (such as `und-Latn-t-und-cyrl`) using compiled data.
+        #[diplomat::rust_link(
+            icu::experimental::transliterate::Transliterator::try_new,
+            FnInStruct
+        )]
+        #[diplomat::attr(supports = fallible_constructors, constructor)]
+        #[diplomat::attr(demo_gen, disable)]
+        #[cfg(feature = "compiled_data")]
+        pub fn create(locale: &Locale) -> Result<Box<Transliterator>, DataError> {
+            Ok(Box::new(Transliterator(
+                icu_experimental::transliterate::Transliterator::try_new(&locale.0)?,
+            )))
+        }
+
+        /// 🚧 This API is experimental and may experience breaking changes outside major releases.
+        ///
+        /// Creates a new `Transliterator` for the transform with the given BCP-47 ID
+        /// (such as `und-Latn-t-und-cyrl`) using a particular data source.
+        #[diplomat::rust_link(
+            icu::experimental::transliterate::Transliterator::try_new,
+            FnInStruct
+        )]
+        #[diplomat::attr(all(supports = fallible_constructors, supports = named_constructors), named_constructor = "with_provider")]
+        #[diplomat::attr(demo_gen, disable)]
+        #[cfg(feature = "buffer_provider")]
+        pub fn create_with_provider(
+            provider: &DataProvider,
+            locale: &Locale,
+        ) -> Result<Box<Transliterator>, DataError> {
+            Ok(Box::new(Transliterator(
+                icu_experimental::transliterate::Transliterator::try_new_with_buffer_provider(
+                    provider.get()?,
+                    &locale.0,
+                )?,
+            )))
+        }
+
+        /// 🚧 This API is experimental and may experience breaking changes outside major releases.
+        ///
+        /// Transliterates the given string.
+        #[diplomat::rust_link(
+            icu::experimental::transliterate::Transliterator::transliterate,
+            FnInStruct
+        )]
+        #[diplomat::rust_link(
+            icu::experimental::transliterate::Transliterator::transliterate_in_place,
+            FnInStruct,
+            hidden
+        )]
+        #[diplomat::attr(not(supports = utf8_strings), disable)]
+        #[diplomat::attr(demo_gen, disable)]
+        pub fn transliterate(&self, input: &str, write: &mut DiplomatWrite) {
+            let _infallible = self.0.transliterate(input.into()).write_to(write);
+        }
+
+        /// 🚧 This API is experimental and may experience breaking changes outside major releases.
+        ///
+        /// Transliterates the given string.
+        ///
+        /// Unpaired surrogates are treated as if they had been replaced with REPLACEMENT CHARACTERs.
+        ///
+        /// Like all strings written to a `DiplomatWrite`, the output is UTF-8.
+        #[diplomat::rust_link(
+            icu::experimental::transliterate::Transliterator::transliterate_utf16,
+            FnInStruct
+        )]
+        #[diplomat::attr(not(supports = utf8_strings), rename = "transliterate")]
+        #[diplomat::attr(supports = utf8_strings, rename = "transliterate16")]
+        #[diplomat::attr(demo_gen, disable)]
+        pub fn transliterate_utf16(&self, input: &DiplomatStr16, write: &mut DiplomatWrite) {
+            let output = self.0.transliterate_utf16(input);
+            let _infallible =
+                writeable::adapters::LossyWrap(potential_utf::PotentialUtf16::from_slice(&output))
+                    .write_to(write);
+        }
+    }
+}
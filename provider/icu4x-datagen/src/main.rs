@@ -138,6 +138,22 @@ struct Cli {
     #[cfg(feature = "provider")]
     tzdb_root: Option<PathBuf>,
 
+    #[arg(long, value_name = "TAG", default_value = "latest")]
+    #[arg(
+        help = "Download the Unicode Character Database for this Unicode version (https://www.unicode.org/Public/)\n\
+                  Use 'latest' for the latest version verified to work with this version of the binary.\n\
+                  Ignored if '--ucd-root' is present. Requires binary to be built with `networking` Cargo feature (enabled by default)."
+    )]
+    #[cfg_attr(not(feature = "networking"), arg(hide = true))]
+    #[cfg(feature = "provider")]
+    ucd_tag: String,
+
+    #[arg(long, value_name = "PATH")]
+    #[arg(help = "Path to a local Unicode Character Database directory \
+                (see https://www.unicode.org/Public/16.0.0/ucd/).")]
+    #[cfg(feature = "provider")]
+    ucd_root: Option<PathBuf>,
+
     #[arg(long, value_enum, default_value_t = TrieType::Small)]
     #[arg(
         help = "Whether to optimize CodePointTrie data structures for size (\"small\") or speed (\"fast\").\n\
@@ -351,6 +367,10 @@ fn main() -> eyre::Result<()> {
             eyre::bail!(
                 "Timezone data is required for this invocation, set --tzdb-path or --tzdb-tag"
             );
+        } else if SourceDataProvider::is_missing_ucd_error(e) {
+            eyre::bail!(
+                "Unicode Character Database is required for this invocation, set --ucd-root or --ucd-tag"
+            );
         }
 
         Err(e.into())
@@ -438,6 +458,16 @@ fn main() -> eyre::Result<()> {
                 (None, _) => p,
             };
 
+            p = match (cli.ucd_root, cli.ucd_tag.as_str()) {
+                (Some(path), _) => p.with_ucd(&path)?,
+                #[cfg(feature = "networking")]
+                (_, "latest") => p.with_ucd_for_tag(SourceDataProvider::TESTED_UCD_TAG),
+                #[cfg(feature = "networking")]
+                (_, tag) => p.with_ucd_for_tag(tag),
+                #[cfg(not(feature = "networking"))]
+                (None, _) => p,
+            };
+
             if cli.locales.as_slice() == ["recommended"] {
                 preprocessed_locales = Some(PreprocessedLocales::Locales(
                     p.locales_for_coverage_levels([
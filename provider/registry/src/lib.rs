@@ -191,11 +191,13 @@ macro_rules! registry(
             icu::properties::provider::PropertyEnumBidiMirroringGlyphV1: PropertyEnumBidiMirroringGlyphV1,
             icu::properties::provider::PropertyBinaryBasicEmojiV1: PropertyBinaryBasicEmojiV1,
             icu::properties::provider::PropertyScriptWithExtensionsV1: PropertyScriptWithExtensionsV1,
+            icu::properties::provider::PropertyCharacterNamesV1: PropertyCharacterNamesV1,
             icu::segmenter::provider::SegmenterDictionaryExtendedV1: SegmenterDictionaryExtendedV1,
             icu::segmenter::provider::SegmenterDictionaryAutoV1: SegmenterDictionaryAutoV1,
             icu::segmenter::provider::SegmenterBreakGraphemeClusterV1: SegmenterBreakGraphemeClusterV1,
             icu::segmenter::provider::SegmenterBreakLineV1: SegmenterBreakLineV1,
             icu::segmenter::provider::SegmenterLstmAutoV1: SegmenterLstmAutoV1,
+            icu::segmenter::provider::SegmenterPhraseJapaneseV1: SegmenterPhraseJapaneseV1,
             icu::segmenter::provider::SegmenterBreakSentenceOverrideV1: SegmenterBreakSentenceOverrideV1,
             icu::segmenter::provider::SegmenterBreakSentenceV1: SegmenterBreakSentenceV1,
             icu::segmenter::provider::SegmenterBreakWordOverrideV1: SegmenterBreakWordOverrideV1,
@@ -289,6 +291,7 @@ macro_rules! registry(
             icu::experimental::relativetime::provider::ShortYearRelativeV1: ShortYearRelativeV1,
             icu::experimental::relativetime::provider::NarrowYearRelativeV1: NarrowYearRelativeV1,
             icu::experimental::personnames::provider::PersonNamesFormatV1: PersonNamesFormatV1,
+            icu::experimental::transliterate::provider::TransliteratorIdsV1: TransliteratorIdsV1,
             icu::experimental::transliterate::provider::TransliteratorRulesV1: TransliteratorRulesV1,
             icu::experimental::units::provider::UnitsInfoV1: UnitsInfoV1,
             icu::plurals::provider::PluralsRangesV1: PluralsRangesV1,
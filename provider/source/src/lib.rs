@@ -13,7 +13,7 @@
 //! * `networking`
 //!   * enables networking support to download CLDR and ICU source data from GitHub
 //! * `use_wasm` / `use_icu4c`
-//!   * see the documentation on [`icu_codepointtrie_builder`](icu_codepointtrie_builder#build-configuration)
+//!   * build code point tries with ICU4C instead of the Rust builder, see the documentation on [`icu_codepointtrie_builder`](icu_codepointtrie_builder#build-configuration)
 //! * `experimental`
 //!   * enables markers defined in the unstable `icu::experimental` module
 
@@ -76,6 +76,8 @@ mod tests;
 /// * [`is_missing_cldr_error`](Self::is_missing_cldr_error)
 /// * [`is_missing_icuexport_error`](Self::is_missing_icuexport_error)
 /// * [`is_missing_segmenter_lstm_error`](Self::is_missing_segmenter_lstm_error)
+/// * [`is_missing_tzdb_error`](Self::is_missing_tzdb_error)
+/// * [`is_missing_ucd_error`](Self::is_missing_ucd_error)
 #[allow(clippy::exhaustive_structs)] // any information will be added to SourceData
 #[derive(Debug, Clone)]
 pub struct SourceDataProvider {
@@ -83,6 +85,7 @@ pub struct SourceDataProvider {
     icuexport_paths: Option<Arc<SerdeCache>>,
     segmenter_lstm_paths: Option<Arc<SerdeCache>>,
     tzdb_paths: Option<Arc<TzdbCache>>,
+    ucd_paths: Option<Arc<SerdeCache>>,
     trie_type: TrieType,
     collation_root_han: CollationRootHan,
     pub(crate) timezone_horizon: time_zones::Timestamp,
@@ -121,12 +124,16 @@ impl SourceDataProvider {
     /// The TZDB tag that has been verified to work with this version of `SourceDataProvider`.
     pub const TESTED_TZDB_TAG: &'static str = "2025b";
 
+    /// The Unicode Character Database version that has been verified to work with this version of `SourceDataProvider`.
+    pub const TESTED_UCD_TAG: &'static str = "16.0.0";
+
     /// A provider using the data that has been verified to work with this version of `SourceDataProvider`.
     ///
     /// See [`TESTED_CLDR_TAG`](Self::TESTED_CLDR_TAG),
     /// [`TESTED_ICUEXPORT_TAG`](Self::TESTED_ICUEXPORT_TAG),
     /// [`TESTED_SEGMENTER_LSTM_TAG`](Self::TESTED_SEGMENTER_LSTM_TAG),
-    /// [`TESTED_TZDB_TAG`](Self::TESTED_TZDB_TAG).
+    /// [`TESTED_TZDB_TAG`](Self::TESTED_TZDB_TAG),
+    /// [`TESTED_UCD_TAG`](Self::TESTED_UCD_TAG).
     ///
     /// ✨ *Enabled with the `networking` Cargo feature.*
     #[cfg(feature = "networking")]
@@ -141,6 +148,7 @@ impl SourceDataProvider {
                     .with_icuexport_for_tag(Self::TESTED_ICUEXPORT_TAG)
                     .with_segmenter_lstm_for_tag(Self::TESTED_SEGMENTER_LSTM_TAG)
                     .with_tzdb_for_tag(Self::TESTED_TZDB_TAG)
+                    .with_ucd_for_tag(Self::TESTED_UCD_TAG)
             })
             .clone()
     }
@@ -149,13 +157,15 @@ impl SourceDataProvider {
     /// will return errors.
     ///
     /// Use [`with_cldr`](Self::with_cldr), [`with_icuexport`](Self::with_icuexport),
-    /// [`with_segmenter_lstm`](Self::with_segmenter_lstm) to set data sources.
+    /// [`with_segmenter_lstm`](Self::with_segmenter_lstm), [`with_tzdb`](Self::with_tzdb),
+    /// [`with_ucd`](Self::with_ucd) to set data sources.
     pub fn new_custom() -> Self {
         Self {
             cldr_paths: None,
             icuexport_paths: None,
             segmenter_lstm_paths: None,
             tzdb_paths: None,
+            ucd_paths: None,
             trie_type: Default::default(),
             timezone_horizon: time_zones::Timestamp::try_offset_only_from_str(
                 "2015-01-01T00:00:00Z",
@@ -211,6 +221,15 @@ impl SourceDataProvider {
         })
     }
 
+    /// Adds Unicode Character Database source data to the provider. The path should point to a local
+    /// `ucd` directory or `UCD.zip` file (see [unicode.org](https://www.unicode.org/Public/)).
+    pub fn with_ucd(self, root: &Path) -> Result<Self, DataError> {
+        Ok(Self {
+            ucd_paths: Some(Arc::new(SerdeCache::new(AbstractFs::new(root)?))),
+            ..self
+        })
+    }
+
     /// Adds CLDR source data to the provider. The data will be downloaded from GitHub
     /// using the given tag (see [GitHub releases](https://github.com/unicode-org/cldr-json/releases)).
     ///
@@ -282,6 +301,22 @@ impl SourceDataProvider {
         }
     }
 
+    /// Adds Unicode Character Database source data to the provider. The data will be downloaded
+    /// from unicode.org using the given Unicode version (see [unicode.org](https://www.unicode.org/Public/)).
+    ///
+    /// Also see: [`TESTED_UCD_TAG`](Self::TESTED_UCD_TAG)
+    ///
+    /// ✨ *Enabled with the `networking` Cargo feature.*
+    #[cfg(feature = "networking")]
+    pub fn with_ucd_for_tag(self, tag: &str) -> Self {
+        Self {
+            ucd_paths: Some(Arc::new(SerdeCache::new(AbstractFs::new_from_url(
+                format!("https://www.unicode.org/Public/{tag}/ucd/UCD.zip"),
+            )))),
+            ..self
+        }
+    }
+
     const MISSING_CLDR_ERROR: DataError =
         DataError::custom("Missing CLDR data. Use `.with_cldr[_for_tag]` to set CLDR data.");
 
@@ -295,6 +330,10 @@ impl SourceDataProvider {
     const MISSING_TZDB_ERROR: DataError =
         DataError::custom("Missing tzdb data. Use `.with_tzdb[_for_tag]` to set tzdb data.");
 
+    const MISSING_UCD_ERROR: DataError = DataError::custom(
+        "Missing Unicode Character Database. Use `.with_ucd[_for_tag]` to set UCD data.",
+    );
+
     /// Identifies errors that are due to missing CLDR data.
     pub fn is_missing_cldr_error(mut e: DataError) -> bool {
         e.marker = None;
@@ -319,6 +358,12 @@ impl SourceDataProvider {
         e == Self::MISSING_TZDB_ERROR
     }
 
+    /// Identifies errors that are due to missing Unicode Character Database data.
+    pub fn is_missing_ucd_error(mut e: DataError) -> bool {
+        e.marker = None;
+        e == Self::MISSING_UCD_ERROR
+    }
+
     fn cldr(&self) -> Result<&CldrCache, DataError> {
         self.cldr_paths.as_deref().ok_or(Self::MISSING_CLDR_ERROR)
     }
@@ -339,6 +384,10 @@ impl SourceDataProvider {
         self.tzdb_paths.as_deref().ok_or(Self::MISSING_TZDB_ERROR)
     }
 
+    fn ucd(&self) -> Result<&SerdeCache, DataError> {
+        self.ucd_paths.as_deref().ok_or(Self::MISSING_UCD_ERROR)
+    }
+
     /// Set this to use tries optimized for speed instead of data size
     pub fn with_fast_tries(self) -> Self {
         Self {
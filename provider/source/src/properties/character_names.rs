@@ -0,0 +1,189 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use crate::SourceDataProvider;
+use icu::properties::provider::character_names::PropertyCharacterNames;
+use icu::properties::provider::PropertyCharacterNamesV1;
+use icu::properties::NameAliasType;
+use icu_provider::prelude::*;
+use std::collections::HashSet;
+
+/// Parses a code point field of a UCD file.
+fn parse_code_point(field: &str) -> Result<u32, DataError> {
+    u32::from_str_radix(field.trim(), 16).map_err(|e| {
+        DataError::custom("Invalid code point in UCD file")
+            .with_display_context(field)
+            .with_display_context(&e)
+    })
+}
+
+/// Returns the lines of a UCD file with comments and empty lines removed, split into fields.
+fn ucd_lines(source: &str) -> impl Iterator<Item = Vec<&str>> {
+    source
+        .lines()
+        .map(|line| line.split_once('#').map(|(l, _)| l).unwrap_or(line).trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split(';').map(str::trim).collect())
+}
+
+/// Returns the name prefix of a range from `UnicodeData.txt`, such as
+/// `<CJK Ideograph Extension A, First>`, whose names are derived by rule NR2 of UAX #44.
+///
+/// Other ranges either have no names (such as private use characters), or have
+/// names computed at runtime (Hangul syllables).
+fn derived_name_prefix(label: &str) -> Option<&'static str> {
+    if label.starts_with("CJK Ideograph") {
+        Some("CJK UNIFIED IDEOGRAPH-")
+    } else if label.starts_with("Tangut Ideograph") {
+        Some("TANGUT IDEOGRAPH-")
+    } else if label.starts_with("Egyptian Hieroglyph") {
+        Some("EGYPTIAN HIEROGLYPH-")
+    } else {
+        None
+    }
+}
+
+impl DataProvider<PropertyCharacterNamesV1> for SourceDataProvider {
+    fn load(&self, req: DataRequest) -> Result<DataResponse<PropertyCharacterNamesV1>, DataError> {
+        self.check_req::<PropertyCharacterNamesV1>(req)?;
+
+        let unicode_data = self.ucd()?.root.read_to_string("UnicodeData.txt")?;
+        let name_aliases = self.ucd()?.root.read_to_string("NameAliases.txt")?;
+
+        let mut names = Vec::new();
+        let mut ranges = Vec::new();
+        let mut range_start = None;
+        for fields in ucd_lines(&unicode_data) {
+            let (Some(cp), Some(&name)) = (fields.first(), fields.get(1)) else {
+                return Err(DataError::custom("Invalid line in UnicodeData.txt")
+                    .with_debug_context(&fields));
+            };
+            let cp = parse_code_point(cp)?;
+            if let Some(label) = name.strip_prefix('<') {
+                if let Some(label) = label.strip_suffix(", First>") {
+                    range_start = Some((cp, label));
+                } else if let Some(label) = label.strip_suffix(", Last>") {
+                    match range_start.take() {
+                        Some((start, l)) if l == label => {
+                            if let Some(prefix) = derived_name_prefix(label) {
+                                ranges.push((start..=cp, prefix));
+                            }
+                        }
+                        _ => {
+                            return Err(DataError::custom("Unmatched range in UnicodeData.txt")
+                                .with_display_context(name))
+                        }
+                    }
+                }
+                // Other labels, such as `<control>`, are not names
+                continue;
+            }
+            names.push((cp, name));
+        }
+
+        let mut aliases = Vec::new();
+        for fields in ucd_lines(&name_aliases) {
+            let &[cp, alias, alias_type] = fields.as_slice() else {
+                return Err(DataError::custom("Invalid line in NameAliases.txt")
+                    .with_debug_context(&fields));
+            };
+            let alias_type = match alias_type {
+                "correction" => NameAliasType::Correction,
+                "control" => NameAliasType::Control,
+                "alternate" => NameAliasType::Alternate,
+                "figment" => NameAliasType::Figment,
+                "abbreviation" => NameAliasType::Abbreviation,
+                _ => {
+                    return Err(DataError::custom("Unknown alias type in NameAliases.txt")
+                        .with_display_context(alias_type))
+                }
+            };
+            aliases.push((parse_code_point(cp)?, alias_type, alias));
+        }
+
+        let data_struct = PropertyCharacterNames::try_new(names, ranges, aliases)?;
+
+        Ok(DataResponse {
+            metadata: Default::default(),
+            payload: DataPayload::from_owned(data_struct),
+        })
+    }
+}
+
+impl crate::IterableDataProviderCached<PropertyCharacterNamesV1> for SourceDataProvider {
+    fn iter_ids_cached(&self) -> Result<HashSet<DataIdentifierCow<'static>>, DataError> {
+        Ok(HashSet::from_iter([Default::default()]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use icu::properties::CharacterNames;
+
+    #[test]
+    fn test_ucd_lines() {
+        let lines = ucd_lines(
+            "# NameAliases-16.0.0.txt\n\
+             \n\
+             000A;LINE FEED;control\n\
+             000A;LF;abbreviation # comment\n",
+        )
+        .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                vec!["000A", "LINE FEED", "control"],
+                vec!["000A", "LF", "abbreviation"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_character_names_data_provider() {
+        let provider = SourceDataProvider::new_testing();
+
+        let names = CharacterNames::try_new_unstable(&provider).unwrap();
+        let names = names.as_borrowed();
+        let name = |c| names.get(c).map(|n| n.to_string());
+
+        assert_eq!(name('ß').as_deref(), Some("LATIN SMALL LETTER SHARP S"));
+        assert_eq!(name('\u{1F600}').as_deref(), Some("GRINNING FACE"));
+        assert_eq!(name('\u{1CC00}').as_deref(), Some("UP-POINTING GO-KART"));
+        assert_eq!(name('中').as_deref(), Some("CJK UNIFIED IDEOGRAPH-4E2D"));
+        assert_eq!(
+            name('\u{2EBF0}').as_deref(),
+            Some("CJK UNIFIED IDEOGRAPH-2EBF0")
+        );
+        assert_eq!(name('\u{17000}').as_deref(), Some("TANGUT IDEOGRAPH-17000"));
+        assert_eq!(name('한').as_deref(), Some("HANGUL SYLLABLE HAN"));
+        assert_eq!(name('\n'), None);
+        assert_eq!(name('\u{E000}'), None);
+
+        assert_eq!(
+            names.aliases('\u{FE18}').collect::<Vec<_>>(),
+            [(
+                NameAliasType::Correction,
+                "PRESENTATION FORM FOR VERTICAL RIGHT WHITE LENTICULAR BRACKET"
+            )]
+        );
+
+        assert_eq!(names.get_loose("grinning face"), Some('\u{1F600}'));
+        assert_eq!(names.get_loose("Line_Feed"), Some('\n'));
+    }
+
+    #[test]
+    fn test_derived_name_prefix() {
+        assert_eq!(
+            derived_name_prefix("CJK Ideograph Extension A"),
+            Some("CJK UNIFIED IDEOGRAPH-")
+        );
+        assert_eq!(
+            derived_name_prefix("Tangut Ideograph Supplement"),
+            Some("TANGUT IDEOGRAPH-")
+        );
+        assert_eq!(derived_name_prefix("Hangul Syllable"), None);
+        assert_eq!(derived_name_prefix("Plane 15 Private Use"), None);
+    }
+}
@@ -29,6 +29,7 @@ use zerovec::ZeroVec;
 
 mod dictionary;
 mod lstm;
+mod phrase;
 
 // state machine name define by builtin name
 // [[tables]]
@@ -98,7 +99,6 @@ fn fill_bounded(dst: &mut [u8], r: RangeInclusive<u32>, value: u8) {
     dst[start..=end].fill(value);
 }
 
-#[cfg(any(feature = "use_wasm", feature = "use_icu4c"))]
 fn generate_rule_break_data(
     provider: &SourceDataProvider,
     rules_file: &str,
@@ -632,7 +632,6 @@ fn generate_rule_break_data(
     }
 }
 
-#[cfg(any(feature = "use_wasm", feature = "use_icu4c"))]
 fn generate_rule_break_data_override(
     provider: &SourceDataProvider,
     rules_file: &str,
@@ -706,25 +705,17 @@ macro_rules! implement {
     ($marker:ident, $rules:literal) => {
         impl DataProvider<$marker> for SourceDataProvider {
             fn load(&self, req: DataRequest) -> Result<DataResponse<$marker>, DataError> {
-                #[cfg(not(any(feature = "use_wasm", feature = "use_icu4c")))]
-                return Err(DataError::custom(
-                    "icu_provider_source must be built with use_icu4c or use_wasm to build segmentation rules",
-                )
-                .with_req($marker::INFO, req));
-                #[cfg(any(feature = "use_wasm", feature = "use_icu4c"))]
-                return {
-                    self.check_req::<$marker>(req)?;
-                    let data = generate_rule_break_data(
-                        &hardcoded_segmenter_provider(),
-                        $rules,
-                        self.trie_type(),
-                    );
-
-                    Ok(DataResponse {
-                        metadata: Default::default(),
-                        payload: DataPayload::from_owned(data),
-                    })
-                };
+                self.check_req::<$marker>(req)?;
+                let data = generate_rule_break_data(
+                    &hardcoded_segmenter_provider(),
+                    $rules,
+                    self.trie_type(),
+                );
+
+                Ok(DataResponse {
+                    metadata: Default::default(),
+                    payload: DataPayload::from_owned(data),
+                })
             }
         }
 
@@ -740,25 +731,17 @@ macro_rules! implement_override {
     ($marker:ident, $rules:literal, [$($supported:expr),*]) => {
         impl DataProvider<$marker> for SourceDataProvider {
             fn load(&self, req: DataRequest) -> Result<DataResponse<$marker>, DataError> {
-                #[cfg(not(any(feature = "use_wasm", feature = "use_icu4c")))]
-                return Err(DataError::custom(
-                    "icu_provider_source must be built with use_icu4c or use_wasm to build segmentation rules",
-                )
-                .with_req($marker::INFO, req));
-                #[cfg(any(feature = "use_wasm", feature = "use_icu4c"))]
-                return {
-                    self.check_req::<$marker>(req)?;
-                    let data = generate_rule_break_data_override(
-                        &hardcoded_segmenter_provider(),
-                        $rules,
-                        self.trie_type(),
-                    );
-
-                    Ok(DataResponse {
-                        metadata: Default::default(),
-                        payload: DataPayload::from_owned(data),
-                    })
-                };
+                self.check_req::<$marker>(req)?;
+                let data = generate_rule_break_data_override(
+                    &hardcoded_segmenter_provider(),
+                    $rules,
+                    self.trie_type(),
+                );
+
+                Ok(DataResponse {
+                    metadata: Default::default(),
+                    payload: DataPayload::from_owned(data),
+                })
             }
         }
 
@@ -833,6 +816,10 @@ fn hardcoded_segmenter_provider() -> SourceDataProvider {
                             "segmenter/line.toml",
                             include_bytes!("../../data/segmenter/line.toml").as_slice(),
                         ),
+                        (
+                            "segmenter/phrase_ja.toml",
+                            include_bytes!("../../data/segmenter/phrase_ja.toml").as_slice(),
+                        ),
                         (
                             "segmenter/sentence.toml",
                             include_bytes!("../../data/segmenter/sentence.toml").as_slice(),
@@ -0,0 +1,82 @@
+// This file is part of ICU4X. For terms of use, please see the file
+// called LICENSE at the top level of the ICU4X source tree
+// (online at: https://github.com/unicode-org/icu4x/blob/main/LICENSE ).
+
+use super::hardcoded_segmenter_provider;
+use crate::IterableDataProviderCached;
+use crate::SourceDataProvider;
+use icu::collections::codepointinvlist::CodePointInversionListBuilder;
+use icu::properties::{props::Script, CodePointMapData};
+use icu::segmenter::provider::{PhraseBreakData, SegmenterPhraseJapaneseV1};
+use icu_provider::prelude::*;
+use std::collections::HashSet;
+use zerovec::VarZeroVec;
+
+#[derive(serde::Deserialize, Debug)]
+struct PhraseData {
+    attached_words: Vec<String>,
+    prefixes: Vec<String>,
+}
+
+fn sorted(mut words: Vec<String>) -> Vec<String> {
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+impl DataProvider<SegmenterPhraseJapaneseV1> for SourceDataProvider {
+    fn load(&self, req: DataRequest) -> Result<DataResponse<SegmenterPhraseJapaneseV1>, DataError> {
+        self.check_req::<SegmenterPhraseJapaneseV1>(req)?;
+        let provider = hardcoded_segmenter_provider();
+
+        let data = provider
+            .icuexport()?
+            .read_and_parse_toml::<PhraseData>("segmenter/phrase_ja.toml")?;
+
+        // Like in ICU, all single hiragana characters attach to the preceding word.
+        let script = CodePointMapData::<Script>::try_new_unstable(&provider)?;
+        let mut attached_characters = CodePointInversionListBuilder::new();
+        for range in script.as_borrowed().iter_ranges_for_value(Script::Hiragana) {
+            attached_characters.add_range32(range);
+        }
+
+        Ok(DataResponse {
+            metadata: Default::default(),
+            payload: DataPayload::from_owned(PhraseBreakData {
+                attached_words: VarZeroVec::from(&sorted(data.attached_words.clone())),
+                attached_characters: attached_characters.build(),
+                prefixes: VarZeroVec::from(&sorted(data.prefixes.clone())),
+            }),
+        })
+    }
+}
+
+impl IterableDataProviderCached<SegmenterPhraseJapaneseV1> for SourceDataProvider {
+    fn iter_ids_cached(&self) -> Result<HashSet<DataIdentifierCow<'static>>, DataError> {
+        Ok(HashSet::from_iter([Default::default()]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_phrase_data() {
+        let provider = SourceDataProvider::new_testing();
+        let response: DataResponse<SegmenterPhraseJapaneseV1> = provider
+            .load(Default::default())
+            .expect("Loading should succeed!");
+        let data = response.payload.get();
+
+        assert!(data.attached_characters.contains('は'));
+        assert!(!data.attached_characters.contains('ハ'));
+        assert!(data.attached_words.iter().any(|w| w == "ます"));
+        assert!(data.prefixes.iter().any(|w| w == "お"));
+        assert!(data
+            .attached_words
+            .iter()
+            .zip(data.attached_words.iter().skip(1))
+            .all(|(a, b)| a < b));
+    }
+}
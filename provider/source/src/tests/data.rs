@@ -694,6 +694,12 @@ impl SourceDataProvider {
                         ("vanguard.zi", include_bytes!("../../tests/data/tzdb/vanguard.zi").as_slice())
                     ].into_iter().collect(),
                 ), transitions: Default::default() })),
+                ucd_paths: Some(Arc::new(SerdeCache::new(AbstractFs::Memory(
+                    [
+                        ("NameAliases.txt", include_bytes!("../../tests/data/ucd/NameAliases.txt").as_slice()),
+                        ("UnicodeData.txt", include_bytes!("../../tests/data/ucd/UnicodeData.txt").as_slice())
+                    ].into_iter().collect(),
+                )))),
                 ..SourceDataProvider::new_custom()
             })
             .clone()
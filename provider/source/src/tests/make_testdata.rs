@@ -16,7 +16,6 @@ use std::sync::Mutex;
 include!("../../tests/locales.rs.data");
 
 #[test]
-#[cfg(feature = "use_wasm")]
 fn make_testdata() {
     // Only produce output if the variable is set. Test is hermetic otherwise.
     let exporter: Box<dyn DataExporter> = if std::option_env!("ICU4X_WRITE_TESTDATA").is_none() {
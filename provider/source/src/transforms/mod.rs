@@ -207,6 +207,25 @@ impl crate::IterableDataProviderCached<TransliteratorRulesV1> for SourceDataProv
     }
 }
 
+impl DataProvider<TransliteratorIdsV1> for SourceDataProvider {
+    fn load(&self, req: DataRequest) -> Result<DataResponse<TransliteratorIdsV1>, DataError> {
+        self.check_req::<TransliteratorIdsV1>(req)?;
+        self.cldr()?
+            .transforms()?
+            .lock()
+            .expect("poison")
+            .as_provider_unstable(self, self, self)?
+            .load(req)
+    }
+}
+
+impl crate::IterableDataProviderCached<TransliteratorIdsV1> for SourceDataProvider {
+    fn iter_ids_cached(&self) -> Result<HashSet<DataIdentifierCow<'static>>, DataError> {
+        self.cldr()?.transforms()?;
+        Ok(HashSet::from_iter([Default::default()]))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,6 +246,17 @@ mod tests {
             .payload;
     }
 
+    #[test]
+    fn test_legacy_ids() {
+        let provider = SourceDataProvider::new_testing();
+
+        let data: DataPayload<TransliteratorIdsV1> =
+            provider.load(Default::default()).unwrap().payload;
+
+        assert_eq!(data.get().get("De-ASCII"), Some("de-t-de-d0-ascii"));
+        assert_eq!(data.get().get("ASCII-Latin"), Some("und-Latn-t-s0-ascii"));
+    }
+
     #[test]
     fn test_latin_ascii_backward() {
         let provider = SourceDataProvider::new_testing();
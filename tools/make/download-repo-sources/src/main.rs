@@ -213,6 +213,22 @@ fn main() -> eyre::Result<()> {
         &mut Default::default(),
     )?;
 
+    // Not present before the first run
+    match std::fs::remove_dir_all(out_root.join("tests/data/ucd")) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    extract_zip(
+        cached(&format!(
+            "https://www.unicode.org/Public/{}/ucd/UCD.zip",
+            SourceDataProvider::TESTED_UCD_TAG,
+        ))
+        .with_context(|| "Failed to download UCD ZIP".to_owned())?,
+        UCD_GLOB.iter().copied().map(String::from).collect(),
+        out_root.join("tests/data/ucd"),
+        &mut Default::default(),
+    )?;
+
     let mut tzdb_data = TZDB_GLOB.iter().copied().collect::<BTreeSet<_>>();
 
     let gen_files = ["rearguard.zi", "vanguard.zi"];
@@ -260,6 +276,13 @@ fn main() -> eyre::Result<()> {
         })
         .collect::<Vec<_>>()
         .join(",\n                        ");
+    let ucd_data = UCD_GLOB
+        .iter()
+        .map(|path| {
+            format!(r#"("{path}", include_bytes!("../../tests/data/ucd/{path}").as_slice())"#)
+        })
+        .collect::<Vec<_>>()
+        .join(",\n                        ");
 
     write!(&mut crlify::BufWriterWithLineEndingFix::new(File::create(out_root.join("src/tests/data.rs")).unwrap()), "\
 // This file is part of ICU4X. For terms of use, please see the file
@@ -297,6 +320,11 @@ impl SourceDataProvider {{
                         {tzdb_data}
                     ].into_iter().collect(),
                 ), transitions: Default::default() }})),
+                ucd_paths: Some(Arc::new(SerdeCache::new(AbstractFs::Memory(
+                    [
+                        {ucd_data}
+                    ].into_iter().collect(),
+                )))),
                 ..SourceDataProvider::new_custom()
             }})
             .clone()